@@ -1,10 +1,12 @@
 use crate::domain::models::types::*;
+use crate::domain::models::{ArgumentMap, ArgumentTag, BayesNetFormat, CalibrationReport, CheckpointSummary, Evidence, MergeReport, NodeEvidence, Resolution, TemplateListing, Timeline, TreeDiff, TabularFormat, TabularTable, EvidenceStrength, Stance, FaultTreeAnalysis, Gate, PathRankChange, ProbabilityBasis, RiskMatrix, DEFAULT_RISK_LEVELS, SubtreeInsertion, SubtreeNode, SubtreeRelocation, TimeWindow, TreeConfig, TreeMode, TreeResource, TreeResourceUri, NODE_URI_TEMPLATE};
 use crate::domain::services::tree_engine_service::TreeEngineService;
-use rmcp::{ handler::server::{tool::ToolRouter, wrapper::Parameters}, model::{ErrorCode, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo}, schemars::JsonSchema, tool, tool_handler, tool_router, ErrorData, ServerHandler};
+use rmcp::{ handler::server::{tool::ToolRouter, wrapper::Parameters}, model::{AnnotateAble, CallToolResult, Content, ErrorCode, GetPromptRequestParam, GetPromptResult, Implementation, JsonObject, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam, Prompt, PromptArgument, PromptMessage, PromptMessageRole, ProtocolVersion, RawResource, RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, Resource, ResourceContents, ResourceUpdatedNotificationParam, ServerCapabilities, ServerInfo, SubscribeRequestParam, UnsubscribeRequestParam}, schemars::JsonSchema, service::RequestContext, tool, tool_handler, tool_router, ErrorData, Peer, RoleServer, ServerHandler};
 use serde::{Deserialize, Serialize};
 use surrealdb::Surreal;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
-use tokio::sync::{Mutex, OnceCell};
+use tokio::sync::{broadcast::error::RecvError, Mutex, OnceCell};
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CreateTreeRequest {
@@ -29,6 +31,8 @@ pub struct ExpandLeafRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PruneTreeRequest {
     pub aggressiveness: Option<f64>,
+    /// Preview the result without writing anything
+    pub dry_run: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -57,6 +61,289 @@ pub struct NavigateToRequest {
 pub struct BalanceLeafsRequest {
     pub uncertainty_type: String,
     pub reasoning: String,
+    /// Preview the result without writing anything
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchNodesRequest {
+    pub query: Option<String>,
+    pub mode: Option<String>,
+    pub min_probability: Option<f64>,
+    pub max_probability: Option<f64>,
+    pub min_confidence: Option<i64>,
+    pub max_confidence: Option<i64>,
+    pub min_depth: Option<i64>,
+    pub max_depth: Option<i64>,
+    pub status: Option<String>,
+    pub metadata_keys: Option<Vec<String>>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AttachEvidenceRequest {
+    pub node_id: String,
+    pub source_title: String,
+    pub source_ref: String,
+    pub quote: Option<String>,
+    pub date: Option<String>,
+    pub strength: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListEvidenceRequest {
+    pub node_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetPropositionRequest {
+    pub node_id: String,
+    pub proposition: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddCrossLinkRequest {
+    pub parent_id: String,
+    pub child_id: String,
+    pub probability: f64,
+    pub reasoning: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RemoveCrossLinkRequest {
+    pub parent_id: String,
+    pub child_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckpointRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListCheckpointsRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffTreesRequest {
+    pub from: String,
+    pub to: String,
+    pub top_k: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeTreeRequest {
+    pub source: String,
+    pub similarity_threshold: Option<f64>,
+    /// Preview the merge without writing anything
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResolveOutcomeRequest {
+    pub node_id: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalibrationReportRequest {
+    pub tree_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateTreeFromTemplateRequest {
+    pub template: String,
+    pub subject: String,
+    pub complexity: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListTemplatesRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RegisterTemplateRequest {
+    pub json: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetTimeHorizonRequest {
+    pub node_id: String,
+    pub window_start: Option<String>,
+    pub window_end: Option<String>,
+    pub review_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimelineRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddSubtreeRequest {
+    pub parent_id: String,
+    pub nodes: Vec<SubtreeNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MoveSubtreeRequest {
+    pub node_id: String,
+    pub new_parent_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CopySubtreeRequest {
+    pub node_id: String,
+    pub new_parent_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockNodeRequest {
+    pub node_id: String,
+    /// Defaults to true; false removes the lock
+    pub locked: Option<bool>,
+    /// Freeze the node's whole subtree instead of locking only the node
+    pub subtree: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetGateRequest {
+    pub node_id: String,
+    /// AND, OR or K_OF_N; omit to clear the gate
+    pub gate: Option<String>,
+    /// Threshold of a K_OF_N gate
+    pub k: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FaultTreeAnalysisRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetRiskProfileRequest {
+    pub node_id: String,
+    /// Severity if the scenario occurs, 0-10; omit to clear
+    pub impact: Option<f64>,
+    /// Risk category such as "supply"; omit to clear
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RiskMatrixRequest {
+    /// "path" (default) or "marginal"
+    pub basis: Option<String>,
+    /// Probability bands, 2-10 (defaults to 5)
+    pub probability_levels: Option<usize>,
+    /// Impact bands, 2-10 (defaults to 5)
+    pub impact_levels: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetTreeModeRequest {
+    /// "scenario" or "argument"
+    pub mode: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TagArgumentRequest {
+    pub node_id: String,
+    /// "pro" or "con" towards the parent claim; omit to clear the tag
+    pub stance: Option<String>,
+    /// How strongly the argument bears on its parent, 0-1 (defaults to 1)
+    pub strength: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArgumentMapRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportBayesNetRequest {
+    /// "xmlbif" (default) or "bif"
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportBayesNetRequest {
+    /// XMLBIF or plain-text BIF document
+    pub network: String,
+    /// Complexity of the new tree, 1-10 (defaults to 5)
+    pub complexity: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportTableRequest {
+    /// "paths" (latest export_paths report) or "nodes" (every node)
+    pub table: String,
+    /// "csv" (default) or "jsonl"
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportOutlineRequest {
+    /// Markdown outline: a "# premise" heading, then nested "- Premise (p=0.4, c=7): reasoning" bullets
+    pub outline: String,
+    /// Complexity of the new tree, 1-10 (defaults to 5)
+    pub complexity: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportOutlineRequest{}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetExhaustiveRequest {
+    pub node_id: String,
+    /// Whether the children form a complete set of outcomes (defaults to true)
+    pub exhaustive: Option<bool>,
+    /// Maintain an "other / unanticipated" child absorbing the leftover probability (defaults to false)
+    pub residual: Option<bool>,
+}
+
+/// Environment variable naming a directory of JSON templates loaded at startup.
+const TEMPLATE_DIR_ENV: &str = "DEEP_ANALYTICS_TEMPLATE_DIR";
+
+/// JSON Schema of a structured tool result serialized from `T`.
+fn output_schema<T: JsonSchema>() -> Arc<JsonObject> {
+    let mut schema = serde_json::to_value(rmcp::schemars::schema_for!(T))
+        .ok()
+        .and_then(|value| value.as_object().cloned())
+        .unwrap_or_default();
+    schema.remove("$schema");
+    Arc::new(schema)
+}
+
+/// Successful tool result: the human-readable text as content, `data` as structured content.
+fn structured_result<T: Serialize>(text: String, data: &T) -> CallToolResult {
+    let mut result = CallToolResult::success(vec![Content::text(text)]);
+    result.structured_content = serde_json::to_value(data).ok();
+    result
+}
+
+/// Failed tool result; it carries no structured content, so clients skip schema validation.
+fn error_result(text: String) -> CallToolResult {
+    CallToolResult::error(vec![Content::text(text)])
+}
+
+/// Parses an optional YYYY-MM-DD tool argument; blank counts as absent.
+fn parse_date(value: Option<&str>) -> Result<Option<chrono::NaiveDate>, String> {
+    match value.map(str::trim).filter(|date| !date.is_empty()) {
+        Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", date)),
+        None => Ok(None),
+    }
+}
+/// Lists the paths a prune, balance or merge changes, one per line.
+/// Lists the paths a dry-run prune or balance would change, one per line.
+fn affected_paths_text(paths: &[PathRankChange]) -> String {
+    if paths.is_empty() {
+        return "   No path changes".to_string();
+    }
+    let describe = |rank: Option<usize>, probability: Option<f64>, missing: &str| match (rank, probability) {
+        (Some(rank), Some(probability)) => format!("#{} p={:.4}", rank, probability),
+        _ => missing.to_string(),
+    };
+    paths.iter()
+        .map(|path| format!("   {}: {} → {}",
+            path.premises.join(" → "),
+            describe(path.rank_before, path.probability_before, "—"),
+            describe(path.rank_after, path.probability_after, "removed")))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// TreeEngineServer provides an MCP (Model Context Protocol) interface for the probability tree engine.
@@ -91,6 +378,12 @@ pub struct TreeEngineServer {
     service: OnceCell<Arc<Mutex<TreeEngineService>>>,
     /// MCP tool router for handling method dispatch
     tool_router: ToolRouter<TreeEngineServer>,
+    /// Resource URIs the client subscribed to
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    /// Task forwarding tree changes as `resources/updated`, started on the first subscribe
+    change_forwarder: OnceCell<()>,
+    /// Why the templates in `DEEP_ANALYTICS_TEMPLATE_DIR` could not be loaded, reported by `list_templates`
+    template_load_error: OnceCell<String>,
 }
 
 impl TreeEngineServer {
@@ -114,6 +407,9 @@ impl TreeEngineServer {
         TreeEngineServer {
             service: OnceCell::new(),
             tool_router: Self::tool_router(),
+            subscriptions: Arc::new(Mutex::new(BTreeSet::new())),
+            change_forwarder: OnceCell::new(),
+            template_load_error: OnceCell::new(),
         }
     }
 
@@ -131,6 +427,9 @@ impl TreeEngineServer {
     /// - Namespace: "analytics"
     /// - Database: "trees"
     /// - No persistence - data is session-specific
+    ///
+    /// Team templates found in the directory named by `DEEP_ANALYTICS_TEMPLATE_DIR`
+    /// are registered alongside the built-in ones; a loading failure is kept for `list_templates`.
     async fn get_service(&self) -> &Arc<Mutex<TreeEngineService>> {
         self.service.get_or_init(|| async {
             let db = Surreal::new::<surrealdb::engine::local::Mem>(())
@@ -138,10 +437,219 @@ impl TreeEngineServer {
                 .unwrap();
             db.use_ns("analytics").use_db("trees").await.unwrap();
 
-            let service = TreeEngineService::new(Arc::new(db));
+            let mut service = TreeEngineService::new(Arc::new(db));
+            if let Ok(dir) = std::env::var(TEMPLATE_DIR_ENV) {
+                if let Err(e) = service.register_template_file(std::path::Path::new(&dir)) {
+                    let _ = self.template_load_error.set(format!("Failed to load templates from {}: {}", dir, e));
+                }
+            }
             Arc::new(Mutex::new(service))
         }).await
     }
+
+    /// Sends `resources/updated` to `peer` for each subscribed URI of a tree whenever it changes.
+    async fn forward_changes(&self, peer: Peer<RoleServer>) {
+        let mut changes = self.get_service().await.lock().await.subscribe_changes();
+        let subscriptions = self.subscriptions.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let tree_id = match changes.recv().await {
+                    Ok(tree_id) => tree_id,
+                    // Missed changes need no replay: the next notification tells the client to re-read
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return,
+                };
+
+                let prefix = TreeResourceUri::tree_prefix(&tree_id);
+                let uris: Vec<String> = subscriptions.lock().await.iter()
+                    .filter(|uri| uri.starts_with(&prefix))
+                    .cloned()
+                    .collect();
+                for uri in uris {
+                    if peer.notify_resource_updated(ResourceUpdatedNotificationParam { uri }).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Renders one tree resource; errors are messages for a resource-not-found reply.
+    async fn read_tree_resource(&self, uri: &TreeResourceUri) -> Result<String, String> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let state = service.get_state().await.map_err(|_| "No tree has been created yet".to_string())?;
+        if state.tree_id != uri.tree_id {
+            return Err(format!("Tree {} is not loaded; the current tree is {}", uri.tree_id, state.tree_id));
+        }
+
+        match &uri.resource {
+            TreeResource::Tree => service.inspect_tree().await
+                .map(|visualization| visualization.to_string())
+                .map_err(|e| e.to_string()),
+            TreeResource::Node(node_id) => {
+                let alias = service.get_friendly_id(node_id).await.map_err(|e| e.to_string())?;
+                let mut visualization = service.inspect_tree().await.map_err(|e| e.to_string())?;
+                let node = visualization.node_details.remove(&alias)
+                    .ok_or_else(|| format!("Node {} not found", node_id))?;
+                serde_json::to_string_pretty(&node).map_err(|e| e.to_string())
+            }
+            TreeResource::Export => {
+                let snapshot = service.snapshot().await.map_err(|e| e.to_string())?;
+                serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())
+            }
+            TreeResource::Report => match service.latest_report().await.map_err(|e| e.to_string())? {
+                Some(report) => Ok(report.to_string()),
+                None => Err(format!("No report exported yet for tree {}; run export_paths first", uri.tree_id)),
+            },
+        }
+    }
+}
+
+/// Guided-workflow prompts as `(name, description, [(argument, description, required)])`.
+const PROMPTS: [(&str, &str, &[(&str, &str, bool)]); 3] = [
+    ("start_analysis", "Start a new probability tree for a topic, with the limits of the chosen complexity", &[
+        ("topic", "Question or decision to analyze", true),
+        ("complexity", "Analysis complexity from 1 to 10 (default 5)", false),
+    ]),
+    ("expand_branch", "Expand one node into children that fit its probability budget", &[
+        ("node_id", "Friendly alias, record id or unambiguous prefix of the node to expand", true),
+    ]),
+    ("final_report", "Validate, refine and export the current tree as a report", &[
+        ("style", "Analytical, Strategic or Storytelling (default Analytical)", false),
+    ]),
+];
+
+/// Complexity used by start_analysis when none is given.
+const DEFAULT_PROMPT_COMPLEXITY: i64 = 5;
+
+/// Live state shared by every prompt: the cursor and the leaves that can still be expanded.
+fn live_tree_context(tree: &TreeVisualization, cursor: Option<&str>) -> String {
+    let describe = |alias: &str| tree.node_details.get(alias)
+        .map(|node| format!("[{}] {} (p={:.3}, depth {})", alias, node.premise_summary, node.probability, node.depth))
+        .unwrap_or_else(|| format!("[{}]", alias));
+
+    let mut context = format!("## CURRENT TREE ({})\n- Nodes: {} ({} active paths)\n- Cursor: {}\n",
+        tree.tree_metadata.tree_id, tree.statistics.total_nodes, tree.statistics.active_paths,
+        cursor.map(describe).unwrap_or_else(|| "not set".to_string()));
+
+    let mut expandable: Vec<(&String, &NodeVisualization)> = tree.node_details.iter()
+        .filter(|(_, node)| node.is_leaf && node.can_expand && node.status == NodeStatus::Active)
+        .collect();
+    expandable.sort_by(|a, b| b.1.probability.partial_cmp(&a.1.probability).unwrap_or(std::cmp::Ordering::Equal));
+    if expandable.is_empty() {
+        context.push_str("- No leaves can be expanded further\n");
+    } else {
+        context.push_str("- Leaves that can still be expanded:\n");
+        for (alias, _) in expandable {
+            context.push_str(&format!("  - {}\n", describe(alias)));
+        }
+    }
+    context
+}
+
+/// Trimmed prompt arguments, leaving out blank ones.
+fn prompt_arguments(arguments: Option<JsonObject>) -> HashMap<String, String> {
+    arguments.unwrap_or_default().into_iter()
+        .map(|(name, value)| (name, value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())))
+        .map(|(name, value)| (name, value.trim().to_string()))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+}
+
+/// Text of the prompt `name` for the live `tree`, if any, whose `node_id` arguments are
+/// already friendly aliases; errors describe the invalid argument.
+fn render_prompt(name: &str, arguments: &HashMap<String, String>, tree: Option<&TreeVisualization>, cursor: Option<&str>) -> Result<String, String> {
+    let argument = |name: &str| arguments.get(name).map(String::as_str);
+    let live = tree
+        .map(|tree| live_tree_context(tree, cursor))
+        .unwrap_or_else(|| "## CURRENT TREE\nNo tree exists yet in this session.\n".to_string());
+
+    let text = match name {
+        "start_analysis" => {
+            let topic = argument("topic").ok_or("start_analysis requires a topic")?;
+            let complexity = match argument("complexity") {
+                Some(value) => value.parse::<i64>().ok().filter(|c| (1..=10).contains(c))
+                    .ok_or_else(|| format!("Complexity '{}' must be an integer from 1 to 10", value))?,
+                None => DEFAULT_PROMPT_COMPLEXITY,
+            };
+            let config = TreeConfig::new(complexity);
+            let replaces = if tree.is_some() { "\n⚠️  create_tree clears the current tree below; checkpoint it first if it is still needed.\n" } else { "" };
+            format!(
+                "Build a probability tree analyzing: {topic}\n\n\
+                1. Call create_tree with premise \"{topic}\" and complexity {complexity}.\n\
+                2. Add 2-{branches} mutually exclusive root branches with add_leaf; their probabilities may sum to at most 1.0.\n\
+                3. Expand the most consequential branches with expand_leaf, then add their children.\n\
+                4. Run inspect_tree often, and validate_coherence plus probability_status before pruning or exporting.\n\n\
+                Limits at complexity {complexity}: depth {depth}, at most {branches} children per node, \
+                branches below p={min:.2} are pruning candidates.\n{replaces}\n{live}",
+                topic = topic, complexity = complexity, branches = config.branch_limit, depth = config.max_depth,
+                min = config.min_probability, replaces = replaces, live = live,
+            )
+        }
+        "expand_branch" => {
+            let alias = argument("node_id").ok_or("expand_branch requires a node_id")?;
+            let tree = tree.ok_or("No tree exists yet; use start_analysis first")?;
+            let node = tree.node_details.get(alias)
+                .ok_or_else(|| format!("Node {} not found", alias))?;
+
+            let allocated: f64 = node.children_ids.iter()
+                .filter_map(|child| tree.node_details.get(child))
+                .map(|child| child.probability)
+                .sum();
+            let slots = tree.tree_metadata.config.branch_limit.saturating_sub(node.children_count);
+            let blocked = if node.can_expand && slots > 0 {
+                String::new()
+            } else {
+                "\n⚠️  This node cannot take more children (depth or branch limit reached); pick another leaf below.\n".to_string()
+            };
+            format!(
+                "Expand [{alias}] {premise}\n\n\
+                Current estimate: p={probability:.3}, confidence {confidence}/10, depth {depth}.\n\
+                Reasoning so far: {reasoning}\n\n\
+                1. Call expand_leaf with node_id \"{alias}\" and a rationale for why this branch deserves detail.\n\
+                2. Add up to {slots} children with add_leaf. Probability budget left: {budget:.3} \
+                (p={probability:.3} minus {allocated:.3} already given to {children} existing children).\n\
+                3. Make the children mutually exclusive ways this premise can play out, each with specific reasoning.\n{blocked}\n{live}",
+                alias = alias, premise = node.full_premise, probability = node.probability, confidence = node.confidence,
+                depth = node.depth, reasoning = node.full_reasoning, slots = slots,
+                budget = (node.probability - allocated).max(0.0), allocated = allocated,
+                children = node.children_count, blocked = blocked, live = live,
+            )
+        }
+        "final_report" => {
+            let style = match argument("style") {
+                None => "Analytical",
+                Some(style @ ("Analytical" | "Strategic" | "Storytelling")) => style,
+                Some(other) => return Err(format!("Unknown style '{}'; use Analytical, Strategic or Storytelling", other)),
+            };
+            let tree = tree.ok_or("No tree exists yet; use start_analysis first")?;
+            let top_paths: String = tree.active_paths.iter().take(5)
+                .map(|path| format!("  - [{}] {} (p={:.3}, confidence {})\n", path.leaf_id, path.premise, path.probability, path.confidence))
+                .collect();
+            format!(
+                "Produce the final {style} report for the current tree.\n\n\
+                1. Run validate_coherence and probability_status; fix any contradiction or violation they report.\n\
+                2. Optionally refine with prune_tree (drop weak branches) or balance_leafs (moderate extreme estimates).\n\
+                3. Call export_paths with narrative_style \"{style}\", at least 3 insights drawn from the paths below, \
+                and your overall confidence from 0.0 to 1.0.\n\n\
+                Most likely active paths:\n{top_paths}\n{live}",
+                style = style, top_paths = top_paths, live = live,
+            )
+        }
+        other => return Err(format!("Unknown prompt '{}'", other)),
+    };
+    Ok(text)
+}
+
+/// Resource entry for `uri`, typed with the URI's MIME type.
+fn tree_resource(uri: TreeResourceUri, name: String, description: &str) -> Resource {
+    let mut resource = RawResource::new(uri.to_string(), name);
+    resource.description = Some(description.to_string());
+    resource.mime_type = Some(uri.mime_type().to_string());
+    resource.no_annotation()
 }
 
 #[tool_router]
@@ -157,7 +665,7 @@ impl TreeEngineServer {
     /// - `complexity` (i64): Analysis complexity level from 1-10 affecting tree behavior
     ///
     /// # Returns
-    /// - Success: "Successfully created probability tree with ID: {node_id}"
+    /// - Success: "Successfully created probability tree with ID: {alias} (record: {node_id})"
     /// - Error: "Failed to create tree: {error_description}"
     ///
     /// # Example MCP Request
@@ -173,14 +681,20 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "TREE INITIALIZATION: Create a new probability tree with a root premise and complexity level (1-10). This is the mandatory first step that clears any existing tree and sets the cursor at the root for adding initial child branches. Use complexity 1-3 for simple analysis, 4-7 for balanced analysis, 8-10 for complex multi-layered analysis. After creation, use add_leaf to add initial branches to the root.")]
-    async fn create_tree(&self, Parameters(request): Parameters<CreateTreeRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "TREE INITIALIZATION: Create a new probability tree with a root premise and complexity level (1-10). This is the mandatory first step that clears any existing tree and sets the cursor at the root for adding initial child branches. Use complexity 1-3 for simple analysis, 4-7 for balanced analysis, 8-10 for complex multi-layered analysis. After creation, use add_leaf to add initial branches to the root.", output_schema = output_schema::<NodeRef>())]
+    async fn create_tree(&self, Parameters(request): Parameters<CreateTreeRequest>) -> Result<CallToolResult, ErrorData> {
         let service = self.get_service().await;
         let mut service = service.lock().await;
 
         match service.create_tree(request.premise, request.complexity).await {
-            Ok(tree_id) => Ok(format!("Successfully created probability tree with ID: {}", tree_id)),
-            Err(e) => Ok(format!("Failed to create tree: {}", e)),
+            Ok(tree_id) => {
+                let alias = service.get_friendly_id(&tree_id).await.unwrap_or_else(|_| tree_id.clone());
+                Ok(structured_result(
+                    format!("Successfully created probability tree with ID: {} (record: {})", alias, tree_id),
+                    &NodeRef { node_id: tree_id, friendly_id: alias },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to create tree: {}", e))),
         }
     }
 
@@ -197,7 +711,7 @@ impl TreeEngineServer {
     /// - `confidence` (i64): Confidence level from 1-10 indicating assessment certainty
     ///
     /// # Returns
-    /// - Success: "Successfully added leaf node with ID: {node_id}"
+    /// - Success: "Successfully added leaf node with ID: {alias} (record: {node_id})"
     /// - Error: Various error messages for validation failures or system errors
     ///
     /// # Example MCP Request
@@ -215,8 +729,8 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "BRANCH CREATION: Add a new child node to the current cursor position in the tree. This requires a premise (the branch statement), detailed reasoning (explanation/evidence), probability (0.0-1.0), and confidence level (1-10). The cursor is automatically positioned by create_tree (at root) or expand_leaf (at expanded node). Use this after create_tree to add root's children, or after expand_leaf to add children to the expanded node.")]
-    async fn add_leaf(&self, Parameters(request): Parameters<AddLeafRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "BRANCH CREATION: Add a new child node to the current cursor position in the tree. This requires a premise (the branch statement), detailed reasoning (explanation/evidence), probability (0.0-1.0), and confidence level (1-10). The cursor is automatically positioned by create_tree (at root) or expand_leaf (at expanded node). Use this after create_tree to add root's children, or after expand_leaf to add children to the expanded node.", output_schema = output_schema::<NodeRef>())]
+    async fn add_leaf(&self, Parameters(request): Parameters<AddLeafRequest>) -> Result<CallToolResult, ErrorData> {
         let service = self.get_service().await;
         let mut service = service.lock().await;
 
@@ -226,8 +740,99 @@ impl TreeEngineServer {
             request.probability,
             request.confidence,
         ).await {
-            Ok(node_id) => Ok(format!("Successfully added leaf node with ID: {}", node_id)),
-            Err(e) => Ok(format!("Failed to add leaf: {}", e)),
+            Ok(node_id) => {
+                let alias = service.get_friendly_id(&node_id).await.unwrap_or_else(|_| node_id.clone());
+                Ok(structured_result(
+                    format!("Successfully added leaf node with ID: {} (record: {})", alias, node_id),
+                    &NodeRef { node_id, friendly_id: alias },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to add leaf: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Adds a nested subtree of nodes under one parent in a single transaction.
+    ///
+    /// # MCP Tool Parameters
+    /// - `parent_id` (string): Friendly alias, record id or unambiguous prefix of the parent
+    /// - `nodes` (array): Nodes with `premise`, `reasoning`, `probability`, `confidence` and optional `children`
+    ///
+    /// # Returns
+    /// - Success: The new alias and record id of every node, keyed by its position (e.g. `2.1`)
+    /// - Error: "Failed to add subtree: {error_description}", listing every violated constraint
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "add_subtree",
+    ///     "arguments": {
+    ///       "parent_id": "clever_turing",
+    ///       "nodes": [
+    ///         {
+    ///           "premise": "Supplier delays",
+    ///           "reasoning": "Lead times doubled in Q1",
+    ///           "probability": 0.5,
+    ///           "confidence": 7,
+    ///           "children": [
+    ///             { "premise": "Port strike", "reasoning": "Union vote in May", "probability": 0.2, "confidence": 5 }
+    ///           ]
+    ///         },
+    ///         { "premise": "Launch on time", "reasoning": "Buffer stock covers six weeks", "probability": 0.4, "confidence": 6 }
+    ///       ]
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "BATCH CONSTRUCTION: Add a whole nested subtree under parent_id in one call instead of many expand_leaf/add_leaf round trips. Each node needs premise, reasoning, probability (0-1) and confidence (1-10), plus optional children with the same shape. Every constraint (depth, branch limit, children summing to at most their parent's probability, including the parent's existing children) is checked before anything is written; on failure nothing is added and all violations are listed. Nodes are inserted in a single transaction and the cursor does not move. Returns the new alias of every node by position (1, 1.1, 2, ...).", output_schema = output_schema::<SubtreeInsertion>())]
+    async fn add_subtree(&self, Parameters(request): Parameters<AddSubtreeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.add_subtree(request.parent_id, request.nodes).await {
+            Ok(insertion) => Ok(structured_result(insertion.to_string(), &insertion)),
+            Err(e) => Ok(error_result(format!("Failed to add subtree: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Re-parents a node together with all its descendants.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Root of the subtree to move (friendly alias, record id or prefix)
+    /// - `new_parent_id` (string): Node to move it under
+    ///
+    /// # Returns
+    /// - Success: The moved nodes with their recomputed depths
+    /// - Error: "Failed to move subtree: {error_description}"
+    #[tool(description = "RESTRUCTURING: Move node_id and all its descendants under new_parent_id, for when a branch turns out to belong elsewhere. Children lists and depths are fixed up automatically; aliases and the cursor stay the same. Refused if it would exceed max depth or the branch limit, push the new parent's children above its probability, or create a cycle (destination inside the moved subtree). The root cannot be moved.", output_schema = output_schema::<SubtreeRelocation>())]
+    async fn move_subtree(&self, Parameters(request): Parameters<MoveSubtreeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.move_subtree(request.node_id, request.new_parent_id).await {
+            Ok(relocation) => Ok(structured_result(relocation.to_string(), &relocation)),
+            Err(e) => Ok(error_result(format!("Failed to move subtree: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Duplicates a node and all its descendants under another parent.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Root of the subtree to copy (friendly alias, record id or prefix)
+    /// - `new_parent_id` (string): Node to place the copy under
+    ///
+    /// # Returns
+    /// - Success: Each source node with the alias of its copy
+    /// - Error: "Failed to copy subtree: {error_description}"
+    #[tool(description = "RESTRUCTURING: Copy node_id and all its descendants under new_parent_id, e.g. to reuse the same causal breakdown in another scenario. Copies get new aliases and keep premises, reasoning, probabilities, evidence and time horizons; cross-links are not copied. Refused if it would exceed max depth or the branch limit, or push the new parent's children above its probability.", output_schema = output_schema::<SubtreeRelocation>())]
+    async fn copy_subtree(&self, Parameters(request): Parameters<CopySubtreeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.copy_subtree(request.node_id, request.new_parent_id).await {
+            Ok(relocation) => Ok(structured_result(relocation.to_string(), &relocation)),
+            Err(e) => Ok(error_result(format!("Failed to copy subtree: {}", e))),
         }
     }
 
@@ -238,7 +843,7 @@ impl TreeEngineServer {
     /// The expansion follows the established probability tree analysis patterns.
     ///
     /// # MCP Tool Parameters
-    /// - `node_id` (string): ID of the leaf node to expand (must be a valid leaf node)
+    /// - `node_id` (string): Friendly alias, record id or unambiguous prefix of the leaf node to expand
     /// - `rationale` (string): New reasoning/rationale for the expanded analysis
     ///
     /// # Returns
@@ -252,21 +857,26 @@ impl TreeEngineServer {
     ///   "params": {
     ///     "name": "expand_leaf",
     ///     "arguments": {
-    ///       "node_id": "node:xyz789",
+    ///       "node_id": "clever_turing",
     ///       "rationale": "Further analysis reveals multiple subcategories requiring separate evaluation"
     ///     }
     ///   }
     /// }
     /// ```
-    #[tool(description = "CURSOR POSITIONING: Expand a specific leaf node to prepare it for adding children. This moves the internal cursor to the specified node_id and updates its reasoning. After expansion, use add_leaf to add child nodes to this expanded node. Essential for building tree depth - always expand a node before adding its children. Provide the exact node_id from previous tool responses and detailed rationale for the expansion.")]
-    async fn expand_leaf(&self, Parameters(request): Parameters<ExpandLeafRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "CURSOR POSITIONING: Expand a specific leaf node to prepare it for adding children. This moves the internal cursor to the specified node_id and updates its reasoning. After expansion, use add_leaf to add child nodes to this expanded node. Essential for building tree depth - always expand a node before adding its children. Provide the node_id as the friendly alias from previous tool responses (e.g. 'clever_turing'), the full record id, or an unambiguous prefix, plus a detailed rationale for the expansion.", output_schema = output_schema::<NodeRef>())]
+    async fn expand_leaf(&self, Parameters(request): Parameters<ExpandLeafRequest>) -> Result<CallToolResult, ErrorData> {
         let service = self.get_service().await;
         let mut service = service.lock().await;
 
-        let node_id = request.node_id.clone();
         match service.expand_leaf(request.node_id, request.rationale).await {
-            Ok(_result) => Ok(format!("Successfully expanded leaf. Now working in {}.", node_id)),
-            Err(e) => Ok(format!("Failed to expand leaf: {}", e)),
+            Ok(node_id) => {
+                let alias = service.get_friendly_id(&node_id).await.unwrap_or_else(|_| node_id.clone());
+                Ok(structured_result(
+                    format!("Successfully expanded leaf. Now working in {}.", alias),
+                    &NodeRef { node_id, friendly_id: alias },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to expand leaf: {}", e))),
         }
     }
 
@@ -282,6 +892,7 @@ impl TreeEngineServer {
     ///   - 0.0 = Very conservative (removes only extremely low probability branches)
     ///   - 0.5 = Balanced pruning (default) - good starting point for most analyses
     ///   - 1.0 = Aggressive pruning (removes more branches, keeps only highest probabilities)
+    /// - `dry_run` (optional bool): Report what would be pruned and which paths change, without writing
     ///
     /// # Returns
     /// - Success: "Pruned {removed} nodes, preserved {preserved} nodes with aggressiveness level {level}"
@@ -307,29 +918,47 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "COST-COMPLEXITY PRUNING: Intelligently removes branches using minimal cost-complexity theory from decision tree research. Formula: R_α(T) = error + α×nodes, where higher α penalizes larger trees. Aggressiveness 0.0-1.0 maps to α values (0.0=very conservative, 0.3=moderate, 0.6=aggressive, 1.0=very aggressive). Based on Breiman's CART algorithm - mathematically sound approach that balances model complexity against accuracy. Reports effective threshold and method explanation.")]
-    async fn prune_tree(&self, Parameters(request): Parameters<PruneTreeRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "COST-COMPLEXITY PRUNING: Intelligently removes branches using minimal cost-complexity theory from decision tree research. Formula: R_α(T) = error + α×nodes, where higher α penalizes larger trees. Aggressiveness 0.0-1.0 maps to α values (0.0=very conservative, 0.3=moderate, 0.6=aggressive, 1.0=very aggressive). Based on Breiman's CART algorithm - mathematically sound approach that balances model complexity against accuracy. Reports effective threshold and method explanation. Nodes pinned with lock_node are never removed and are listed as skipped. Set dry_run=true to preview the removals and affected paths without changing the tree.", output_schema = output_schema::<PruningResult>())]
+    async fn prune_tree(&self, Parameters(request): Parameters<PruneTreeRequest>) -> Result<CallToolResult, ErrorData> {
         let service = self.get_service().await;
         let mut service = service.lock().await;
 
         let aggressiveness = request.aggressiveness.unwrap_or(0.5);
 
-        match service.prune_tree(aggressiveness).await {
-            Ok(result) => Ok(format!(
+        match service.prune_tree(aggressiveness, request.dry_run.unwrap_or(false)).await {
+            Ok(result) if result.dry_run => Ok(structured_result(format!(
+                "COST-COMPLEXITY PRUNING PREVIEW (dry run, nothing was written):\n\
+                • Would remove: {} nodes | Would preserve: {} nodes\n\
+                • Effective threshold: {:.3}\n\
+                • Skipped (locked): {}\n\
+                • Skipped (residual): {}\n\
+                • Affected paths:\n{}",
+                result.statistics.removed_count,
+                result.statistics.preserved_count,
+                result.statistics.effective_threshold,
+                if result.skipped_locked.is_empty() { "none".to_string() } else { result.skipped_locked.join(", ") },
+                if result.skipped_residual.is_empty() { "none".to_string() } else { result.skipped_residual.join(", ") },
+                affected_paths_text(&result.affected_paths)
+            ), &result)),
+            Ok(result) => Ok(structured_result(format!(
                 "COST-COMPLEXITY PRUNING COMPLETE:\n\
                 • Removed: {} nodes | Preserved: {} nodes\n\
                 • Aggressiveness: {:.2} → Cost-complexity α: {:.3}\n\
                 • Effective threshold: {:.3}\n\
                 • Method: {}\n\
+                • Skipped (locked): {}\n\
+                • Skipped (residual): {}\n\
                 • Result: Optimized tree structure using Breiman's minimal cost-complexity principle",
                 result.statistics.removed_count,
                 result.statistics.preserved_count,
                 result.statistics.aggressiveness_level,
                 result.statistics.cost_complexity_alpha,
                 result.statistics.effective_threshold,
-                result.statistics.method_explanation
-            )),
-            Err(e) => Ok(format!("Failed to prune tree: {}", e)),
+                result.statistics.method_explanation,
+                if result.skipped_locked.is_empty() { "none".to_string() } else { result.skipped_locked.join(", ") },
+                if result.skipped_residual.is_empty() { "none".to_string() } else { result.skipped_residual.join(", ") }
+            ), &result)),
+            Err(e) => Ok(error_result(format!("Failed to prune tree: {}", e))),
         }
     }
 
@@ -377,8 +1006,8 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "FINAL ANALYSIS OUTPUT: Generate comprehensive analysis report of all viable probability paths with integrated insights. Choose narrative style: 'Analytical' (technical/data-focused), 'Strategic' (business/decision-focused), or 'Storytelling' (engaging/narrative). Provide minimum 3 user insights to integrate and overall confidence assessment (0.0-1.0). This is typically the final step after tree building, pruning, and validation.")]
-    async fn export_paths(&self, Parameters(request): Parameters<ExportPathsRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "FINAL ANALYSIS OUTPUT: Generate comprehensive analysis report of all viable probability paths with integrated insights. Choose narrative style: 'Analytical' (technical/data-focused), 'Strategic' (business/decision-focused), or 'Storytelling' (engaging/narrative). Provide minimum 3 user insights to integrate and overall confidence assessment (0.0-1.0). This is typically the final step after tree building, pruning, and validation.", output_schema = output_schema::<AnalysisResult>())]
+    async fn export_paths(&self, Parameters(request): Parameters<ExportPathsRequest>) -> Result<CallToolResult, ErrorData> {
         let service_arc = self.get_service().await;
         let service = service_arc.lock().await;
 
@@ -389,8 +1018,8 @@ impl TreeEngineServer {
         };
 
         match service.export_paths(style, request.insights, request.confidence_assessment).await {
-            Ok(result) => Ok(result.to_string()),
-            Err(e) => Ok(format!("Failed to export paths: {}", e)),
+            Ok(result) => Ok(structured_result(result.to_string(), &result)),
+            Err(e) => Ok(error_result(format!("Failed to export paths: {}", e))),
         }
     }
 
@@ -433,14 +1062,14 @@ impl TreeEngineServer {
     /// - Avg probability: 0.72
     /// - Complexity score: 1.85
     /// ```
-    #[tool(description = "TREE ANALYSIS: Get comprehensive overview of current tree structure including node counts, depth analysis, probability statistics, and complexity metrics. Use this to understand tree development progress, identify structural patterns, and guide further expansion decisions. Essential for monitoring tree health during construction and before major operations like pruning or export.")]
-    async fn inspect_tree(&self, Parameters(_request): Parameters<InspectTreeRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "TREE ANALYSIS: Get comprehensive overview of current tree structure including node counts, depth analysis, probability statistics, and complexity metrics. Use this to understand tree development progress, identify structural patterns, and guide further expansion decisions. Essential for monitoring tree health during construction and before major operations like pruning or export.", output_schema = output_schema::<TreeVisualization>())]
+    async fn inspect_tree(&self, Parameters(_request): Parameters<InspectTreeRequest>) -> Result<CallToolResult, ErrorData> {
         let service_arc = self.get_service().await;
         let service = service_arc.lock().await;
 
         match service.inspect_tree().await {
-            Ok(visualization) => Ok(visualization.to_string()),
-            Err(e) => Ok(format!("Failed to inspect tree: {}", e)),
+            Ok(visualization) => Ok(structured_result(visualization.to_string(), &visualization)),
+            Err(e) => Ok(error_result(format!("Failed to inspect tree: {}", e))),
         }
     }
 
@@ -455,12 +1084,14 @@ impl TreeEngineServer {
     ///
     /// # Returns
     /// - Success: "Coherence validation: {PASSED/FAILED} (coherent: {bool}, {count} contradictions found, {count} nodes eliminated)"
+    ///   followed by one line per contradiction, the eliminated nodes and a truth-table summary
     /// - Error: "Failed to validate coherence: {error_description}"
     ///
     /// # Analysis Performed
     /// - Logical consistency between parent-child relationships
     /// - Probability value coherence across branches
     /// - Reasoning chain validation
+    /// - Truth-assignment checks over node propositions (see set_proposition)
     /// - Contradiction detection and resolution suggestions
     ///
     /// # Example MCP Request
@@ -473,18 +1104,38 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "LOGICAL VALIDATION: Perform comprehensive logical consistency analysis to detect contradictions, reasoning conflicts, and coherence violations. Identifies problematic nodes and provides elimination suggestions. Use this during tree development to ensure logical soundness and before final analysis to guarantee reasoning integrity. Reports pass/fail status with detailed findings.")]
-    async fn validate_coherence(&self, Parameters(_request): Parameters<ValidateCoherenceRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "LOGICAL VALIDATION: Perform comprehensive logical consistency analysis to detect contradictions, reasoning conflicts, and coherence violations. Identifies problematic nodes and provides elimination suggestions. Use this during tree development to ensure logical soundness and before final analysis to guarantee reasoning integrity. Reports pass/fail status with detailed findings.", output_schema = output_schema::<CoherenceAnalysis>())]
+    async fn validate_coherence(&self, Parameters(_request): Parameters<ValidateCoherenceRequest>) -> Result<CallToolResult, ErrorData> {
         let service_arc = self.get_service().await;
         let service = service_arc.lock().await;
 
         match service.validate_coherence().await {
-            Ok(result) => Ok(format!("Coherence validation: {} (coherent: {}, {} contradictions found, {} nodes eliminated)",
-                if result.is_coherent { "PASSED" } else { "FAILED" },
-                result.is_coherent,
-                result.contradictions.len(),
-                result.eliminated_nodes.len())),
-            Err(e) => Ok(format!("Failed to validate coherence: {}", e)),
+            Ok(result) => {
+                let mut report = format!("Coherence validation: {} (coherent: {}, {} contradictions found, {} nodes eliminated)",
+                    if result.is_coherent { "PASSED" } else { "FAILED" },
+                    result.is_coherent,
+                    result.contradictions.len(),
+                    result.eliminated_nodes.len());
+                for contradiction in &result.contradictions {
+                    report.push_str(&format!("\n⚠️  [{}] {}", contradiction.node_id, contradiction.explanation));
+                    for premise in &contradiction.conflicting_premises {
+                        report.push_str(&format!("\n      - {}", premise));
+                    }
+                }
+                if !result.eliminated_nodes.is_empty() {
+                    report.push_str(&format!("\n❌ Eliminated: {}", result.eliminated_nodes.join(", ")));
+                }
+                for warning in &result.warnings {
+                    report.push_str(&format!("\n❔ [{}] {}", warning.node_id, warning.message));
+                }
+                if !result.truth_table.is_empty() {
+                    let consistent = result.truth_table.iter().filter(|row| row.is_consistent).count();
+                    report.push_str(&format!("\n🔢 Truth table: {} of {} assignments satisfy at least one scenario path",
+                        consistent, result.truth_table.len()));
+                }
+                Ok(structured_result(report, &result))
+            }
+            Err(e) => Ok(error_result(format!("Failed to validate coherence: {}", e))),
         }
     }
 
@@ -518,18 +1169,18 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "MATHEMATICAL VALIDATION: Analyze probability values throughout the tree for mathematical consistency. Validates probability ranges [0.0-1.0], confidence levels [1-10], sibling probability relationships, and parent-child consistency. Use this to ensure mathematical soundness before pruning or export. Provides detailed violation reports and correction suggestions.")]
-    async fn probability_status(&self, Parameters(_request): Parameters<ProbabilityStatusRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "MATHEMATICAL VALIDATION: Analyze probability values throughout the tree for mathematical consistency. Validates probability ranges [0.0-1.0], confidence levels [1-10], sibling probability relationships, and parent-child consistency. Use this to ensure mathematical soundness before pruning or export. Provides detailed violation reports and correction suggestions.", output_schema = output_schema::<ValidationResult>())]
+    async fn probability_status(&self, Parameters(_request): Parameters<ProbabilityStatusRequest>) -> Result<CallToolResult, ErrorData> {
         let service_arc = self.get_service().await;
         let service = service_arc.lock().await;
 
         match service.probability_status().await {
-            Ok(result) => Ok(format!("Probability status: {} (valid: {}, {} violations, {} suggestions)",
+            Ok(result) => Ok(structured_result(format!("Probability status: {} (valid: {}, {} violations, {} suggestions)",
                 if result.is_valid { "VALID" } else { "INVALID" },
                 result.is_valid,
                 result.violations.len(),
-                result.suggestions.len())),
-            Err(e) => Ok(format!("Failed to get probability status: {}", e)),
+                result.suggestions.len()), &result)),
+            Err(e) => Ok(error_result(format!("Failed to get probability status: {}", e))),
         }
     }
 
@@ -541,7 +1192,7 @@ impl TreeEngineServer {
     /// for contextual operations.
     ///
     /// # MCP Tool Parameters
-    /// - `node_id` (string): ID of the node to navigate to (must be a valid existing node)
+    /// - `node_id` (string): Friendly alias, record id or unambiguous prefix of the node to navigate to
     /// - `justification` (string): Reason for navigating to this node (for logging/debugging)
     ///
     /// # Returns
@@ -561,20 +1212,27 @@ impl TreeEngineServer {
     ///   "params": {
     ///     "name": "navigate_to",
     ///     "arguments": {
-    ///       "node_id": "node:xyz789",
+    ///       "node_id": "clever_turing",
     ///       "justification": "Want to add more children to this specific branch"
     ///     }
     ///   }
     /// }
     /// ```
-    #[tool(description = "MANUAL CURSOR CONTROL: Explicitly set the cursor to any existing node for manual control over where add_leaf will place new children. Useful for non-linear tree building, returning to previous branches, or precise cursor positioning beyond automatic expand_leaf behavior. Provide exact node_id and justification. After navigation, add_leaf adds children to the target node.")]
-    async fn navigate_to(&self, Parameters(request): Parameters<NavigateToRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "MANUAL CURSOR CONTROL: Explicitly set the cursor to any existing node for manual control over where add_leaf will place new children. Useful for non-linear tree building, returning to previous branches, or precise cursor positioning beyond automatic expand_leaf behavior. Provide the node_id (friendly alias, record id or unambiguous prefix) and justification. After navigation, add_leaf adds children to the target node.", output_schema = output_schema::<NodeRef>())]
+    async fn navigate_to(&self, Parameters(request): Parameters<NavigateToRequest>) -> Result<CallToolResult, ErrorData> {
         let service = self.get_service().await;
         let mut service = service.lock().await;
 
         match service.navigate_to(request.node_id.clone()).await {
-            Ok(_) => Ok(format!("Successfully navigated to node {}. Ready to add children to this node.", request.node_id)),
-            Err(e) => Ok(format!("Failed to navigate: {}", e)),
+            Ok(_) => {
+                let node_id = service.resolve_node_ref(&request.node_id).await.map(|id| id.to_string()).unwrap_or_else(|_| request.node_id.clone());
+                let alias = service.get_friendly_id(&request.node_id).await.unwrap_or_else(|_| request.node_id.clone());
+                Ok(structured_result(
+                    format!("Successfully navigated to node {}. Ready to add children to this node.", alias),
+                    &NodeRef { node_id, friendly_id: alias },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to navigate: {}", e))),
         }
     }
 
@@ -590,6 +1248,7 @@ impl TreeEngineServer {
     ///   - "EqualLikelihood": Adjusts probabilities toward equal distribution
     ///   - "CognitiveOverload": Simplifies complex probability distributions
     /// - `reasoning` (string): Explanation for why balancing is needed in this scenario
+    /// - `dry_run` (optional bool): Report the new probabilities and which paths change, without writing
     ///
     /// # Returns
     /// - Success: "Balanced {count} nodes for {uncertainty_type}: {details}"
@@ -619,8 +1278,8 @@ impl TreeEngineServer {
     ///   }
     /// }
     /// ```
-    #[tool(description = "LAPLACE SMOOTHING: Applies Bayesian probability smoothing using Laplace's rule of succession to handle uncertainty and prevent zero-probability scenarios. Uses different α parameters: InsufficientData (α=0.5, Jeffreys prior), EqualLikelihood (α=1.0, uniform prior), CognitiveOverload (α=2.0, strong regularization). Formula: P_smooth = (count + α) / (total + α×categories). This is the standard technique in machine learning for probability estimation with sparse data.")]
-    async fn balance_leafs(&self, Parameters(request): Parameters<BalanceLeafsRequest>) -> Result<String, ErrorData> {
+    #[tool(description = "LAPLACE SMOOTHING: Applies Bayesian probability smoothing using Laplace's rule of succession to handle uncertainty and prevent zero-probability scenarios. Uses different α parameters: InsufficientData (α=0.5, Jeffreys prior), EqualLikelihood (α=1.0, uniform prior), CognitiveOverload (α=2.0, strong regularization). Formula: P_smooth = (count + α) / (total + α×categories). This is the standard technique in machine learning for probability estimation with sparse data. Leaves pinned with lock_node keep their probabilities and the others share the remaining mass; skipped leaves are listed. Set dry_run=true to preview the new probabilities and affected paths without changing the tree.", output_schema = output_schema::<BalancingResult>())]
+    async fn balance_leafs(&self, Parameters(request): Parameters<BalanceLeafsRequest>) -> Result<CallToolResult, ErrorData> {
         let service_arc = self.get_service().await;
         let mut service = service_arc.lock().await;
 
@@ -630,73 +1289,1157 @@ impl TreeEngineServer {
             _ => UncertaintyType::InsufficientData, // Default
         };
 
-        match service.balance_leafs(uncertainty_type).await {
+        match service.balance_leafs(uncertainty_type, request.dry_run.unwrap_or(false)).await {
+            Ok(result) if result.dry_run => Ok(structured_result(format!(
+                "LAPLACE SMOOTHING PREVIEW (dry run, nothing was written):\n\
+                • Would balance: {} nodes | Laplace α: {:.1}\n\
+                • Method: {}\n\
+                • Skipped (locked): {}\n\
+                • Skipped (residual): {}\n\
+                • Affected paths:\n{}",
+                result.balanced_nodes.len(),
+                result.laplace_alpha,
+                result.smoothing_explanation,
+                if result.skipped_locked.is_empty() { "none".to_string() } else { result.skipped_locked.join(", ") },
+                if result.skipped_residual.is_empty() { "none".to_string() } else { result.skipped_residual.join(", ") },
+                affected_paths_text(&result.affected_paths)
+            ), &result)),
             Ok(result) => {
-                Ok(format!(
+                Ok(structured_result(format!(
                     "LAPLACE SMOOTHING COMPLETE:\n\
                     • Balanced: {} nodes | Total processed: {}\n\
                     • Uncertainty type: {:?} | Laplace α: {:.1}\n\
                     • Method: {}\n\
                     • Reasoning: {}\n\
+                    • Skipped (locked): {}\n\
+                    • Skipped (residual): {}\n\
                     • Result: Probabilities smoothed using Bayesian prior to reduce overfitting and handle sparse data",
                     result.balanced_nodes.len(),
                     result.total_count,
                     result.uncertainty_type,
                     result.laplace_alpha,
                     result.smoothing_explanation,
-                    request.reasoning
-                ))
+                    request.reasoning,
+                    if result.skipped_locked.is_empty() { "none".to_string() } else { result.skipped_locked.join(", ") },
+                    if result.skipped_residual.is_empty() { "none".to_string() } else { result.skipped_residual.join(", ") }
+                ), &result))
             },
-            Err(e) => Ok(format!("Failed to balance leafs: {}", e)),
+            Err(e) => Ok(error_result(format!("Failed to balance leafs: {}", e))),
         }
     }
-}
 
-#[tool_handler]
-impl ServerHandler for TreeEngineServer {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            protocol_version: ProtocolVersion::V_2025_06_18,
-            capabilities: ServerCapabilities::builder()
-                .enable_tools()
-                .build(),
-            server_info: Implementation::from_build_env(),
-            instructions: Some(
-                "# Deep Analytics Tree Engine MCP Server\n\n\
-                This server provides advanced probabilistic decision tree analysis for complex decision-making scenarios.\n\n\
-                ## PRIMARY PURPOSE\n\
-                Build and analyze probability trees to evaluate complex decisions by systematically breaking them down into\n\
-                logical branches with quantified probabilities, detailed reasoning, and confidence assessments.\n\n\
-                ## MANDATORY WORKFLOW SEQUENCE (MUST follow in order):\n\n\
-                ### Phase 1: INITIALIZATION (Required First Step)\n\
-                1. **create_tree(premise, complexity)** - ALWAYS START HERE\n\
-                   - Creates root node and positions cursor at root\n\
-                   - Complexity 1-3: Simple binary decisions\n\
-                   - Complexity 4-7: Multi-factor analysis (most common)\n\
-                   - Complexity 8-10: Complex multi-layered decisions\n\
-                   - This CLEARS any existing tree state\n\n\
-                ### Phase 2: BUILDING (Core Tree Construction)\n\
-                2. **add_leaf(premise, reasoning, probability, confidence)** - Add root's children\n\
-                   - IMMEDIATELY after create_tree, add 2-4 initial branches to root\n\
-                   - Each branch represents a major pathway/outcome\n\
-                   - Probability: 0.0-1.0 (CRITICAL: siblings must NOT exceed parent's probability)\n\
-                   - For root children: total can approach 1.0 but should not exceed it\n\
-                   - For deeper levels: children's probabilities are constrained by parent's probability\n\
-                   - Confidence: 1-10 (higher = more certain about assessment)\n\
-                   - Reasoning must be detailed and specific\n\n\
-                3. **expand_leaf(node_id, rationale)** - Prepare node for children\n\
-                   - Moves cursor to specific node for deeper analysis\n\
-                   - MANDATORY before adding children to any non-root node\n\
-                   - Use exact node_id from previous responses\n\
-                   - Provide detailed rationale for why this branch needs expansion\n\n\
-                4. **REPEAT**: expand_leaf → add_leaf cycles for each level\n\
-                   - Build incrementally: root children → expand one → add its children → repeat\n\
-                   - Typical depth: 2-4 levels depending on complexity\n\n\
-                ### Phase 3: VALIDATION (Quality Assurance)\n\
-                5. **inspect_tree()** - Monitor construction progress\n\
-                   - Use frequently during building to track structure\n\
-                   - Shows node counts, depth, probability distribution\n\n\
-                6. **validate_coherence()** - Check logical consistency\n\
+    /// MCP Tool: Searches tree nodes by keyword or fuzzy text and by attributes.
+    ///
+    /// This tool finds nodes in large trees without scrolling through inspect_tree.
+    /// Text is matched against premises and reasoning; attribute filters narrow the
+    /// result by probability, confidence, depth, status and metadata keys.
+    ///
+    /// # MCP Tool Parameters
+    /// - `query` (optional string): Text to search for in premise and reasoning
+    /// - `mode` (optional string): "keyword" (full-text, default) or "fuzzy" (typo tolerant)
+    /// - `min_probability` / `max_probability` (optional f64): Probability range
+    /// - `min_confidence` / `max_confidence` (optional i64): Confidence range
+    /// - `min_depth` / `max_depth` (optional i64): Depth range (root is 0)
+    /// - `status` (optional string): "Active", "Expanded" or "Invalidated" ("Pruned" is accepted for "Invalidated")
+    /// - `metadata_keys` (optional array of strings): Metadata keys that must be present
+    /// - `limit` (optional usize): Maximum number of hits (default 20)
+    ///
+    /// # Returns
+    /// - Success: Ranked list of matching nodes with aliases and root paths
+    /// - Error: "Failed to search nodes: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "search_nodes",
+    ///     "arguments": {
+    ///       "query": "supplier delays",
+    ///       "mode": "fuzzy",
+    ///       "min_probability": 0.2
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "NODE SEARCH: Find nodes by text and attributes instead of scrolling through inspect_tree. 'query' is matched against premise and reasoning using full-text keyword search (mode 'keyword', default, with stemming and BM25 ranking) or typo-tolerant matching (mode 'fuzzy'); unknown modes or statuses are rejected. Optional filters: min/max_probability, min/max_confidence, min/max_depth, status ('Active' = no children yet, 'Expanded' = has children, 'Invalidated' or 'Pruned' = pruned; case-insensitive), metadata_keys (all must be present) and limit (default 20). Returns matching nodes with their friendly aliases and full root paths; the aliases can be passed to expand_leaf or navigate_to.", output_schema = output_schema::<NodeSearchResult>())]
+    async fn search_nodes(&self, Parameters(request): Parameters<SearchNodesRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let mode = match request.mode.as_deref().map(SearchMode::parse).unwrap_or(Ok(SearchMode::Keyword)) {
+            Ok(mode) => mode,
+            Err(e) => return Ok(error_result(format!("Failed to search nodes: {}", e))),
+        };
+
+        let status = match request.status.as_deref().map(NodeStatus::parse).transpose() {
+            Ok(status) => status,
+            Err(e) => return Ok(error_result(format!("Failed to search nodes: {}", e))),
+        };
+
+        let query = NodeSearchQuery {
+            text: request.query,
+            mode,
+            min_probability: request.min_probability,
+            max_probability: request.max_probability,
+            min_confidence: request.min_confidence,
+            max_confidence: request.max_confidence,
+            min_depth: request.min_depth,
+            max_depth: request.max_depth,
+            status,
+            metadata_keys: request.metadata_keys.unwrap_or_default(),
+            limit: request.limit,
+        };
+
+        match service.search_nodes(query).await {
+            Ok(result) => Ok(structured_result(result.to_string(), &result)),
+            Err(e) => Ok(error_result(format!("Failed to search nodes: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Attaches a cited source to a node as structured evidence.
+    ///
+    /// This tool records why an estimate was made, instead of leaving it buried in
+    /// free-text reasoning. Evidence is listed with list_evidence and gathered into
+    /// a per-path bibliography by export_paths.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or unambiguous prefix of the node
+    /// - `source_title` (string): Title of the source
+    /// - `source_ref` (string): URI or document reference (e.g. "Q3 board deck, p.12")
+    /// - `quote` (optional string): Relevant excerpt from the source
+    /// - `date` (optional string): Source date as YYYY-MM-DD
+    /// - `strength` (optional string): "Weak", "Moderate" (default), "Strong" or "Conclusive"
+    ///
+    /// # Returns
+    /// - Success: "Attached evidence {evidence_id} to node {node_id}: {evidence}"
+    /// - Error: "Failed to attach evidence: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "attach_evidence",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing",
+    ///       "source_title": "Supplier audit",
+    ///       "source_ref": "https://example.com/audit.pdf",
+    ///       "quote": "Lead times doubled in Q1",
+    ///       "date": "2025-03-14",
+    ///       "strength": "Strong"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "EVIDENCE CITATION: Attach a structured source to a node to document why its probability was estimated. Requires node_id (friendly alias, record id or prefix), source_title and source_ref (URI or document reference). Optional: quote (relevant excerpt), date (YYYY-MM-DD) and strength ('Weak', 'Moderate' default, 'Strong', 'Conclusive'; case-insensitive, other values are rejected). Attached evidence appears in list_evidence and in the bibliography of export_paths for every surviving path that passes through the node.", output_schema = output_schema::<Evidence>())]
+    async fn attach_evidence(&self, Parameters(request): Parameters<AttachEvidenceRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let date = match parse_date(request.date.as_deref()) {
+            Ok(date) => date,
+            Err(e) => return Ok(error_result(format!("Failed to attach evidence: {}", e))),
+        };
+
+        let strength = match request.strength.as_deref().map(EvidenceStrength::parse).unwrap_or(Ok(EvidenceStrength::Moderate)) {
+            Ok(strength) => strength,
+            Err(e) => return Ok(error_result(format!("Failed to attach evidence: {}", e))),
+        };
+
+        let node_id = request.node_id.clone();
+        match service.attach_evidence(
+            request.node_id,
+            request.source_title,
+            request.source_ref,
+            request.quote.unwrap_or_default(),
+            date,
+            strength,
+        ).await {
+            Ok(evidence) => {
+                let alias = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                Ok(structured_result(format!("Attached evidence {} to node {}: {}", evidence.id, alias, evidence), &evidence))
+            },
+            Err(e) => Ok(error_result(format!("Failed to attach evidence: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Lists the evidence attached to a node or to the whole tree.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (optional string): Node to list; when omitted, every node with evidence is listed
+    ///
+    /// # Returns
+    /// - Success: Evidence grouped by node with title, reference, date, strength and quote
+    /// - Error: "Failed to list evidence: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "list_evidence",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "EVIDENCE REVIEW: List the structured evidence attached with attach_evidence. Provide node_id to list one node's sources, or omit it to list every node that has evidence. Each entry shows source title, URI/document reference, date, strength and quote. Use this to audit which estimates are well supported before export.", output_schema = output_schema::<ItemList<NodeEvidence>>())]
+    async fn list_evidence(&self, Parameters(request): Parameters<ListEvidenceRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.list_evidence(request.node_id).await {
+            Ok(entries) => {
+                let text = if entries.is_empty() {
+                    "No evidence attached to any node yet.".to_string()
+                } else {
+                    entries.iter().map(|entry| entry.to_string()).collect::<Vec<_>>().join("\n")
+                };
+                Ok(structured_result(text, &ItemList { items: entries }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to list evidence: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Attaches a formal proposition to a node's premise.
+    ///
+    /// Propositions let validate_coherence reason about the logic of the tree rather
+    /// than only its numbers: it enumerates truth assignments over the atoms, flags
+    /// paths whose propositions cannot all hold, and siblings that can hold at once.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `proposition` (optional string): Formula over atoms with AND/&, OR/|, NOT/! and
+    ///   parentheses, e.g. "recession AND NOT rates_up"; omit or leave blank to clear it
+    ///
+    /// # Returns
+    /// - Success: "Set proposition of node {node_id}: {formula}" or "Cleared proposition of node {node_id}"
+    /// - Error: "Failed to set proposition: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "set_proposition",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing",
+    ///       "proposition": "recession AND NOT rates_up"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "FORMAL LOGIC: Attach a propositional formula to a node's premise so validate_coherence can check it logically. Requires node_id (friendly alias, record id or prefix) and proposition, built from atoms (e.g. recession, rates_up) with AND/&, OR/|, NOT/! and parentheses. Use the same atom name wherever nodes talk about the same fact. Omit proposition to clear it. validate_coherence then reports paths whose propositions are jointly unsatisfiable (eliminating the nodes below them) and sibling branches that are not mutually exclusive.", output_schema = output_schema::<PropositionUpdate>())]
+    async fn set_proposition(&self, Parameters(request): Parameters<SetPropositionRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let node_id = request.node_id.clone();
+        match service.set_proposition(request.node_id, request.proposition).await {
+            Ok(proposition) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let text = match &proposition {
+                    Some(formula) => format!("Set proposition of node {}: {}", node_id, formula),
+                    None => format!("Cleared proposition of node {}", node_id),
+                };
+                Ok(structured_result(text, &PropositionUpdate { node_id, proposition }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to set proposition: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Links an existing node as an additional child of another node.
+    ///
+    /// Cross-links turn the tree into a scenario DAG so that two different causes can
+    /// lead to the same downstream outcome without duplicating the branch. Paths through
+    /// the link are enumerated by export_paths and shown by inspect_tree.
+    ///
+    /// # MCP Tool Parameters
+    /// - `parent_id` (string): Friendly alias, record id or prefix of the extra parent
+    /// - `child_id` (string): Friendly alias, record id or prefix of the shared outcome
+    /// - `probability` (number): Probability of reaching the child through this parent (0.0-1.0)
+    /// - `reasoning` (string): Why this cause also leads to the outcome
+    ///
+    /// # Returns
+    /// - Success: "Linked {child} under {parent} with probability {p}"
+    /// - Error: "Failed to add cross-link: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "add_cross_link",
+    ///     "arguments": {
+    ///       "parent_id": "clever_turing",
+    ///       "child_id": "brave_curie",
+    ///       "probability": 0.3,
+    ///       "reasoning": "A rate shock also triggers the market collapse"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "SCENARIO DAG: Make an existing node an additional child of another node, so shared downstream outcomes (e.g. two causes leading to the same market collapse) are modeled once instead of duplicated. Requires parent_id and child_id (friendly alias, record id or prefix), probability (0.0-1.0, chance of reaching the child through this parent) and reasoning. The link counts toward the parent's child probability budget; links that would create a cycle, duplicate an existing edge or target the root are rejected. export_paths enumerates one path per distinct route through the DAG.", output_schema = output_schema::<CrossLinkRef>())]
+    async fn add_cross_link(&self, Parameters(request): Parameters<AddCrossLinkRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let parent_id = request.parent_id.clone();
+        let child_id = request.child_id.clone();
+        match service.add_cross_link(request.parent_id, request.child_id, request.probability, request.reasoning).await {
+            Ok(link) => {
+                let parent = service.get_friendly_id(&parent_id).await.unwrap_or(parent_id);
+                let child = service.get_friendly_id(&child_id).await.unwrap_or(child_id);
+                Ok(structured_result(
+                    format!("Linked {} under {} with probability {:.3}", child, parent, link.probability),
+                    &CrossLinkRef { parent_id: parent, child_id: child, probability: link.probability, reasoning: link.reasoning },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to add cross-link: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Removes a cross-link previously added with add_cross_link.
+    ///
+    /// # MCP Tool Parameters
+    /// - `parent_id` (string): Friendly alias, record id or prefix of the linked parent
+    /// - `child_id` (string): Friendly alias, record id or prefix of the linked child
+    ///
+    /// # Returns
+    /// - Success: "Removed cross-link {parent} -> {child}"
+    /// - Error: "Failed to remove cross-link: {error_description}"
+    #[tool(description = "SCENARIO DAG: Remove a cross-link added with add_cross_link. Requires parent_id and child_id. Only the extra edge is removed; the child keeps its primary parent.", output_schema = output_schema::<CrossLinkRef>())]
+    async fn remove_cross_link(&self, Parameters(request): Parameters<RemoveCrossLinkRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let parent_id = request.parent_id.clone();
+        let child_id = request.child_id.clone();
+        match service.remove_cross_link(request.parent_id, request.child_id).await {
+            Ok(link) => {
+                let parent = service.get_friendly_id(&parent_id).await.unwrap_or(parent_id);
+                let child = service.get_friendly_id(&child_id).await.unwrap_or(child_id);
+                Ok(structured_result(
+                    format!("Removed cross-link {} -> {}", parent, child),
+                    &CrossLinkRef { parent_id: parent, child_id: child, probability: link.probability, reasoning: link.reasoning },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to remove cross-link: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Captures the full current tree under a name.
+    ///
+    /// # MCP Tool Parameters
+    /// - `name` (string): Unique checkpoint name, e.g. "before pruning" ("current" is reserved)
+    ///
+    /// # Returns
+    /// - Success: "Checkpoint saved: {summary}"
+    /// - Error: "Failed to create checkpoint: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "checkpoint",
+    ///     "arguments": {
+    ///       "name": "before pruning"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "VERSIONING: Save the full current tree (state, nodes, probabilities, evidence and cross-links) under a unique name such as 'before pruning' or 'after new evidence'. Checkpoints survive create_tree, so later trees can be compared against them with diff_trees. The name 'current' is reserved for the live tree.", output_schema = output_schema::<CheckpointSummary>())]
+    async fn checkpoint(&self, Parameters(request): Parameters<CheckpointRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.checkpoint(request.name).await {
+            Ok(summary) => Ok(structured_result(format!("Checkpoint saved: {}", summary), &summary)),
+            Err(e) => Ok(error_result(format!("Failed to create checkpoint: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Lists every saved checkpoint, oldest first.
+    ///
+    /// # Returns
+    /// - Success: One line per checkpoint with name, tree id, node count and capture time
+    /// - Error: "Failed to list checkpoints: {error_description}"
+    #[tool(description = "VERSIONING: List all checkpoints saved with the checkpoint tool, oldest first, with their tree id, node count and capture time. Use the names as from/to in diff_trees.", output_schema = output_schema::<ItemList<CheckpointSummary>>())]
+    async fn list_checkpoints(&self, Parameters(_request): Parameters<ListCheckpointsRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.list_checkpoints().await {
+            Ok(checkpoints) => {
+                let text = if checkpoints.is_empty() {
+                    "No checkpoints saved yet.".to_string()
+                } else {
+                    checkpoints.iter().map(|checkpoint| checkpoint.to_string()).collect::<Vec<_>>().join("\n")
+                };
+                Ok(structured_result(text, &ItemList { items: checkpoints }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to list checkpoints: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Reports structural differences between two tree versions.
+    ///
+    /// # MCP Tool Parameters
+    /// - `from` (string): Checkpoint name, tree id or "current"
+    /// - `to` (string): Checkpoint name, tree id or "current"
+    /// - `top_k` (optional number): How many top-ranked paths to compare (default 5)
+    ///
+    /// # Returns
+    /// - Success: Added/removed nodes, probability, confidence and premise changes, and top-k ranking changes
+    /// - Error: "Failed to diff trees: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "diff_trees",
+    ///     "arguments": {
+    ///       "from": "before pruning",
+    ///       "to": "current",
+    ///       "top_k": 3
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "VERSIONING: Compare two versions of a tree. from and to each accept a checkpoint name, a tree id (uses its latest checkpoint) or 'current' for the live tree. Reports nodes added or removed, probability and confidence deltas, changed premises, and paths whose rank changed among the top_k most probable root-to-leaf paths (default 5). Nodes are matched by id, or by identical premise across different trees.", output_schema = output_schema::<TreeDiff>())]
+    async fn diff_trees(&self, Parameters(request): Parameters<DiffTreesRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.diff_trees(request.from, request.to, request.top_k.unwrap_or(5)).await {
+            Ok(diff) => Ok(structured_result(diff.to_string(), &diff)),
+            Err(e) => Ok(error_result(format!("Failed to diff trees: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Merges another analyst's tree, saved as a checkpoint, into the live tree.
+    ///
+    /// # MCP Tool Parameters
+    /// - `source` (string): Checkpoint name or tree id of the tree to merge in
+    /// - `similarity_threshold` (optional number): Minimum premise similarity to match nodes (default 0.75)
+    /// - `dry_run` (optional bool): Report the merge and which paths change, without writing
+    ///
+    /// # Returns
+    /// - Success: Matched and pooled nodes, grafted nodes, sibling-sum conflicts and affected paths
+    /// - Error: "Failed to merge tree: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "merge_tree",
+    ///     "arguments": {
+    ///       "source": "analyst_b",
+    ///       "similarity_threshold": 0.8
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "COLLABORATION: Merge another tree built from the same root premise into the live tree. Save the other tree first with checkpoint, then pass its name (or tree id) as source. Children of matched parents are aligned by premise similarity (similarity_threshold, default 0.75); matched nodes get a confidence-weighted pooled probability with combined reasoning and evidence, and unmatched subtrees are grafted under their matched parent. Probabilities are not renormalized: parents whose children now sum above them are returned as conflicts for manual resolution; a graft beyond the depth or branch limit fails the whole merge. Set dry_run=true to preview the merge and affected paths without changing the tree.", output_schema = output_schema::<MergeReport>())]
+    async fn merge_tree(&self, Parameters(request): Parameters<MergeTreeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.merge_tree(request.source, request.similarity_threshold, request.dry_run.unwrap_or(false)).await {
+            Ok(report) => Ok(structured_result(format!("{}\n• Affected paths:\n{}",
+                report, affected_paths_text(&report.affected_paths)), &report)),
+            Err(e) => Ok(error_result(format!("Failed to merge tree: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Records which leaf scenario actually occurred.
+    ///
+    /// The tree's leaf probabilities are frozen with the resolution so the forecast can
+    /// be scored later, even after a new tree replaces it.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the leaf that occurred
+    /// - `notes` (optional string): How the outcome was established
+    ///
+    /// # Returns
+    /// - Success: The resolution with the forecast probability, Brier score and log score
+    /// - Error: "Failed to resolve outcome: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "resolve_outcome",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing",
+    ///       "notes": "Launch slipped three weeks because of the supplier"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "FORECAST RESOLUTION: Record which leaf scenario actually happened once a forecast resolves. Requires node_id (friendly alias, record id or prefix of a leaf); optional notes. Freezes every leaf's probability with the outcome and reports the tree's Brier and log scores. Resolving the same tree again replaces the earlier resolution. Use calibration_report to track accuracy across trees.", output_schema = output_schema::<Resolution>())]
+    async fn resolve_outcome(&self, Parameters(request): Parameters<ResolveOutcomeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.resolve_outcome(request.node_id, request.notes).await {
+            Ok(resolution) => Ok(structured_result(resolution.to_string(), &Resolution { id: None, ..resolution })),
+            Err(e) => Ok(error_result(format!("Failed to resolve outcome: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Scores resolved forecasts and reports their calibration.
+    ///
+    /// # MCP Tool Parameters
+    /// - `tree_id` (optional string): A tree id or "current"; omit to cover every resolved tree
+    ///
+    /// # Returns
+    /// - Success: Per-tree Brier and log scores, their means, and calibration buckets
+    /// - Error: "Failed to build calibration report: {error_description}"
+    #[tool(description = "CALIBRATION: Measure how good past probability estimates were. Optional tree_id (a tree id or 'current'); omit it to cover every tree resolved with resolve_outcome. Reports each tree's Brier score (0 perfect, 2 worst) and log score (0 perfect), their means, and calibration buckets comparing stated probabilities with how often those scenarios actually occurred.", output_schema = output_schema::<CalibrationReport>())]
+    async fn calibration_report(&self, Parameters(request): Parameters<CalibrationReportRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.calibration_report(request.tree_id).await {
+            Ok(report) => Ok(structured_result(report.to_string(), &report)),
+            Err(e) => Ok(error_result(format!("Failed to build calibration report: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Sets a node's expected time window and review date.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `window_start` / `window_end` (optional strings): YYYY-MM-DD dates between which
+    ///   the scenario is expected to occur; give both or neither
+    /// - `review_by` (optional string): YYYY-MM-DD date by which to revisit the estimate
+    ///
+    /// Omitted values are cleared.
+    ///
+    /// # Returns
+    /// - Success: "Set time horizon of node {node_id}: window {window}, review by {date}"
+    /// - Error: "Failed to set time horizon: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "set_time_horizon",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing",
+    ///       "window_start": "2025-04-01",
+    ///       "window_end": "2025-06-30",
+    ///       "review_by": "2025-05-01"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "TIME HORIZON: Record when a scenario is expected to occur and when its estimate should be revisited, so the tree can be maintained as a living forecast. Requires node_id; optional window_start and window_end (YYYY-MM-DD, give both) and review_by (YYYY-MM-DD). Omitted values are cleared. Dated scenarios are ordered by the timeline tool, and inspect_tree flags estimates whose review date or window has passed.", output_schema = output_schema::<TimeHorizonUpdate>())]
+    async fn set_time_horizon(&self, Parameters(request): Parameters<SetTimeHorizonRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let (start, end, review_by) = match (
+            parse_date(request.window_start.as_deref()),
+            parse_date(request.window_end.as_deref()),
+            parse_date(request.review_by.as_deref()),
+        ) {
+            (Ok(start), Ok(end), Ok(review_by)) => (start, end, review_by),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return Ok(error_result(format!("Failed to set time horizon: {}", e))),
+        };
+        let time_window = match (start, end) {
+            (Some(start), Some(end)) => match TimeWindow::new(start, end) {
+                Ok(window) => Some(window),
+                Err(e) => return Ok(error_result(format!("Failed to set time horizon: {}", e))),
+            },
+            (None, None) => None,
+            _ => return Ok(error_result("Failed to set time horizon: window_start and window_end must be given together".to_string())),
+        };
+
+        let node_id = request.node_id.clone();
+        match service.set_time_horizon(request.node_id, time_window, review_by).await {
+            Ok(()) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                Ok(structured_result(format!("Set time horizon of node {}: window {}, review by {}",
+                    node_id,
+                    time_window.map(|window| window.to_string()).unwrap_or_else(|| "none".to_string()),
+                    review_by.map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "none".to_string())),
+                    &TimeHorizonUpdate { node_id, time_window, review_by }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to set time horizon: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Pins a node, or a whole subtree, against automated changes.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `locked` (optional bool): Defaults to true; false removes the lock
+    /// - `subtree` (optional bool): Freeze the node and all its descendants instead
+    ///
+    /// # Returns
+    /// - Success: "Locked node {node_id}" / "Froze subtree of {node_id}" and their reverses
+    /// - Error: "Failed to lock node: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "lock_node",
+    ///     "arguments": {
+    ///       "node_id": "clever_turing",
+    ///       "subtree": true
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "LOCK: Pin an estimate the analyst has settled so automated operations such as prune_tree and balance_leafs leave it alone. Requires node_id; locked defaults to true (false unlocks); subtree=true freezes the node and every descendant, including ones added later. Locked nodes are never pruned, balancing redistributes probability only among unlocked leaves, and both reports list the nodes skipped because they were locked.", output_schema = output_schema::<LockUpdate>())]
+    async fn lock_node(&self, Parameters(request): Parameters<LockNodeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let locked = request.locked.unwrap_or(true);
+        let subtree = request.subtree.unwrap_or(false);
+        let node_id = request.node_id.clone();
+        match service.set_lock(request.node_id, locked, subtree).await {
+            Ok(still_locked) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let action = match (locked, subtree) {
+                    (true, false) => format!("Locked node {}", node_id),
+                    (true, true) => format!("Froze subtree of {}", node_id),
+                    (false, false) => format!("Unlocked node {}", node_id),
+                    (false, true) => format!("Unfroze subtree of {}", node_id),
+                };
+                let text = if locked {
+                    format!("{}: prune and balance operations will leave it unchanged", action)
+                } else if still_locked {
+                    format!("{}, but it stays pinned by its own lock or a frozen ancestor", action)
+                } else {
+                    format!("{}: prune and balance operations may change it again", action)
+                };
+                Ok(structured_result(text, &LockUpdate { node_id, locked, subtree, still_locked }))
+            }
+            Err(e) => Ok(error_result(format!("Failed to lock node: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Sets the fault-tree gate combining a node's child events.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `gate` (optional string): "AND", "OR" or "K_OF_N"; omit to clear the gate
+    /// - `k` (optional integer): Threshold of a K_OF_N gate
+    ///
+    /// # Returns
+    /// - Success: "Set gate of node {node_id} to {gate}"
+    /// - Error: "Failed to set gate: {error_description}"
+    #[tool(description = "FAULT TREE GATE: Turn a node into a logic gate over its children for fault tree analysis. Requires node_id; gate is AND (all children occur), OR (any child occurs) or K_OF_N with k (at least k children occur); omit gate to clear it. Nodes with children but no gate act as OR. Leaves are basic events whose probability is their independent chance of occurring.", output_schema = output_schema::<GateUpdate>())]
+    async fn set_gate(&self, Parameters(request): Parameters<SetGateRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let gate = match request.gate.as_deref().map(str::trim).filter(|gate| !gate.is_empty()) {
+            Some(name) => match Gate::parse(name, request.k) {
+                Ok(gate) => Some(gate),
+                Err(e) => return Ok(error_result(format!("Failed to set gate: {}", e))),
+            },
+            None => None,
+        };
+
+        let node_id = request.node_id.clone();
+        match service.set_gate(request.node_id, gate).await {
+            Ok(()) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let text = match gate {
+                    Some(gate) => format!("Set gate of node {} to {}", node_id, gate),
+                    None => format!("Cleared gate of node {}; it acts as OR again", node_id),
+                };
+                Ok(structured_result(text, &GateUpdate { node_id, gate }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to set gate: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Analyzes the tree as a fault tree.
+    ///
+    /// # MCP Tool Parameters
+    /// None - the root is the top event and leaves are basic events.
+    ///
+    /// # Returns
+    /// - Success: Top-event probability, gate outputs, minimal cut sets and importance measures
+    /// - Error: "Failed to analyze fault tree: {error_description}"
+    #[tool(description = "FAULT TREE ANALYSIS: Reliability analysis of the tree read as a fault tree. The root is the top event, leaves are independent basic events with their probability, and every other node combines its children through its gate (set_gate; default OR). Reports the top-event probability, each gate's output probability, the minimal cut sets (smallest combinations of basic events causing the top event) and each basic event's Fussell-Vesely and Birnbaum importance. Pruned nodes and cross-links are ignored.", output_schema = output_schema::<FaultTreeAnalysis>())]
+    async fn fault_tree_analysis(&self, Parameters(_request): Parameters<FaultTreeAnalysisRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.fault_tree_analysis().await {
+            Ok(analysis) => Ok(structured_result(analysis.to_string(), &analysis)),
+            Err(e) => Ok(error_result(format!("Failed to analyze fault tree: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Sets the impact score and risk category of a node.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `impact` (optional number): Severity if the scenario occurs, 0-10; omit to clear
+    /// - `category` (optional string): Risk category; omit to clear
+    ///
+    /// # Returns
+    /// - Success: "Set risk profile of node {node_id}: impact {impact}, category {category}"
+    /// - Error: "Failed to set risk profile: {error_description}"
+    #[tool(description = "RISK PROFILE: Give a node an impact (severity if the scenario occurs, 0 = negligible to 10 = catastrophic) and a risk category such as supply, legal or safety. Leaves without their own values inherit them from the nearest ancestor, so scoring a branch scores every scenario below it. Omitted fields are cleared. Feeds risk_matrix.", output_schema = output_schema::<RiskProfileUpdate>())]
+    async fn set_risk_profile(&self, Parameters(request): Parameters<SetRiskProfileRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let node_id = request.node_id.clone();
+        let (impact, category) = (request.impact, request.category.clone());
+        match service.set_risk_profile(request.node_id, request.impact, request.category).await {
+            Ok(()) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let category = category.map(|category| category.trim().to_string()).filter(|category| !category.is_empty());
+                Ok(structured_result(format!("Set risk profile of node {}: impact {}, category {}",
+                    node_id,
+                    impact.map(|impact| format!("{:.1}", impact)).unwrap_or_else(|| "inherited".to_string()),
+                    category.clone().unwrap_or_else(|| "inherited".to_string())),
+                    &RiskProfileUpdate { node_id, impact, category }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to set risk profile: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Bins the surviving leaves into a probability × impact heatmap.
+    ///
+    /// # MCP Tool Parameters
+    /// - `basis` (optional string): "path" (default) or "marginal" probability
+    /// - `probability_levels` (optional integer): Probability bands, 2-10 (defaults to 5)
+    /// - `impact_levels` (optional integer): Impact bands, 2-10 (defaults to 5)
+    ///
+    /// # Returns
+    /// - Success: The grid, each occupied cell's leaves and the aggregate risk per category
+    /// - Error: "Failed to build risk matrix: {error_description}"
+    #[tool(description = "RISK MATRIX: Probability × impact heatmap for reporting. Bins every surviving leaf scenario into a configurable grid (default 5×5) of equal-width probability and impact bands, using either path probability (basis=path, one entry per root-to-leaf path) or marginal probability (basis=marginal, mass reaching each leaf over all routes). Impact and category come from set_risk_profile, inherited from the nearest ancestor. Returns the grid with each cell's occupants and rating, the total probability × impact per category, and the leaves left unscored for lack of an impact.", output_schema = output_schema::<RiskMatrix>())]
+    async fn risk_matrix(&self, Parameters(request): Parameters<RiskMatrixRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let basis = match request.basis.as_deref().map(ProbabilityBasis::parse) {
+            Some(Ok(basis)) => basis,
+            Some(Err(e)) => return Ok(error_result(format!("Failed to build risk matrix: {}", e))),
+            None => ProbabilityBasis::Path,
+        };
+        let probability_levels = request.probability_levels.unwrap_or(DEFAULT_RISK_LEVELS);
+        let impact_levels = request.impact_levels.unwrap_or(DEFAULT_RISK_LEVELS);
+
+        match service.risk_matrix(basis, probability_levels, impact_levels).await {
+            Ok(matrix) => Ok(structured_result(matrix.to_string(), &matrix)),
+            Err(e) => Ok(error_result(format!("Failed to build risk matrix: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Switches the tree between scenario and argument mode.
+    ///
+    /// # MCP Tool Parameters
+    /// - `mode` (string): "scenario" or "argument"
+    ///
+    /// # Returns
+    /// - Success: "Tree is now in {mode} mode"
+    /// - Error: "Failed to set tree mode: {error_description}"
+    #[tool(description = "TREE MODE: Choose how children relate to their parent. scenario (default): children are mutually exclusive scenarios whose probabilities sum to at most the parent's. argument: the root is a claim under debate and children are arguments for or against their parent (tag them with tag_argument); sibling probabilities are not constrained and each node's probability is how plausible the argument is on its own. Score with argument_map.", output_schema = output_schema::<TreeModeUpdate>())]
+    async fn set_tree_mode(&self, Parameters(request): Parameters<SetTreeModeRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let mode = match TreeMode::parse(&request.mode) {
+            Ok(mode) => mode,
+            Err(e) => return Ok(error_result(format!("Failed to set tree mode: {}", e))),
+        };
+        match service.set_tree_mode(mode).await {
+            Ok(()) => Ok(structured_result(match mode {
+                TreeMode::Scenario => "Tree is now in scenario mode: sibling probabilities must sum to at most their parent's".to_string(),
+                TreeMode::Argument => "Tree is now in argument mode: tag children pro or con with tag_argument, then score with argument_map".to_string(),
+            }, &TreeModeUpdate { mode })),
+            Err(e) => Ok(error_result(format!("Failed to set tree mode: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Tags a node as an argument for or against its parent claim.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the node
+    /// - `stance` (optional string): "pro" or "con"; omit to clear the tag
+    /// - `strength` (optional number): 0-1, defaults to 1
+    ///
+    /// # Returns
+    /// - Success: "Tagged node {node_id} as {stance} (strength {strength})"
+    /// - Error: "Failed to tag argument: {error_description}"
+    #[tool(description = "TAG ARGUMENT: In argument mode, mark a node as pro (supports) or con (opposes) its parent claim, with a strength 0-1 (default 1) saying how much it would matter if it holds. The node's probability is its own plausibility. Arguments can have their own pro and con sub-arguments; a con against a con ends up supporting the root. Omit stance to clear the tag.", output_schema = output_schema::<ArgumentTagUpdate>())]
+    async fn tag_argument(&self, Parameters(request): Parameters<TagArgumentRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let tag = match request.stance.as_deref().map(str::trim).filter(|stance| !stance.is_empty()) {
+            Some(stance) => match Stance::parse(stance).and_then(|stance| ArgumentTag::new(stance, request.strength.unwrap_or(1.0))) {
+                Ok(tag) => Some(tag),
+                Err(e) => return Ok(error_result(format!("Failed to tag argument: {}", e))),
+            },
+            None => None,
+        };
+
+        let node_id = request.node_id.clone();
+        match service.tag_argument(request.node_id, tag).await {
+            Ok(()) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let text = match tag {
+                    Some(tag) => format!("Tagged node {} as {} (strength {:.2})", node_id, tag.stance, tag.strength),
+                    None => format!("Cleared argument tag of node {}; it is ignored by argument_map", node_id),
+                };
+                Ok(structured_result(text, &ArgumentTagUpdate { node_id, tag }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to tag argument: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Scores an argument-mode tree and judges its root claim.
+    ///
+    /// # MCP Tool Parameters
+    /// None - the root is the claim and tagged nodes are its arguments.
+    ///
+    /// # Returns
+    /// - Success: Verdict, claim score and the strongest arguments on each side
+    /// - Error: "Failed to score arguments: {error_description}"
+    #[tool(description = "ARGUMENT MAP: Verdict on the root claim of an argument-mode tree. Aggregates support bottom-up with DF-QuAD gradual semantics: each argument's plausibility (its probability) is raised by its pro sub-arguments and lowered by its con sub-arguments, each pulling with strength × score; the root claim starts undecided at 0.5. Returns HOLDS (≥ 0.6), CONTESTED or REJECTED (≤ 0.4), the claim score, and the strongest arguments on each side, counting a con against a con as support.", output_schema = output_schema::<ArgumentMap>())]
+    async fn argument_map(&self, Parameters(_request): Parameters<ArgumentMapRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.argument_map().await {
+            Ok(map) => Ok(structured_result(map.to_string(), &map)),
+            Err(e) => Ok(error_result(format!("Failed to score arguments: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Exports the tree as a Bayesian network.
+    ///
+    /// # MCP Tool Parameters
+    /// - `format` (optional string): "xmlbif" (default) or "bif"
+    ///
+    /// # Returns
+    /// - Success: The XMLBIF or BIF document
+    /// - Error: "Failed to export Bayesian network: {error_description}"
+    #[tool(description = "EXPORT BAYESIAN NETWORK: Convert the tree into a Bayesian network to continue the analysis in dedicated BN tools (Weka, SamIam, pgmpy, GeNIe via converters). Each sibling group becomes one variable whose states are the children's friendly ids (plus 'other' for unassigned probability); it depends on the variable holding its parent node and takes 'na' when that node is not the case. CPT entries are child probability / parent probability. Premises are kept as variable properties. format: xmlbif (XMLBIF 0.3, default) or bif (plain-text BIF).", output_schema = output_schema::<ExportedDocument>())]
+    async fn export_bayes_net(&self, Parameters(request): Parameters<ExportBayesNetRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let format = match request.format.as_deref().map(BayesNetFormat::parse).unwrap_or(Ok(BayesNetFormat::XmlBif)) {
+            Ok(format) => format,
+            Err(e) => return Ok(error_result(format!("Failed to export Bayesian network: {}", e))),
+        };
+        match service.export_bayes_net(format).await {
+            Ok(network) => Ok(structured_result(network.clone(), &ExportedDocument { format: format.as_str().to_string(), content: network })),
+            Err(e) => Ok(error_result(format!("Failed to export Bayesian network: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Creates a new tree from a tree-shaped Bayesian network.
+    ///
+    /// # MCP Tool Parameters
+    /// - `network` (string): XMLBIF or plain-text BIF document
+    /// - `complexity` (optional i64): Complexity of the new tree, 1-10 (defaults to 5)
+    ///
+    /// # Returns
+    /// - Success: The created nodes with their ids
+    /// - Error: "Failed to import Bayesian network: {error_description}"
+    #[tool(description = "IMPORT BAYESIAN NETWORK: Start a new tree from a simple tree-shaped Bayesian network in XMLBIF or plain-text BIF (detected automatically), such as one written by export_bayes_net. Each variable may have at most one parent and exactly one variable has none; its states become the top-level branches, and each state is expanded by the one child variable that applies under it (not all mass on 'na'). Node probability = parent node probability × conditional probability; 'other', 'na' and zero-probability states are skipped. Everything is validated before the current tree is replaced.", output_schema = output_schema::<SubtreeInsertion>())]
+    async fn import_bayes_net(&self, Parameters(request): Parameters<ImportBayesNetRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.import_bayes_net(request.network, request.complexity.unwrap_or(5)).await {
+            Ok(insertion) => Ok(structured_result(format!("Imported Bayesian network as a new tree\n\n{}", insertion), &insertion)),
+            Err(e) => Ok(error_result(format!("Failed to import Bayesian network: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Exports paths or nodes as CSV or JSON Lines.
+    ///
+    /// # MCP Tool Parameters
+    /// - `table` (string): "paths" or "nodes"
+    /// - `format` (optional string): "csv" (default) or "jsonl"
+    ///
+    /// # Returns
+    /// - Success: The table, one row per path or node
+    /// - Error: "Failed to export table: {error_description}"
+    #[tool(description = "EXPORT TABLE: Flat export for spreadsheets and notebooks. table=paths gives one row per surviving path of the latest export_paths report (rank, leaf, node ids, premises, probability, confidence, reasoning); table=nodes gives one row per node with every inspect_tree field flattened (time window split into start/end, argument tag into stance/strength). format=csv (header row, RFC 4180 quoting) or jsonl (one JSON object per line). Column order is fixed, every row carries schema_version, and list cells join values with ' | '.", output_schema = output_schema::<ExportedDocument>())]
+    async fn export_table(&self, Parameters(request): Parameters<ExportTableRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let table = match TabularTable::parse(&request.table) {
+            Ok(table) => table,
+            Err(e) => return Ok(error_result(format!("Failed to export table: {}", e))),
+        };
+        let format = match request.format.as_deref().map(TabularFormat::parse).unwrap_or(Ok(TabularFormat::Csv)) {
+            Ok(format) => format,
+            Err(e) => return Ok(error_result(format!("Failed to export table: {}", e))),
+        };
+        match service.export_table(table, format).await {
+            Ok(rows) => Ok(structured_result(rows.clone(), &ExportedDocument { format: format.as_str().to_string(), content: rows })),
+            Err(e) => Ok(error_result(format!("Failed to export table: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Creates a new tree from a Markdown outline.
+    ///
+    /// # MCP Tool Parameters
+    /// - `outline` (string): "# premise" heading followed by nested bullets
+    /// - `complexity` (optional i64): Complexity of the new tree, 1-10 (defaults to 5)
+    ///
+    /// # Returns
+    /// - Success: The created nodes with their ids
+    /// - Error: "Failed to import outline: {error_description}"
+    #[tool(description = "IMPORT OUTLINE: Start a new tree from a Markdown outline. The first line is a '# premise' heading for the root; each scenario is a bullet '- Premise (p=0.4, c=7): reasoning' ('*' and '+' bullets and p=40% also work), nested under the bullet above it by indentation. Every node is checked with the add_leaf rules (probability and confidence ranges, sibling sums, branch limit, maximum depth) before the current tree is replaced, and all problems are reported with their line numbers.", output_schema = output_schema::<SubtreeInsertion>())]
+    async fn import_outline(&self, Parameters(request): Parameters<ImportOutlineRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.import_outline(request.outline, request.complexity.unwrap_or(5)).await {
+            Ok(insertion) => Ok(structured_result(format!("Imported outline as a new tree\n\n{}", insertion), &insertion)),
+            Err(e) => Ok(error_result(format!("Failed to import outline: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Exports the tree as a Markdown outline.
+    ///
+    /// # MCP Tool Parameters
+    /// None - exports the whole current tree.
+    ///
+    /// # Returns
+    /// - Success: The outline, readable by import_outline
+    /// - Error: "Failed to export outline: {error_description}"
+    #[tool(description = "EXPORT OUTLINE: Write the tree as a Markdown outline for editing in any text editor: a '# premise' heading for the root, then one '- Premise (p=0.4, c=7): reasoning' bullet per node, indented two spaces per level. Pruned nodes and cross-links are left out. Feed the edited text back through import_outline.", output_schema = output_schema::<ExportedDocument>())]
+    async fn export_outline(&self, Parameters(_request): Parameters<ExportOutlineRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.export_outline().await {
+            Ok(outline) => Ok(structured_result(outline.clone(), &ExportedDocument { format: "markdown".to_string(), content: outline })),
+            Err(e) => Ok(error_result(format!("Failed to export outline: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Marks a node's children as an exhaustive set of outcomes.
+    ///
+    /// # MCP Tool Parameters
+    /// - `node_id` (string): Friendly alias, record id or prefix of the parent node
+    /// - `exhaustive` (optional bool): Set or clear the mark (defaults to true)
+    /// - `residual` (optional bool): Maintain a residual child (defaults to false)
+    ///
+    /// # Returns
+    /// - Success: Whether the node is exhaustive and the id of its residual child
+    /// - Error: "Failed to set exhaustive: {error_description}"
+    #[tool(description = "EXHAUSTIVE OUTCOMES: Declare that a node's children are mutually exclusive and collectively exhaustive (MECE), i.e. a complete list of what can happen. validate_coherence then flags the node unless its active children sum to its probability within 0.01. With residual=true an 'Other / unanticipated outcome' child is added and kept equal to whatever probability the other children leave: it shrinks to make room when you add siblings, grows when they are pruned or lowered, and is skipped by prune and balance. exhaustive=false clears the mark; an existing residual stays as an ordinary child.", output_schema = output_schema::<ExhaustiveUpdate>())]
+    async fn set_exhaustive(&self, Parameters(request): Parameters<SetExhaustiveRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let node_id = request.node_id.clone();
+        let exhaustive = request.exhaustive.unwrap_or(true);
+        match service.set_exhaustive(request.node_id, exhaustive, request.residual.unwrap_or(false)).await {
+            Ok(residual_id) => {
+                let node_id = service.get_friendly_id(&node_id).await.unwrap_or(node_id);
+                let text = match (exhaustive, &residual_id) {
+                    (true, Some(residual_id)) => format!(
+                        "Marked children of node {} as exhaustive; residual child {} absorbs the leftover probability", node_id, residual_id),
+                    (true, None) => format!(
+                        "Marked children of node {} as exhaustive; they must sum to its probability", node_id),
+                    (false, _) => format!("Children of node {} are no longer exhaustive", node_id),
+                };
+                Ok(structured_result(text, &ExhaustiveUpdate { node_id, exhaustive, residual_id }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to set exhaustive: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Lists the tree's scenarios in chronological order.
+    ///
+    /// # MCP Tool Parameters
+    /// None - covers every surviving scenario path of the current tree.
+    ///
+    /// # Returns
+    /// - Success: Scenarios ordered by expected window, with their dated milestones
+    /// - Error: "Failed to build timeline: {error_description}"
+    #[tool(description = "TIMELINE: Order the tree's scenarios chronologically by the time windows set with set_time_horizon. Each root-to-leaf scenario is dated by its most specific dated node and lists the dated milestones along it; scenarios whose window has passed or is in progress are marked, and undated ones are listed last.", output_schema = output_schema::<Timeline>())]
+    async fn timeline(&self, Parameters(_request): Parameters<TimelineRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        match service.timeline().await {
+            Ok(timeline) => Ok(structured_result(timeline.to_string(), &timeline)),
+            Err(e) => Ok(error_result(format!("Failed to build timeline: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Creates a new tree from a structured analysis template.
+    ///
+    /// # MCP Tool Parameters
+    /// - `template` (string): Template name, e.g. "pre_mortem", "swot_scenarios",
+    ///   "competing_hypotheses", "five_whys", "go_no_go" or a registered one
+    /// - `subject` (string): What is being analyzed; fills `{subject}` in the root premise
+    /// - `complexity` (optional i64): Overrides the template's complexity (1-10)
+    ///
+    /// # Returns
+    /// - Success: Root ID plus the number of placeholder branches created
+    /// - Error: "Failed to create tree from template: {error_description}"
+    ///
+    /// # Example MCP Request
+    /// ```json
+    /// {
+    ///   "method": "tools/call",
+    ///   "params": {
+    ///     "name": "create_tree_from_template",
+    ///     "arguments": {
+    ///       "template": "pre_mortem",
+    ///       "subject": "the EU launch"
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    #[tool(description = "TREE INITIALIZATION FROM TEMPLATE: Alternative to create_tree that starts from a structured framework instead of a blank root. Built-in templates: 'pre_mortem' (assume failure, find causes), 'swot_scenarios' (S-O, S-T, W-O, W-T scenarios), 'competing_hypotheses' (ACH with consistent/inconsistent evidence), 'five_whys' (root-cause chain) and 'go_no_go' (proceed, stop or conditional go). Requires template and subject (substituted into the root premise); optional complexity override. Creates placeholder branches with suggested probability slots and low confidence; rewrite premises marked with <...> and re-estimate probabilities. Clears any existing tree and leaves the cursor at the root. Use list_templates to see registered templates.", output_schema = output_schema::<NodeRef>())]
+    async fn create_tree_from_template(&self, Parameters(request): Parameters<CreateTreeFromTemplateRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        let template = request.template.clone();
+        match service.create_tree_from_template(request.template, request.subject, request.complexity).await {
+            Ok(tree_id) => {
+                let alias = service.get_friendly_id(&tree_id).await.unwrap_or_else(|_| tree_id.clone());
+                let slots = service.list_templates().iter()
+                    .find(|candidate| candidate.name == template.trim())
+                    .map(|candidate| candidate.slot_count())
+                    .unwrap_or(0);
+                Ok(structured_result(
+                    format!("Successfully created probability tree from template '{}' with ID: {} (record: {}). Added {} placeholder branches; use inspect_tree to review them, then replace <...> placeholders and adjust the suggested probabilities.", template.trim(), alias, tree_id, slots),
+                    &NodeRef { node_id: tree_id, friendly_id: alias },
+                ))
+            },
+            Err(e) => Ok(error_result(format!("Failed to create tree from template: {}", e))),
+        }
+    }
+
+    /// MCP Tool: Lists the available analysis templates.
+    ///
+    /// # Returns
+    /// - Success: Name, title, complexity, slot count, description and root premise of each template,
+    ///   preceded by the error if the templates in `DEEP_ANALYTICS_TEMPLATE_DIR` failed to load
+    /// - Error: never fails
+    #[tool(description = "TEMPLATE LIBRARY: List built-in and registered analysis templates with their complexity, number of placeholder branches and root premise. Use a name with create_tree_from_template.", output_schema = output_schema::<TemplateListing>())]
+    async fn list_templates(&self, Parameters(_request): Parameters<ListTemplatesRequest>) -> Result<CallToolResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        let listing = TemplateListing {
+            templates: service.list_templates(),
+            load_error: self.template_load_error.get().cloned(),
+        };
+        let lines: Vec<String> = listing.load_error.clone().into_iter()
+            .chain(listing.templates.iter().map(|template| template.to_string()))
+            .collect();
+        Ok(structured_result(lines.join("\n"), &listing))
+    }
+
+    /// MCP Tool: Registers team templates from inline JSON.
+    ///
+    /// Templates on the server's disk are only loaded at startup, from `DEEP_ANALYTICS_TEMPLATE_DIR`,
+    /// so clients cannot make the server read arbitrary paths.
+    ///
+    /// # MCP Tool Parameters
+    /// - `json` (string): Template JSON (one template or an array)
+    ///
+    /// # Returns
+    /// - Success: "Registered templates: {names}"
+    /// - Error: "Failed to register template: {error_description}"
+    #[tool(description = "TEMPLATE LIBRARY: Register your team's own templates from inline json (one template or an array). A template is {name, title, description, root_premise (use {subject}), complexity, branches: [{premise, reasoning, probability, confidence?, children?}]}; sibling probabilities must not exceed their parent. A template with an existing name replaces it.", output_schema = output_schema::<ItemList<String>>())]
+    async fn register_template(&self, Parameters(request): Parameters<RegisterTemplateRequest>) -> Result<CallToolResult, ErrorData> {
+        let service = self.get_service().await;
+        let mut service = service.lock().await;
+
+        match service.register_templates_json(&request.json) {
+            Ok(names) => {
+                let text = if names.is_empty() {
+                    "No templates found to register.".to_string()
+                } else {
+                    format!("Registered templates: {}", names.join(", "))
+                };
+                Ok(structured_result(text, &ItemList { items: names }))
+            },
+            Err(e) => Ok(error_result(format!("Failed to register template: {}", e))),
+        }
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for TreeEngineServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2025_06_18,
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(
+                "# Deep Analytics Tree Engine MCP Server\n\n\
+                This server provides advanced probabilistic decision tree analysis for complex decision-making scenarios.\n\n\
+                ## PRIMARY PURPOSE\n\
+                Build and analyze probability trees to evaluate complex decisions by systematically breaking them down into\n\
+                logical branches with quantified probabilities, detailed reasoning, and confidence assessments.\n\n\
+                Clients that support MCP prompts can instead follow the start_analysis, expand_branch and\n\
+                final_report prompts, which fill in the live cursor, probability budgets and expandable leaves.\n\n\
+                ## MANDATORY WORKFLOW SEQUENCE (MUST follow in order):\n\n\
+                ### Phase 1: INITIALIZATION (Required First Step)\n\
+                1. **create_tree(premise, complexity)** - ALWAYS START HERE\n\
+                   - Creates root node and positions cursor at root\n\
+                   - Complexity 1-3: Simple binary decisions\n\
+                   - Complexity 4-7: Multi-factor analysis (most common)\n\
+                   - Complexity 8-10: Complex multi-layered decisions\n\
+                   - This CLEARS any existing tree state\n\n\
+                ### Phase 2: BUILDING (Core Tree Construction)\n\
+                2. **add_leaf(premise, reasoning, probability, confidence)** - Add root's children\n\
+                   - IMMEDIATELY after create_tree, add 2-4 initial branches to root\n\
+                   - Each branch represents a major pathway/outcome\n\
+                   - Probability: 0.0-1.0 (CRITICAL: siblings must NOT exceed parent's probability)\n\
+                   - For root children: total can approach 1.0 but should not exceed it\n\
+                   - For deeper levels: children's probabilities are constrained by parent's probability\n\
+                   - Confidence: 1-10 (higher = more certain about assessment)\n\
+                   - Reasoning must be detailed and specific\n\n\
+                3. **expand_leaf(node_id, rationale)** - Prepare node for children\n\
+                   - Moves cursor to specific node for deeper analysis\n\
+                   - MANDATORY before adding children to any non-root node\n\
+                   - Use the friendly alias from previous responses (record ids and unambiguous prefixes also work)\n\
+                   - Provide detailed rationale for why this branch needs expansion\n\n\
+                4. **REPEAT**: expand_leaf → add_leaf cycles for each level\n\
+                   - Build incrementally: root children → expand one → add its children → repeat\n\
+                   - Typical depth: 2-4 levels depending on complexity\n\n\
+                ### Phase 3: VALIDATION (Quality Assurance)\n\
+                5. **inspect_tree()** - Monitor construction progress\n\
+                   - Use frequently during building to track structure\n\
+                   - Shows node counts, depth, probability distribution\n\n\
+                6. **validate_coherence()** - Check logical consistency\n\
                    - Identifies contradictions and reasoning conflicts\n\
                    - MANDATORY before pruning or final analysis\n\n\
                 7. **probability_status()** - Validate mathematical consistency\n\
@@ -728,7 +2471,7 @@ impl ServerHandler for TreeEngineServer {
                 ## CRITICAL RULES (Violations cause errors):\n\
                 ⚠️  NEVER skip create_tree - it's mandatory first step\n\
                 ⚠️  NEVER add children without expand_leaf (except for root's children)\n\
-                ⚠️  ALWAYS save node_ids from responses for expand_leaf\n\
+                ⚠️  ALWAYS save node aliases (e.g. clever_turing) from responses for expand_leaf\n\
                 ⚠️  NEVER exceed parent probability when adding children (system enforces this)\n\
                 ⚠️  NEVER prune/balance before validation (coherence + probability checks)\n\
                 ⚠️  ALWAYS validate before export\n\
@@ -755,7 +2498,7 @@ impl ServerHandler for TreeEngineServer {
                 add_leaf('Market research negative', 'Low engagement in focus groups', 0.3, 6)\n\
                 \n\
                 # 3. Expand first branch for deeper analysis\n\
-                expand_leaf('node:abc123', 'Positive research needs breakdown by market segment')\n\
+                expand_leaf('clever_turing', 'Positive research needs breakdown by market segment')\n\
                 \n\
                 # 4. Add children to expanded node\n\
                 add_leaf('B2B segment viable', 'Enterprise customers show strong demand', 0.85, 9)\n\
@@ -774,15 +2517,35 @@ impl ServerHandler for TreeEngineServer {
                 ```\n\n\
                 ## WHEN TO USE EACH TOOL:\n\
                 - **create_tree**: Always first, when starting any new analysis\n\
+                - **create_tree_from_template**: Instead of create_tree, to start from a pre-mortem, SWOT, ACH, five-whys or go/no-go skeleton\n\
                 - **add_leaf**: After create_tree (for root children) or expand_leaf (for node children)\n\
+                - **add_subtree**: When you already know several levels of branches; adds them all at once\n\
+                - **move_subtree** / **copy_subtree**: Re-parent a misplaced branch, or reuse it under another parent\n\
                 - **expand_leaf**: When you want to analyze a branch deeper (add children to it)\n\
                 - **inspect_tree**: Frequently during building to monitor progress\n\
                 - **validate_coherence**: Before optimization, to check logical consistency\n\
                 - **probability_status**: Before optimization, to check mathematical validity\n\
-                - **prune_tree**: After validation, to remove weak branches permanently\n\
-                - **balance_leafs**: After validation, to adjust probabilities without removing nodes\n\
+                - **prune_tree**: After validation, to remove weak branches permanently (dry_run=true previews the affected paths first)\n\
+                - **balance_leafs**: After validation, to adjust probabilities without removing nodes (also supports dry_run)\n\
                 - **export_paths**: Final step, to generate analysis report\n\
-                - **navigate_to**: Advanced cursor control for non-linear building\n\n\
+                - **navigate_to**: Advanced cursor control for non-linear building\n\
+                - **search_nodes**: Find nodes by keyword, fuzzy text or attributes in large trees\n\
+                - **attach_evidence** / **list_evidence**: Cite sources behind an estimate; export_paths adds a bibliography\n\
+                - **set_proposition**: Give premises formal propositions so validate_coherence can catch logical contradictions\n\
+                - **add_cross_link** / **remove_cross_link**: Let two causes share one downstream outcome instead of duplicating it\n\
+                - **checkpoint** / **list_checkpoints** / **diff_trees**: Save named versions and compare them (e.g. before pruning vs. after new evidence)\n\
+                - **merge_tree**: Combine another analyst's checkpointed tree into the live one and review the conflict report\n\
+                - **resolve_outcome** / **calibration_report**: Once a forecast resolves, record what happened and score estimates over time\n\
+                - **set_time_horizon** / **timeline**: Date scenarios and review deadlines; inspect_tree then flags stale estimates\n\
+                - **lock_node**: Pin settled estimates (or freeze a subtree) before prune_tree or balance_leafs\n\
+                - **set_gate** / **fault_tree_analysis**: Reliability mode: AND/OR/k-of-n gates, top-event probability, minimal cut sets and importance\n\
+                - **set_risk_profile** / **risk_matrix**: Score impact and category, then report a probability × impact heatmap with risk per category\n\
+                - **set_tree_mode** / **tag_argument** / **argument_map**: Debate whether a claim holds: pro/con arguments with strengths instead of exclusive scenarios\n\
+                - **export_bayes_net** / **import_bayes_net**: Move an analysis to or from dedicated Bayesian network tools (XMLBIF, BIF)\n\
+                - **export_table**: Load paths or nodes into spreadsheets and notebooks as CSV or JSON Lines\n\
+                - **import_outline** / **export_outline**: Sketch or edit a tree as a nested Markdown bullet list in any text editor\n\
+                - **set_exhaustive**: Declare a complete (MECE) set of outcomes, optionally with a residual 'other' branch kept in sync\n\
+                - **Resources** `tree://{tree_id}/tree`, `/nodes/{node_id}`, `/export.json`, `/report`: Read or subscribe to live tree state instead of polling inspect_tree\n\n\
                 ## QUANTIFICATION STRATEGY (Plan Before Building):\n\
                 1. **Root Level Planning**: Decide how many major branches (2-5 recommended)\n\
                 2. **Probability Budget**: Allocate probability budget across branches before adding\n\
@@ -795,4 +2558,246 @@ impl ServerHandler for TreeEngineServer {
             ),
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+
+        // Before create_tree there is nothing to expose
+        let Ok(snapshot) = service.snapshot().await else {
+            return Ok(ListResourcesResult::with_all_items(vec![]));
+        };
+        let tree_id = snapshot.tree_id.as_str();
+        let root_premise = snapshot.nodes.iter()
+            .find(|node| node.parent_id.is_none())
+            .map(|node| node.premise.clone())
+            .unwrap_or_default();
+
+        let mut resources = vec![
+            tree_resource(TreeResourceUri::new(tree_id, TreeResource::Tree), format!("Tree: {}", root_premise),
+                "Overview of the live tree, as rendered by inspect_tree"),
+            tree_resource(TreeResourceUri::new(tree_id, TreeResource::Export), format!("Export: {}", root_premise),
+                "Tree state, every node and every cross-link as JSON"),
+        ];
+        if matches!(service.latest_report().await, Ok(Some(_))) {
+            resources.push(tree_resource(TreeResourceUri::new(tree_id, TreeResource::Report), format!("Report: {}", root_premise),
+                "Latest analysis report produced by export_paths"));
+        }
+        for node in &snapshot.nodes {
+            let node_id = node.display_id();
+            resources.push(tree_resource(TreeResourceUri::new(tree_id, TreeResource::Node(node_id.clone())),
+                format!("[{}] {}", node_id, node.premise), "Node details as shown by inspect_tree"));
+        }
+
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult::with_all_items(vec![
+            RawResourceTemplate {
+                uri_template: NODE_URI_TEMPLATE.to_string(),
+                name: "Tree node".to_string(),
+                title: None,
+                description: Some("A node of the current tree, by friendly alias or record id".to_string()),
+                mime_type: Some("application/json".to_string()),
+            }
+            .no_annotation(),
+        ]))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let uri = TreeResourceUri::parse(&request.uri)
+            .map_err(|e| ErrorData::invalid_params(e, None))?;
+        let text = self.read_tree_resource(&uri).await
+            .map_err(|e| ErrorData::resource_not_found(e, None))?;
+
+        let mut contents = ResourceContents::text(text, request.uri);
+        if let ResourceContents::TextResourceContents { mime_type, .. } = &mut contents {
+            *mime_type = Some(uri.mime_type().to_string());
+        }
+        Ok(ReadResourceResult { contents: vec![contents] })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        TreeResourceUri::parse(&request.uri)
+            .map_err(|e| ErrorData::invalid_params(e, None))?;
+
+        self.change_forwarder.get_or_init(|| self.forward_changes(context.peer.clone())).await;
+        self.subscriptions.lock().await.insert(request.uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscriptions.lock().await.remove(&request.uri);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        let prompts = PROMPTS.iter()
+            .map(|(name, description, arguments)| Prompt::new(*name, Some(*description), Some(
+                arguments.iter()
+                    .map(|(argument, description, required)| PromptArgument {
+                        name: argument.to_string(),
+                        title: None,
+                        description: Some(description.to_string()),
+                        required: Some(*required),
+                    })
+                    .collect(),
+            )))
+            .collect();
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let Some((_, description, _)) = PROMPTS.iter().find(|(name, _, _)| *name == request.name) else {
+            return Err(ErrorData::invalid_params(format!("Unknown prompt '{}'", request.name), None));
+        };
+        let mut arguments = prompt_arguments(request.arguments);
+
+        let service_arc = self.get_service().await;
+        let service = service_arc.lock().await;
+        let tree = service.inspect_tree().await.ok();
+        let cursor = match service.cursor() {
+            Some(cursor) => service.get_friendly_id(cursor).await.ok(),
+            None => None,
+        };
+        // Prompts look nodes up by alias; accept record ids and prefixes too
+        if let (Some(node_id), Some(_)) = (arguments.get("node_id").cloned(), &tree) {
+            let alias = service.get_friendly_id(&node_id).await
+                .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+            arguments.insert("node_id".to_string(), alias);
+        }
+
+        let text = render_prompt(&request.name, &arguments, tree.as_ref(), cursor.as_deref())
+            .map_err(|e| ErrorData::invalid_params(e, None))?;
+
+        Ok(GetPromptResult {
+            description: Some(description.to_string()),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        let object: JsonObject = pairs.iter()
+            .map(|(name, value)| (name.to_string(), serde_json::Value::String(value.to_string())))
+            .collect();
+        prompt_arguments(Some(object))
+    }
+
+    async fn sample_tree() -> (TreeVisualization, String, String) {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        // Complexity 1 allows 3 children per node
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 1).await.unwrap();
+        let delays = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.5, 7).await.unwrap();
+        service.add_leaf("Team attrition".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+        service.add_leaf("Regulatory hold".to_string(), "New certification rules".to_string(), 0.1, 4).await.unwrap();
+
+        let root = service.get_friendly_id(&root_id).await.unwrap();
+        let delays = service.get_friendly_id(&delays).await.unwrap();
+        (service.inspect_tree().await.unwrap(), root, delays)
+    }
+
+    #[test]
+    fn test_prompt_arguments() {
+        let parsed = arguments(&[("topic", "  EU launch "), ("style", "   ")]);
+        assert_eq!(parsed.get("topic").map(String::as_str), Some("EU launch"));
+        assert!(!parsed.contains_key("style"));
+    }
+
+    #[tokio::test]
+    async fn test_start_analysis_prompt() {
+        assert!(render_prompt("start_analysis", &arguments(&[]), None, None).unwrap_err().contains("requires a topic"));
+        for complexity in ["0", "11", "high"] {
+            let error = render_prompt("start_analysis", &arguments(&[("topic", "EU launch"), ("complexity", complexity)]), None, None).unwrap_err();
+            assert!(error.contains("must be an integer from 1 to 10"));
+        }
+
+        let text = render_prompt("start_analysis", &arguments(&[("topic", "EU launch"), ("complexity", "3")]), None, None).unwrap();
+        assert!(text.contains("complexity 3"));
+        assert!(text.contains("depth 4, at most 4 children per node"));
+        assert!(text.contains("No tree exists yet"));
+        assert!(!text.contains("clears the current tree"));
+
+        let (tree, _, _) = sample_tree().await;
+        let text = render_prompt("start_analysis", &arguments(&[("topic", "EU launch")]), Some(&tree), None).unwrap();
+        assert!(text.contains(&format!("complexity {}", DEFAULT_PROMPT_COMPLEXITY)));
+        assert!(text.contains("clears the current tree"));
+    }
+
+    #[tokio::test]
+    async fn test_expand_branch_prompt() {
+        assert!(render_prompt("expand_branch", &arguments(&[]), None, None).unwrap_err().contains("requires a node_id"));
+        assert!(render_prompt("expand_branch", &arguments(&[("node_id", "brave_curie")]), None, None).unwrap_err().contains("No tree exists yet"));
+
+        let (tree, root, delays) = sample_tree().await;
+        assert!(render_prompt("expand_branch", &arguments(&[("node_id", "missing_node")]), Some(&tree), None).unwrap_err().contains("not found"));
+
+        // The root is full: 0.9 of its probability given to 3 children, the branch limit
+        let text = render_prompt("expand_branch", &arguments(&[("node_id", &root)]), Some(&tree), Some(&root)).unwrap();
+        assert!(text.contains("Add up to 0 children"));
+        assert!(text.contains("Probability budget left: 0.100 (p=1.000 minus 0.900 already given to 3 existing children)"));
+        assert!(text.contains("cannot take more children"));
+
+        let text = render_prompt("expand_branch", &arguments(&[("node_id", &delays)]), Some(&tree), Some(&root)).unwrap();
+        assert!(text.contains("Add up to 3 children"));
+        assert!(text.contains("Probability budget left: 0.500 (p=0.500 minus 0.000 already given to 0 existing children)"));
+        assert!(!text.contains("cannot take more children"));
+        assert!(text.contains(&format!("- Cursor: [{}]", root)));
+    }
+
+    #[tokio::test]
+    async fn test_final_report_prompt() {
+        assert!(render_prompt("final_report", &arguments(&[]), None, None).unwrap_err().contains("No tree exists yet"));
+
+        let (tree, _, delays) = sample_tree().await;
+        let error = render_prompt("final_report", &arguments(&[("style", "Poetic")]), Some(&tree), None).unwrap_err();
+        assert_eq!(error, "Unknown style 'Poetic'; use Analytical, Strategic or Storytelling");
+
+        let text = render_prompt("final_report", &arguments(&[]), Some(&tree), None).unwrap();
+        assert!(text.contains("final Analytical report"));
+        let text = render_prompt("final_report", &arguments(&[("style", "Strategic")]), Some(&tree), None).unwrap();
+        assert!(text.contains("narrative_style \"Strategic\""));
+        assert!(text.contains(&format!("[{}] Supplier delays (p=0.500", delays)));
+
+        assert_eq!(render_prompt("summarize", &arguments(&[]), Some(&tree), None).unwrap_err(), "Unknown prompt 'summarize'");
+    }
 }
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Claims scoring at least this much are judged to hold.
+pub const HOLDS_THRESHOLD: f64 = 0.6;
+
+/// Claims scoring at most this much are judged rejected.
+pub const REJECTED_THRESHOLD: f64 = 0.4;
+
+/// Arguments listed on each side of a verdict.
+pub const STRONGEST_ARGUMENTS: usize = 3;
+
+/// Score the root claim starts from before any argument is weighed.
+pub const UNDECIDED: f64 = 0.5;
+
+/// Whether an argument supports or opposes the claim it is attached to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum Stance {
+    Pro,
+    Con,
+}
+
+/// Role of a node in an argument map: its stance towards its parent and how much it weighs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ArgumentTag {
+    pub stance: Stance,
+    /// How strongly the argument bears on its parent if it holds (0-1)
+    pub strength: f64,
+}
+
+/// A claim or argument with its own nested arguments, as read from the tree.
+#[derive(Debug, Clone)]
+pub struct ArgumentNode {
+    pub node_id: String,
+    pub premise: String,
+    /// Plausibility of the argument on its own, before its sub-arguments are weighed;
+    /// ignored for the root claim, which starts at `UNDECIDED`
+    pub base: f64,
+    /// `None` for the root claim and for children not tagged yet
+    pub tag: Option<ArgumentTag>,
+    pub children: Vec<ArgumentNode>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum Verdict {
+    Holds,
+    Contested,
+    Rejected,
+}
+
+/// Aggregated score of one tagged argument.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArgumentScore {
+    pub node_id: String,
+    pub premise: String,
+    pub stance: Stance,
+    /// Effect on the root claim: a con against a con ends up supporting it
+    pub side: Stance,
+    pub strength: f64,
+    pub base: f64,
+    /// Plausibility after its own sub-arguments are weighed (0-1)
+    pub score: f64,
+    /// strength × score, the pull the argument exerts on its parent
+    pub weight: f64,
+    pub depth: usize,
+}
+
+/// Verdict on the root claim of an argument-mode tree.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArgumentMap {
+    pub claim_id: String,
+    pub claim: String,
+    pub score: f64,
+    pub verdict: Verdict,
+    /// Combined pull of the claim's direct pro arguments (0-1)
+    pub support: f64,
+    /// Combined pull of the claim's direct con arguments (0-1)
+    pub opposition: f64,
+    /// Heaviest arguments whose effect supports the claim
+    pub strongest_pro: Vec<ArgumentScore>,
+    /// Heaviest arguments whose effect opposes the claim
+    pub strongest_con: Vec<ArgumentScore>,
+    /// Every tagged argument, heaviest first
+    pub arguments: Vec<ArgumentScore>,
+    /// Nodes left out of the scoring because they have no stance yet
+    pub untagged: Vec<String>,
+}
+
+impl Stance {
+    /// Parses `pro` or `con`; `support`/`for` and `oppose`/`against` are accepted too.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "pro" | "support" | "for" => Ok(Stance::Pro),
+            "con" | "oppose" | "against" => Ok(Stance::Con),
+            other => Err(format!("Unknown stance '{}', expected pro or con", other)),
+        }
+    }
+
+    /// Stance towards the root claim of an argument taking this stance towards a parent on `side`.
+    pub fn relative_to(self, side: Stance) -> Stance {
+        if self == side { Stance::Pro } else { Stance::Con }
+    }
+}
+
+impl ArgumentTag {
+    pub fn new(stance: Stance, strength: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(format!("Strength {} is out of range [0,1]", strength));
+        }
+        Ok(Self { stance, strength })
+    }
+}
+
+impl ArgumentNode {
+    /// Scores every argument bottom-up and judges the root claim.
+    ///
+    /// Uses DF-QuAD gradual semantics: the pulls (`strength × score`) of the pro and con
+    /// arguments under a claim are each combined as independent causes, `1 - Π(1 - pull)`.
+    /// If opposition outweighs support, the claim's base score drops towards 0 by the
+    /// difference; otherwise it rises towards 1. The root claim starts at `UNDECIDED`.
+    pub fn evaluate(&self) -> ArgumentMap {
+        let mut arguments = Vec::new();
+        let mut untagged = Vec::new();
+        let (support, opposition) = self.pressure(Stance::Pro, 0, &mut arguments, &mut untagged);
+        let score = Self::combine(UNDECIDED, support, opposition);
+
+        arguments.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap_or(std::cmp::Ordering::Equal));
+        let strongest = |side: Stance| arguments.iter()
+            .filter(|argument| argument.side == side)
+            .take(STRONGEST_ARGUMENTS)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        ArgumentMap {
+            claim_id: self.node_id.clone(),
+            claim: self.premise.clone(),
+            score,
+            verdict: if score >= HOLDS_THRESHOLD {
+                Verdict::Holds
+            } else if score <= REJECTED_THRESHOLD {
+                Verdict::Rejected
+            } else {
+                Verdict::Contested
+            },
+            support,
+            opposition,
+            strongest_pro: strongest(Stance::Pro),
+            strongest_con: strongest(Stance::Con),
+            arguments,
+            untagged,
+        }
+    }
+
+    /// Combined support and opposition from the tagged children, recording each child's score.
+    fn pressure(&self, side: Stance, depth: usize, arguments: &mut Vec<ArgumentScore>, untagged: &mut Vec<String>) -> (f64, f64) {
+        let (mut unsupported, mut unopposed) = (1.0, 1.0);
+        for child in &self.children {
+            let Some(tag) = child.tag else {
+                untagged.push(child.node_id.clone());
+                continue;
+            };
+            let child_side = tag.stance.relative_to(side);
+            let (support, opposition) = child.pressure(child_side, depth + 1, arguments, untagged);
+            let score = Self::combine(child.base, support, opposition);
+            let weight = tag.strength * score;
+            match tag.stance {
+                Stance::Pro => unsupported *= 1.0 - weight,
+                Stance::Con => unopposed *= 1.0 - weight,
+            }
+            arguments.push(ArgumentScore {
+                node_id: child.node_id.clone(),
+                premise: child.premise.clone(),
+                stance: tag.stance,
+                side: child_side,
+                strength: tag.strength,
+                base: child.base,
+                score,
+                weight,
+                depth: depth + 1,
+            });
+        }
+        (1.0 - unsupported, 1.0 - unopposed)
+    }
+
+    fn combine(base: f64, support: f64, opposition: f64) -> f64 {
+        if opposition >= support {
+            base - base * (opposition - support)
+        } else {
+            base + (1.0 - base) * (support - opposition)
+        }
+    }
+}
+
+impl Display for Stance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Stance::Pro => write!(f, "PRO"),
+            Stance::Con => write!(f, "CON"),
+        }
+    }
+}
+
+impl Display for ArgumentMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let verdict = match self.verdict {
+            Verdict::Holds => "✅ HOLDS",
+            Verdict::Contested => "⚖️  CONTESTED",
+            Verdict::Rejected => "❌ REJECTED",
+        };
+        writeln!(f, "🗣️  ARGUMENT MAP: [{}] {}\n", self.claim_id, self.claim)?;
+        writeln!(f, "   Verdict: {} (score {:.3}; holds ≥ {}, rejected ≤ {})", verdict, self.score, HOLDS_THRESHOLD, REJECTED_THRESHOLD)?;
+        writeln!(f, "   Direct support {:.3} vs opposition {:.3} across {} arguments", self.support, self.opposition, self.arguments.len())?;
+
+        for (title, side) in [("👍 STRONGEST FOR", &self.strongest_pro), ("👎 STRONGEST AGAINST", &self.strongest_con)] {
+            writeln!(f, "\n{}:", title)?;
+            if side.is_empty() {
+                writeln!(f, "   None")?;
+            }
+            for argument in side.iter() {
+                writeln!(f, "   [{}] {} (weight {:.3} = strength {:.2} × score {:.3}, {} at depth {})",
+                    argument.node_id, argument.premise, argument.weight, argument.strength, argument.score,
+                    argument.stance, argument.depth)?;
+            }
+        }
+
+        if !self.untagged.is_empty() {
+            writeln!(f, "\n❔ UNTAGGED (ignored until tagged pro or con): {}", self.untagged.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(node_id: &str, base: f64, tag: Option<(Stance, f64)>, children: Vec<ArgumentNode>) -> ArgumentNode {
+        ArgumentNode {
+            node_id: node_id.to_string(),
+            premise: format!("Argument {}", node_id),
+            base,
+            tag: tag.map(|(stance, strength)| ArgumentTag::new(stance, strength).unwrap()),
+            children,
+        }
+    }
+
+    #[test]
+    fn test_scores_bottom_up() {
+        let claim = argument("claim", 1.0, None, vec![
+            argument("pro", 0.8, Some((Stance::Pro, 1.0)), vec![]),
+            argument("con", 0.6, Some((Stance::Con, 0.5)), vec![
+                // Rebuttal of the con: supports the claim indirectly
+                argument("rebuttal", 1.0, Some((Stance::Con, 0.5)), vec![]),
+            ]),
+            argument("aside", 0.5, None, vec![]),
+        ]);
+
+        let map = claim.evaluate();
+        // con: 0.6 - 0.6 × 0.5 = 0.3, pulling 0.15; claim: 0.5 + 0.5 × (0.8 - 0.15)
+        assert!((map.support - 0.8).abs() < 1e-9);
+        assert!((map.opposition - 0.15).abs() < 1e-9);
+        assert!((map.score - 0.825).abs() < 1e-9);
+        assert_eq!(map.verdict, Verdict::Holds);
+        assert_eq!(map.untagged, vec!["aside"]);
+
+        let pro: Vec<&str> = map.strongest_pro.iter().map(|argument| argument.node_id.as_str()).collect();
+        assert_eq!(pro, vec!["pro", "rebuttal"]);
+        assert_eq!(map.strongest_con[0].node_id, "con");
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let rejected = argument("claim", 1.0, None, vec![argument("con", 0.9, Some((Stance::Con, 1.0)), vec![])]);
+        assert_eq!(rejected.evaluate().verdict, Verdict::Rejected);
+
+        let contested = argument("claim", 1.0, None, vec![
+            argument("pro", 0.7, Some((Stance::Pro, 1.0)), vec![]),
+            argument("con", 0.7, Some((Stance::Con, 1.0)), vec![]),
+        ]);
+        let map = contested.evaluate();
+        assert_eq!(map.verdict, Verdict::Contested);
+        assert!((map.score - UNDECIDED).abs() < 1e-9);
+
+        assert!(ArgumentTag::new(Stance::Pro, 1.5).is_err());
+        assert_eq!(Stance::parse("Against").unwrap(), Stance::Con);
+    }
+}
@@ -0,0 +1,619 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+use crate::domain::models::subtree::SubtreeNode;
+
+/// State absorbing the probability a sibling group leaves unassigned.
+pub const OTHER_STATE: &str = "other";
+
+/// State of a variable whose sibling group is not reached under the parent's current state.
+pub const NOT_APPLICABLE_STATE: &str = "na";
+
+/// Property holding the premise of the node whose children form a variable.
+pub const PREMISE_PROPERTY: &str = "premise";
+
+/// Prefix of the properties holding each state's premise, e.g. `outcome_clever_turing`.
+pub const OUTCOME_PROPERTY_PREFIX: &str = "outcome_";
+
+/// Confidence given to nodes imported from a network, which carries none.
+pub const IMPORTED_CONFIDENCE: i64 = 5;
+
+/// Tolerance when checking that a CPT row sums to 1.
+const ROW_TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum BayesNetFormat {
+    /// XMLBIF 0.3, read by Weka, SamIam, pgmpy and most BN tools
+    XmlBif,
+    /// The plain-text Bayesian Interchange Format (BIF 0.15)
+    Bif,
+}
+
+/// A discrete Bayesian network with at most one parent per variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BayesNet {
+    pub name: String,
+    /// Parents come before their children
+    pub variables: Vec<BayesVariable>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BayesVariable {
+    pub name: String,
+    pub states: Vec<String>,
+    pub parent: Option<String>,
+    /// One distribution over `states` per parent state, in the parent's state order;
+    /// a single row for a variable without parent
+    pub table: Vec<Vec<f64>>,
+    pub properties: BTreeMap<String, String>,
+}
+
+impl BayesNetFormat {
+    /// Parses `xmlbif` (also `xml`) or `bif`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "xmlbif" | "xml" => Ok(BayesNetFormat::XmlBif),
+            "bif" => Ok(BayesNetFormat::Bif),
+            other => Err(format!("Unknown network format '{}', expected xmlbif or bif", other)),
+        }
+    }
+
+    /// Canonical name, as accepted by `parse`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BayesNetFormat::XmlBif => "xmlbif",
+            BayesNetFormat::Bif => "bif",
+        }
+    }
+}
+
+impl BayesVariable {
+    /// Premise of the state, falling back to its name with underscores as spaces.
+    pub fn outcome_premise(&self, state: &str) -> String {
+        self.properties.get(&format!("{}{}", OUTCOME_PROPERTY_PREFIX, state))
+            .cloned()
+            .unwrap_or_else(|| state.replace('_', " "))
+    }
+
+    /// Probability of `state` in the table row for parent state index `row`, if it is a state.
+    fn probability(&self, row: usize, state: &str) -> Option<f64> {
+        let column = self.states.iter().position(|candidate| candidate == state)?;
+        self.table.get(row).map(|row| row[column])
+    }
+}
+
+impl BayesNet {
+    /// Renders the network in the chosen format.
+    pub fn render(&self, format: BayesNetFormat) -> String {
+        match format {
+            BayesNetFormat::XmlBif => self.to_xmlbif(),
+            BayesNetFormat::Bif => self.to_bif(),
+        }
+    }
+
+    pub fn to_xmlbif(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\"?>\n<BIF VERSION=\"0.3\">\n<NETWORK>\n");
+        out.push_str(&format!("<NAME>{}</NAME>\n", xml_escape(&self.name)));
+        for variable in &self.variables {
+            out.push_str("<VARIABLE TYPE=\"nature\">\n");
+            out.push_str(&format!("    <NAME>{}</NAME>\n", xml_escape(&variable.name)));
+            for state in &variable.states {
+                out.push_str(&format!("    <OUTCOME>{}</OUTCOME>\n", xml_escape(state)));
+            }
+            for (key, value) in &variable.properties {
+                out.push_str(&format!("    <PROPERTY>{} = {}</PROPERTY>\n", xml_escape(key), xml_escape(value)));
+            }
+            out.push_str("</VARIABLE>\n");
+        }
+        for variable in &self.variables {
+            out.push_str("<DEFINITION>\n");
+            out.push_str(&format!("    <FOR>{}</FOR>\n", xml_escape(&variable.name)));
+            if let Some(parent) = &variable.parent {
+                out.push_str(&format!("    <GIVEN>{}</GIVEN>\n", xml_escape(parent)));
+            }
+            let table: Vec<String> = variable.table.iter().flatten().map(|p| format_probability(*p)).collect();
+            out.push_str(&format!("    <TABLE>{}</TABLE>\n", table.join(" ")));
+            out.push_str("</DEFINITION>\n");
+        }
+        out.push_str("</NETWORK>\n</BIF>\n");
+        out
+    }
+
+    pub fn to_bif(&self) -> String {
+        let mut out = format!("network \"{}\" {{\n}}\n", bif_escape(&self.name));
+        for variable in &self.variables {
+            out.push_str(&format!("variable {} {{\n", variable.name));
+            out.push_str(&format!("  type discrete [ {} ] {{ {} }};\n", variable.states.len(), variable.states.join(", ")));
+            for (key, value) in &variable.properties {
+                out.push_str(&format!("  property \"{} = {}\";\n", bif_escape(key), bif_escape(value)));
+            }
+            out.push_str("}\n");
+        }
+        for variable in &self.variables {
+            let row = |probabilities: &[f64]| probabilities.iter().map(|p| format_probability(*p)).collect::<Vec<_>>().join(", ");
+            match &variable.parent {
+                None => {
+                    out.push_str(&format!("probability ( {} ) {{\n", variable.name));
+                    out.push_str(&format!("  table {};\n", row(&variable.table[0])));
+                }
+                Some(parent) => {
+                    out.push_str(&format!("probability ( {} | {} ) {{\n", variable.name, parent));
+                    let parent_states = self.variable(parent).map(|parent| parent.states.clone()).unwrap_or_default();
+                    for (state, probabilities) in parent_states.iter().zip(&variable.table) {
+                        out.push_str(&format!("  ({}) {};\n", state, row(probabilities)));
+                    }
+                }
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+
+    /// Parses XMLBIF (text starting with `<`) or plain-text BIF.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let net = if text.trim_start().starts_with('<') {
+            Self::parse_xmlbif(text)?
+        } else {
+            Self::parse_bif(text)?
+        };
+        net.validate()?;
+        Ok(net)
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&BayesVariable> {
+        self.variables.iter().find(|variable| variable.name == name)
+    }
+
+    /// Converts a tree-shaped network back into nested tree nodes under a root premise.
+    ///
+    /// Each state of the root variable becomes a top-level node. Below the node for
+    /// state `x` of a variable come the states of the one child variable that applies
+    /// under `x`, i.e. does not put all its mass on `na` there. Node probabilities are
+    /// joint: the parent node's probability times the conditional one. `other` and `na`
+    /// states and zero-probability states are left out.
+    ///
+    /// # Returns
+    /// The root premise (the root variable's `premise` property or the network name)
+    /// and the top-level nodes.
+    pub fn to_subtree(&self) -> Result<(String, Vec<SubtreeNode>), String> {
+        let roots: Vec<&BayesVariable> = self.variables.iter().filter(|variable| variable.parent.is_none()).collect();
+        let root = match roots.as_slice() {
+            [root] => *root,
+            [] => return Err("The network has no variable without a parent".to_string()),
+            _ => return Err(format!("The network has {} root variables; only single-rooted trees can be imported", roots.len())),
+        };
+
+        let premise = root.properties.get(PREMISE_PROPERTY).cloned().unwrap_or_else(|| self.name.clone());
+        let nodes = self.subtree_nodes(root, 0, 1.0)?;
+        Ok((premise, nodes))
+    }
+
+    fn subtree_nodes(&self, variable: &BayesVariable, row: usize, mass: f64) -> Result<Vec<SubtreeNode>, String> {
+        let mut nodes = Vec::new();
+        for (column, state) in variable.states.iter().enumerate() {
+            let probability = variable.table[row][column];
+            if state == OTHER_STATE || state == NOT_APPLICABLE_STATE || probability <= 0.0 {
+                continue;
+            }
+
+            let applicable: Vec<&BayesVariable> = self.variables.iter()
+                .filter(|child| child.parent.as_deref() == Some(variable.name.as_str()))
+                .filter(|child| child.probability(column, NOT_APPLICABLE_STATE).is_none_or(|na| na < 1.0 - ROW_TOLERANCE))
+                .collect();
+            let children = match applicable.as_slice() {
+                [] => Vec::new(),
+                [child] => self.subtree_nodes(child, column, mass * probability)?,
+                _ => return Err(format!("State {} of {} branches into several variables ({}); only tree-shaped networks can be imported",
+                    state, variable.name, applicable.iter().map(|child| child.name.as_str()).collect::<Vec<_>>().join(", "))),
+            };
+
+            nodes.push(SubtreeNode {
+                premise: variable.outcome_premise(state),
+                reasoning: format!("Imported from Bayesian network variable {} = {} (p = {})",
+                    variable.name, state, format_probability(probability)),
+                probability: mass * probability,
+                confidence: IMPORTED_CONFIDENCE,
+                children,
+            });
+        }
+        Ok(nodes)
+    }
+
+    /// Checks the structure the importer relies on: known single parents and well-formed CPTs.
+    fn validate(&self) -> Result<(), String> {
+        for variable in &self.variables {
+            if variable.states.is_empty() {
+                return Err(format!("Variable {} has no states", variable.name));
+            }
+            let rows = match &variable.parent {
+                None => 1,
+                Some(parent) => self.variable(parent)
+                    .ok_or_else(|| format!("Variable {} depends on unknown variable {}", variable.name, parent))?
+                    .states.len(),
+            };
+            if variable.table.len() != rows || variable.table.iter().any(|row| row.len() != variable.states.len()) {
+                return Err(format!("Table of {} should have {} rows of {} probabilities", variable.name, rows, variable.states.len()));
+            }
+            for (i, row) in variable.table.iter().enumerate() {
+                let total: f64 = row.iter().sum();
+                if row.iter().any(|p| !(0.0..=1.0).contains(p)) || (total - 1.0).abs() > ROW_TOLERANCE {
+                    return Err(format!("Row {} of the table of {} must hold probabilities summing to 1, got {:.4}", i + 1, variable.name, total));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_xmlbif(text: &str) -> Result<Self, String> {
+        let network = xml_elements(text, "NETWORK").into_iter().next()
+            .ok_or_else(|| "XMLBIF document has no NETWORK element".to_string())?;
+        // The network name is the first NAME outside any VARIABLE
+        let header = network.split_once("<VARIABLE").map(|(header, _)| header).unwrap_or(network);
+        let name = xml_elements(header, "NAME").first().map(|name| xml_unescape(name)).unwrap_or_default();
+
+        let mut variables = Vec::new();
+        for element in xml_elements(network, "VARIABLE") {
+            let name = xml_elements(element, "NAME").first().map(|name| xml_unescape(name))
+                .ok_or_else(|| "VARIABLE without NAME".to_string())?;
+            let states = xml_elements(element, "OUTCOME").iter().map(|state| xml_unescape(state)).collect();
+            let properties = xml_elements(element, "PROPERTY").iter()
+                .filter_map(|property| xml_unescape(property).split_once('=').map(|(key, value)| (key.trim().to_string(), value.trim().to_string())))
+                .collect();
+            variables.push(BayesVariable { name, states, parent: None, table: Vec::new(), properties });
+        }
+
+        for definition in xml_elements(network, "DEFINITION") {
+            let name = xml_elements(definition, "FOR").first().map(|name| xml_unescape(name))
+                .ok_or_else(|| "DEFINITION without FOR".to_string())?;
+            let given: Vec<String> = xml_elements(definition, "GIVEN").iter().map(|parent| xml_unescape(parent)).collect();
+            if given.len() > 1 {
+                return Err(format!("Variable {} has {} parents; only tree-shaped networks can be imported", name, given.len()));
+            }
+            let table: Vec<f64> = xml_elements(definition, "TABLE").first().copied().unwrap_or_default()
+                .split_whitespace()
+                .map(|value| value.parse::<f64>().map_err(|_| format!("Invalid probability '{}' in the table of {}", value, name)))
+                .collect::<Result<_, _>>()?;
+
+            let variable = variables.iter_mut().find(|variable| variable.name == name)
+                .ok_or_else(|| format!("DEFINITION for unknown variable {}", name))?;
+            variable.parent = given.into_iter().next();
+            variable.table = table.chunks(variable.states.len().max(1)).map(<[f64]>::to_vec).collect();
+        }
+
+        Ok(Self { name, variables })
+    }
+
+    fn parse_bif(text: &str) -> Result<Self, String> {
+        let tokens = bif_tokens(text)?;
+        let mut cursor = Tokens { tokens: &tokens, position: 0 };
+        let mut name = String::new();
+        let mut variables: Vec<BayesVariable> = Vec::new();
+        // Rows of conditional tables keyed by parent state, resolved once every variable is known
+        let mut keyed_rows: Vec<(String, Vec<(String, Vec<f64>)>)> = Vec::new();
+
+        while let Some(token) = cursor.advance() {
+            match token {
+                "network" => {
+                    name = cursor.expect_any()?.to_string();
+                    cursor.skip_block()?;
+                }
+                "variable" => {
+                    let mut variable = BayesVariable {
+                        name: cursor.expect_any()?.to_string(),
+                        states: Vec::new(),
+                        parent: None,
+                        table: Vec::new(),
+                        properties: BTreeMap::new(),
+                    };
+                    cursor.expect("{")?;
+                    loop {
+                        match cursor.expect_any()? {
+                            "}" => break,
+                            "type" => {
+                                cursor.expect("discrete")?;
+                                cursor.expect("[")?;
+                                cursor.expect_any()?;
+                                cursor.expect("]")?;
+                                cursor.expect("{")?;
+                                variable.states = cursor.list("}")?;
+                                cursor.expect(";")?;
+                            }
+                            "property" => {
+                                let property = cursor.expect_any()?;
+                                if let Some((key, value)) = property.split_once('=') {
+                                    variable.properties.insert(key.trim().to_string(), value.trim().to_string());
+                                }
+                                cursor.expect(";")?;
+                            }
+                            _ => cursor.skip_statement()?,
+                        }
+                    }
+                    variables.push(variable);
+                }
+                "probability" => {
+                    cursor.expect("(")?;
+                    let variable_name = cursor.expect_any()?.to_string();
+                    let mut parents = Vec::new();
+                    if cursor.peek() == Some("|") {
+                        cursor.advance();
+                        parents = cursor.list(")")?;
+                    } else {
+                        cursor.expect(")")?;
+                    }
+                    if parents.len() > 1 {
+                        return Err(format!("Variable {} has {} parents; only tree-shaped networks can be imported", variable_name, parents.len()));
+                    }
+
+                    let mut table = Vec::new();
+                    let mut rows = Vec::new();
+                    cursor.expect("{")?;
+                    loop {
+                        match cursor.expect_any()? {
+                            "}" => break,
+                            "table" => table = parse_probabilities(&cursor.list(";")?)?,
+                            "(" => {
+                                let key = cursor.list(")")?.join(",");
+                                rows.push((key, parse_probabilities(&cursor.list(";")?)?));
+                            }
+                            _ => cursor.skip_statement()?,
+                        }
+                    }
+
+                    let variable = variables.iter_mut().find(|variable| variable.name == variable_name)
+                        .ok_or_else(|| format!("Probability for unknown variable {}", variable_name))?;
+                    variable.parent = parents.into_iter().next();
+                    if !table.is_empty() {
+                        variable.table = table.chunks(variable.states.len().max(1)).map(<[f64]>::to_vec).collect();
+                    } else {
+                        keyed_rows.push((variable_name, rows));
+                    }
+                }
+                other => return Err(format!("Unexpected '{}' in BIF network", other)),
+            }
+        }
+
+        for (variable_name, rows) in keyed_rows {
+            let parent_states = variables.iter()
+                .find(|variable| variable.name == variable_name)
+                .and_then(|variable| variable.parent.clone())
+                .and_then(|parent| variables.iter().find(|variable| variable.name == parent))
+                .map(|parent| parent.states.clone())
+                .unwrap_or_default();
+            let mut table = Vec::new();
+            for state in &parent_states {
+                let row = rows.iter().find(|(key, _)| key == state)
+                    .ok_or_else(|| format!("The table of {} has no row for parent state {}", variable_name, state))?;
+                table.push(row.1.clone());
+            }
+            if let Some(variable) = variables.iter_mut().find(|variable| variable.name == variable_name) {
+                variable.table = table;
+            }
+        }
+
+        Ok(Self { name, variables })
+    }
+}
+
+/// A name usable as a BIF identifier and XML text: letters, digits and underscores.
+pub fn identifier(text: &str) -> String {
+    let name: String = text.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        format!("n_{}", name)
+    } else {
+        name
+    }
+}
+
+fn format_probability(probability: f64) -> String {
+    let rounded = (probability * 1e6).round() / 1e6;
+    format!("{}", rounded)
+}
+
+fn parse_probabilities(values: &[String]) -> Result<Vec<f64>, String> {
+    values.iter()
+        .map(|value| value.parse::<f64>().map_err(|_| format!("Invalid probability '{}'", value)))
+        .collect()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.trim()
+        .replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Contents of every `<tag ...>...</tag>` element in `text`, matching tag names case-insensitively.
+fn xml_elements<'a>(text: &'a str, tag: &str) -> Vec<&'a str> {
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower` slice `text`
+    let lower = text.to_ascii_lowercase();
+    let open = format!("<{}", tag.to_ascii_lowercase());
+    let close = format!("</{}>", tag.to_ascii_lowercase());
+
+    let mut elements = Vec::new();
+    let mut position = 0;
+    while let Some(start) = lower[position..].find(&open).map(|offset| position + offset) {
+        let after_name = start + open.len();
+        // Skip longer tag names sharing the prefix, e.g. <NAMES> when looking for <NAME>
+        if !lower[after_name..].starts_with(|c: char| c == '>' || c.is_whitespace()) {
+            position = after_name;
+            continue;
+        }
+        let Some(content_start) = lower[after_name..].find('>').map(|offset| after_name + offset + 1) else { break };
+        let Some(end) = lower[content_start..].find(&close).map(|offset| content_start + offset) else { break };
+        elements.push(&text[content_start..end]);
+        position = end + close.len();
+    }
+    elements
+}
+
+fn bif_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Splits BIF text into words, quoted strings and single punctuation tokens, dropping comments.
+fn bif_tokens(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => token.extend(chars.next()),
+                        Some('"') => break,
+                        Some(next) => token.push(next),
+                        None => return Err("Unterminated string in BIF network".to_string()),
+                    }
+                }
+                tokens.push(token);
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ',' | ';' | '|' => tokens.push(c.to_string()),
+            _ => {
+                let mut token = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "{}()[],;|\"".contains(next) {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Tokens<'a> {
+    tokens: &'a [String],
+    position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn advance(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position)?;
+        self.position += 1;
+        Some(token.as_str())
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn expect_any(&mut self) -> Result<&'a str, String> {
+        self.advance().ok_or_else(|| "Unexpected end of BIF network".to_string())
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.expect_any()? {
+            token if token == expected => Ok(()),
+            token => Err(format!("Expected '{}' in BIF network, found '{}'", expected, token)),
+        }
+    }
+
+    /// Comma-separated tokens up to and including `end`.
+    fn list(&mut self, end: &str) -> Result<Vec<String>, String> {
+        let mut items = Vec::new();
+        loop {
+            match self.expect_any()? {
+                token if token == end => return Ok(items),
+                "," => {}
+                token => items.push(token.to_string()),
+            }
+        }
+    }
+
+    fn skip_statement(&mut self) -> Result<(), String> {
+        while self.expect_any()? != ";" {}
+        Ok(())
+    }
+
+    fn skip_block(&mut self) -> Result<(), String> {
+        self.expect("{")?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.expect_any()? {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(name: &str, states: &[&str], parent: Option<&str>, table: Vec<Vec<f64>>) -> BayesVariable {
+        BayesVariable {
+            name: name.to_string(),
+            states: states.iter().map(|state| state.to_string()).collect(),
+            parent: parent.map(str::to_string),
+            table,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    fn launch_net() -> BayesNet {
+        let mut root = variable("root", &["delays", "on_time", OTHER_STATE], None, vec![vec![0.6, 0.3, 0.1]]);
+        root.properties.insert(PREMISE_PROPERTY.to_string(), "Will the launch slip?".to_string());
+        root.properties.insert(format!("{}delays", OUTCOME_PROPERTY_PREFIX), "Supplier \"delays\" & strikes".to_string());
+        let delays = variable("delays", &["port", "fire", NOT_APPLICABLE_STATE], Some("root"), vec![
+            vec![0.5, 0.5, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 1.0],
+        ]);
+        BayesNet { name: "tree_1".to_string(), variables: vec![root, delays] }
+    }
+
+    #[test]
+    fn test_round_trips_both_formats() {
+        let net = launch_net();
+        for format in [BayesNetFormat::XmlBif, BayesNetFormat::Bif] {
+            let text = net.render(format);
+            assert_eq!(BayesNet::parse(&text).unwrap(), net, "{:?} round trip:\n{}", format, text);
+        }
+        assert!(net.to_bif().contains("probability ( delays | root ) {\n  (delays) 0.5, 0.5, 0;"));
+        assert!(net.to_xmlbif().contains("<TABLE>0.6 0.3 0.1</TABLE>"));
+    }
+
+    #[test]
+    fn test_converts_to_subtree() {
+        let (premise, nodes) = launch_net().to_subtree().unwrap();
+        assert_eq!(premise, "Will the launch slip?");
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].premise, "Supplier \"delays\" & strikes");
+        assert_eq!(nodes[1].premise, "on time");
+        assert_eq!(nodes[0].children.len(), 2);
+        assert!((nodes[0].children[0].probability - 0.3).abs() < 1e-9);
+        assert!(nodes[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_non_tree_networks() {
+        let mut net = launch_net();
+        net.variables.push(variable("also", &["x", "y"], Some("root"), vec![vec![0.5, 0.5]; 3]));
+        let error = net.to_subtree().unwrap_err();
+        assert!(error.contains("branches into several variables"), "{}", error);
+
+        let two_parents = "network n {}\nvariable a { type discrete [ 2 ] { x, y }; }\nprobability ( a | b, c ) { table 0.5, 0.5; }";
+        assert!(BayesNet::parse(two_parents).unwrap_err().contains("2 parents"));
+
+        let bad_row = "network n {}\nvariable a { type discrete [ 2 ] { x, y }; }\nprobability ( a ) { table 0.5, 0.6; }";
+        assert!(BayesNet::parse(bad_row).unwrap_err().contains("summing to 1"));
+    }
+}
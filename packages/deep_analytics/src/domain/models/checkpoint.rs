@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use surrealdb::RecordId;
+use crate::domain::models::cross_link::CrossLink;
+use crate::domain::models::tree_node::TreeNode;
+use crate::domain::models::tree_state::TreeState;
+
+/// Full copy of a tree captured under a name, stored in the `checkpoint` table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeCheckpoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<RecordId>,
+    pub name: String,
+    pub tree_id: String,
+    pub state: TreeState,
+    pub nodes: Vec<TreeNode>,
+    #[serde(default)]
+    pub cross_links: Vec<CrossLink>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Lightweight description of a checkpoint, as returned by `list_checkpoints`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckpointSummary {
+    pub name: String,
+    pub tree_id: String,
+    pub node_count: usize,
+    pub cross_link_count: usize,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Structural differences between two versions of a tree.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TreeDiff {
+    pub from: String,
+    pub to: String,
+    pub added_nodes: Vec<DiffNode>,
+    pub removed_nodes: Vec<DiffNode>,
+    pub probability_changes: Vec<ValueDelta>,
+    pub confidence_changes: Vec<ValueDelta>,
+    pub premise_changes: Vec<PremiseChange>,
+    pub top_k: usize,
+    /// Paths whose rank changed among the top-k of either version
+    pub ranking_changes: Vec<PathRankChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiffNode {
+    pub node_id: String,
+    pub premise: String,
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ValueDelta {
+    pub node_id: String,
+    pub premise: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PremiseChange {
+    pub node_id: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Rank of one root-to-leaf path in each version; `None` when the path is absent.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PathRankChange {
+    pub premises: Vec<String>,
+    pub rank_before: Option<usize>,
+    pub rank_after: Option<usize>,
+    pub probability_before: Option<f64>,
+    pub probability_after: Option<f64>,
+}
+
+impl TreeCheckpoint {
+    pub fn summary(&self) -> CheckpointSummary {
+        CheckpointSummary {
+            name: self.name.clone(),
+            tree_id: self.tree_id.clone(),
+            node_count: self.nodes.len(),
+            cross_link_count: self.cross_links.len(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl ValueDelta {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+impl TreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.probability_changes.is_empty()
+            && self.confidence_changes.is_empty()
+            && self.premise_changes.is_empty()
+            && self.ranking_changes.is_empty()
+    }
+}
+
+impl Display for CheckpointSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "📌 {} — tree {} | {} nodes, {} cross-links | {}",
+            self.name, self.tree_id, self.node_count, self.cross_link_count,
+            self.created_at.format("%Y-%m-%d %H:%M:%S UTC"))
+    }
+}
+
+fn format_rank(rank: Option<usize>) -> String {
+    rank.map(|rank| format!("#{}", rank)).unwrap_or_else(|| "—".to_string())
+}
+
+impl Display for TreeDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🔀 TREE DIFF: {} → {}\n", self.from, self.to)?;
+
+        if self.is_empty() {
+            writeln!(f, "No structural differences.")?;
+            return Ok(());
+        }
+
+        if !self.added_nodes.is_empty() {
+            writeln!(f, "➕ ADDED NODES ({}):", self.added_nodes.len())?;
+            for node in &self.added_nodes {
+                writeln!(f, "   [{}] {} (p={:.3})", node.node_id, node.premise, node.probability)?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.removed_nodes.is_empty() {
+            writeln!(f, "➖ REMOVED NODES ({}):", self.removed_nodes.len())?;
+            for node in &self.removed_nodes {
+                writeln!(f, "   [{}] {} (p={:.3})", node.node_id, node.premise, node.probability)?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.probability_changes.is_empty() {
+            writeln!(f, "📊 PROBABILITY CHANGES ({}):", self.probability_changes.len())?;
+            for change in &self.probability_changes {
+                writeln!(f, "   [{}] {}: {:.3} → {:.3} ({:+.3})",
+                    change.node_id, change.premise, change.before, change.after, change.delta())?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.confidence_changes.is_empty() {
+            writeln!(f, "🎯 CONFIDENCE CHANGES ({}):", self.confidence_changes.len())?;
+            for change in &self.confidence_changes {
+                writeln!(f, "   [{}] {}: {} → {} ({:+})",
+                    change.node_id, change.premise, change.before, change.after, change.delta())?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.premise_changes.is_empty() {
+            writeln!(f, "✏️  PREMISE CHANGES ({}):", self.premise_changes.len())?;
+            for change in &self.premise_changes {
+                writeln!(f, "   [{}] \"{}\" → \"{}\"", change.node_id, change.before, change.after)?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.ranking_changes.is_empty() {
+            writeln!(f, "🏆 TOP-{} PATH RANKING CHANGES ({}):", self.top_k, self.ranking_changes.len())?;
+            for change in &self.ranking_changes {
+                writeln!(f, "   {} → {}: {}",
+                    format_rank(change.rank_before), format_rank(change.rank_after), change.premises.join(" → "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_diff() -> TreeDiff {
+        TreeDiff {
+            from: "before pruning".to_string(),
+            to: "current".to_string(),
+            added_nodes: vec![],
+            removed_nodes: vec![],
+            probability_changes: vec![],
+            confidence_changes: vec![],
+            premise_changes: vec![],
+            top_k: 5,
+            ranking_changes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_empty_diff() {
+        let diff = empty_diff();
+        assert!(diff.is_empty());
+        assert!(diff.to_string().contains("No structural differences"));
+    }
+
+    #[test]
+    fn test_diff_display() {
+        let mut diff = empty_diff();
+        diff.probability_changes.push(ValueDelta {
+            node_id: "clever_turing".to_string(),
+            premise: "Demand shock".to_string(),
+            before: 0.5,
+            after: 0.35,
+        });
+        diff.ranking_changes.push(PathRankChange {
+            premises: vec!["Root".to_string(), "Demand shock".to_string()],
+            rank_before: Some(1),
+            rank_after: None,
+            probability_before: Some(0.5),
+            probability_after: None,
+        });
+
+        assert!(!diff.is_empty());
+        assert!((diff.probability_changes[0].delta() + 0.15).abs() < 1e-9);
+        let text = diff.to_string();
+        assert!(text.contains("0.500 → 0.350 (-0.150)"));
+        assert!(text.contains("#1 → —: Root → Demand shock"));
+    }
+}
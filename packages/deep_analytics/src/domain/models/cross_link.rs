@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::RecordId;
+
+/// An extra parent → child edge stored as a `cross_link` relation.
+///
+/// Cross-links let a shared downstream outcome hang off several causes without
+/// duplicating the branch, turning the tree into a scenario DAG. The node's
+/// `parent_id` stays its primary parent; every cross-link adds another one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrossLink {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<RecordId>,
+    #[serde(rename = "in")]
+    pub parent: RecordId,
+    #[serde(rename = "out")]
+    pub child: RecordId,
+    /// Probability of reaching the child through this parent, used in place of
+    /// the child's own probability when a path crosses this link
+    pub probability: f64,
+    pub reasoning: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_relation() {
+        let link = CrossLink {
+            id: None,
+            parent: "node:a".parse().unwrap(),
+            child: "node:b".parse().unwrap(),
+            probability: 0.4,
+            reasoning: "Both causes end in a market collapse".to_string(),
+            created_at: chrono::Utc::now(),
+        };
+
+        let value = serde_json::to_value(&link).unwrap();
+        assert!(value.get("in").is_some());
+        assert!(value.get("out").is_some());
+        assert!(value.get("id").is_none());
+
+        let deserialized: CrossLink = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, link);
+    }
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// A cited source backing the estimate made on a node.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Evidence {
+    pub id: String,
+    pub source_title: String,
+    /// URI or document reference (e.g. "https://...", "Q3 board deck, p.12")
+    pub source_ref: String,
+    pub quote: String,
+    pub date: Option<chrono::NaiveDate>,
+    pub strength: EvidenceStrength,
+    pub added_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+pub enum EvidenceStrength {
+    Weak,
+    Moderate,
+    Strong,
+    Conclusive,
+}
+
+/// Evidence attached to a single node, as returned by `list_evidence`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NodeEvidence {
+    pub node_id: String,
+    pub premise: String,
+    pub evidence: Vec<Evidence>,
+}
+
+/// Evidence supporting one surviving path, ordered from root to leaf.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PathBibliography {
+    pub path_number: usize,
+    pub leaf_id: String,
+    pub citations: Vec<Citation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Citation {
+    pub node_id: String,
+    pub evidence: Evidence,
+}
+
+impl Evidence {
+    pub fn new(
+        source_title: String,
+        source_ref: String,
+        quote: String,
+        date: Option<chrono::NaiveDate>,
+        strength: EvidenceStrength,
+    ) -> Self {
+        Self {
+            id: format!("ev_{}", &uuid::Uuid::new_v4().simple().to_string()[..8]),
+            source_title,
+            source_ref,
+            quote,
+            date,
+            strength,
+            added_at: chrono::Utc::now(),
+        }
+    }
+}
+
+impl Default for EvidenceStrength {
+    fn default() -> Self {
+        EvidenceStrength::Moderate
+    }
+}
+
+impl EvidenceStrength {
+    /// Parses `weak`, `moderate`, `strong` or `conclusive`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "weak" => Ok(EvidenceStrength::Weak),
+            "moderate" => Ok(EvidenceStrength::Moderate),
+            "strong" => Ok(EvidenceStrength::Strong),
+            "conclusive" => Ok(EvidenceStrength::Conclusive),
+            other => Err(format!("Unknown strength '{}', expected Weak, Moderate, Strong or Conclusive", other)),
+        }
+    }
+}
+
+impl Display for Evidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[{}] {} — {}", self.id, self.source_title, self.source_ref)?;
+        if let Some(date) = self.date {
+            write!(f, " ({})", date.format("%Y-%m-%d"))?;
+        }
+        write!(f, " | strength: {:?}", self.strength)?;
+        if !self.quote.trim().is_empty() {
+            write!(f, " | \"{}\"", self.quote)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for NodeEvidence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "📎 [{}] {} ({} source(s))", self.node_id, self.premise, self.evidence.len())?;
+        for evidence in &self.evidence {
+            writeln!(f, "   └─ {}", evidence)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_evidence() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let evidence = Evidence::new(
+            "Supplier audit".to_string(),
+            "https://example.com/audit.pdf".to_string(),
+            "Lead times doubled in Q1".to_string(),
+            Some(date),
+            EvidenceStrength::Strong,
+        );
+
+        assert!(evidence.id.starts_with("ev_"));
+        assert_eq!(evidence.id.len(), 11);
+        assert_eq!(evidence.date, Some(date));
+        assert_eq!(evidence.strength, EvidenceStrength::Strong);
+    }
+
+    #[test]
+    fn test_strength_ordering() {
+        assert!(EvidenceStrength::Weak < EvidenceStrength::Moderate);
+        assert!(EvidenceStrength::Strong < EvidenceStrength::Conclusive);
+        assert_eq!(EvidenceStrength::default(), EvidenceStrength::Moderate);
+    }
+
+    #[test]
+    fn test_parse_strength() {
+        assert_eq!(EvidenceStrength::parse("strong").unwrap(), EvidenceStrength::Strong);
+        assert_eq!(EvidenceStrength::parse(" Conclusive ").unwrap(), EvidenceStrength::Conclusive);
+        assert!(EvidenceStrength::parse("very strong").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let evidence = Evidence::new(
+            "Market report".to_string(),
+            "Gartner 2025, p.4".to_string(),
+            String::new(),
+            None,
+            EvidenceStrength::Weak,
+        );
+
+        let text = evidence.to_string();
+        assert!(text.contains("Market report — Gartner 2025, p.4"));
+        assert!(text.contains("strength: Weak"));
+        assert!(!text.contains('"'));
+    }
+}
@@ -0,0 +1,354 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::domain::models::tree_node::Gate;
+
+/// Largest number of minimal cut sets enumerated before the analysis gives up.
+pub const MAX_CUT_SETS: usize = 10_000;
+
+/// An event of a fault tree: a basic event with its own probability, or a gate over input events.
+#[derive(Debug, Clone)]
+pub struct FaultEvent {
+    pub node_id: String,
+    pub premise: String,
+    pub kind: FaultEventKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum FaultEventKind {
+    /// Leaf event with an independent probability of occurring
+    Basic(f64),
+    Gate(Gate, Vec<FaultEvent>),
+}
+
+/// Top-event probability, minimal cut sets and basic event importance of a fault tree.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FaultTreeAnalysis {
+    pub top_event_id: String,
+    pub top_event: String,
+    pub top_event_probability: f64,
+    /// Gates with the probability of their output event, top-down
+    pub gates: Vec<GateOutput>,
+    /// Smallest sets of basic events that together cause the top event, most probable first
+    pub minimal_cut_sets: Vec<CutSet>,
+    /// Basic events, highest Fussell-Vesely importance first
+    pub basic_events: Vec<BasicEventImportance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GateOutput {
+    pub node_id: String,
+    pub premise: String,
+    pub gate: Gate,
+    pub inputs: usize,
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CutSet {
+    /// Ids of the basic events in the set
+    pub events: Vec<String>,
+    /// Probability that every event of the set occurs
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BasicEventImportance {
+    pub node_id: String,
+    pub premise: String,
+    pub probability: f64,
+    /// Share of the top-event probability removed if the event could not occur
+    pub fussell_vesely: f64,
+    /// Top-event probability if the event occurs minus if it does not
+    pub birnbaum: f64,
+}
+
+impl Gate {
+    /// Output probability from independent input probabilities.
+    pub fn combine(&self, inputs: &[f64]) -> f64 {
+        match self {
+            Gate::And => inputs.iter().product(),
+            Gate::Or => 1.0 - inputs.iter().map(|p| 1.0 - p).product::<f64>(),
+            Gate::KOfN(k) => {
+                // exactly[j] = probability that exactly j inputs occur so far
+                let mut exactly = vec![1.0];
+                for p in inputs {
+                    let mut next = vec![0.0; exactly.len() + 1];
+                    for (j, q) in exactly.iter().enumerate() {
+                        next[j] += q * (1.0 - p);
+                        next[j + 1] += q * p;
+                    }
+                    exactly = next;
+                }
+                exactly.iter().skip(*k).sum()
+            }
+        }
+    }
+}
+
+impl FaultEvent {
+    /// Probability of the event, with the basic event `forced` (if any) set to a fixed probability.
+    fn probability_with(&self, forced: Option<(&str, f64)>) -> f64 {
+        match &self.kind {
+            FaultEventKind::Basic(probability) => match forced {
+                Some((node_id, forced_probability)) if node_id == self.node_id => forced_probability,
+                _ => *probability,
+            },
+            FaultEventKind::Gate(gate, inputs) => {
+                let probabilities: Vec<f64> = inputs.iter().map(|input| input.probability_with(forced)).collect();
+                gate.combine(&probabilities)
+            }
+        }
+    }
+
+    pub fn probability(&self) -> f64 {
+        self.probability_with(None)
+    }
+
+    /// Gates whose threshold cannot be met by their number of inputs.
+    fn validate(&self, errors: &mut Vec<String>) {
+        if let FaultEventKind::Gate(gate, inputs) = &self.kind {
+            if matches!(gate, Gate::KOfN(k) if *k == 0 || *k > inputs.len()) {
+                errors.push(format!("[{}] {} gate has only {} inputs", self.node_id, gate, inputs.len()));
+            }
+            for input in inputs {
+                input.validate(errors);
+            }
+        }
+    }
+
+    fn collect(&self, gates: &mut Vec<GateOutput>, basic_events: &mut Vec<(String, String, f64)>) {
+        match &self.kind {
+            FaultEventKind::Basic(probability) => basic_events.push((self.node_id.clone(), self.premise.clone(), *probability)),
+            FaultEventKind::Gate(gate, inputs) => {
+                gates.push(GateOutput {
+                    node_id: self.node_id.clone(),
+                    premise: self.premise.clone(),
+                    gate: *gate,
+                    inputs: inputs.len(),
+                    probability: self.probability(),
+                });
+                for input in inputs {
+                    input.collect(gates, basic_events);
+                }
+            }
+        }
+    }
+
+    /// Minimal cut sets as sets of basic event ids, by top-down expansion of the gates.
+    pub fn cut_sets(&self) -> Result<Vec<BTreeSet<String>>, String> {
+        let sets = match &self.kind {
+            FaultEventKind::Basic(_) => vec![BTreeSet::from([self.node_id.clone()])],
+            FaultEventKind::Gate(gate, inputs) => {
+                let input_sets = inputs.iter().map(FaultEvent::cut_sets).collect::<Result<Vec<_>, _>>()?;
+                match gate {
+                    Gate::Or => input_sets.into_iter().flatten().collect(),
+                    Gate::And => all_of(&input_sets.iter().collect::<Vec<_>>())?,
+                    Gate::KOfN(k) => {
+                        let mut sets = Vec::new();
+                        for chosen in combinations(input_sets.len(), *k) {
+                            sets.extend(all_of(&chosen.iter().map(|&i| &input_sets[i]).collect::<Vec<_>>())?);
+                            check_size(sets.len())?;
+                        }
+                        sets
+                    }
+                }
+            }
+        };
+        check_size(sets.len())?;
+        Ok(minimize(sets))
+    }
+}
+
+fn check_size(count: usize) -> Result<(), String> {
+    if count > MAX_CUT_SETS {
+        return Err(format!("The fault tree has more than {} cut sets to enumerate", MAX_CUT_SETS));
+    }
+    Ok(())
+}
+
+/// Cut sets of an AND over inputs: one set from each input, merged.
+fn all_of(input_sets: &[&Vec<BTreeSet<String>>]) -> Result<Vec<BTreeSet<String>>, String> {
+    let mut sets = vec![BTreeSet::new()];
+    for input in input_sets {
+        let mut next = Vec::new();
+        for set in &sets {
+            for other in input.iter() {
+                next.push(set.union(other).cloned().collect());
+            }
+            check_size(next.len())?;
+        }
+        sets = minimize(next);
+    }
+    Ok(sets)
+}
+
+/// Drops sets that contain another set, and duplicates.
+fn minimize(mut sets: Vec<BTreeSet<String>>) -> Vec<BTreeSet<String>> {
+    sets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    let mut minimal: Vec<BTreeSet<String>> = Vec::new();
+    for set in sets {
+        if !minimal.iter().any(|kept| kept.is_subset(&set)) {
+            minimal.push(set);
+        }
+    }
+    minimal
+}
+
+/// Every choice of `k` indices out of `0..n`, in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 || k > n {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    let mut chosen: Vec<usize> = (0..k).collect();
+    loop {
+        result.push(chosen.clone());
+        // Advance the rightmost index that still has room
+        let Some(i) = (0..k).rev().find(|&i| chosen[i] < n - k + i) else {
+            return result;
+        };
+        chosen[i] += 1;
+        for j in i + 1..k {
+            chosen[j] = chosen[j - 1] + 1;
+        }
+    }
+}
+
+impl FaultTreeAnalysis {
+    /// Analyzes the fault tree under `top`, assuming independent basic events.
+    ///
+    /// Fussell-Vesely importance is computed as the relative drop in top-event
+    /// probability when the basic event cannot occur.
+    pub fn analyze(top: &FaultEvent) -> Result<Self, String> {
+        let mut errors = Vec::new();
+        top.validate(&mut errors);
+        if !errors.is_empty() {
+            return Err(errors.join("; "));
+        }
+
+        let mut gates = Vec::new();
+        let mut events = Vec::new();
+        top.collect(&mut gates, &mut events);
+        let top_event_probability = top.probability();
+
+        let probability_of = |node_id: &str| events.iter()
+            .find(|(id, _, _)| id == node_id)
+            .map(|(_, _, probability)| *probability)
+            .unwrap_or(0.0);
+        let mut minimal_cut_sets: Vec<CutSet> = top.cut_sets()?.into_iter()
+            .map(|set| CutSet {
+                probability: set.iter().map(|node_id| probability_of(node_id)).product(),
+                events: set.into_iter().collect(),
+            })
+            .collect();
+        minimal_cut_sets.sort_by(|a, b| b.probability.partial_cmp(&a.probability).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut basic_events: Vec<BasicEventImportance> = events.iter()
+            .map(|(node_id, premise, probability)| {
+                let if_occurs = top.probability_with(Some((node_id, 1.0)));
+                let if_not = top.probability_with(Some((node_id, 0.0)));
+                BasicEventImportance {
+                    node_id: node_id.clone(),
+                    premise: premise.clone(),
+                    probability: *probability,
+                    fussell_vesely: if top_event_probability > 0.0 { (top_event_probability - if_not) / top_event_probability } else { 0.0 },
+                    birnbaum: if_occurs - if_not,
+                }
+            })
+            .collect();
+        basic_events.sort_by(|a, b| b.fussell_vesely.partial_cmp(&a.fussell_vesely).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(Self {
+            top_event_id: top.node_id.clone(),
+            top_event: top.premise.clone(),
+            top_event_probability,
+            gates,
+            minimal_cut_sets,
+            basic_events,
+        })
+    }
+}
+
+impl Display for FaultTreeAnalysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "⚠️  FAULT TREE ANALYSIS: [{}] {}", self.top_event_id, self.top_event)?;
+        writeln!(f, "   Top event probability: {:.6}\n", self.top_event_probability)?;
+
+        writeln!(f, "🔀 GATES ({}):", self.gates.len())?;
+        for gate in &self.gates {
+            writeln!(f, "   [{}] {} — {} over {} inputs → p={:.6}",
+                gate.node_id, gate.premise, gate.gate, gate.inputs, gate.probability)?;
+        }
+
+        writeln!(f, "\n✂️  MINIMAL CUT SETS ({}):", self.minimal_cut_sets.len())?;
+        for (i, cut_set) in self.minimal_cut_sets.iter().enumerate() {
+            writeln!(f, "   {}. {{{}}} p={:.6}", i + 1, cut_set.events.join(", "), cut_set.probability)?;
+        }
+
+        writeln!(f, "\n📊 BASIC EVENT IMPORTANCE:")?;
+        for event in &self.basic_events {
+            writeln!(f, "   [{}] {} (p={:.4}) | Fussell-Vesely {:.4} | Birnbaum {:.4}",
+                event.node_id, event.premise, event.probability, event.fussell_vesely, event.birnbaum)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basic(node_id: &str, probability: f64) -> FaultEvent {
+        FaultEvent { node_id: node_id.to_string(), premise: node_id.to_uppercase(), kind: FaultEventKind::Basic(probability) }
+    }
+
+    fn gate(node_id: &str, gate: Gate, inputs: Vec<FaultEvent>) -> FaultEvent {
+        FaultEvent { node_id: node_id.to_string(), premise: node_id.to_uppercase(), kind: FaultEventKind::Gate(gate, inputs) }
+    }
+
+    #[test]
+    fn test_gate_probabilities() {
+        assert!((Gate::And.combine(&[0.5, 0.2]) - 0.1).abs() < 1e-12);
+        assert!((Gate::Or.combine(&[0.5, 0.2]) - 0.6).abs() < 1e-12);
+        // 2-of-3 with p=0.1: 3·0.01·0.9 + 0.001
+        assert!((Gate::KOfN(2).combine(&[0.1, 0.1, 0.1]) - 0.028).abs() < 1e-12);
+        assert_eq!(combinations(4, 2).len(), 6);
+        assert!(combinations(2, 3).is_empty());
+    }
+
+    #[test]
+    fn test_cut_sets_and_importance() {
+        // Pump fails, or both power supplies fail
+        let top = gate("top", Gate::Or, vec![
+            basic("pump", 0.01),
+            gate("power", Gate::And, vec![basic("grid", 0.1), basic("generator", 0.2)]),
+        ]);
+
+        let analysis = FaultTreeAnalysis::analyze(&top).unwrap();
+        assert!((analysis.top_event_probability - (1.0 - 0.99 * 0.98)).abs() < 1e-12);
+        let cut_sets: Vec<Vec<String>> = analysis.minimal_cut_sets.iter().map(|cut_set| cut_set.events.clone()).collect();
+        assert_eq!(cut_sets, vec![vec!["generator".to_string(), "grid".to_string()], vec!["pump".to_string()]]);
+
+        let grid = analysis.basic_events.iter().find(|event| event.node_id == "grid").unwrap();
+        // Birnbaum: P(top | grid) - P(top | no grid) = (1 - 0.99·0.8) - 0.01
+        assert!((grid.birnbaum - (1.0 - 0.99 * 0.8 - 0.01)).abs() < 1e-12);
+        assert!(grid.fussell_vesely > 0.0 && grid.fussell_vesely < 1.0);
+        assert_eq!(analysis.basic_events[0].node_id, "grid");
+        assert!(analysis.to_string().contains("{generator, grid}"));
+    }
+
+    #[test]
+    fn test_k_of_n_cut_sets_and_validation() {
+        let top = gate("top", Gate::KOfN(2), vec![basic("a", 0.1), basic("b", 0.1), basic("c", 0.1)]);
+        assert_eq!(top.cut_sets().unwrap().len(), 3);
+
+        let shared = gate("top", Gate::Or, vec![basic("a", 0.1), gate("both", Gate::And, vec![basic("a", 0.1), basic("b", 0.1)])]);
+        assert_eq!(shared.cut_sets().unwrap(), vec![BTreeSet::from(["a".to_string()])]);
+
+        let impossible = gate("top", Gate::KOfN(3), vec![basic("a", 0.1), basic("b", 0.1)]);
+        assert!(FaultTreeAnalysis::analyze(&impossible).unwrap_err().contains("3-of-n gate has only 2 inputs"));
+    }
+}
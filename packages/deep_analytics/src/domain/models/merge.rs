@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::domain::models::checkpoint::{DiffNode, PathRankChange};
+
+/// Outcome of merging another version of the tree into the live one.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergeReport {
+    pub source: String,
+    pub similarity_threshold: f64,
+    pub matched: Vec<MergedNode>,
+    /// Every node copied over from unmatched source subtrees
+    pub grafted: Vec<DiffNode>,
+    pub conflicts: Vec<SiblingConflict>,
+    /// True when the merge was only previewed and nothing was written
+    pub dry_run: bool,
+    /// Root-to-leaf paths whose probability or rank the merge changes
+    pub affected_paths: Vec<PathRankChange>,
+}
+
+/// A live node aligned with a source node and given their pooled estimate.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MergedNode {
+    pub node_id: String,
+    pub premise: String,
+    pub source_premise: String,
+    pub similarity: f64,
+    pub probability_before: f64,
+    pub source_probability: f64,
+    pub pooled_probability: f64,
+}
+
+/// Children whose probabilities sum above their parent's after a merge.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SiblingConflict {
+    pub parent_id: String,
+    pub parent_premise: String,
+    pub parent_probability: f64,
+    pub children: Vec<DiffNode>,
+    pub children_total: f64,
+}
+
+impl MergeReport {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+impl Display for MergeReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🔗 MERGE FROM '{}' (similarity ≥ {:.2}){}\n", self.source, self.similarity_threshold,
+            if self.dry_run { " - PREVIEW, nothing was written" } else { "" })?;
+
+        writeln!(f, "🤝 MATCHED NODES ({}):", self.matched.len())?;
+        for node in &self.matched {
+            writeln!(f, "   [{}] {} ≈ \"{}\" ({:.0}% similar): {:.3} + {:.3} → {:.3}",
+                node.node_id, node.premise, node.source_premise, node.similarity * 100.0,
+                node.probability_before, node.source_probability, node.pooled_probability)?;
+        }
+
+        if !self.grafted.is_empty() {
+            writeln!(f, "\n🌱 GRAFTED NODES ({}):", self.grafted.len())?;
+            for node in &self.grafted {
+                writeln!(f, "   [{}] {} (p={:.3})", node.node_id, node.premise, node.probability)?;
+            }
+        }
+
+        if self.conflicts.is_empty() {
+            writeln!(f, "\n✅ No sibling probability conflicts")?;
+        } else {
+            writeln!(f, "\n⚠️  CONFLICTS REQUIRING MANUAL RESOLUTION ({}):", self.conflicts.len())?;
+            for conflict in &self.conflicts {
+                writeln!(f, "{}", conflict)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for SiblingConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "   [{}] {}: children sum to {:.3}, exceeding parent probability {:.3}",
+            self.parent_id, self.parent_premise, self.children_total, self.parent_probability)?;
+        for child in &self.children {
+            writeln!(f, "      └─ [{}] {} (p={:.3})", child.node_id, child.premise, child.probability)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_display() {
+        let report = MergeReport {
+            source: "analyst_b".to_string(),
+            similarity_threshold: 0.75,
+            matched: vec![],
+            grafted: vec![],
+            dry_run: true,
+            affected_paths: vec![],
+            conflicts: vec![SiblingConflict {
+                parent_id: "clever_turing".to_string(),
+                parent_premise: "Root".to_string(),
+                parent_probability: 1.0,
+                children: vec![DiffNode {
+                    node_id: "brave_curie".to_string(),
+                    premise: "Supplier delays".to_string(),
+                    probability: 0.7,
+                }],
+                children_total: 1.2,
+            }],
+        };
+
+        assert!(report.has_conflicts());
+        let text = report.to_string();
+        assert!(text.contains("PREVIEW, nothing was written"));
+        assert!(text.contains("MANUAL RESOLUTION (1)"));
+        assert!(text.contains("children sum to 1.200, exceeding parent probability 1.000"));
+    }
+}
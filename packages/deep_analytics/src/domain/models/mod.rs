@@ -1,7 +1,37 @@
+pub mod argument;
+pub mod bayes_net;
+pub mod checkpoint;
+pub mod cross_link;
+pub mod evidence;
+pub mod fault_tree;
+pub mod merge;
+pub mod outline;
+pub mod resolution;
+pub mod resource_uri;
+pub mod risk;
+pub mod subtree;
+pub mod tabular;
+pub mod template;
+pub mod timeline;
 pub mod tree_node;
 pub mod tree_state;
 pub mod types;
 
-pub use tree_node::{TreeNode, NodeType};
-pub use tree_state::TreeConfig;
-pub use types::*;
\ No newline at end of file
+pub use argument::{Stance, ArgumentTag, ArgumentNode, ArgumentMap, ArgumentScore, Verdict};
+pub use bayes_net::{BayesNet, BayesVariable, BayesNetFormat};
+pub use checkpoint::{TreeCheckpoint, CheckpointSummary, TreeDiff, DiffNode, ValueDelta, PremiseChange, PathRankChange};
+pub use cross_link::CrossLink;
+pub use evidence::{Evidence, EvidenceStrength, NodeEvidence, PathBibliography, Citation};
+pub use fault_tree::{FaultEvent, FaultEventKind, FaultTreeAnalysis, GateOutput, CutSet, BasicEventImportance, MAX_CUT_SETS};
+pub use merge::{MergeReport, MergedNode, SiblingConflict};
+pub use outline::Outline;
+pub use resolution::{Resolution, LeafForecast, CalibrationReport, TreeScore, CalibrationBucket};
+pub use resource_uri::{TreeResourceUri, TreeResource, TREE_URI_SCHEME, NODE_URI_TEMPLATE};
+pub use risk::{ProbabilityBasis, RiskMatrix, RiskCell, RiskItem, CategoryRisk, MAX_IMPACT, DEFAULT_RISK_LEVELS};
+pub use subtree::{SubtreeNode, SubtreeAnchor, SubtreeInsertion, InsertedNode, SubtreeRelocation, RelocatedNode};
+pub use tabular::{TabularFormat, TabularTable, TabularRecord, PathRecord, NodeRecord, TABULAR_SCHEMA_VERSION};
+pub use template::{AnalysisTemplate, TemplateBranch, TemplateLibrary, TemplateListing};
+pub use timeline::{TimeWindow, Timeline, TimelineScenario, TimelineMilestone};
+pub use tree_node::{TreeNode, NodeType, Gate, EXHAUSTIVE_TOLERANCE, RESIDUAL_PREMISE};
+pub use tree_state::{TreeConfig, TreeMode};
+pub use types::*;
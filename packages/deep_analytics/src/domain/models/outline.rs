@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use crate::domain::models::subtree::{SubtreeAnchor, SubtreeNode};
+use crate::domain::models::tree_state::TreeConfig;
+
+/// Columns a tab counts for when measuring indentation.
+const TAB_WIDTH: usize = 4;
+
+/// Spaces per nesting level in exported outlines.
+const INDENT: &str = "  ";
+
+/// Shape of a bullet, quoted in parse errors.
+const BULLET_FORMAT: &str = "'- Premise (p=0.4, c=7): reasoning'";
+
+/// A tree read from a Markdown outline.
+///
+/// The outline opens with a `# premise` heading for the root, followed by nested bullets
+/// (`-`, `*` or `+`) of the form `- Premise (p=0.4, c=7): reasoning`. Deeper indentation
+/// makes a bullet a child of the bullet above it; blank lines are ignored.
+#[derive(Debug, Clone)]
+pub struct Outline {
+    pub premise: String,
+    /// 1-based line of the heading
+    pub premise_line: usize,
+    pub nodes: Vec<SubtreeNode>,
+    /// 1-based source line of each node, keyed by its `add_subtree` path such as `2.1`
+    pub lines: HashMap<String, usize>,
+}
+
+/// A bullet whose parent is still open while later lines are read.
+struct OpenBullet {
+    indent: usize,
+    path: String,
+    node: SubtreeNode,
+}
+
+impl Outline {
+    /// Parses an outline, returning every malformed line as `line N: ...`.
+    pub fn parse(text: &str) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut heading: Option<(String, usize)> = None;
+        let mut roots: Vec<SubtreeNode> = Vec::new();
+        let mut open: Vec<OpenBullet> = Vec::new();
+        let mut lines = HashMap::new();
+
+        for (i, raw) in text.lines().enumerate() {
+            let line = i + 1;
+            let trimmed = raw.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if trimmed.starts_with('#') {
+                if heading.is_some() || !lines.is_empty() {
+                    errors.push(format!("line {}: only one '# premise' heading is allowed, before the first bullet", line));
+                } else {
+                    heading = Some((trimmed.trim_start_matches('#').trim().to_string(), line));
+                }
+                continue;
+            }
+
+            let Some(item) = ["- ", "* ", "+ "].iter().find_map(|bullet| trimmed.strip_prefix(bullet)) else {
+                errors.push(format!("line {}: expected a bullet {}", line, BULLET_FORMAT));
+                continue;
+            };
+            if heading.is_none() {
+                errors.push(format!("line {}: the outline must start with a '# premise' heading for the root", line));
+                heading = Some((String::new(), 0));
+            }
+            let node = match Self::parse_item(item) {
+                Ok(node) => node,
+                Err(e) => {
+                    errors.push(format!("line {}: {}", line, e));
+                    continue;
+                }
+            };
+
+            let indent = raw.chars()
+                .take_while(|c| c.is_whitespace())
+                .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+                .sum::<usize>();
+            let mut sibling_indent = None;
+            while open.last().is_some_and(|bullet| bullet.indent >= indent) {
+                let closed = open.pop().unwrap();
+                sibling_indent = Some(closed.indent);
+                Self::attach(closed.node, &mut open, &mut roots);
+            }
+            if sibling_indent.is_some_and(|sibling| sibling != indent) {
+                errors.push(format!("line {}: indentation does not line up with any bullet above", line));
+                continue;
+            }
+
+            let path = match open.last() {
+                Some(parent) => format!("{}.{}", parent.path, parent.node.children.len() + 1),
+                None => (roots.len() + 1).to_string(),
+            };
+            lines.insert(path.clone(), line);
+            open.push(OpenBullet { indent, path, node });
+        }
+        while let Some(closed) = open.pop() {
+            Self::attach(closed.node, &mut open, &mut roots);
+        }
+
+        let (premise, premise_line) = heading.unwrap_or_default();
+        if premise_line == 0 && errors.is_empty() {
+            errors.push("line 1: the outline must start with a '# premise' heading for the root".to_string());
+        } else if roots.is_empty() && errors.is_empty() {
+            errors.push(format!("line {}: the outline has no bullets under its heading", premise_line));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(Self { premise, premise_line, nodes: roots, lines })
+    }
+
+    /// Checks the nodes as `add_subtree` would under a fresh root, naming them by source line.
+    pub fn validate(&self, config: &TreeConfig) -> Vec<String> {
+        let anchor = SubtreeAnchor { probability: 1.0, depth: 0, existing_children: 0, allocated_probability: 0.0, gated: false };
+        SubtreeNode::validate_labeled(&self.nodes, &format!("line {}", self.premise_line), &anchor, config, &|path| {
+            self.lines.get(path).map(|line| format!("line {}", line)).unwrap_or_else(|| path.to_string())
+        })
+    }
+
+    /// Writes a root premise and its nested children in the format `parse` reads.
+    pub fn render(premise: &str, nodes: &[SubtreeNode]) -> String {
+        let mut out = format!("# {}\n\n", single_line(premise));
+        Self::render_level(nodes, 0, &mut out);
+        out
+    }
+
+    fn render_level(nodes: &[SubtreeNode], depth: usize, out: &mut String) {
+        for node in nodes {
+            out.push_str(&format!("{}- {} (p={}, c={}): {}\n",
+                INDENT.repeat(depth), single_line(&node.premise), format_probability(node.probability),
+                node.confidence, single_line(&node.reasoning)));
+            Self::render_level(&node.children, depth + 1, out);
+        }
+    }
+
+    fn attach(node: SubtreeNode, open: &mut [OpenBullet], roots: &mut Vec<SubtreeNode>) {
+        match open.last_mut() {
+            Some(parent) => parent.node.children.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    /// Parses `Premise (p=0.4, c=7): reasoning`; `p` also accepts a percentage.
+    fn parse_item(item: &str) -> Result<SubtreeNode, String> {
+        let is_key = |rest: &str| ["p=", "c=", "probability=", "confidence="].iter().any(|key| rest.trim_start().starts_with(key));
+        let open = item.match_indices('(')
+            .map(|(i, _)| i)
+            .find(|&i| is_key(&item[i + 1..]))
+            .ok_or_else(|| format!("missing '(p=..., c=...)' after the premise; expected {}", BULLET_FORMAT))?;
+        let close = item[open..].find(')').map(|i| open + i)
+            .ok_or_else(|| "unclosed '(' after the premise".to_string())?;
+
+        let (mut probability, mut confidence) = (None, None);
+        for field in item[open + 1..close].split(',') {
+            let (key, value) = field.split_once('=').ok_or_else(|| format!("expected key=value, got '{}'", field.trim()))?;
+            let value = value.trim();
+            match key.trim() {
+                "p" | "probability" => {
+                    let (number, scale) = match value.strip_suffix('%') {
+                        Some(percent) => (percent.trim(), 100.0),
+                        None => (value, 1.0),
+                    };
+                    let parsed = number.parse::<f64>().map_err(|_| format!("probability '{}' is not a number", value))?;
+                    probability = Some(parsed / scale);
+                }
+                "c" | "confidence" => {
+                    confidence = Some(value.parse::<i64>().map_err(|_| format!("confidence '{}' is not a whole number", value))?);
+                }
+                other => return Err(format!("unknown field '{}', expected p and c", other)),
+            }
+        }
+
+        let rest = item[close + 1..].trim_start();
+        let reasoning = match rest.strip_prefix(':') {
+            Some(reasoning) => reasoning.trim().to_string(),
+            None if rest.is_empty() => String::new(),
+            None => return Err(format!("expected ':' before the reasoning, got '{}'", rest)),
+        };
+
+        Ok(SubtreeNode {
+            premise: item[..open].trim().to_string(),
+            reasoning,
+            probability: probability.ok_or_else(|| "missing p=".to_string())?,
+            confidence: confidence.ok_or_else(|| "missing c=".to_string())?,
+            children: Vec::new(),
+        })
+    }
+}
+
+/// Collapses line breaks so a text fits on one outline line.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn format_probability(probability: f64) -> String {
+    let rounded = (probability * 1e6).round() / 1e6;
+    format!("{}", rounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTLINE: &str = "\
+# Will the product launch on time?
+
+- Delayed (p=0.6, c=7): Supplier lead times doubled
+  - Port strike (p=40%, c=5): Union vote in May
+  * Factory fire (c=4, p=0.1)
+- On time (p=0.3, c=6): Plan (v2) is on track
+";
+
+    #[test]
+    fn test_parse_and_render() {
+        let outline = Outline::parse(OUTLINE).unwrap();
+        assert_eq!(outline.premise, "Will the product launch on time?");
+        assert_eq!(outline.nodes.len(), 2);
+        assert_eq!(outline.nodes[0].children[0].premise, "Port strike");
+        assert!((outline.nodes[0].children[0].probability - 0.4).abs() < 1e-9);
+        assert_eq!(outline.nodes[0].children[1].reasoning, "");
+        assert_eq!(outline.nodes[1].reasoning, "Plan (v2) is on track");
+        assert_eq!(outline.lines["1.2"], 5);
+
+        // The fire has no reasoning, reported against its source line
+        let errors = outline.validate(&TreeConfig::new(5));
+        assert_eq!(errors, vec!["line 5: reasoning cannot be empty"]);
+
+        let rendered = Outline::render(&outline.premise, &outline.nodes);
+        assert!(rendered.contains("\n  - Port strike (p=0.4, c=5): Union vote in May\n"));
+        let reparsed = Outline::parse(&rendered).unwrap();
+        assert_eq!(Outline::render(&reparsed.premise, &reparsed.nodes), rendered);
+    }
+
+    #[test]
+    fn test_line_numbered_errors() {
+        let errors = Outline::parse("- Orphan (p=0.5, c=5): no heading\n# Root premise here\nplain text\n- Bad (p=high, c=5): x\n      - Deep (p=0.1, c=5): y\n   - Misaligned (p=0.1, c=5): z\n").unwrap_err();
+        assert_eq!(errors, vec![
+            "line 1: the outline must start with a '# premise' heading for the root",
+            "line 2: only one '# premise' heading is allowed, before the first bullet",
+            format!("line 3: expected a bullet {}", BULLET_FORMAT).as_str(),
+            "line 4: probability 'high' is not a number",
+            "line 6: indentation does not line up with any bullet above",
+        ]);
+
+        let over = Outline::parse("# Will the product launch?\n- A (p=0.7, c=5): a\n- B (p=0.5, c=5): b\n").unwrap();
+        assert_eq!(over.validate(&TreeConfig::new(5)), vec!["line 1: children sum to 1.200, exceeding its probability 1.000"]);
+    }
+}
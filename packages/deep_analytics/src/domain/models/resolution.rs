@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use surrealdb::RecordId;
+
+/// Number of equal-width probability buckets in a calibration report.
+pub const CALIBRATION_BUCKETS: usize = 10;
+
+/// Probability floor applied before taking logarithms, so a resolved 0% forecast
+/// scores badly instead of negative infinity.
+const LOG_SCORE_FLOOR: f64 = 1e-6;
+
+/// What actually happened for a tree, stored in the `resolution` table keyed by tree id.
+///
+/// The leaf forecasts are frozen at resolution time, so later edits or a new tree
+/// do not change how the forecast is scored.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Resolution {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub id: Option<RecordId>,
+    pub tree_id: String,
+    pub root_premise: String,
+    pub resolved_node_id: String,
+    pub resolved_premise: String,
+    pub forecasts: Vec<LeafForecast>,
+    #[serde(default)]
+    pub notes: String,
+    pub resolved_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Probability the tree gave to one leaf scenario, and whether it occurred.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LeafForecast {
+    pub node_id: String,
+    pub premise: String,
+    pub probability: f64,
+    pub occurred: bool,
+}
+
+/// Scores of every resolved tree in scope, plus their pooled calibration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalibrationReport {
+    pub scope: String,
+    pub trees: Vec<TreeScore>,
+    /// Mean Brier score across trees (0 is perfect, 2 is the worst possible)
+    pub brier_score: f64,
+    /// Mean natural-log probability given to what happened (0 is perfect)
+    pub log_score: f64,
+    pub buckets: Vec<CalibrationBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TreeScore {
+    pub tree_id: String,
+    pub root_premise: String,
+    pub resolved_premise: String,
+    pub resolved_probability: f64,
+    pub brier_score: f64,
+    pub log_score: f64,
+    pub resolved_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Leaf forecasts whose probability fell in `[lower, upper)` and how often they came true.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalibrationBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub forecasts: usize,
+    pub mean_probability: f64,
+    pub observed_frequency: f64,
+}
+
+impl Resolution {
+    /// Probability the tree gave to the outcome that occurred.
+    pub fn resolved_probability(&self) -> f64 {
+        self.forecasts.iter()
+            .filter(|forecast| forecast.occurred)
+            .map(|forecast| forecast.probability)
+            .sum()
+    }
+
+    /// Multi-category Brier score over the leaves.
+    ///
+    /// Probability mass not assigned to any leaf counts as one extra "unlisted
+    /// outcome" category that did not occur.
+    pub fn brier_score(&self) -> f64 {
+        let assigned: f64 = self.forecasts.iter().map(|forecast| forecast.probability).sum();
+        let unlisted = (1.0 - assigned).max(0.0);
+        self.forecasts.iter()
+            .map(|forecast| (forecast.probability - if forecast.occurred { 1.0 } else { 0.0 }).powi(2))
+            .sum::<f64>()
+            + unlisted.powi(2)
+    }
+
+    /// Natural log of the probability given to what happened.
+    pub fn log_score(&self) -> f64 {
+        self.resolved_probability().max(LOG_SCORE_FLOOR).ln()
+    }
+
+    pub fn score(&self) -> TreeScore {
+        TreeScore {
+            tree_id: self.tree_id.clone(),
+            root_premise: self.root_premise.clone(),
+            resolved_premise: self.resolved_premise.clone(),
+            resolved_probability: self.resolved_probability(),
+            brier_score: self.brier_score(),
+            log_score: self.log_score(),
+            resolved_at: self.resolved_at,
+        }
+    }
+}
+
+impl CalibrationReport {
+    /// Scores each resolution and pools every leaf forecast into calibration buckets.
+    pub fn from_resolutions(scope: String, resolutions: &[Resolution]) -> Self {
+        let trees: Vec<TreeScore> = resolutions.iter().map(Resolution::score).collect();
+        let mean = |values: Vec<f64>| if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 };
+
+        let mut buckets: Vec<CalibrationBucket> = (0..CALIBRATION_BUCKETS)
+            .map(|i| CalibrationBucket {
+                lower: i as f64 / CALIBRATION_BUCKETS as f64,
+                upper: (i + 1) as f64 / CALIBRATION_BUCKETS as f64,
+                forecasts: 0,
+                mean_probability: 0.0,
+                observed_frequency: 0.0,
+            })
+            .collect();
+        for forecast in resolutions.iter().flat_map(|resolution| &resolution.forecasts) {
+            let index = ((forecast.probability * CALIBRATION_BUCKETS as f64) as usize).min(CALIBRATION_BUCKETS - 1);
+            let bucket = &mut buckets[index];
+            bucket.forecasts += 1;
+            bucket.mean_probability += forecast.probability;
+            bucket.observed_frequency += if forecast.occurred { 1.0 } else { 0.0 };
+        }
+        for bucket in buckets.iter_mut().filter(|bucket| bucket.forecasts > 0) {
+            bucket.mean_probability /= bucket.forecasts as f64;
+            bucket.observed_frequency /= bucket.forecasts as f64;
+        }
+
+        Self {
+            scope,
+            brier_score: mean(trees.iter().map(|tree| tree.brier_score).collect()),
+            log_score: mean(trees.iter().map(|tree| tree.log_score).collect()),
+            trees,
+            buckets,
+        }
+    }
+}
+
+impl Display for Resolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🏁 RESOLVED {}: {}", self.tree_id, self.root_premise)?;
+        writeln!(f, "   Outcome: [{}] {} (forecast p={:.3})",
+            self.resolved_node_id, self.resolved_premise, self.resolved_probability())?;
+        writeln!(f, "   Brier score: {:.4} | Log score: {:.4}", self.brier_score(), self.log_score())?;
+        if !self.notes.is_empty() {
+            writeln!(f, "   Notes: {}", self.notes)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for CalibrationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🎯 CALIBRATION REPORT ({})\n", self.scope)?;
+
+        if self.trees.is_empty() {
+            writeln!(f, "No resolved forecasts yet. Use resolve_outcome once a tree's outcome is known.")?;
+            return Ok(());
+        }
+
+        writeln!(f, "📈 SCORES ({} resolved trees):", self.trees.len())?;
+        writeln!(f, "   Mean Brier score: {:.4} (0 = perfect)", self.brier_score)?;
+        writeln!(f, "   Mean log score: {:.4} (0 = perfect)\n", self.log_score)?;
+
+        for tree in &self.trees {
+            writeln!(f, "   {} | {} → {} (p={:.3}) | Brier {:.4} | Log {:.4} | {}",
+                tree.tree_id, tree.root_premise, tree.resolved_premise, tree.resolved_probability,
+                tree.brier_score, tree.log_score, tree.resolved_at.format("%Y-%m-%d"))?;
+        }
+
+        writeln!(f, "\n📊 CALIBRATION BUCKETS:")?;
+        for bucket in self.buckets.iter().filter(|bucket| bucket.forecasts > 0) {
+            writeln!(f, "   {:>3.0}-{:>3.0}%: {} forecasts, mean p={:.3}, occurred {:.1}%",
+                bucket.lower * 100.0, bucket.upper * 100.0, bucket.forecasts,
+                bucket.mean_probability, bucket.observed_frequency * 100.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolution(forecasts: &[(f64, bool)]) -> Resolution {
+        Resolution {
+            id: None,
+            tree_id: "tree_1".to_string(),
+            root_premise: "Will the launch slip?".to_string(),
+            resolved_node_id: "clever_turing".to_string(),
+            resolved_premise: "Supplier delays".to_string(),
+            forecasts: forecasts.iter().enumerate()
+                .map(|(i, (probability, occurred))| LeafForecast {
+                    node_id: format!("leaf_{}", i),
+                    premise: format!("Scenario {}", i),
+                    probability: *probability,
+                    occurred: *occurred,
+                })
+                .collect(),
+            notes: String::new(),
+            resolved_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_scores() {
+        let resolved = resolution(&[(0.6, true), (0.3, false)]);
+        assert!((resolved.resolved_probability() - 0.6).abs() < 1e-9);
+        // (0.6-1)² + 0.3² + unlisted 0.1²
+        assert!((resolved.brier_score() - 0.26).abs() < 1e-9);
+        assert!((resolved.log_score() - 0.6f64.ln()).abs() < 1e-9);
+
+        let certain_miss = resolution(&[(1.0, false), (0.0, true)]);
+        assert!((certain_miss.brier_score() - 2.0).abs() < 1e-9);
+        assert!(certain_miss.log_score().is_finite());
+    }
+
+    #[test]
+    fn test_calibration_buckets() {
+        let report = CalibrationReport::from_resolutions("all trees".to_string(), &[
+            resolution(&[(0.65, true), (0.35, false)]),
+            resolution(&[(0.62, false), (0.38, true)]),
+        ]);
+
+        assert_eq!(report.trees.len(), 2);
+        assert_eq!(report.buckets.len(), CALIBRATION_BUCKETS);
+        let sixties = &report.buckets[6];
+        assert_eq!(sixties.forecasts, 2);
+        assert!((sixties.mean_probability - 0.635).abs() < 1e-9);
+        assert!((sixties.observed_frequency - 0.5).abs() < 1e-9);
+        assert!(report.to_string().contains("60- 70%: 2 forecasts"));
+
+        let empty = CalibrationReport::from_resolutions("tree_9".to_string(), &[]);
+        assert!(empty.to_string().contains("No resolved forecasts yet"));
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Scheme of the MCP resources exposing a tree.
+pub const TREE_URI_SCHEME: &str = "tree://";
+
+/// RFC 6570 template advertised for individual node resources.
+pub const NODE_URI_TEMPLATE: &str = "tree://{tree_id}/nodes/{node_id}";
+
+/// A tree resource addressed as `tree://{tree_id}/...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeResourceUri {
+    pub tree_id: String,
+    pub resource: TreeResource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeResource {
+    /// `tree://{tree_id}/tree`: overview of the live tree, as rendered by inspect_tree
+    Tree,
+    /// `tree://{tree_id}/nodes/{node_id}`: one node, addressed by friendly alias or record id
+    Node(String),
+    /// `tree://{tree_id}/export.json`: every node and cross-link of the live tree
+    Export,
+    /// `tree://{tree_id}/report`: the latest export_paths report
+    Report,
+}
+
+impl TreeResourceUri {
+    pub fn new(tree_id: &str, resource: TreeResource) -> Self {
+        Self { tree_id: tree_id.to_string(), resource }
+    }
+
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri.strip_prefix(TREE_URI_SCHEME)
+            .ok_or_else(|| format!("Resource URI '{}' must start with {}", uri, TREE_URI_SCHEME))?;
+        let (tree_id, path) = rest.split_once('/')
+            .ok_or_else(|| format!("Resource URI '{}' names no resource inside the tree", uri))?;
+        if tree_id.is_empty() {
+            return Err(format!("Resource URI '{}' has no tree id", uri));
+        }
+
+        let resource = match path {
+            "tree" => TreeResource::Tree,
+            "export.json" => TreeResource::Export,
+            "report" => TreeResource::Report,
+            _ => match path.strip_prefix("nodes/") {
+                Some(node_id) if !node_id.is_empty() && !node_id.contains('/') => TreeResource::Node(node_id.to_string()),
+                _ => return Err(format!("Unknown tree resource '{}'", path)),
+            },
+        };
+        Ok(Self::new(tree_id, resource))
+    }
+
+    /// Prefix shared by every resource of a tree, used to match subscriptions.
+    pub fn tree_prefix(tree_id: &str) -> String {
+        format!("{}{}/", TREE_URI_SCHEME, tree_id)
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self.resource {
+            TreeResource::Tree | TreeResource::Report => "text/plain",
+            TreeResource::Node(_) | TreeResource::Export => "application/json",
+        }
+    }
+}
+
+impl Display for TreeResourceUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", Self::tree_prefix(&self.tree_id))?;
+        match &self.resource {
+            TreeResource::Tree => write!(f, "tree"),
+            TreeResource::Node(node_id) => write!(f, "nodes/{}", node_id),
+            TreeResource::Export => write!(f, "export.json"),
+            TreeResource::Report => write!(f, "report"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let node = TreeResourceUri::parse("tree://tree_1700000000/nodes/clever_turing").unwrap();
+        assert_eq!(node, TreeResourceUri::new("tree_1700000000", TreeResource::Node("clever_turing".to_string())));
+        assert_eq!(node.to_string(), "tree://tree_1700000000/nodes/clever_turing");
+        assert_eq!(node.mime_type(), "application/json");
+
+        let export = TreeResourceUri::new("tree_1", TreeResource::Export);
+        assert_eq!(TreeResourceUri::parse(&export.to_string()).unwrap(), export);
+        assert!(export.to_string().starts_with(&TreeResourceUri::tree_prefix("tree_1")));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_uris() {
+        assert!(TreeResourceUri::parse("file:///tmp/tree").is_err());
+        assert!(TreeResourceUri::parse("tree://tree_1").is_err());
+        assert!(TreeResourceUri::parse("tree:///tree").is_err());
+        assert!(TreeResourceUri::parse("tree://tree_1/nodes/").is_err());
+        assert!(TreeResourceUri::parse("tree://tree_1/paths").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Upper bound of the impact scale; impacts run from 0 to this value.
+pub const MAX_IMPACT: f64 = 10.0;
+
+/// Levels per axis used when a caller does not choose, giving the usual 5×5 matrix.
+pub const DEFAULT_RISK_LEVELS: usize = 5;
+
+/// Category reported for leaves with no category on their path.
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Which probability places a leaf on the probability axis.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum ProbabilityBasis {
+    /// Product of probabilities along each root-to-leaf path; a cross-linked leaf appears once per path
+    Path,
+    /// Total probability mass reaching each leaf over every route into it
+    Marginal,
+}
+
+/// One surviving leaf scenario placed on the matrix.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RiskItem {
+    pub node_id: String,
+    pub premise: String,
+    pub probability: f64,
+    /// Impact of the leaf, or of its nearest ancestor with one (0-10)
+    pub impact: f64,
+    /// Category of the leaf, or of its nearest ancestor with one
+    pub category: Option<String>,
+    /// probability × impact
+    pub risk: f64,
+}
+
+/// One cell of the matrix; levels are 1-based and grow with probability and impact.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RiskCell {
+    pub probability_level: usize,
+    pub impact_level: usize,
+    /// probability_level × impact_level, the conventional heatmap rating
+    pub rating: usize,
+    pub occupants: Vec<RiskItem>,
+}
+
+/// Aggregate risk of the leaves sharing a category.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CategoryRisk {
+    pub category: String,
+    pub leaves: usize,
+    /// Sum of probability × impact over the category's leaves
+    pub total_risk: f64,
+    pub max_impact: f64,
+}
+
+/// Surviving leaves binned by probability and impact.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RiskMatrix {
+    pub basis: ProbabilityBasis,
+    pub probability_levels: usize,
+    pub impact_levels: usize,
+    /// Indexed `[probability_level - 1][impact_level - 1]`
+    pub cells: Vec<Vec<RiskCell>>,
+    /// Categories by total risk, highest first
+    pub categories: Vec<CategoryRisk>,
+    /// Leaves with no impact on their path, left off the matrix
+    pub unscored: Vec<String>,
+}
+
+impl ProbabilityBasis {
+    /// Parses `path` or `marginal`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "path" => Ok(ProbabilityBasis::Path),
+            "marginal" => Ok(ProbabilityBasis::Marginal),
+            other => Err(format!("Unknown probability basis '{}', expected path or marginal", other)),
+        }
+    }
+}
+
+impl RiskMatrix {
+    /// Bins `items` into a `probability_levels` × `impact_levels` grid of equal-width bands.
+    pub fn build(
+        basis: ProbabilityBasis,
+        probability_levels: usize,
+        impact_levels: usize,
+        items: Vec<RiskItem>,
+        unscored: Vec<String>,
+    ) -> Result<Self, String> {
+        for (axis, levels) in [("probability_levels", probability_levels), ("impact_levels", impact_levels)] {
+            if !(2..=10).contains(&levels) {
+                return Err(format!("{} must be between 2 and 10, got {}", axis, levels));
+            }
+        }
+
+        let level = |value: f64, levels: usize| ((value * levels as f64) as usize).min(levels - 1);
+        let mut cells: Vec<Vec<RiskCell>> = (0..probability_levels)
+            .map(|p| (0..impact_levels)
+                .map(|i| RiskCell { probability_level: p + 1, impact_level: i + 1, rating: (p + 1) * (i + 1), occupants: Vec::new() })
+                .collect())
+            .collect();
+
+        let mut by_category: BTreeMap<String, CategoryRisk> = BTreeMap::new();
+        for item in items {
+            let category = item.category.clone().unwrap_or_else(|| UNCATEGORIZED.to_string());
+            let entry = by_category.entry(category.clone())
+                .or_insert_with(|| CategoryRisk { category, leaves: 0, total_risk: 0.0, max_impact: 0.0 });
+            entry.leaves += 1;
+            entry.total_risk += item.risk;
+            entry.max_impact = entry.max_impact.max(item.impact);
+
+            let p = level(item.probability.clamp(0.0, 1.0), probability_levels);
+            let i = level(item.impact.clamp(0.0, MAX_IMPACT) / MAX_IMPACT, impact_levels);
+            cells[p][i].occupants.push(item);
+        }
+        for cell in cells.iter_mut().flatten() {
+            cell.occupants.sort_by(|a, b| b.risk.partial_cmp(&a.risk).unwrap_or(std::cmp::Ordering::Equal));
+        }
+
+        let mut categories: Vec<CategoryRisk> = by_category.into_values().collect();
+        categories.sort_by(|a, b| b.total_risk.partial_cmp(&a.total_risk).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(Self { basis, probability_levels, impact_levels, cells, categories, unscored })
+    }
+
+    /// Number of leaves placed on the matrix.
+    pub fn scored(&self) -> usize {
+        self.cells.iter().flatten().map(|cell| cell.occupants.len()).sum()
+    }
+}
+
+impl Display for RiskMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let basis = match self.basis {
+            ProbabilityBasis::Path => "path probability",
+            ProbabilityBasis::Marginal => "marginal probability",
+        };
+        writeln!(f, "🔥 RISK MATRIX {}×{} ({} leaves by {})\n",
+            self.probability_levels, self.impact_levels, self.scored(), basis)?;
+
+        // Highest probability on top, impact growing to the right
+        for row in self.cells.iter().rev() {
+            write!(f, "   P{:<2}|", row[0].probability_level)?;
+            for cell in row {
+                if cell.occupants.is_empty() {
+                    write!(f, "  ·")?;
+                } else {
+                    write!(f, "{:>3}", cell.occupants.len())?;
+                }
+            }
+            writeln!(f)?;
+        }
+        write!(f, "      ")?;
+        for i in 1..=self.impact_levels {
+            write!(f, " I{}", i)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "\n📍 OCCUPIED CELLS (highest rating first):")?;
+        let mut occupied: Vec<&RiskCell> = self.cells.iter().flatten().filter(|cell| !cell.occupants.is_empty()).collect();
+        occupied.sort_by(|a, b| b.rating.cmp(&a.rating).then_with(|| b.probability_level.cmp(&a.probability_level)));
+        if occupied.is_empty() {
+            writeln!(f, "   No scored leaves. Use set_risk_profile to give nodes an impact.")?;
+        }
+        for cell in occupied {
+            writeln!(f, "   P{} × I{} (rating {}):", cell.probability_level, cell.impact_level, cell.rating)?;
+            for item in &cell.occupants {
+                writeln!(f, "      [{}] {} (p={:.3}, impact {:.1}, risk {:.3}{})",
+                    item.node_id, item.premise, item.probability, item.impact, item.risk,
+                    item.category.as_ref().map(|category| format!(", {}", category)).unwrap_or_default())?;
+            }
+        }
+
+        if !self.categories.is_empty() {
+            writeln!(f, "\n📊 RISK BY CATEGORY:")?;
+            for category in &self.categories {
+                writeln!(f, "   {}: total risk {:.3} over {} leaves, max impact {:.1}",
+                    category.category, category.total_risk, category.leaves, category.max_impact)?;
+            }
+        }
+
+        if !self.unscored.is_empty() {
+            writeln!(f, "\n❔ UNSCORED ({} leaves without an impact): {}", self.unscored.len(), self.unscored.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(node_id: &str, probability: f64, impact: f64, category: Option<&str>) -> RiskItem {
+        RiskItem {
+            node_id: node_id.to_string(),
+            premise: format!("Scenario {}", node_id),
+            probability,
+            impact,
+            category: category.map(str::to_string),
+            risk: probability * impact,
+        }
+    }
+
+    #[test]
+    fn test_binning_and_categories() {
+        let matrix = RiskMatrix::build(ProbabilityBasis::Path, 5, 5, vec![
+            item("a", 0.95, 10.0, Some("supply")),
+            item("b", 0.0, 0.0, Some("supply")),
+            item("c", 0.45, 5.0, Some("legal")),
+            item("d", 0.3, 7.0, None),
+        ], vec!["e".to_string()]).unwrap();
+
+        assert_eq!(matrix.cells[4][4].occupants[0].node_id, "a");
+        assert_eq!(matrix.cells[4][4].rating, 25);
+        assert_eq!(matrix.cells[0][0].occupants[0].node_id, "b");
+        assert_eq!(matrix.cells[2][2].occupants[0].node_id, "c");
+        assert_eq!(matrix.cells[1][3].occupants[0].node_id, "d");
+        assert_eq!(matrix.scored(), 4);
+
+        let names: Vec<&str> = matrix.categories.iter().map(|category| category.category.as_str()).collect();
+        assert_eq!(names, vec!["supply", "legal", UNCATEGORIZED]);
+        assert!((matrix.categories[0].total_risk - 9.5).abs() < 1e-9);
+        assert_eq!(matrix.categories[0].leaves, 2);
+        assert!(matrix.to_string().contains("UNSCORED (1 leaves without an impact): e"));
+    }
+
+    #[test]
+    fn test_rejects_bad_levels() {
+        assert!(RiskMatrix::build(ProbabilityBasis::Marginal, 1, 5, vec![], vec![]).is_err());
+        assert!(RiskMatrix::build(ProbabilityBasis::Marginal, 5, 11, vec![], vec![]).is_err());
+        assert_eq!(ProbabilityBasis::parse(" Marginal ").unwrap(), ProbabilityBasis::Marginal);
+        assert!(ProbabilityBasis::parse("expected").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::domain::models::tree_state::TreeConfig;
+
+/// Tolerance when comparing sibling probability sums with their parent, as in `add_leaf`.
+const PROBABILITY_TOLERANCE: f64 = 0.001;
+
+/// One node of a subtree submitted to `add_subtree`, with its children nested inside.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubtreeNode {
+    pub premise: String,
+    pub reasoning: String,
+    pub probability: f64,
+    pub confidence: i64,
+    #[serde(default)]
+    pub children: Vec<SubtreeNode>,
+}
+
+/// The existing node a subtree is attached under.
+#[derive(Debug, Clone)]
+pub struct SubtreeAnchor {
+    pub probability: f64,
+    pub depth: i64,
+    pub existing_children: usize,
+    /// Probability already given to existing children and cross-linked children
+    pub allocated_probability: f64,
+    /// Whether the node combines its children through a fault-tree gate, which makes them
+    /// independent events that need not fit within its probability
+    pub gated: bool,
+}
+
+/// Nodes created by `add_subtree`, mapping each submitted position to its new id.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubtreeInsertion {
+    pub parent_id: String,
+    pub nodes: Vec<InsertedNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InsertedNode {
+    /// 1-based position in the submitted structure, e.g. `2.1` for the first child of the second node
+    pub path: String,
+    pub node_id: String,
+    pub friendly_id: String,
+    pub premise: String,
+    pub probability: f64,
+    pub depth: i64,
+}
+
+/// Nodes moved or copied under a new parent by `move_subtree` or `copy_subtree`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubtreeRelocation {
+    pub copied: bool,
+    pub new_parent_id: String,
+    /// Former parent of a moved subtree; `None` for copies
+    pub old_parent_id: Option<String>,
+    /// Every node of the subtree, its root first
+    pub nodes: Vec<RelocatedNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RelocatedNode {
+    /// Alias of the original node; equal to `friendly_id` for moves
+    pub source_id: String,
+    pub friendly_id: String,
+    pub node_id: String,
+    pub premise: String,
+    /// Depth at the new position
+    pub depth: i64,
+}
+
+impl SubtreeNode {
+    /// Checks every node against the tree limits before anything is written.
+    ///
+    /// Returns one message per violation, so a caller can fix them all at once.
+    pub fn validate_all(nodes: &[SubtreeNode], anchor: &SubtreeAnchor, config: &TreeConfig) -> Vec<String> {
+        let mut errors = Vec::new();
+        if nodes.is_empty() {
+            errors.push("Subtree has no nodes".to_string());
+            return errors;
+        }
+
+        Self::validate_level(nodes, "", "parent", anchor, config, &|path| path.to_string(), &mut errors);
+        errors
+    }
+
+    /// Like `validate_all`, but names each node with `label(path)` instead of its
+    /// 1-based path, and the anchor with `parent_label`.
+    pub fn validate_labeled(
+        nodes: &[SubtreeNode],
+        parent_label: &str,
+        anchor: &SubtreeAnchor,
+        config: &TreeConfig,
+        label: &dyn Fn(&str) -> String,
+    ) -> Vec<String> {
+        let mut errors = Vec::new();
+        Self::validate_level(nodes, "", parent_label, anchor, config, label, &mut errors);
+        errors
+    }
+
+    fn validate_level(
+        nodes: &[SubtreeNode],
+        prefix: &str,
+        parent_label: &str,
+        parent: &SubtreeAnchor,
+        config: &TreeConfig,
+        label: &dyn Fn(&str) -> String,
+        errors: &mut Vec<String>,
+    ) {
+        if parent.existing_children + nodes.len() > config.branch_limit {
+            errors.push(format!("{}: {} children exceed the branch limit of {}",
+                parent_label, parent.existing_children + nodes.len(), config.branch_limit));
+        }
+
+        let total = parent.allocated_probability + nodes.iter().map(|node| node.probability).sum::<f64>();
+        if config.enforces_sibling_sums() && !parent.gated && total > parent.probability + PROBABILITY_TOLERANCE {
+            errors.push(format!("{}: children sum to {:.3}, exceeding its probability {:.3}",
+                parent_label, total, parent.probability));
+        }
+
+        let depth = parent.depth + 1;
+
+        for (i, node) in nodes.iter().enumerate() {
+            let path = format!("{}{}", prefix, i + 1);
+            let name = label(&path);
+            if node.premise.trim().is_empty() {
+                errors.push(format!("{}: premise cannot be empty", name));
+            }
+            if node.reasoning.trim().is_empty() {
+                errors.push(format!("{}: reasoning cannot be empty", name));
+            }
+            if !(0.0..=1.0).contains(&node.probability) {
+                errors.push(format!("{}: probability {} is out of range [0,1]", name, node.probability));
+            }
+            if !(1..=10).contains(&node.confidence) {
+                errors.push(format!("{}: confidence {} is out of range [1,10]", name, node.confidence));
+            }
+            if depth >= config.max_depth {
+                errors.push(format!("{}: depth {} reaches the maximum depth {}", name, depth, config.max_depth));
+                continue;
+            }
+            if !node.children.is_empty() {
+                let anchor = SubtreeAnchor { probability: node.probability, depth, existing_children: 0, allocated_probability: 0.0, gated: false };
+                Self::validate_level(&node.children, &format!("{}.", path), &name, &anchor, config, label, errors);
+            }
+        }
+    }
+
+    /// Number of nodes in `nodes` and all their descendants.
+    pub fn count(nodes: &[SubtreeNode]) -> usize {
+        nodes.iter().map(|node| 1 + Self::count(&node.children)).sum()
+    }
+}
+
+impl Display for SubtreeInsertion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🌳 SUBTREE ADDED under [{}]: {} nodes\n", self.parent_id, self.nodes.len())?;
+        for node in &self.nodes {
+            let indent = "   ".repeat(node.path.matches('.').count());
+            writeln!(f, "   {}{} → [{}] {} (p={:.3}, depth {}, record: {})",
+                indent, node.path, node.friendly_id, node.premise, node.probability, node.depth, node.node_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for SubtreeRelocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let root = self.nodes.first().map(|node| node.source_id.as_str()).unwrap_or_default();
+        match &self.old_parent_id {
+            Some(old_parent_id) => writeln!(f, "🚚 MOVED SUBTREE [{}] from [{}] to [{}]: {} nodes\n",
+                root, old_parent_id, self.new_parent_id, self.nodes.len())?,
+            None => writeln!(f, "📋 COPIED SUBTREE [{}] under [{}]: {} nodes\n",
+                root, self.new_parent_id, self.nodes.len())?,
+        }
+        for node in &self.nodes {
+            if self.copied {
+                writeln!(f, "   [{}] → [{}] {} (depth {}, record: {})",
+                    node.source_id, node.friendly_id, node.premise, node.depth, node.node_id)?;
+            } else {
+                writeln!(f, "   [{}] {} (depth {})", node.friendly_id, node.premise, node.depth)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(premise: &str, probability: f64, children: Vec<SubtreeNode>) -> SubtreeNode {
+        SubtreeNode {
+            premise: premise.to_string(),
+            reasoning: format!("Why {}", premise),
+            probability,
+            confidence: 6,
+            children,
+        }
+    }
+
+    fn anchor() -> SubtreeAnchor {
+        SubtreeAnchor { probability: 1.0, depth: 0, existing_children: 1, allocated_probability: 0.3, gated: false }
+    }
+
+    #[test]
+    fn test_valid_subtree() {
+        let nodes = vec![
+            node("Supplier delays", 0.4, vec![node("Port strike", 0.1, vec![]), node("Factory fire", 0.2, vec![])]),
+            node("On time", 0.3, vec![]),
+        ];
+        assert!(SubtreeNode::validate_all(&nodes, &anchor(), &TreeConfig::new(5)).is_empty());
+        assert_eq!(SubtreeNode::count(&nodes), 4);
+    }
+
+    #[test]
+    fn test_reports_every_violation() {
+        let mut bad_leaf = node("", 0.3, vec![]);
+        bad_leaf.confidence = 11;
+        let nodes = vec![
+            node("Supplier delays", 0.5, vec![node("Port strike", 0.4, vec![]), node("Factory fire", 0.2, vec![])]),
+            bad_leaf,
+        ];
+
+        let errors = SubtreeNode::validate_all(&nodes, &anchor(), &TreeConfig::new(5));
+        assert_eq!(errors, vec![
+            "parent: children sum to 1.100, exceeding its probability 1.000",
+            "1: children sum to 0.600, exceeding its probability 0.500",
+            "2: premise cannot be empty",
+            "2: confidence 11 is out of range [1,10]",
+        ]);
+
+        // The inputs of a gate are independent events, so only their own subtrees are summed
+        let gated = SubtreeAnchor { gated: true, ..anchor() };
+        let errors = SubtreeNode::validate_all(&nodes, &gated, &TreeConfig::new(5));
+        assert_eq!(errors[0], "1: children sum to 0.600, exceeding its probability 0.500");
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_depth_and_branch_limits() {
+        // Complexity 1 allows depth 3 and 3 children per node
+        let config = TreeConfig::new(1);
+        let deep = vec![node("a", 0.5, vec![node("b", 0.4, vec![node("c", 0.3, vec![])])])];
+        let errors = SubtreeNode::validate_all(&deep, &anchor(), &config);
+        assert_eq!(errors, vec!["1.1.1: depth 3 reaches the maximum depth 3"]);
+
+        let wide: Vec<SubtreeNode> = (0..3).map(|i| node(&format!("n{}", i), 0.1, vec![])).collect();
+        let errors = SubtreeNode::validate_all(&wide, &anchor(), &config);
+        assert_eq!(errors, vec!["parent: 4 children exceed the branch limit of 3"]);
+    }
+}
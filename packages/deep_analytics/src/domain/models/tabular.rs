@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::domain::models::types::{NodeVisualization, PathResult};
+
+/// Version of the tabular column layout, written in every row.
+///
+/// Bump it whenever a column is renamed, removed or changes meaning; adding a column
+/// at the end keeps the version.
+pub const TABULAR_SCHEMA_VERSION: u32 = 1;
+
+/// Separator joining list values, such as a path's node ids, into one cell.
+pub const LIST_SEPARATOR: &str = " | ";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum TabularFormat {
+    /// Comma-separated values with a header row (RFC 4180 quoting)
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum TabularTable {
+    /// The surviving paths of the latest export_paths report, one row per path
+    Paths,
+    /// Every node of the live tree, one row per node
+    Nodes,
+}
+
+/// One row of a tabular export with a fixed column order.
+pub trait TabularRecord: Serialize {
+    /// Column names, in the same order as the record's fields
+    const COLUMNS: &'static [&'static str];
+
+    /// Cell values in `COLUMNS` order, as written to CSV.
+    fn values(&self) -> Vec<String>;
+}
+
+/// A `PathResult` flattened into scalar columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathRecord {
+    pub schema_version: u32,
+    pub tree_id: String,
+    /// 1-based position in the report
+    pub rank: usize,
+    pub leaf_id: String,
+    /// Node ids from the root down, joined with `LIST_SEPARATOR`
+    pub path: String,
+    /// Premises from the root down, joined with `LIST_SEPARATOR`
+    pub premises: String,
+    pub depth: usize,
+    pub final_probability: f64,
+    pub confidence_score: f64,
+    pub reasoning_chain: String,
+}
+
+/// A `NodeVisualization` flattened into scalar columns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeRecord {
+    pub schema_version: u32,
+    pub tree_id: String,
+    pub friendly_id: String,
+    pub premise_summary: String,
+    pub full_premise: String,
+    pub full_reasoning: String,
+    pub probability: f64,
+    pub depth: u32,
+    pub children_count: usize,
+    pub children_ids: String,
+    pub parent_id: String,
+    pub linked_parent_ids: String,
+    pub linked_children_ids: String,
+    pub is_leaf: bool,
+    pub can_expand: bool,
+    pub confidence: i64,
+    pub status: String,
+    pub evidence_count: usize,
+    pub time_window_start: Option<chrono::NaiveDate>,
+    pub time_window_end: Option<chrono::NaiveDate>,
+    pub review_by: Option<chrono::NaiveDate>,
+    pub locked: bool,
+    pub gate: Option<String>,
+    pub argument_stance: Option<String>,
+    pub argument_strength: Option<f64>,
+    pub exhaustive: bool,
+    pub residual: bool,
+}
+
+impl TabularFormat {
+    /// Parses `csv` or `jsonl` (also `json_lines` and `ndjson`), case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "csv" => Ok(TabularFormat::Csv),
+            "jsonl" | "json_lines" | "ndjson" => Ok(TabularFormat::JsonLines),
+            other => Err(format!("Unknown tabular format '{}', expected csv or jsonl", other)),
+        }
+    }
+
+    /// Canonical name, as accepted by `parse`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TabularFormat::Csv => "csv",
+            TabularFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl TabularTable {
+    /// Parses `paths` or `nodes`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "paths" => Ok(TabularTable::Paths),
+            "nodes" => Ok(TabularTable::Nodes),
+            other => Err(format!("Unknown table '{}', expected paths or nodes", other)),
+        }
+    }
+}
+
+impl PathRecord {
+    pub fn new(tree_id: &str, rank: usize, path: &PathResult) -> Self {
+        Self {
+            schema_version: TABULAR_SCHEMA_VERSION,
+            tree_id: tree_id.to_string(),
+            rank,
+            leaf_id: path.path.last().cloned().unwrap_or_default(),
+            path: path.path.join(LIST_SEPARATOR),
+            premises: path.premises.join(LIST_SEPARATOR),
+            depth: path.path.len().saturating_sub(1),
+            final_probability: path.final_probability,
+            confidence_score: path.confidence_score,
+            reasoning_chain: path.reasoning_chain.clone(),
+        }
+    }
+}
+
+impl NodeRecord {
+    pub fn new(tree_id: &str, node: &NodeVisualization) -> Self {
+        Self {
+            schema_version: TABULAR_SCHEMA_VERSION,
+            tree_id: tree_id.to_string(),
+            friendly_id: node.friendly_id.clone(),
+            premise_summary: node.premise_summary.clone(),
+            full_premise: node.full_premise.clone(),
+            full_reasoning: node.full_reasoning.clone(),
+            probability: node.probability,
+            depth: node.depth,
+            children_count: node.children_count,
+            children_ids: node.children_ids.join(LIST_SEPARATOR),
+            parent_id: node.parent_id.clone().unwrap_or_default(),
+            linked_parent_ids: node.linked_parent_ids.join(LIST_SEPARATOR),
+            linked_children_ids: node.linked_children_ids.join(LIST_SEPARATOR),
+            is_leaf: node.is_leaf,
+            can_expand: node.can_expand,
+            confidence: node.confidence,
+            status: node.status.as_str().to_string(),
+            evidence_count: node.evidence_count,
+            time_window_start: node.time_window.map(|window| window.start),
+            time_window_end: node.time_window.map(|window| window.end),
+            review_by: node.review_by,
+            locked: node.locked,
+            gate: node.gate.map(|gate| gate.to_string()),
+            argument_stance: node.argument.map(|tag| tag.stance.to_string()),
+            argument_strength: node.argument.map(|tag| tag.strength),
+            exhaustive: node.exhaustive,
+            residual: node.residual,
+        }
+    }
+}
+
+impl TabularRecord for PathRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "schema_version", "tree_id", "rank", "leaf_id", "path", "premises", "depth",
+        "final_probability", "confidence_score", "reasoning_chain",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.schema_version.to_string(),
+            self.tree_id.clone(),
+            self.rank.to_string(),
+            self.leaf_id.clone(),
+            self.path.clone(),
+            self.premises.clone(),
+            self.depth.to_string(),
+            self.final_probability.to_string(),
+            self.confidence_score.to_string(),
+            self.reasoning_chain.clone(),
+        ]
+    }
+}
+
+impl TabularRecord for NodeRecord {
+    const COLUMNS: &'static [&'static str] = &[
+        "schema_version", "tree_id", "friendly_id", "premise_summary", "full_premise", "full_reasoning",
+        "probability", "depth", "children_count", "children_ids", "parent_id", "linked_parent_ids",
+        "linked_children_ids", "is_leaf", "can_expand", "confidence", "status", "evidence_count",
+        "time_window_start", "time_window_end", "review_by", "locked", "gate", "argument_stance",
+        "argument_strength", "exhaustive", "residual",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        vec![
+            self.schema_version.to_string(),
+            self.tree_id.clone(),
+            self.friendly_id.clone(),
+            self.premise_summary.clone(),
+            self.full_premise.clone(),
+            self.full_reasoning.clone(),
+            self.probability.to_string(),
+            self.depth.to_string(),
+            self.children_count.to_string(),
+            self.children_ids.clone(),
+            self.parent_id.clone(),
+            self.linked_parent_ids.clone(),
+            self.linked_children_ids.clone(),
+            self.is_leaf.to_string(),
+            self.can_expand.to_string(),
+            self.confidence.to_string(),
+            self.status.clone(),
+            self.evidence_count.to_string(),
+            optional(self.time_window_start.map(|date| date.format("%Y-%m-%d").to_string())),
+            optional(self.time_window_end.map(|date| date.format("%Y-%m-%d").to_string())),
+            optional(self.review_by.map(|date| date.format("%Y-%m-%d").to_string())),
+            self.locked.to_string(),
+            optional(self.gate.clone()),
+            optional(self.argument_stance.clone()),
+            optional(self.argument_strength.map(|strength| strength.to_string())),
+            self.exhaustive.to_string(),
+            self.residual.to_string(),
+        ]
+    }
+}
+
+/// Writes records as CSV with a header row, or as JSON Lines.
+pub fn render<R: TabularRecord>(records: &[R], format: TabularFormat) -> Result<String, String> {
+    let mut out = String::new();
+    match format {
+        TabularFormat::Csv => {
+            out.push_str(&R::COLUMNS.join(","));
+            out.push('\n');
+            for record in records {
+                let cells: Vec<String> = record.values().iter().map(|value| csv_escape(value)).collect();
+                out.push_str(&cells.join(","));
+                out.push('\n');
+            }
+        }
+        TabularFormat::JsonLines => {
+            for record in records {
+                out.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+                out.push('\n');
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_result() -> PathResult {
+        PathResult {
+            path: vec!["root".to_string(), "clever_turing".to_string()],
+            premises: vec!["Will the launch slip?".to_string(), "Supplier \"delays\", again".to_string()],
+            final_probability: 0.6,
+            reasoning_chain: "Lead times doubled\nsince March".to_string(),
+            confidence_score: 0.7,
+        }
+    }
+
+    /// Field names as serialized, sorted, to check them against `COLUMNS`.
+    fn serialized_columns<R: TabularRecord>(record: &R) -> Vec<String> {
+        let value = serde_json::to_value(record).unwrap();
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_columns_match_fields() {
+        let record = PathRecord::new("tree_1", 1, &path_result());
+        let mut columns = PathRecord::COLUMNS.to_vec();
+        columns.sort();
+        assert_eq!(serialized_columns(&record), columns);
+        assert_eq!(record.values().len(), PathRecord::COLUMNS.len());
+        assert_eq!(NodeRecord::COLUMNS.len(), 27);
+    }
+
+    #[test]
+    fn test_csv_and_json_lines() {
+        let records = vec![PathRecord::new("tree_1", 1, &path_result())];
+        let csv = render(&records, TabularFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), PathRecord::COLUMNS.join(","));
+        assert!(csv.contains("1,tree_1,1,clever_turing,root | clever_turing,\"Will the launch slip? | Supplier \"\"delays\"\", again\",1,0.6,0.7,\"Lead times doubled\nsince March\"\n"));
+
+        let jsonl = render(&records, TabularFormat::JsonLines).unwrap();
+        assert_eq!(jsonl.lines().count(), 1);
+        let row: serde_json::Value = serde_json::from_str(jsonl.trim()).unwrap();
+        assert_eq!(row["schema_version"], TABULAR_SCHEMA_VERSION);
+        assert_eq!(row["leaf_id"], "clever_turing");
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::domain::models::tree_state::TreeConfig;
+
+/// Placeholder in `root_premise` replaced by the subject of the analysis.
+pub const SUBJECT_PLACEHOLDER: &str = "{subject}";
+
+const BUILTIN_TEMPLATES: [&str; 5] = [
+    include_str!("../../../templates/pre_mortem.json"),
+    include_str!("../../../templates/swot_scenarios.json"),
+    include_str!("../../../templates/competing_hypotheses.json"),
+    include_str!("../../../templates/five_whys.json"),
+    include_str!("../../../templates/go_no_go.json"),
+];
+
+/// Skeleton of a tree for a structured analysis framework.
+///
+/// Templates are plain JSON (see `templates/` for the built-in ones), so teams can
+/// register their own without code changes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct AnalysisTemplate {
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// Root premise, with `{subject}` standing for what is being analyzed
+    pub root_premise: String,
+    pub complexity: i64,
+    pub branches: Vec<TemplateBranch>,
+}
+
+/// A placeholder branch with its suggested probability slot.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct TemplateBranch {
+    pub premise: String,
+    pub reasoning: String,
+    pub probability: f64,
+    #[serde(default = "default_slot_confidence")]
+    pub confidence: i64,
+    #[serde(default)]
+    pub children: Vec<TemplateBranch>,
+}
+
+/// Built-in templates plus any registered by the team, keyed by name.
+#[derive(Debug, Clone)]
+pub struct TemplateLibrary {
+    templates: BTreeMap<String, AnalysisTemplate>,
+}
+
+/// Templates available to `create_tree_from_template`, as returned by `list_templates`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TemplateListing {
+    pub templates: Vec<AnalysisTemplate>,
+    /// Why the templates of the configured template directory could not be loaded
+    pub load_error: Option<String>,
+}
+
+/// Placeholder slots start with low confidence until the analyst fills them in.
+fn default_slot_confidence() -> i64 {
+    3
+}
+
+impl AnalysisTemplate {
+    /// Parses one template, or an array of templates, from JSON.
+    pub fn from_json(json: &str) -> Result<Vec<AnalysisTemplate>, String> {
+        let templates = if json.trim_start().starts_with('[') {
+            serde_json::from_str::<Vec<AnalysisTemplate>>(json)
+        } else {
+            serde_json::from_str::<AnalysisTemplate>(json).map(|template| vec![template])
+        }.map_err(|e| format!("Invalid template JSON: {}", e))?;
+        for template in &templates {
+            template.validate()?;
+        }
+        Ok(templates)
+    }
+
+    /// Checks that the template can be instantiated as a valid tree.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() || self.name.chars().any(char::is_whitespace) {
+            return Err(format!("Template name '{}' must be non-empty and contain no whitespace", self.name));
+        }
+        if !(1..=10).contains(&self.complexity) {
+            return Err(format!("Template '{}': complexity must be between 1 and 10", self.name));
+        }
+        if self.branches.is_empty() {
+            return Err(format!("Template '{}' has no branches", self.name));
+        }
+
+        let max_depth = TreeConfig::new(self.complexity).max_depth;
+        Self::validate_branches(&self.name, &self.branches, 1.0, 1, max_depth)
+    }
+
+    fn validate_branches(name: &str, branches: &[TemplateBranch], parent_probability: f64, depth: i64, max_depth: i64) -> Result<(), String> {
+        if branches.is_empty() {
+            return Ok(());
+        }
+        if depth >= max_depth {
+            return Err(format!("Template '{}' is deeper than complexity allows (max depth {})", name, max_depth));
+        }
+
+        let total: f64 = branches.iter().map(|branch| branch.probability).sum();
+        if total > parent_probability + 0.001 {
+            return Err(format!("Template '{}': sibling probabilities sum to {:.3}, exceeding parent probability {:.3}", name, total, parent_probability));
+        }
+
+        for branch in branches {
+            if branch.premise.trim().is_empty() || branch.reasoning.trim().is_empty() {
+                return Err(format!("Template '{}': every branch needs a premise and reasoning", name));
+            }
+            if !(0.0..=1.0).contains(&branch.probability) {
+                return Err(format!("Template '{}': probability {} is out of range [0,1]", name, branch.probability));
+            }
+            if !(1..=10).contains(&branch.confidence) {
+                return Err(format!("Template '{}': confidence {} is out of range [1,10]", name, branch.confidence));
+            }
+            Self::validate_branches(name, &branch.children, branch.probability, depth + 1, max_depth)?;
+        }
+        Ok(())
+    }
+
+    /// Root premise with the subject filled in.
+    pub fn render_root(&self, subject: &str) -> String {
+        self.root_premise.replace(SUBJECT_PLACEHOLDER, subject.trim())
+    }
+
+    /// Number of branches the template creates below the root.
+    pub fn slot_count(&self) -> usize {
+        fn count(branches: &[TemplateBranch]) -> usize {
+            branches.iter().map(|branch| 1 + count(&branch.children)).sum()
+        }
+        count(&self.branches)
+    }
+}
+
+impl TemplateLibrary {
+    /// Library holding only the built-in templates.
+    pub fn with_builtins() -> Self {
+        let mut library = Self { templates: BTreeMap::new() };
+        for json in BUILTIN_TEMPLATES {
+            for template in AnalysisTemplate::from_json(json).expect("built-in templates are valid") {
+                library.register(template);
+            }
+        }
+        library
+    }
+
+    /// Adds a template, replacing any existing one with the same name.
+    ///
+    /// Returns true if a template was replaced.
+    pub fn register(&mut self, template: AnalysisTemplate) -> bool {
+        self.templates.insert(template.name.clone(), template).is_some()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AnalysisTemplate> {
+        self.templates.get(name.trim())
+    }
+
+    pub fn list(&self) -> Vec<&AnalysisTemplate> {
+        self.templates.values().collect()
+    }
+}
+
+impl Default for TemplateLibrary {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+impl Display for AnalysisTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "📋 {} — {} (complexity {}, {} slots)", self.name, self.title, self.complexity, self.slot_count())?;
+        if !self.description.is_empty() {
+            writeln!(f, "   {}", self.description)?;
+        }
+        writeln!(f, "   Root: {}", self.root_premise)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_are_valid() {
+        let library = TemplateLibrary::with_builtins();
+        let names: Vec<&str> = library.list().iter().map(|template| template.name.as_str()).collect();
+        assert_eq!(names, vec!["competing_hypotheses", "five_whys", "go_no_go", "pre_mortem", "swot_scenarios"]);
+        assert_eq!(library.get("five_whys").unwrap().slot_count(), 6);
+    }
+
+    #[test]
+    fn test_render_root() {
+        let library = TemplateLibrary::with_builtins();
+        let template = library.get("go_no_go").unwrap();
+        assert_eq!(template.render_root("the EU launch"), "Should we proceed with the EU launch?");
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_templates() {
+        let over_budget = r#"{
+            "name": "bad", "title": "Bad", "root_premise": "Is {subject} viable?", "complexity": 3,
+            "branches": [
+                { "premise": "A", "reasoning": "a", "probability": 0.7 },
+                { "premise": "B", "reasoning": "b", "probability": 0.5 }
+            ]
+        }"#;
+        assert!(AnalysisTemplate::from_json(over_budget).unwrap_err().contains("sum to 1.200"));
+
+        let named_with_space = r#"[{
+            "name": "my template", "title": "T", "root_premise": "Is {subject} viable?", "complexity": 3,
+            "branches": [{ "premise": "A", "reasoning": "a", "probability": 0.5 }]
+        }]"#;
+        assert!(AnalysisTemplate::from_json(named_with_space).is_err());
+    }
+
+    #[test]
+    fn test_register_replaces_by_name() {
+        let mut library = TemplateLibrary::with_builtins();
+        let mut custom = library.get("pre_mortem").unwrap().clone();
+        custom.title = "Team pre-mortem".to_string();
+        assert!(library.register(custom));
+        assert_eq!(library.get("pre_mortem").unwrap().title, "Team pre-mortem");
+    }
+}
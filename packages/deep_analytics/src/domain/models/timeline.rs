@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Inclusive date range in which a scenario is expected to occur.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct TimeWindow {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// Root-to-leaf scenarios ordered by when they are expected to occur.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Timeline {
+    pub as_of: NaiveDate,
+    /// Dated scenarios first, by window start then end; undated ones last
+    pub scenarios: Vec<TimelineScenario>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimelineScenario {
+    pub leaf_id: String,
+    pub premises: Vec<String>,
+    pub probability: f64,
+    /// Window of the most specific dated node on the path
+    pub window: Option<TimeWindow>,
+    /// Every dated node along the path, from the root down
+    pub milestones: Vec<TimelineMilestone>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimelineMilestone {
+    pub node_id: String,
+    pub premise: String,
+    pub window: TimeWindow,
+}
+
+impl TimeWindow {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self, String> {
+        if start > end {
+            return Err(format!("Window start {} is after its end {}", start, end));
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    pub fn has_passed(&self, today: NaiveDate) -> bool {
+        self.end < today
+    }
+}
+
+impl Timeline {
+    /// Orders scenarios chronologically, keeping undated ones at the end by probability.
+    pub fn new(as_of: NaiveDate, mut scenarios: Vec<TimelineScenario>) -> Self {
+        scenarios.sort_by(|a, b| {
+            let key = |scenario: &TimelineScenario| scenario.window.map(|window| (window.start, window.end));
+            match (key(a), key(b)) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+            .then_with(|| b.probability.partial_cmp(&a.probability).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        Self { as_of, scenarios }
+    }
+}
+
+impl Display for TimeWindow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} → {}", self.start.format("%Y-%m-%d"), self.end.format("%Y-%m-%d"))
+    }
+}
+
+impl Display for Timeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "🗓️  SCENARIO TIMELINE (as of {})\n", self.as_of.format("%Y-%m-%d"))?;
+
+        if self.scenarios.is_empty() {
+            writeln!(f, "No scenarios yet.")?;
+            return Ok(());
+        }
+
+        for scenario in &self.scenarios {
+            let marker = match scenario.window {
+                Some(window) if window.has_passed(self.as_of) => "⌛",
+                Some(window) if window.contains(self.as_of) => "▶️ ",
+                Some(_) => "📅",
+                None => "❔",
+            };
+            let when = scenario.window.map(|window| window.to_string()).unwrap_or_else(|| "undated".to_string());
+            writeln!(f, "{} {} | [{}] {} (p={:.3})",
+                marker, when, scenario.leaf_id, scenario.premises.join(" → "), scenario.probability)?;
+            for milestone in &scenario.milestones {
+                writeln!(f, "      • {} {}", milestone.window, milestone.premise)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn scenario(leaf_id: &str, probability: f64, window: Option<TimeWindow>) -> TimelineScenario {
+        TimelineScenario {
+            leaf_id: leaf_id.to_string(),
+            premises: vec![leaf_id.to_string()],
+            probability,
+            window,
+            milestones: vec![],
+        }
+    }
+
+    #[test]
+    fn test_window_bounds() {
+        assert!(TimeWindow::new(date(2025, 6, 1), date(2025, 5, 1)).is_err());
+        let window = TimeWindow::new(date(2025, 4, 1), date(2025, 6, 30)).unwrap();
+        assert!(window.contains(date(2025, 6, 30)));
+        assert!(!window.has_passed(date(2025, 6, 30)));
+        assert!(window.has_passed(date(2025, 7, 1)));
+    }
+
+    #[test]
+    fn test_timeline_orders_chronologically() {
+        let q3 = TimeWindow::new(date(2025, 7, 1), date(2025, 9, 30)).unwrap();
+        let q2 = TimeWindow::new(date(2025, 4, 1), date(2025, 6, 30)).unwrap();
+        let timeline = Timeline::new(date(2025, 5, 15), vec![
+            scenario("undated_low", 0.1, None),
+            scenario("q3", 0.3, Some(q3)),
+            scenario("undated_high", 0.4, None),
+            scenario("q2", 0.2, Some(q2)),
+        ]);
+
+        let order: Vec<&str> = timeline.scenarios.iter().map(|scenario| scenario.leaf_id.as_str()).collect();
+        assert_eq!(order, vec!["q2", "q3", "undated_high", "undated_low"]);
+        assert!(timeline.to_string().contains("▶️  2025-04-01 → 2025-06-30 | [q2]"));
+    }
+}
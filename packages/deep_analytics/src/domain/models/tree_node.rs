@@ -1,11 +1,24 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
 use surrealdb::RecordId;
+use crate::domain::models::argument::ArgumentTag;
+use crate::domain::models::evidence::Evidence;
+use crate::domain::models::timeline::TimeWindow;
+
+/// Largest gap allowed between an exhaustive node's probability and the sum of its children.
+pub const EXHAUSTIVE_TOLERANCE: f64 = 0.01;
+
+/// Premise given to the residual child of an exhaustive node.
+pub const RESIDUAL_PREMISE: &str = "Other / unanticipated outcome";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TreeNode {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<RecordId>,
+    /// Human-friendly alias (e.g. `clever_turing`) accepted anywhere a node id is
+    #[serde(default)]
+    pub friendly_id: String,
     pub premise: String,
     pub reasoning: String,
     pub probability: f64,
@@ -17,6 +30,41 @@ pub struct TreeNode {
     pub depth: i64,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    /// Optional formal proposition (atoms with AND/OR/NOT) the premise asserts
+    #[serde(default)]
+    pub proposition: Option<String>,
+    /// Dates between which the scenario is expected to occur
+    #[serde(default)]
+    pub time_window: Option<TimeWindow>,
+    /// Date by which the estimate should be revisited
+    #[serde(default)]
+    pub review_by: Option<chrono::NaiveDate>,
+    /// Pinned by the analyst: prune and balance operations leave this node untouched
+    #[serde(default)]
+    pub locked: bool,
+    /// Pins this node and every descendant, as if each were locked
+    #[serde(default)]
+    pub frozen: bool,
+    /// Fault-tree gate combining the children's events; nodes with children but no gate act as OR
+    #[serde(default)]
+    pub gate: Option<Gate>,
+    /// Severity if the scenario occurs, from 0 (negligible) to 10 (catastrophic)
+    #[serde(default)]
+    pub impact: Option<f64>,
+    /// Risk category used to aggregate scores in the risk matrix
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Stance and strength towards the parent claim in argument-mode trees
+    #[serde(default)]
+    pub argument: Option<ArgumentTag>,
+    /// Children form a mutually exclusive, collectively exhaustive set summing to this node's probability
+    #[serde(default)]
+    pub exhaustive: bool,
+    /// Auto-managed "other/unanticipated" child absorbing the probability its exhaustive siblings leave
+    #[serde(default)]
+    pub residual: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -26,10 +74,47 @@ pub enum NodeType {
     Leaf,
 }
 
+/// Logic gate deciding when a node's event occurs from its children's events in fault tree analysis.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum Gate {
+    /// Every child event occurs
+    And,
+    /// At least one child event occurs
+    Or,
+    /// At least k of the child events occur
+    KOfN(usize),
+}
+
+impl Gate {
+    /// Parses `AND`, `OR` or `K_OF_N` (also `k-of-n`), the latter with its threshold `k`.
+    pub fn parse(name: &str, k: Option<usize>) -> Result<Gate, String> {
+        match name.trim().to_uppercase().replace('-', "_").as_str() {
+            "AND" => Ok(Gate::And),
+            "OR" => Ok(Gate::Or),
+            "K_OF_N" | "KOFN" => match k {
+                Some(k) if k > 0 => Ok(Gate::KOfN(k)),
+                _ => Err("A K_OF_N gate needs k of at least 1".to_string()),
+            },
+            other => Err(format!("Unknown gate '{}', expected AND, OR or K_OF_N", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gate::And => write!(f, "AND"),
+            Gate::Or => write!(f, "OR"),
+            Gate::KOfN(k) => write!(f, "{}-of-n", k),
+        }
+    }
+}
+
 impl TreeNode {
     pub fn new_root(premise: String, complexity: i64) -> Self {
         Self {
             id: None,
+            friendly_id: String::new(),
             premise,
             reasoning: "Root premise of the analysis".to_string(),
             probability: 1.0,
@@ -45,6 +130,18 @@ impl TreeNode {
                 map.insert("complexity".to_string(), complexity.to_string());
                 map
             },
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
         }
     }
 
@@ -58,6 +155,7 @@ impl TreeNode {
     ) -> Self {
         Self {
             id: None,
+            friendly_id: String::new(),
             premise,
             reasoning,
             probability,
@@ -69,6 +167,18 @@ impl TreeNode {
             depth,
             created_at: chrono::Utc::now(),
             metadata: HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
         }
     }
 
@@ -104,6 +214,33 @@ impl TreeNode {
         parent_probability * self.probability
     }
 
+    /// Why the estimate needs revisiting as of `today`, if it does.
+    ///
+    /// An active node is stale once its review date has passed, or once its expected
+    /// window has closed while the node is still active. Updating the node does not clear
+    /// either: move the review date or window forward, or prune the node.
+    pub fn staleness(&self, today: chrono::NaiveDate) -> Option<String> {
+        if self.is_invalidated {
+            return None;
+        }
+        if let Some(review_by) = self.review_by.filter(|review_by| *review_by < today) {
+            return Some(format!("review was due {}", review_by.format("%Y-%m-%d")));
+        }
+        if let Some(window) = self.time_window.filter(|window| window.has_passed(today)) {
+            return Some(format!("expected window ended {}", window.end.format("%Y-%m-%d")));
+        }
+        None
+    }
+
+    /// Returns the friendly alias, falling back to the record id for nodes created without one
+    pub fn display_id(&self) -> String {
+        if !self.friendly_id.is_empty() {
+            self.friendly_id.clone()
+        } else {
+            self.id.as_ref().map(|id| id.to_string()).unwrap_or_default()
+        }
+    }
+
 }
 
 #[cfg(test)]
@@ -283,6 +420,26 @@ mod tests {
         assert!((path_probability - (0.8 * 0.6)).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_display_id() {
+        let mut node = TreeNode::new_root("Test premise".to_string(), 5);
+        node.id = Some("node:abc123".parse().unwrap());
+        assert_eq!(node.display_id(), "node:abc123");
+
+        node.friendly_id = "clever_turing".to_string();
+        assert_eq!(node.display_id(), "clever_turing");
+    }
+
+    #[test]
+    fn test_deserialize_without_friendly_id() {
+        let root = TreeNode::new_root("Test premise".to_string(), 5);
+        let mut value = serde_json::to_value(&root).unwrap();
+        value.as_object_mut().unwrap().remove("friendly_id");
+
+        let deserialized: TreeNode = serde_json::from_value(value).unwrap();
+        assert!(deserialized.friendly_id.is_empty());
+    }
+
     #[test]
     fn test_serialization() {
         let root = TreeNode::new_root("Test premise".to_string(), 5);
@@ -294,4 +451,28 @@ mod tests {
         assert_eq!(root.node_type, deserialized.node_type);
         assert_eq!(root.probability, deserialized.probability);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_staleness() {
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut node = TreeNode::new_leaf(
+            "Supplier delays".to_string(),
+            "Lead times doubled".to_string(),
+            0.6,
+            7,
+            "node:test_parent".parse().unwrap(),
+            1,
+        );
+        let today = date(2025, 7, 1);
+        assert_eq!(node.staleness(today), None);
+
+        node.time_window = Some(TimeWindow::new(date(2025, 4, 1), date(2025, 6, 30)).unwrap());
+        assert_eq!(node.staleness(today), Some("expected window ended 2025-06-30".to_string()));
+
+        node.review_by = Some(date(2025, 5, 15));
+        assert_eq!(node.staleness(today), Some("review was due 2025-05-15".to_string()));
+
+        node.invalidate();
+        assert_eq!(node.staleness(today), None);
+    }
+}
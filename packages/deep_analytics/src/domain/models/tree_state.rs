@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use surrealdb::RecordId;
 use std::collections::HashMap;
 
@@ -13,14 +14,27 @@ pub struct TreeState {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeConfig {
+    #[schemars(with = "Option<serde_json::Value>")]
     pub root_id: Option<RecordId>,
     pub max_depth: i64,
     pub min_probability: f64,
     pub branch_limit: usize,
     pub use_laplace: bool,
     pub complexity: i64,
+    #[serde(default)]
+    pub mode: TreeMode,
+}
+
+/// How a tree's children relate to their parent.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum TreeMode {
+    /// Children are mutually exclusive scenarios whose probabilities sum to at most the parent's
+    #[default]
+    Scenario,
+    /// Children are arguments for or against their parent claim; sibling sums are not constrained
+    Argument,
 }
 
 impl TreeState {
@@ -58,6 +72,23 @@ impl TreeConfig {
             min_probability: 0.15,
             use_laplace: true,
             complexity,
+            mode: TreeMode::Scenario,
+        }
+    }
+
+    /// Whether children must sum to at most their parent's probability.
+    pub fn enforces_sibling_sums(&self) -> bool {
+        self.mode == TreeMode::Scenario
+    }
+}
+
+impl TreeMode {
+    /// Parses `scenario` or `argument`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "scenario" => Ok(TreeMode::Scenario),
+            "argument" => Ok(TreeMode::Argument),
+            other => Err(format!("Unknown tree mode '{}', expected scenario or argument", other)),
         }
     }
 }
@@ -123,6 +154,8 @@ mod tests {
         assert_eq!(config.min_probability, 0.15);
         assert!(config.use_laplace);
         assert_eq!(config.complexity, 5);
+        assert_eq!(config.mode, TreeMode::Scenario);
+        assert!(config.enforces_sibling_sums());
     }
 
     #[test]
@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use crate::domain::models::tree_state::TreeConfig;
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::domain::models::evidence::PathBibliography;
+use crate::domain::models::checkpoint::PathRankChange;
+use crate::domain::models::tree_node::Gate;
+use crate::domain::models::argument::ArgumentTag;
+use crate::domain::models::timeline::TimeWindow;
+use crate::domain::models::tree_state::TreeMode;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AnalysisResult {
     pub surviving_paths: Vec<PathResult>,
     pub insights: Vec<String>,
@@ -16,9 +23,10 @@ pub struct AnalysisResult {
     pub tree_statistics: Option<TreeStatsSummary>,
     pub tree_distributions: Option<TreeDistributions>,
     pub active_paths_detail: Vec<ActivePath>,
+    pub bibliography: Vec<PathBibliography>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PathResult {
     pub path: Vec<String>, // Changed from Uuid to String
     pub premises: Vec<String>,
@@ -27,28 +35,28 @@ pub struct PathResult {
     pub confidence_score: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum NarrativeStyle {
     Analytical,
     Strategic,
     Storytelling,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum UncertaintyType {
     InsufficientData,
     EqualLikelihood,
     CognitiveOverload,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationResult {
     pub is_valid: bool,
     pub violations: Vec<ValidationViolation>,
     pub suggestions: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationViolation {
     pub violation_type: ViolationType,
     pub node_id: String, // Changed from Uuid to String
@@ -56,7 +64,7 @@ pub struct ValidationViolation {
     pub severity: Severity,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum ViolationType {
     KolmogorovAxiom,
     HierarchicalConstraint,
@@ -66,22 +74,30 @@ pub enum ViolationType {
     ProbabilityRange,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum Severity {
     Error,
     Warning,
     Info,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PruningResult {
     pub nodes_removed: Vec<String>, // Changed from Uuid to String
     pub nodes_preserved: Vec<String>, // Changed from Uuid to String
     pub manual_overrides: Vec<String>, // Changed from Uuid to String
+    /// Locked or frozen nodes the pruning would otherwise have removed
+    pub skipped_locked: Vec<String>,
+    /// Residual children left alone because their exhaustive parent sizes them
+    pub skipped_residual: Vec<String>,
+    /// True when the pruning was only previewed and nothing was written
+    pub dry_run: bool,
+    /// Root-to-leaf paths whose probability or rank the pruning changes
+    pub affected_paths: Vec<PathRankChange>,
     pub statistics: PruningStatistics,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PruningStatistics {
     pub original_count: usize,
     pub removed_count: usize,
@@ -92,7 +108,7 @@ pub struct PruningStatistics {
     pub method_explanation: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BalancingResult {
     pub balanced_nodes: Vec<String>, // Changed from Uuid to String
     pub uncertainty_type: UncertaintyType,
@@ -101,31 +117,41 @@ pub struct BalancingResult {
     pub laplace_alpha: f64,
     pub total_count: usize,
     pub smoothing_explanation: String,
+    /// Locked or frozen leaves whose probabilities were kept as they were
+    pub skipped_locked: Vec<String>,
+    /// Residual leaves left out of the smoothing; they absorb what their siblings leave
+    pub skipped_residual: Vec<String>,
+    /// True when the balancing was only previewed and nothing was written
+    pub dry_run: bool,
+    /// Root-to-leaf paths whose probability or rank the balancing changes
+    pub affected_paths: Vec<PathRankChange>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CoherenceAnalysis {
     pub is_coherent: bool,
     pub contradictions: Vec<ContradictionResult>,
     pub truth_table: Vec<TruthTableRow>,
     pub eliminated_nodes: Vec<String>, // Changed from Uuid to String
+    /// Checks that could not be completed, such as propositions too large to check exhaustively
+    pub warnings: Vec<ValidationViolation>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContradictionResult {
     pub node_id: String, // Changed from Uuid to String
     pub conflicting_premises: Vec<String>,
     pub explanation: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TruthTableRow {
     pub premises: HashMap<String, bool>,
     pub is_consistent: bool,
     pub affected_nodes: Vec<String>, // Changed from Uuid to String
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeVisualization {
     pub ascii_tree: String, // Empty in service layer, filled by presentation layer
     pub node_details: HashMap<String, NodeVisualization>,
@@ -136,7 +162,7 @@ pub struct TreeVisualization {
     pub recommendations: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeMetadata {
     pub tree_id: String,
     pub complexity: i64,
@@ -145,14 +171,14 @@ pub struct TreeMetadata {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeDistributions {
     pub confidence_distribution: HashMap<i64, usize>,
     pub depth_distribution: HashMap<u32, usize>,
     pub probability_distribution: Vec<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ActivePath {
     pub path_number: usize,
     pub leaf_id: String,
@@ -161,7 +187,7 @@ pub struct ActivePath {
     pub confidence: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NodeVisualization {
     pub friendly_id: String,
     pub premise_summary: String,
@@ -172,13 +198,30 @@ pub struct NodeVisualization {
     pub children_count: usize,
     pub children_ids: Vec<String>,
     pub parent_id: Option<String>,
+    /// Extra parents reached through cross-links
+    pub linked_parent_ids: Vec<String>,
+    /// Extra children reached through cross-links
+    pub linked_children_ids: Vec<String>,
     pub is_leaf: bool,
     pub can_expand: bool,
     pub confidence: i64,
     pub status: NodeStatus,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
+    pub evidence_count: usize,
+    pub time_window: Option<TimeWindow>,
+    pub review_by: Option<chrono::NaiveDate>,
+    /// Locked itself or inside a frozen subtree
+    pub locked: bool,
+    /// Fault-tree gate combining the children's events
+    pub gate: Option<Gate>,
+    /// Stance and strength towards the parent claim in argument-mode trees
+    pub argument: Option<ArgumentTag>,
+    /// Children form an exhaustive set that must sum to this node's probability
+    pub exhaustive: bool,
+    /// Auto-managed child absorbing the probability its exhaustive siblings leave
+    pub residual: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum NodeStatus {
     Active,
     Invalidated,
@@ -186,12 +229,15 @@ pub enum NodeStatus {
     Expanded,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TreeStatsSummary {
     pub total_nodes: usize,
     pub active_nodes: usize,
     pub invalidated_nodes: usize,
     pub active_paths: usize,
+    pub cross_links: usize,
+    /// Distinct root-to-leaf paths once cross-links are followed
+    pub scenario_paths: usize,
     pub max_depth: u32,
     pub avg_depth: f64,
     pub avg_probability: f64,
@@ -202,6 +248,151 @@ pub struct TreeStatsSummary {
     pub avg_reasoning_length: f64,
 }
 
+/// Identifiers of a node created or targeted by a tool, returned as structured content.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NodeRef {
+    /// SurrealDB record id, e.g. `node:abc123`
+    pub node_id: String,
+    /// Friendly alias accepted anywhere a node id is
+    pub friendly_id: String,
+}
+
+/// A list returned as structured content, which must be a JSON object rather than an array.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ItemList<T> {
+    pub items: Vec<T>,
+}
+
+/// A document written by an export tool.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportedDocument {
+    /// Format name, e.g. `xmlbif`, `csv` or `markdown`
+    pub format: String,
+    pub content: String,
+}
+
+/// An extra parent → child edge, with both ends given by friendly alias.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrossLinkRef {
+    pub parent_id: String,
+    pub child_id: String,
+    pub probability: f64,
+    pub reasoning: String,
+}
+
+/// Proposition of a node after `set_proposition`; `None` once cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PropositionUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub proposition: Option<String>,
+}
+
+/// Time horizon of a node after `set_time_horizon`; cleared values are `None`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimeHorizonUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub time_window: Option<TimeWindow>,
+    pub review_by: Option<chrono::NaiveDate>,
+}
+
+/// Lock state of a node after `lock_node`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub locked: bool,
+    /// Whether the lock covered the whole subtree (frozen) rather than the node alone
+    pub subtree: bool,
+    /// Whether automated operations still leave the node alone, e.g. under a frozen ancestor
+    pub still_locked: bool,
+}
+
+/// Gate of a node after `set_gate`; `None` acts as OR.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GateUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub gate: Option<Gate>,
+}
+
+/// Risk profile of a node after `set_risk_profile`; `None` is inherited from the nearest ancestor.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RiskProfileUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub impact: Option<f64>,
+    pub category: Option<String>,
+}
+
+/// Mode of the tree after `set_tree_mode`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TreeModeUpdate {
+    pub mode: TreeMode,
+}
+
+/// Argument tag of a node after `tag_argument`; `None` once cleared.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArgumentTagUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub tag: Option<ArgumentTag>,
+}
+
+/// Exhaustiveness of a node's children after `set_exhaustive`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExhaustiveUpdate {
+    /// Friendly alias of the node
+    pub node_id: String,
+    pub exhaustive: bool,
+    /// Friendly alias of the residual child absorbing the leftover probability, if any
+    pub residual_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub enum SearchMode {
+    Keyword,
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NodeSearchQuery {
+    pub text: Option<String>,
+    pub mode: SearchMode,
+    pub min_probability: Option<f64>,
+    pub max_probability: Option<f64>,
+    pub min_confidence: Option<i64>,
+    pub max_confidence: Option<i64>,
+    pub min_depth: Option<i64>,
+    pub max_depth: Option<i64>,
+    pub status: Option<NodeStatus>,
+    pub metadata_keys: Vec<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NodeSearchHit {
+    pub friendly_id: String,
+    pub node_id: String,
+    pub premise: String,
+    pub reasoning: String,
+    pub probability: f64,
+    pub confidence: i64,
+    pub depth: u32,
+    pub status: NodeStatus,
+    pub score: f64,
+    pub root_path: Vec<String>, // Friendly ids from root to the matching node
+    pub root_path_premises: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NodeSearchResult {
+    pub query: NodeSearchQuery,
+    pub total_matches: usize,
+    pub hits: Vec<NodeSearchHit>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTreeRequest {
     pub premise: String,
@@ -273,6 +464,47 @@ impl Default for UncertaintyType {
     }
 }
 
+impl Default for SearchMode {
+    fn default() -> Self {
+        SearchMode::Keyword
+    }
+}
+
+impl SearchMode {
+    /// Parses `keyword` or `fuzzy`, case-insensitively.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "keyword" => Ok(SearchMode::Keyword),
+            "fuzzy" => Ok(SearchMode::Fuzzy),
+            other => Err(format!("Unknown search mode '{}', expected keyword or fuzzy", other)),
+        }
+    }
+}
+
+impl NodeStatus {
+    /// Parses a status filter, case-insensitively.
+    ///
+    /// Pruned nodes are reported as `Invalidated`, so `pruned` parses to that status.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "active" => Ok(NodeStatus::Active),
+            "expanded" => Ok(NodeStatus::Expanded),
+            "invalidated" | "pruned" => Ok(NodeStatus::Invalidated),
+            other => Err(format!("Unknown status '{}', expected Active, Expanded or Invalidated", other)),
+        }
+    }
+
+    /// Name of the status as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeStatus::Active => "Active",
+            NodeStatus::Invalidated => "Invalidated",
+            NodeStatus::Pruned => "Pruned",
+            NodeStatus::Expanded => "Expanded",
+        }
+    }
+}
+
 // Display implementations for verbose output
 
 impl Display for AnalysisResult {
@@ -300,6 +532,18 @@ impl Display for AnalysisResult {
             writeln!(f)?;
         }
 
+        // Bibliography Section
+        if self.bibliography.iter().any(|entry| !entry.citations.is_empty()) {
+            writeln!(f, "📚 BIBLIOGRAPHY:")?;
+            for entry in self.bibliography.iter().filter(|entry| !entry.citations.is_empty()) {
+                writeln!(f, "  Path {} ({}):", entry.path_number, entry.leaf_id)?;
+                for citation in &entry.citations {
+                    writeln!(f, "    - [{}] {}", citation.node_id, citation.evidence)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
         // Tree Statistics Section (if available)
         if let Some(ref stats) = self.tree_statistics {
             writeln!(f, "📊 TREE STATISTICS:")?;
@@ -445,14 +689,30 @@ impl fmt::Display for NodeVisualization {
         writeln!(f, "{}    ├─ PROBABILITY: {:.4} ({:.1}%) | CONFIDENCE: {}/10 | DEPTH: {}",
             indent, self.probability, self.probability * 100.0, self.confidence, self.depth)?;
 
-        writeln!(f, "{}    ├─ CHILDREN: {} | EXPANDABLE: {} | STATUS: {:?}",
-            indent, self.children_count, if self.can_expand { "YES" } else { "NO" }, self.status)?;
+        writeln!(f, "{}    ├─ CHILDREN: {} | EXPANDABLE: {} | STATUS: {:?} | EVIDENCE: {}{}{}{}{}{}",
+            indent, self.children_count, if self.can_expand { "YES" } else { "NO" }, self.status, self.evidence_count,
+            self.gate.map(|gate| format!(" | GATE: {}", gate)).unwrap_or_default(),
+            self.argument.map(|tag| format!(" | {} {:.2}", tag.stance, tag.strength)).unwrap_or_default(),
+            if self.exhaustive { " | EXHAUSTIVE" } else { "" },
+            if self.residual { " | 🧩 RESIDUAL" } else { "" },
+            if self.locked { " | 🔒 LOCKED" } else { "" })?;
+
+        if self.time_window.is_some() || self.review_by.is_some() {
+            writeln!(f, "{}    ├─ WINDOW: {} | REVIEW BY: {}", indent,
+                self.time_window.map(|window| window.to_string()).unwrap_or_else(|| "—".to_string()),
+                self.review_by.map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "—".to_string()))?;
+        }
 
         if !self.children_ids.is_empty() {
-            let friendly_children: Vec<String> = self.children_ids.iter()
-                .map(|c| format!("N{}", c.chars().take(8).collect::<String>()))
-                .collect();
-            writeln!(f, "{}    └─ CHILD_IDs: [{}]", indent, friendly_children.join(", "))?;
+            writeln!(f, "{}    └─ CHILD_IDs: [{}]", indent, self.children_ids.join(", "))?;
+        }
+
+        if !self.linked_parent_ids.is_empty() {
+            writeln!(f, "{}    └─ LINKED FROM: [{}]", indent, self.linked_parent_ids.join(", "))?;
+        }
+
+        if !self.linked_children_ids.is_empty() {
+            writeln!(f, "{}    └─ LINKS TO: [{}]", indent, self.linked_children_ids.join(", "))?;
         }
 
         Ok(())
@@ -466,6 +726,10 @@ impl fmt::Display for TreeStatsSummary {
         writeln!(f, "├─ NODE COUNTS: Total={}, Active={}, Invalidated={}, Leaves={}",
             self.total_nodes, self.active_nodes, self.invalidated_nodes, self.active_paths)?;
 
+        if self.cross_links > 0 {
+            writeln!(f, "├─ SCENARIO DAG: Cross-links={}, Root-to-leaf paths={}", self.cross_links, self.scenario_paths)?;
+        }
+
         writeln!(f, "├─ TREE DEPTH: Max={}, Avg={:.1}", self.max_depth, self.avg_depth)?;
 
         writeln!(f, "├─ PROBABILITIES: Avg={:.4}, Median={:.4}, Range=[{:.4}, {:.4}]",
@@ -487,6 +751,25 @@ impl fmt::Display for ActivePath {
     }
 }
 
+impl fmt::Display for NodeSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "🔎 NODE SEARCH: {} match(es), showing {}", self.total_matches, self.hits.len())?;
+        if let Some(ref text) = self.query.text {
+            writeln!(f, "   Query: \"{}\" ({:?})", text, self.query.mode)?;
+        }
+        writeln!(f)?;
+
+        for (i, hit) in self.hits.iter().enumerate() {
+            writeln!(f, "{}. [{}] {} (score={:.3})", i + 1, hit.friendly_id, hit.premise, hit.score)?;
+            writeln!(f, "   ├─ PROBABILITY: {:.4} | CONFIDENCE: {}/10 | DEPTH: {} | STATUS: {:?}",
+                hit.probability, hit.confidence, hit.depth, hit.status)?;
+            writeln!(f, "   └─ PATH: {}", hit.root_path.join(" → "))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for TreeDistributions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "├─ CONFIDENCE DISTRIBUTION:")?;
@@ -528,6 +811,7 @@ mod tests {
             tree_statistics: None,
             tree_distributions: None,
             active_paths_detail: vec![],
+            bibliography: vec![],
         };
 
         assert_eq!(result.insights.len(), 1);
@@ -555,6 +839,18 @@ mod tests {
         assert!(matches!(result.violations[0].severity, Severity::Error));
     }
 
+    #[test]
+    fn test_search_parsers() {
+        assert_eq!(SearchMode::parse(" Fuzzy ").unwrap(), SearchMode::Fuzzy);
+        assert!(SearchMode::parse("regex").is_err());
+        assert_eq!(NodeStatus::parse("expanded").unwrap(), NodeStatus::Expanded);
+        assert_eq!(NodeStatus::parse("PRUNED").unwrap(), NodeStatus::Invalidated);
+        assert!(NodeStatus::parse("open").is_err());
+        for status in [NodeStatus::Active, NodeStatus::Invalidated, NodeStatus::Pruned, NodeStatus::Expanded] {
+            assert_eq!(serde_json::to_value(&status).unwrap(), status.as_str());
+        }
+    }
+
     #[test]
     fn test_default_implementations() {
         let default_style = NarrativeStyle::default();
@@ -577,4 +873,14 @@ mod tests {
         assert_eq!(request.premise, deserialized.premise);
         assert_eq!(request.complexity, deserialized.complexity);
     }
+
+    #[test]
+    fn test_structured_output_schemas() {
+        let schema = serde_json::to_value(schemars::schema_for!(PruningResult)).unwrap();
+        assert_eq!(schema["type"], "object");
+        assert!(schema["properties"]["statistics"].is_object());
+
+        let node_ref = serde_json::to_value(NodeRef { node_id: "node:abc".to_string(), friendly_id: "clever_turing".to_string() }).unwrap();
+        assert_eq!(node_ref["friendly_id"], "clever_turing");
+    }
 }
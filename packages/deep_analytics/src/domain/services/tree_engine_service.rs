@@ -1,16 +1,53 @@
 use crate::domain::errors::{TreeEngineError, TreeResult};
 use crate::domain::models::{
-    tree_node::TreeNode,
-    tree_state::{TreeState},
+    tree_node::{TreeNode, NodeType, Gate, EXHAUSTIVE_TOLERANCE, RESIDUAL_PREMISE},
+    tree_state::{TreeState, TreeConfig, TreeMode},
     AnalysisResult, ValidationResult, PruningResult, PruningStatistics, PathResult,
     BalancingResult, CoherenceAnalysis, TreeVisualization, UncertaintyType, NarrativeStyle,
     ContradictionResult, TruthTableRow, NodeVisualization, TreeStatsSummary, NodeStatus,
-    ValidationViolation, ViolationType, Severity, TreeMetadata, TreeDistributions, ActivePath
+    ValidationViolation, ViolationType, Severity, TreeMetadata, TreeDistributions, ActivePath,
+    NodeSearchQuery, NodeSearchResult, NodeSearchHit, SearchMode,
+    Evidence, EvidenceStrength, NodeEvidence, PathBibliography, Citation, CrossLink,
+    TreeCheckpoint, CheckpointSummary, TreeDiff, DiffNode, ValueDelta, PremiseChange, PathRankChange,
+    MergeReport, MergedNode, SiblingConflict, AnalysisTemplate, TemplateBranch, TemplateLibrary,
+    Resolution, LeafForecast, CalibrationReport, TimeWindow, Timeline, TimelineScenario, TimelineMilestone,
+    SubtreeNode, SubtreeAnchor, SubtreeInsertion, InsertedNode, SubtreeRelocation, RelocatedNode,
+    FaultEvent, FaultEventKind, FaultTreeAnalysis, ProbabilityBasis, RiskMatrix, RiskItem, MAX_IMPACT,
+    ArgumentTag, ArgumentNode, ArgumentMap, BayesNet, BayesVariable, BayesNetFormat,
+    bayes_net::{self, OTHER_STATE, NOT_APPLICABLE_STATE, PREMISE_PROPERTY, OUTCOME_PROPERTY_PREFIX},
+    TabularFormat, TabularTable, PathRecord, NodeRecord, tabular, Outline
 };
-use std::collections::HashMap;
+use crate::utils::proposition::{self, Proposition};
+use crate::utils::text_similarity;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
+use friendly_ids::{FriendlyIdAllocator, WordList};
 use surrealdb::{Surreal, engine::local::Db};
 use surrealdb::RecordId;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+/// Outgoing edges of the scenario DAG: `(child, step probability)` per parent node.
+type ScenarioEdges = HashMap<RecordId, Vec<(RecordId, f64)>>;
+/// A root-to-leaf route through the scenario DAG as `(node, step probability)` steps.
+type ScenarioPath = Vec<(RecordId, f64)>;
+
+/// A root-to-leaf path as `(node ids, path probability, premises)`, used to rank paths.
+type RankedPath = (Vec<RecordId>, f64, Vec<String>);
+
+/// Reference accepted by `diff_trees` for the live tree instead of a checkpoint.
+const CURRENT_TREE_REF: &str = "current";
+
+/// Truth tables are only built for trees whose propositions use at most this many atoms.
+///
+/// Lower than `proposition::MAX_ATOMS` on purpose: satisfiability checks stay internal and
+/// can afford 2^16 assignments, but every truth table row is returned to the client, and
+/// 64 rows is about as much as a reader can scan.
+const MAX_TRUTH_TABLE_ATOMS: usize = 6;
+
+/// Tree changes buffered for a slow subscriber before it starts skipping them.
+const TREE_CHANGE_CAPACITY: usize = 64;
 
 /// TreeEngineService provides a comprehensive engine for managing and analyzing probabilistic decision trees.
 ///
@@ -30,6 +67,12 @@ pub struct TreeEngineService {
     instance_id: String,
     /// Current cursor position in the tree for contextual operations
     cursor_node_id: Option<String>,
+    /// Registry of friendly node aliases, namespaced by tree id
+    friendly_ids: FriendlyIdAllocator,
+    /// Analysis templates available to `create_tree_from_template`
+    templates: TemplateLibrary,
+    /// Publishes the tree id after every mutation, for MCP resource subscribers
+    changes: broadcast::Sender<String>,
 }
 
 impl TreeEngineService {
@@ -60,6 +103,24 @@ impl TreeEngineService {
             db,
             instance_id,
             cursor_node_id: None,
+            friendly_ids: FriendlyIdAllocator::new().with_word_list(WordList::extended()),
+            templates: TemplateLibrary::with_builtins(),
+            changes: broadcast::channel(TREE_CHANGE_CAPACITY).0,
+        }
+    }
+
+    /// Receives the tree id each time the tree is mutated.
+    ///
+    /// Every mutating operation publishes after it succeeds; reads never do.
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<String> {
+        self.changes.subscribe()
+    }
+
+    /// Tells change subscribers that the current tree was mutated.
+    async fn publish_change(&self) {
+        if let Ok(state) = self.get_current_tree_state().await {
+            // Sending only fails when nobody has subscribed yet
+            let _ = self.changes.send(state.tree_id);
         }
     }
 
@@ -153,6 +214,125 @@ impl TreeEngineService {
         Ok(invalidated_nodes)
     }
 
+    /// Allocates a friendly alias that is not yet used by any node in the tree.
+    ///
+    /// Aliases come from the service's `FriendlyIdAllocator`, namespaced by tree id.
+    /// Aliases already stored on nodes are registered first so that nodes written
+    /// outside the allocator (e.g. restored records) are never shadowed.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - A friendly alias unique within the current tree
+    /// * `Err(TreeEngineError::NotFound)` - If no tree state has been initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database query fails
+    async fn allocate_friendly_id(&mut self) -> TreeResult<String> {
+        let namespace = self.get_current_tree_state().await?.tree_id;
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        for node in all_nodes.iter().filter(|node| !node.friendly_id.is_empty()) {
+            self.friendly_ids.register(&namespace, &node.friendly_id);
+        }
+        Ok(self.friendly_ids.allocate(&namespace))
+    }
+
+    /// Resolves a user-supplied node reference to the record id of an existing node.
+    ///
+    /// This is the single lookup used by every operation that accepts a `node_id`.
+    /// A reference may be any of:
+    /// - The node's friendly alias (e.g. `clever_turing`)
+    /// - The full SurrealDB record id (e.g. `node:abc123`)
+    /// - The bare record key (e.g. `abc123`)
+    /// - An unambiguous prefix of any of the above
+    ///
+    /// # Arguments
+    /// * `reference` - The node reference as provided by the client
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - The record id of the matching node
+    /// * `Err(TreeEngineError::InvalidInput)` - If the reference is empty or matches several nodes
+    /// * `Err(TreeEngineError::NotFound)` - If no node matches the reference
+    /// * `Err(TreeEngineError::DatabaseError)` - If database query fails
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Should we invest in renewable energy?".to_string(), 5).await?;
+    /// let root_alias = service.get_friendly_id(&service.get_current_node().await?).await?;
+    /// let root_id = service.resolve_node_ref(&root_alias).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_node_ref(&self, reference: &str) -> TreeResult<RecordId> {
+        let reference = reference.trim();
+        if reference.is_empty() {
+            return Err(TreeEngineError::InvalidInput("node_id".to_string(), "Node reference cannot be empty".to_string()));
+        }
+
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+
+        // Aliases known to the registry are looked up directly
+        if let Ok(tree_state) = self.get_current_tree_state().await {
+            if let Some(alias) = self.friendly_ids.resolve_node_id(&tree_state.tree_id, reference) {
+                if let Some(node) = all_nodes.iter().find(|node| node.friendly_id == alias) {
+                    return Ok(node.id.as_ref().unwrap().clone());
+                }
+            }
+        }
+
+        // Exact matches on alias, full record id or bare record key
+        for node in &all_nodes {
+            let record_id = node.id.as_ref().unwrap();
+            let full_id = record_id.to_string();
+            let key = full_id.split_once(':').map(|(_, key)| key).unwrap_or(&full_id);
+            if node.friendly_id == reference || full_id == reference || key == reference {
+                return Ok(record_id.clone());
+            }
+        }
+
+        // Unambiguous prefix matches
+        let matches: Vec<&TreeNode> = all_nodes.iter()
+            .filter(|node| {
+                let full_id = node.id.as_ref().unwrap().to_string();
+                let key = full_id.split_once(':').map(|(_, key)| key.to_string()).unwrap_or_else(|| full_id.clone());
+                (!node.friendly_id.is_empty() && node.friendly_id.starts_with(reference))
+                    || full_id.starts_with(reference)
+                    || key.starts_with(reference)
+            })
+            .collect();
+
+        match matches.as_slice() {
+            [node] => Ok(node.id.as_ref().unwrap().clone()),
+            [] => Err(TreeEngineError::NotFound(reference.to_string())),
+            candidates => Err(TreeEngineError::InvalidInput(
+                "node_id".to_string(),
+                format!(
+                    "Reference '{}' is ambiguous, it matches: {}",
+                    reference,
+                    candidates.iter().map(|node| node.friendly_id.clone()).collect::<Vec<_>>().join(", ")
+                ),
+            )),
+        }
+    }
+
+    /// Returns the friendly alias of the node identified by `reference`.
+    ///
+    /// # Arguments
+    /// * `reference` - Any reference accepted by [`Self::resolve_node_ref`]
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The node's friendly alias
+    /// * `Err(TreeEngineError::NotFound)` - If no node matches the reference
+    /// * `Err(TreeEngineError::DatabaseError)` - If database query fails
+    pub async fn get_friendly_id(&self, reference: &str) -> TreeResult<String> {
+        let record_id = self.resolve_node_ref(reference).await?;
+        let node: Option<TreeNode> = self.db.select(&record_id).await?;
+        node.map(|node| node.friendly_id)
+            .ok_or_else(|| TreeEngineError::NotFound(reference.to_string()))
+    }
+
     /// Creates a new probability tree with the specified premise and complexity.
     ///
     /// This is the primary method for initializing a new probability tree analysis.
@@ -201,7 +381,8 @@ impl TreeEngineService {
             return Err(TreeEngineError::InvalidInput("premise".to_string(), "Premise must be at least 10 characters long".to_string()));
         }
 
-        let tree_id = format!("tree_{}", chrono::Utc::now().timestamp());
+        // Unique even for trees created within the same second; resolutions are keyed by it
+        let tree_id = format!("tree_{}_{}", chrono::Utc::now().timestamp(), uuid::Uuid::new_v4().simple());
 
         // Create new tree state
         let tree_state = TreeState::new(tree_id.clone(), complexity);
@@ -209,8 +390,11 @@ impl TreeEngineService {
 
         // Clean up any existing nodes for this service instance
         let _: Vec<TreeNode> = self.db.delete("node").await?;
+        let _: Vec<CrossLink> = self.db.delete("cross_link").await?;
+        self.friendly_ids.clear_namespace(&tree_id);
 
-        let root = TreeNode::new_root(premise, complexity);
+        let mut root = TreeNode::new_root(premise, complexity);
+        root.friendly_id = self.allocate_friendly_id().await?;
         let created_node: Option<TreeNode> = self.db.create("node").content(root).await?;
 
         let root_node = created_node.ok_or_else(|| TreeEngineError::DatabaseError("Failed to create root node".to_string()))?;
@@ -225,9 +409,133 @@ impl TreeEngineService {
 
         // TreeState mantiene el root_id directamente, no necesita relación RELATE
 
+        self.publish_change().await;
         Ok(root_id.to_string())
     }
 
+    /// Lists the available analysis templates, built-in and registered, sorted by name.
+    pub fn list_templates(&self) -> Vec<AnalysisTemplate> {
+        self.templates.list().into_iter().cloned().collect()
+    }
+
+    /// Registers templates from JSON holding one template or an array of them.
+    ///
+    /// A template with the same name as an existing one replaces it.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Names of the registered templates
+    /// * `Err(TreeEngineError::InvalidInput)` - If the JSON is malformed or a template is invalid
+    pub fn register_templates_json(&mut self, json: &str) -> TreeResult<Vec<String>> {
+        let templates = AnalysisTemplate::from_json(json)
+            .map_err(|e| TreeEngineError::InvalidInput("template".to_string(), e))?;
+        Ok(templates.into_iter()
+            .map(|template| {
+                let name = template.name.clone();
+                self.templates.register(template);
+                name
+            })
+            .collect())
+    }
+
+    /// Registers templates from a JSON file, or from every `.json` file in a directory.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Names of the registered templates
+    /// * `Err(TreeEngineError::InvalidInput)` - If a file cannot be read or holds an invalid template
+    pub fn register_template_file(&mut self, path: &Path) -> TreeResult<Vec<String>> {
+        let read_error = |e: std::io::Error| TreeEngineError::InvalidInput("path".to_string(), format!("{}: {}", path.display(), e));
+
+        let mut files = if path.is_dir() {
+            std::fs::read_dir(path).map_err(read_error)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "json"))
+                .collect::<Vec<_>>()
+        } else {
+            vec![path.to_path_buf()]
+        };
+        files.sort();
+
+        let mut names = Vec::new();
+        for file in files {
+            let json = std::fs::read_to_string(&file).map_err(read_error)?;
+            let registered = self.register_templates_json(&json).map_err(|e| match e {
+                TreeEngineError::InvalidInput(field, message) => TreeEngineError::InvalidInput(field, format!("{}: {}", file.display(), message)),
+                other => other,
+            })?;
+            names.extend(registered);
+        }
+        Ok(names)
+    }
+
+    /// Creates a new tree from an analysis template.
+    ///
+    /// The root premise is the template's with `{subject}` filled in; every template
+    /// branch becomes a placeholder node carrying its suggested probability slot and a
+    /// low confidence, ready to be rewritten and re-estimated. Like `create_tree`, this
+    /// clears any existing tree and leaves the cursor at the root.
+    ///
+    /// # Arguments
+    /// * `template_name` - Name of a built-in or registered template (e.g. "pre_mortem")
+    /// * `subject` - What is being analyzed, substituted into the root premise
+    /// * `complexity` - Overrides the template's complexity (1-10)
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The root node ID of the new tree
+    /// * `Err(TreeEngineError::NotFound)` - If the template doesn't exist
+    /// * `Err(TreeEngineError::InvalidInput)` - If the subject is empty or the complexity
+    ///   is too low for the template's depth
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// let root_id = service.create_tree_from_template(
+    ///     "pre_mortem".to_string(),
+    ///     "the EU launch".to_string(),
+    ///     None,
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_tree_from_template(
+        &mut self,
+        template_name: String,
+        subject: String,
+        complexity: Option<i64>,
+    ) -> TreeResult<String> {
+        if subject.trim().is_empty() {
+            return Err(TreeEngineError::InvalidInput("subject".to_string(), "Subject cannot be empty".to_string()));
+        }
+
+        let mut template = self.templates.get(&template_name)
+            .cloned()
+            .ok_or_else(|| TreeEngineError::NotFound(format!("template '{}'", template_name)))?;
+        if let Some(complexity) = complexity {
+            template.complexity = complexity;
+            template.validate().map_err(|e| TreeEngineError::InvalidInput("complexity".to_string(), e))?;
+        }
+
+        let root_id = self.create_tree(template.render_root(&subject), template.complexity).await?;
+
+        let mut pending: Vec<(String, &TemplateBranch)> = template.branches.iter().rev()
+            .map(|branch| (root_id.clone(), branch))
+            .collect();
+        while let Some((parent_id, branch)) = pending.pop() {
+            self.cursor_node_id = Some(parent_id);
+            let node_id = self.add_leaf(branch.premise.clone(), branch.reasoning.clone(), branch.probability, branch.confidence).await?;
+            pending.extend(branch.children.iter().rev().map(|child| (node_id.clone(), child)));
+        }
+
+        self.cursor_node_id = Some(root_id.clone());
+        self.publish_change().await;
+        Ok(root_id)
+    }
+
     /// Adds a new leaf node to the probability tree as a child of the specified parent.
     ///
     /// This method creates a new child node with the provided premise, reasoning,
@@ -316,8 +624,10 @@ impl TreeEngineService {
             return Err(TreeEngineError::OperationNotAllowed(format!("Maximum depth {} reached", tree_state.config.max_depth)));
         }
 
-        // Validate probability sum constraint - existing siblings + new probability must not exceed parent's probability
-        if let Some(parent_probability) = Some(parent_node.probability) {
+        // Validate probability sum constraint - existing siblings + new probability must not exceed parent's probability.
+        // Arguments for and against a claim are not exclusive, so argument-mode trees and gate inputs skip it.
+        if Self::limits_children(&tree_state.config, &parent_node) {
+            let parent_probability = parent_node.probability;
             // Get all existing children of the parent
             let existing_children: Vec<TreeNode> = if !parent_node.children.is_empty() {
                 let mut children = Vec::new();
@@ -331,8 +641,17 @@ impl TreeEngineService {
                 Vec::new()
             };
 
-            // Calculate sum of existing siblings' probabilities
-            let existing_probability_sum: f64 = existing_children.iter().map(|child| child.probability).sum();
+            // Calculate sum of existing siblings' probabilities, including cross-linked children.
+            // A residual child only holds what the others leave, so it gives way to the new leaf.
+            let linked_probability_sum: f64 = self.list_cross_links().await?.iter()
+                .filter(|link| link.parent == parent_record_id)
+                .map(|link| link.probability)
+                .sum();
+            let existing_probability_sum: f64 = existing_children.iter()
+                .filter(|child| !child.residual)
+                .map(|child| child.probability)
+                .sum::<f64>()
+                + linked_probability_sum;
 
             // Check if adding new probability would exceed parent's probability
             let total_probability_after_addition = existing_probability_sum + probability;
@@ -349,21 +668,496 @@ impl TreeEngineService {
             }
         }
 
-        let new_leaf = TreeNode::new_leaf(premise, reasoning, probability, confidence, parent_record_id.clone(), parent_node.depth + 1);
-        let created_leaf: Option<TreeNode> = self.db.create("node").content(new_leaf).await?;
-        let leaf_node = created_leaf.ok_or_else(|| TreeEngineError::DatabaseError("Failed to create leaf node".to_string()))?;
-        let leaf_id = leaf_node.id.as_ref().unwrap().clone();
+        let leaf_id = RecordId::from(("node", uuid::Uuid::new_v4().simple().to_string()));
+        let mut new_leaf = TreeNode::new_leaf(premise, reasoning, probability, confidence, parent_record_id.clone(), parent_node.depth + 1);
+        new_leaf.id = Some(leaf_id.clone());
+        new_leaf.friendly_id = self.allocate_friendly_id().await?;
 
-        // Update parent node to include new child
+        // The leaf, its parent and any residual sibling it shrinks are written together
         let mut updated_parent = parent_node;
         updated_parent.add_child(leaf_id.clone());
-        let _: Option<TreeNode> = self.db.update(&parent_record_id).content(updated_parent).await?;
-
-        // No need for explicit relations since we use the children field
+        self.store_with_residuals(vec![new_leaf, updated_parent]).await?;
 
+        self.publish_change().await;
         Ok(leaf_id.to_string())
     }
 
+    /// Adds a whole nested subtree under an existing node in one atomic write.
+    ///
+    /// Every node is validated first (fields, depth, branch limit and sibling sums,
+    /// including the parent's existing children); if anything fails, nothing is written
+    /// and all violations are reported together. The nodes and the parent update are
+    /// then stored in a single transaction. The cursor does not move.
+    ///
+    /// # Arguments
+    /// * `parent_id` - Friendly alias, record id or unambiguous prefix of the parent node
+    /// * `nodes` - Top-level nodes of the subtree, each with its nested children
+    ///
+    /// # Returns
+    /// * `Ok(SubtreeInsertion)` - The new id of every submitted node, keyed by its position
+    /// * `Err(TreeEngineError::InvalidInput)` - Listing every violated constraint
+    /// * `Err(TreeEngineError::NotFound)` - If the parent doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If the transaction fails
+    pub async fn add_subtree(&mut self, parent_id: String, nodes: Vec<SubtreeNode>) -> TreeResult<SubtreeInsertion> {
+        let tree_state = self.get_current_tree_state().await?;
+        let parent_record_id = self.resolve_node_ref(&parent_id).await?;
+        let parent: Option<TreeNode> = self.db.select(&parent_record_id).await?;
+        let mut parent = parent.ok_or_else(|| TreeEngineError::NotFound(parent_id.clone()))?;
+
+        let anchor = SubtreeAnchor {
+            probability: parent.probability,
+            depth: parent.depth,
+            existing_children: parent.children.len(),
+            allocated_probability: self.allocated_probability(&parent).await?,
+            gated: parent.gate.is_some(),
+        };
+
+        let errors = SubtreeNode::validate_all(&nodes, &anchor, &tree_state.config);
+        if !errors.is_empty() {
+            return Err(TreeEngineError::InvalidInput("nodes".to_string(), errors.join("; ")));
+        }
+
+        let mut aliases = Vec::with_capacity(SubtreeNode::count(&nodes));
+        for _ in 0..SubtreeNode::count(&nodes) {
+            aliases.push(self.allocate_friendly_id().await?);
+        }
+        let mut aliases = aliases.into_iter();
+        let mut new_nodes = Vec::new();
+        let mut inserted = Vec::new();
+        let top_level_ids = Self::build_subtree(&nodes, &parent_record_id, parent.depth + 1, "", &mut aliases, &mut new_nodes, &mut inserted);
+
+        for child_id in top_level_ids {
+            parent.add_child(child_id);
+        }
+        parent.expand_to_branch();
+        let parent_alias = parent.display_id();
+        new_nodes.push(parent);
+        self.store_with_residuals(new_nodes).await?;
+
+        self.publish_change().await;
+        Ok(SubtreeInsertion { parent_id: parent_alias, nodes: inserted })
+    }
+
+    /// Probability already given to a node's children, including cross-linked ones.
+    ///
+    /// A residual child is left out: it shrinks to make room for new siblings.
+    async fn allocated_probability(&self, parent: &TreeNode) -> TreeResult<f64> {
+        let mut allocated: f64 = self.list_cross_links().await?.iter()
+            .filter(|link| Some(&link.parent) == parent.id.as_ref())
+            .map(|link| link.probability)
+            .sum();
+        for child_id in &parent.children {
+            if let Some(child) = self.db.select::<Option<TreeNode>>(child_id).await?
+                && !child.residual
+            {
+                allocated += child.probability;
+            }
+        }
+        Ok(allocated)
+    }
+
+    /// Resizes the residual child of every exhaustive node to the probability its other
+    /// active children, cross-linked ones included, leave; never below zero.
+    ///
+    /// Returns the residuals whose probability changed.
+    fn absorb_residuals(nodes: &mut [TreeNode], links: &[CrossLink]) -> Vec<TreeNode> {
+        let index: HashMap<RecordId, usize> = nodes.iter().enumerate()
+            .filter_map(|(i, node)| node.id.clone().map(|id| (id, i)))
+            .collect();
+        // Parents first, so a residual that is itself exhaustive passes its new size down
+        let mut parents: Vec<usize> = (0..nodes.len())
+            .filter(|&i| nodes[i].exhaustive && !nodes[i].is_invalidated)
+            .collect();
+        parents.sort_by_key(|&i| nodes[i].depth);
+
+        let mut resized = Vec::new();
+        for i in parents {
+            let mut residual = None;
+            let mut allocated: f64 = links.iter()
+                .filter(|link| Some(&link.parent) == nodes[i].id.as_ref())
+                .map(|link| link.probability)
+                .sum();
+            for child in nodes[i].children.iter().filter_map(|id| index.get(id).copied()) {
+                if nodes[child].residual {
+                    residual = residual.or(Some(child));
+                } else if !nodes[child].is_invalidated {
+                    allocated += nodes[child].probability;
+                }
+            }
+            let Some(residual) = residual else {
+                continue;
+            };
+            let leftover = (nodes[i].probability - allocated).max(0.0);
+            if (nodes[residual].probability - leftover).abs() > f64::EPSILON {
+                nodes[residual].probability = leftover;
+                resized.push(residual);
+            }
+        }
+        resized.into_iter().map(|i| nodes[i].clone()).collect()
+    }
+
+    /// Stores `nodes` together with every residual child their changes resize, in one transaction.
+    async fn store_with_residuals(&self, mut nodes: Vec<TreeNode>) -> TreeResult<()> {
+        let mut all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        for node in &nodes {
+            match all_nodes.iter_mut().find(|current| current.id == node.id) {
+                Some(current) => *current = node.clone(),
+                None => all_nodes.push(node.clone()),
+            }
+        }
+        let links = self.list_cross_links().await?;
+        for residual in Self::absorb_residuals(&mut all_nodes, &links) {
+            match nodes.iter_mut().find(|node| node.id == residual.id) {
+                Some(node) => *node = residual,
+                None => nodes.push(residual),
+            }
+        }
+        self.store_nodes(nodes).await
+    }
+
+    /// Stores the new size of every residual child after a cross-link changed its siblings.
+    async fn sync_residuals(&self) -> TreeResult<()> {
+        let mut nodes: Vec<TreeNode> = self.db.select("node").await?;
+        if !nodes.iter().any(|node| node.exhaustive) {
+            return Ok(());
+        }
+        let links = self.list_cross_links().await?;
+        let resized = Self::absorb_residuals(&mut nodes, &links);
+        if !resized.is_empty() {
+            self.store_nodes(resized).await?;
+        }
+        Ok(())
+    }
+
+    /// Creates or replaces a set of nodes in one transaction, so a failure leaves the tree untouched.
+    async fn store_nodes(&self, nodes: Vec<TreeNode>) -> TreeResult<()> {
+        self.db
+            .query("BEGIN TRANSACTION; FOR $node IN $nodes { UPSERT $node.id CONTENT $node; }; COMMIT TRANSACTION;")
+            .bind(("nodes", nodes))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Writes rewritten versions of existing nodes in one transaction, unless `dry_run`.
+    ///
+    /// Either way, returns the root-to-leaf paths whose probability or rank the rewrite changes.
+    async fn rewrite_nodes(&self, mut nodes: Vec<TreeNode>, dry_run: bool) -> TreeResult<Vec<PathRankChange>> {
+        let before = self.current_snapshot().await?;
+        let mut after = before.clone();
+        for node in &nodes {
+            if let Some(current) = after.nodes.iter_mut().find(|current| current.id == node.id) {
+                *current = node.clone();
+            }
+        }
+        nodes.extend(Self::absorb_residuals(&mut after.nodes, &after.cross_links));
+        let affected_paths = Self::path_changes(&before, &after);
+
+        if !dry_run {
+            if !nodes.is_empty() {
+                self.store_nodes(nodes).await?;
+            }
+            self.publish_change().await;
+        }
+        Ok(affected_paths)
+    }
+
+    /// Turns validated subtree nodes into tree nodes with pre-assigned record ids.
+    ///
+    /// Returns the ids of this level, so the caller can list them as its children.
+    fn build_subtree(
+        nodes: &[SubtreeNode],
+        parent_id: &RecordId,
+        depth: i64,
+        prefix: &str,
+        aliases: &mut impl Iterator<Item = String>,
+        new_nodes: &mut Vec<TreeNode>,
+        inserted: &mut Vec<InsertedNode>,
+    ) -> Vec<RecordId> {
+        nodes.iter().enumerate()
+            .map(|(i, spec)| {
+                let id = RecordId::from(("node", uuid::Uuid::new_v4().simple().to_string()));
+                let path = format!("{}{}", prefix, i + 1);
+                let mut node = TreeNode::new_leaf(spec.premise.clone(), spec.reasoning.clone(), spec.probability, spec.confidence, parent_id.clone(), depth);
+                node.id = Some(id.clone());
+                node.friendly_id = aliases.next().unwrap_or_default();
+                inserted.push(InsertedNode {
+                    path: path.clone(),
+                    node_id: id.to_string(),
+                    friendly_id: node.friendly_id.clone(),
+                    premise: node.premise.clone(),
+                    probability: node.probability,
+                    depth,
+                });
+
+                // Reserve this node's slot so it precedes its descendants in the insert
+                let slot = new_nodes.len();
+                new_nodes.push(node);
+                let children = Self::build_subtree(&spec.children, &id, depth + 1, &format!("{}.", path), aliases, new_nodes, inserted);
+                let node = &mut new_nodes[slot];
+                if !children.is_empty() {
+                    node.children = children;
+                    node.expand_to_branch();
+                }
+                id
+            })
+            .collect()
+    }
+
+    /// Moves a node and all its descendants under a different parent.
+    ///
+    /// The old and new parents' `children` are updated and every depth in the subtree
+    /// is recomputed. The destination must respect `max_depth`, `branch_limit` and the
+    /// sibling-sum constraint, and it may not lie inside the moved subtree or be reachable
+    /// from it through cross-links, since that would create a cycle. Friendly aliases
+    /// and the cursor are unchanged.
+    ///
+    /// # Arguments
+    /// * `node_id` - Friendly alias, record id or unambiguous prefix of the subtree root
+    /// * `new_parent_id` - Friendly alias, record id or unambiguous prefix of the destination
+    ///
+    /// # Returns
+    /// * `Ok(SubtreeRelocation)` - The moved nodes with their new depths
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - For the root, a cycle or a violated limit
+    /// * `Err(TreeEngineError::NotFound)` - If either node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If the transaction fails
+    pub async fn move_subtree(&mut self, node_id: String, new_parent_id: String) -> TreeResult<SubtreeRelocation> {
+        let tree_state = self.get_current_tree_state().await?;
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let parent_record_id = self.resolve_node_ref(&new_parent_id).await?;
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let edges = Self::scenario_edges(&all_nodes, &links);
+        let nodes_by_id: HashMap<RecordId, TreeNode> = all_nodes.into_iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+
+        let node = nodes_by_id.get(&node_record_id).ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+        let old_parent_id = node.parent_id.clone()
+            .ok_or_else(|| TreeEngineError::OperationNotAllowed("The root node cannot be moved".to_string()))?;
+        if old_parent_id == parent_record_id {
+            return Err(TreeEngineError::OperationNotAllowed(format!("{} is already a child of {}", node_id, new_parent_id)));
+        }
+        let subtree = Self::subtree_ids(&node_record_id, &nodes_by_id);
+        if subtree.contains(&parent_record_id) || Self::reaches(&edges, &node_record_id, &parent_record_id) {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Moving {} under {} would create a cycle: the destination is inside the subtree", node_id, new_parent_id)));
+        }
+
+        let mut new_parent = nodes_by_id.get(&parent_record_id).cloned()
+            .ok_or_else(|| TreeEngineError::NotFound(new_parent_id.clone()))?;
+        Self::check_attachment(&tree_state.config, &new_parent, self.allocated_probability(&new_parent).await?,
+            node.probability, Self::subtree_height(&subtree, &nodes_by_id))?;
+
+        let delta = new_parent.depth + 1 - node.depth;
+        let mut changed: Vec<TreeNode> = subtree.iter()
+            .map(|id| {
+                let mut moved = nodes_by_id[id].clone();
+                moved.depth += delta;
+                if *id == node_record_id {
+                    moved.parent_id = Some(parent_record_id.clone());
+                    // A residual belongs to its exhaustive parent; elsewhere it is an ordinary node
+                    moved.residual = false;
+                }
+                moved
+            })
+            .collect();
+        let relocated = changed.iter()
+            .map(|moved| RelocatedNode {
+                source_id: moved.display_id(),
+                friendly_id: moved.display_id(),
+                node_id: moved.id.as_ref().map(|id| id.to_string()).unwrap_or_default(),
+                premise: moved.premise.clone(),
+                depth: moved.depth,
+            })
+            .collect();
+
+        let mut old_parent = nodes_by_id.get(&old_parent_id).cloned()
+            .ok_or_else(|| TreeEngineError::NotFound(old_parent_id.to_string()))?;
+        old_parent.remove_child(&node_record_id);
+        if old_parent.children.is_empty() && old_parent.node_type == NodeType::Branch {
+            old_parent.node_type = NodeType::Leaf;
+        }
+        new_parent.add_child(node_record_id);
+        new_parent.expand_to_branch();
+
+        let old_parent_alias = old_parent.display_id();
+        let new_parent_alias = new_parent.display_id();
+        changed.push(old_parent);
+        changed.push(new_parent);
+        self.store_with_residuals(changed).await?;
+
+        self.publish_change().await;
+        Ok(SubtreeRelocation {
+            copied: false,
+            new_parent_id: new_parent_alias,
+            old_parent_id: Some(old_parent_alias),
+            nodes: relocated,
+        })
+    }
+
+    /// Copies a node and all its descendants under another parent.
+    ///
+    /// Copies get new record ids and aliases, keep every other field (evidence,
+    /// propositions, time horizons, invalidation) and record their source in
+    /// `metadata["copied_from"]`. Cross-links are not copied. The destination must
+    /// respect `max_depth`, `branch_limit` and the sibling-sum constraint; it may lie
+    /// inside the copied subtree, since the copy is a separate set of nodes.
+    ///
+    /// # Arguments
+    /// * `node_id` - Friendly alias, record id or unambiguous prefix of the subtree root
+    /// * `new_parent_id` - Friendly alias, record id or unambiguous prefix of the destination
+    ///
+    /// # Returns
+    /// * `Ok(SubtreeRelocation)` - Each source node with the alias and record id of its copy
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If a destination limit would be violated
+    /// * `Err(TreeEngineError::NotFound)` - If either node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If the transaction fails
+    pub async fn copy_subtree(&mut self, node_id: String, new_parent_id: String) -> TreeResult<SubtreeRelocation> {
+        let tree_state = self.get_current_tree_state().await?;
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let parent_record_id = self.resolve_node_ref(&new_parent_id).await?;
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, TreeNode> = all_nodes.into_iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+
+        let source = nodes_by_id.get(&node_record_id).ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+        let subtree = Self::subtree_ids(&node_record_id, &nodes_by_id);
+        let mut new_parent = nodes_by_id.get(&parent_record_id).cloned()
+            .ok_or_else(|| TreeEngineError::NotFound(new_parent_id.clone()))?;
+        Self::check_attachment(&tree_state.config, &new_parent, self.allocated_probability(&new_parent).await?,
+            source.probability, Self::subtree_height(&subtree, &nodes_by_id))?;
+
+        let copy_ids: HashMap<RecordId, RecordId> = subtree.iter()
+            .map(|id| (id.clone(), RecordId::from(("node", uuid::Uuid::new_v4().simple().to_string()))))
+            .collect();
+        let delta = new_parent.depth + 1 - source.depth;
+        let mut copies = Vec::with_capacity(subtree.len() + 1);
+        let mut relocated = Vec::with_capacity(subtree.len());
+        for id in &subtree {
+            let original = &nodes_by_id[id];
+            let mut copy = original.clone();
+            copy.id = Some(copy_ids[id].clone());
+            copy.friendly_id = self.allocate_friendly_id().await?;
+            copy.parent_id = if *id == node_record_id {
+                Some(parent_record_id.clone())
+            } else {
+                original.parent_id.as_ref().and_then(|parent_id| copy_ids.get(parent_id).cloned())
+            };
+            copy.children = original.children.iter().filter_map(|child_id| copy_ids.get(child_id).cloned()).collect();
+            copy.depth += delta;
+            copy.created_at = chrono::Utc::now();
+            if *id == node_record_id {
+                copy.residual = false;
+            }
+            if copy.node_type == NodeType::Root {
+                copy.node_type = if copy.children.is_empty() { NodeType::Leaf } else { NodeType::Branch };
+            }
+            copy.metadata.insert("copied_from".to_string(), original.display_id());
+
+            relocated.push(RelocatedNode {
+                source_id: original.display_id(),
+                friendly_id: copy.friendly_id.clone(),
+                node_id: copy_ids[id].to_string(),
+                premise: copy.premise.clone(),
+                depth: copy.depth,
+            });
+            copies.push(copy);
+        }
+
+        new_parent.add_child(copy_ids[&node_record_id].clone());
+        new_parent.expand_to_branch();
+        let new_parent_alias = new_parent.display_id();
+        copies.push(new_parent);
+        self.store_with_residuals(copies).await?;
+
+        self.publish_change().await;
+        Ok(SubtreeRelocation {
+            copied: true,
+            new_parent_id: new_parent_alias,
+            old_parent_id: None,
+            nodes: relocated,
+        })
+    }
+
+    /// Ids of `root` and all its descendants along primary edges, in pre-order.
+    fn subtree_ids(root: &RecordId, nodes_by_id: &HashMap<RecordId, TreeNode>) -> Vec<RecordId> {
+        let mut ids = Vec::new();
+        let mut stack = vec![root.clone()];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = nodes_by_id.get(&id) {
+                stack.extend(node.children.iter().rev().cloned());
+            }
+            ids.push(id);
+        }
+        ids
+    }
+
+    /// Ids of nodes automated operations must leave untouched: locked nodes and every node
+    /// of a frozen subtree. Residual children are not locked; callers skip them on their own.
+    fn locked_ids(nodes: &[TreeNode]) -> HashSet<RecordId> {
+        let nodes_by_id: HashMap<&RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.as_ref().map(|id| (id, node)))
+            .collect();
+
+        nodes.iter()
+            .filter(|node| {
+                if node.locked {
+                    return true;
+                }
+                // Walk up primary parents; the step bound guards against corrupted cycles
+                let mut current = Some(*node);
+                for _ in 0..=nodes.len() {
+                    match current {
+                        Some(ancestor) if ancestor.frozen => return true,
+                        Some(ancestor) => current = ancestor.parent_id.as_ref().and_then(|id| nodes_by_id.get(id).copied()),
+                        None => break,
+                    }
+                }
+                false
+            })
+            .filter_map(|node| node.id.clone())
+            .collect()
+    }
+
+    /// Levels below the first node of `subtree`.
+    fn subtree_height(subtree: &[RecordId], nodes_by_id: &HashMap<RecordId, TreeNode>) -> i64 {
+        let depths: Vec<i64> = subtree.iter().filter_map(|id| nodes_by_id.get(id)).map(|node| node.depth).collect();
+        match (depths.first(), depths.iter().max()) {
+            (Some(root), Some(deepest)) => deepest - root,
+            _ => 0,
+        }
+    }
+
+    /// Whether the children of `parent` must fit within its probability. Arguments are not
+    /// exclusive outcomes, and the inputs of a fault-tree gate are independent events.
+    fn limits_children(config: &TreeConfig, parent: &TreeNode) -> bool {
+        config.enforces_sibling_sums() && parent.gate.is_none()
+    }
+
+    /// Checks that a subtree of `height` levels whose root has `probability` fits under `parent`.
+    fn check_attachment(config: &TreeConfig, parent: &TreeNode, allocated_probability: f64, probability: f64, height: i64) -> TreeResult<()> {
+        if parent.is_invalidated {
+            return Err(TreeEngineError::OperationNotAllowed(format!("Cannot attach to pruned node {}", parent.display_id())));
+        }
+        if parent.depth + 1 + height >= config.max_depth {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Maximum depth {} reached: the subtree needs {} levels below {} at depth {}",
+                config.max_depth, height + 1, parent.display_id(), parent.depth)));
+        }
+        if parent.children.len() + 1 > config.branch_limit {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Branch limit {} reached: {} already has {} children", config.branch_limit, parent.display_id(), parent.children.len())));
+        }
+        if Self::limits_children(config, parent) && allocated_probability + probability > parent.probability + 0.001 {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Probability constraint violation: attaching probability {:.3} under {} would result in total child probability {:.3}, which exceeds parent probability {:.3}",
+                probability, parent.display_id(), allocated_probability + probability, parent.probability)));
+        }
+        Ok(())
+    }
+
     /// Expands a leaf node by converting it to a branch node and adding child nodes.
     ///
     /// This method transforms a leaf node into a branch node by updating its reasoning
@@ -371,12 +1165,12 @@ impl TreeEngineService {
     /// updated reasoning. The expansion follows the tree's complexity configuration.
     ///
     /// # Arguments
-    /// * `node_id` - ID of the leaf node to expand (must be a valid leaf node)
+    /// * `node_id` - Friendly alias, record id or unambiguous prefix of the leaf node to expand
     /// * `new_reasoning` - Updated reasoning for the expanded node (non-empty)
     ///
     /// # Returns
     /// * `Ok(String)` - Confirmation message indicating successful expansion
-    /// * `Err(TreeEngineError::InvalidInput)` - If node_id format is invalid or reasoning is empty
+    /// * `Err(TreeEngineError::InvalidInput)` - If node_id is ambiguous or reasoning is empty
     /// * `Err(TreeEngineError::NotFound)` - If the specified node doesn't exist
     /// * `Err(TreeEngineError::OperationNotAllowed)` - If the node is not a leaf
     /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
@@ -414,8 +1208,7 @@ impl TreeEngineService {
 
         let tree_state = self.get_current_tree_state().await?;
 
-        let node_record_id: RecordId = node_id.parse()
-            .map_err(|_| TreeEngineError::InvalidInput("node_id".to_string(), "Invalid node ID format".to_string()))?;
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
 
         let mut node: Option<TreeNode> = self.db.select(&node_record_id).await?;
         let mut node = node.take().ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
@@ -438,6 +1231,7 @@ impl TreeEngineService {
 
         // Los leaf nodes se obtienen dinámicamente desde la base de datos
 
+        self.publish_change().await;
         Ok(node_record_id.to_string())
     }
 
@@ -448,11 +1242,11 @@ impl TreeEngineService {
     /// The navigation state is persisted in the tree's metadata.
     ///
     /// # Arguments
-    /// * `node_id` - The ID of the target node to navigate to
+    /// * `node_id` - Friendly alias, record id or unambiguous prefix of the target node
     ///
     /// # Returns
     /// * `Ok(())` - If navigation was successful
-    /// * `Err(TreeEngineError::InvalidInput)` - If node_id format is invalid
+    /// * `Err(TreeEngineError::InvalidInput)` - If node_id is ambiguous
     /// * `Err(TreeEngineError::NotFound)` - If the specified node doesn't exist
     /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
     ///
@@ -475,8 +1269,7 @@ impl TreeEngineService {
     /// # }
     /// ```
     pub async fn navigate_to(&mut self, node_id: String) -> TreeResult<()> {
-        let node_record_id: RecordId = node_id.parse()
-            .map_err(|_| TreeEngineError::InvalidInput("node_id".to_string(), "Invalid node ID format".to_string()))?;
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
 
         // Verify node exists
         let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
@@ -492,6 +1285,7 @@ impl TreeEngineService {
         // Set cursor to the navigated node
         self.cursor_node_id = Some(node_record_id.to_string());
 
+        self.publish_change().await;
         Ok(())
     }
 
@@ -506,18 +1300,22 @@ impl TreeEngineService {
     ///   - 0.0: Very conservative, removes only extremely low probability branches
     ///   - 0.5: Balanced pruning approach
     ///   - 1.0: Aggressive pruning, keeps only the highest probability branches
+    /// * `dry_run` - Compute the result without writing anything
     ///
     /// # Returns
     /// * `Ok(PruningResult)` - Detailed results including:
     ///   - List of removed and preserved nodes
     ///   - Pruning statistics (counts, thresholds, etc.)
     ///   - Manual override information
+    ///   - Root-to-leaf paths whose probability or rank the pruning changes
     /// * `Err(TreeEngineError::InvalidInput)` - If aggressiveness is not in [0.0, 1.0]
     /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
     ///
     /// # Pruning Logic
     /// - Calculates probability threshold based on aggressiveness and tree statistics
     /// - Preserves root node and critical path nodes regardless of probability
+    /// - Preserves locked nodes and frozen subtrees, reporting them in `skipped_locked`
+    /// - Leaves residual children to their exhaustive parent, reporting them in `skipped_residual`
     /// - Maintains parent-child relationship integrity
     /// - Updates tree structure after pruning operations
     ///
@@ -530,14 +1328,14 @@ impl TreeEngineService {
     /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
     /// # let mut service = TreeEngineService::new(Arc::new(db));
     /// # service.create_tree("Test premise".to_string(), 5).await?;
-    /// let result = service.prune_tree(0.7).await?;
+    /// let result = service.prune_tree(0.7, false).await?;
     /// println!("Pruned {} nodes, preserved {} nodes",
     ///          result.statistics.removed_count,
     ///          result.statistics.preserved_count);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn prune_tree(&mut self, aggressiveness: f64) -> TreeResult<PruningResult> {
+    pub async fn prune_tree(&mut self, aggressiveness: f64, dry_run: bool) -> TreeResult<PruningResult> {
         if !(0.0..=1.0).contains(&aggressiveness) {
             return Err(TreeEngineError::InvalidInput("aggressiveness".to_string(), "Aggressiveness must be between 0.0 and 1.0".to_string()));
         }
@@ -552,6 +1350,10 @@ impl TreeEngineService {
                 nodes_removed: vec![],
                 nodes_preserved: vec![],
                 manual_overrides: vec![],
+                skipped_locked: vec![],
+                skipped_residual: vec![],
+                dry_run,
+                affected_paths: vec![],
                 statistics: PruningStatistics {
                     original_count: 0,
                     removed_count: 0,
@@ -580,6 +1382,10 @@ impl TreeEngineService {
 
         let mut nodes_to_remove = Vec::new();
         let mut nodes_preserved = Vec::new();
+        let mut skipped_locked = Vec::new();
+        let mut skipped_residual = Vec::new();
+        let mut invalidated = Vec::new();
+        let locked = Self::locked_ids(&all_nodes);
 
         for node in &all_nodes {
             if let Some(root_id) = &tree_state.config.root_id {
@@ -589,19 +1395,25 @@ impl TreeEngineService {
                 }
             }
 
-            if node.probability < threshold {
+            if node.probability < threshold && node.residual {
+                skipped_residual.push(node.id.as_ref().unwrap().to_string());
+                nodes_preserved.push(node.id.as_ref().unwrap().to_string());
+            } else if node.probability < threshold && locked.contains(node.id.as_ref().unwrap()) {
+                skipped_locked.push(node.id.as_ref().unwrap().to_string());
+                nodes_preserved.push(node.id.as_ref().unwrap().to_string());
+            } else if node.probability < threshold {
                 let mut node_to_invalidate = node.clone();
                 node_to_invalidate.invalidate();
-                let node_id = node_to_invalidate.id.as_ref().unwrap().clone();
-                let _: Option<TreeNode> = self.db.update(&node_id).content(node_to_invalidate).await?;
-                nodes_to_remove.push(node_id.to_string());
+                nodes_to_remove.push(node.id.as_ref().unwrap().to_string());
+                invalidated.push(node_to_invalidate);
             } else {
                 nodes_preserved.push(node.id.as_ref().unwrap().to_string());
             }
         }
 
-        // Los nodos removidos ya están marcados como invalidated en la base de datos
+        // Los nodos removidos quedan marcados como invalidated en la base de datos
         // No necesitamos actualizar el TreeState manualmente
+        let affected_paths = self.rewrite_nodes(invalidated, dry_run).await?;
 
         let removed_count = nodes_to_remove.len();
         let preserved_count = nodes_preserved.len();
@@ -610,6 +1422,10 @@ impl TreeEngineService {
             nodes_removed: nodes_to_remove,
             nodes_preserved,
             manual_overrides: vec![],
+            skipped_locked,
+            skipped_residual,
+            dry_run,
+            affected_paths,
             statistics: PruningStatistics {
                 original_count: all_nodes.len(),
                 removed_count,
@@ -641,6 +1457,7 @@ impl TreeEngineService {
     /// # Parameters
     ///
     /// * `max_leafs` - Maximum number of leaf nodes to preserve (must be > 0)
+    /// * `dry_run` - Compute the result without writing anything
     ///
     /// # Returns
     ///
@@ -648,6 +1465,7 @@ impl TreeEngineService {
     ///   - `nodes_removed`: IDs of invalidated leaf nodes
     ///   - `nodes_preserved`: IDs of kept highest-probability leafs
     ///   - `statistics`: Original count, removal count, preservation metrics
+    ///   - `affected_paths`: Root-to-leaf paths whose probability or rank changed
     ///
     /// # Errors
     ///
@@ -675,7 +1493,7 @@ impl TreeEngineService {
     /// service.add_leaf("Option D".to_string(), "Fourth reasoning".to_string(), 0.9, 4).await?;
     ///
     /// // Prune to keep only top 2 leafs
-    /// let result = service.prune_leafs(2).await?;
+    /// let result = service.prune_leafs(2, false).await?;
     ///
     /// // Result preserves Options D (0.9) and A (0.8)
     /// // Removes Options B (0.6) and C (0.4)
@@ -698,13 +1516,17 @@ impl TreeEngineService {
     /// - [`prune_tree()`] - Probability-threshold based pruning with configurable aggressiveness
     /// - [`balance_leafs()`] - Probability adjustment without node removal
     /// - [`validate_coherence()`] - Analysis integrity verification after pruning
-    pub async fn prune_leafs(&mut self, max_leafs: usize) -> TreeResult<PruningResult> {
+    pub async fn prune_leafs(&mut self, max_leafs: usize, dry_run: bool) -> TreeResult<PruningResult> {
         if max_leafs == 0 {
             return Err(TreeEngineError::InvalidInput("max_leafs".to_string(), "max_leafs must be greater than 0".to_string()));
         }
 
-        // Get leaf nodes using relational query
-        let leaf_nodes = self.get_leaf_nodes().await?;
+        // Get leaf nodes using relational query. Residual leaves are sized by their exhaustive
+        // parent, so they neither take a slot nor get removed
+        let (residual_leafs, leaf_nodes): (Vec<TreeNode>, Vec<TreeNode>) = self.get_leaf_nodes().await?
+            .into_iter()
+            .partition(|node| node.residual);
+        let skipped_residual: Vec<String> = residual_leafs.iter().map(|node| node.id.as_ref().unwrap().to_string()).collect();
         let original_count = leaf_nodes.len();
 
         if leaf_nodes.len() <= max_leafs {
@@ -712,6 +1534,10 @@ impl TreeEngineService {
                 nodes_removed: vec![],
                 nodes_preserved: leaf_nodes.iter().map(|n| n.id.as_ref().unwrap().to_string()).collect(),
                 manual_overrides: vec![],
+                skipped_locked: vec![],
+                skipped_residual,
+                dry_run,
+                affected_paths: vec![],
                 statistics: PruningStatistics {
                     original_count,
                     removed_count: 0,
@@ -728,28 +1554,50 @@ impl TreeEngineService {
         let mut sorted_leafs = leaf_nodes;
         sorted_leafs.sort_by(|a, b| b.probability.partial_cmp(&a.probability).unwrap());
 
-        let nodes_to_keep = &sorted_leafs[..max_leafs];
-        let nodes_to_remove = &sorted_leafs[max_leafs..];
+        // Locked leaves are always kept; the remaining slots go to the most probable others
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let locked = Self::locked_ids(&all_nodes);
+        let skipped_locked: Vec<String> = sorted_leafs[max_leafs..].iter()
+            .filter(|node| locked.contains(node.id.as_ref().unwrap()))
+            .map(|node| node.id.as_ref().unwrap().to_string())
+            .collect();
+        let (locked_leafs, unlocked_leafs): (Vec<TreeNode>, Vec<TreeNode>) = sorted_leafs.into_iter()
+            .partition(|node| locked.contains(node.id.as_ref().unwrap()));
+        let keep_unlocked = max_leafs.saturating_sub(locked_leafs.len()).min(unlocked_leafs.len());
+
+        let nodes_to_keep: Vec<&TreeNode> = locked_leafs.iter().chain(&unlocked_leafs[..keep_unlocked]).collect();
+        let nodes_to_remove = &unlocked_leafs[keep_unlocked..];
 
         let mut removed_ids = Vec::new();
+        let mut invalidated = Vec::new();
         for node in nodes_to_remove {
             let mut node_to_invalidate = node.clone();
             node_to_invalidate.invalidate();
-            let node_id = node_to_invalidate.id.as_ref().unwrap().clone();
-            let _: Option<TreeNode> = self.db.update(&node_id).content(node_to_invalidate).await?;
-            removed_ids.push(node_id.to_string());
+            removed_ids.push(node.id.as_ref().unwrap().to_string());
+            invalidated.push(node_to_invalidate);
         }
 
-        // Los nodos removidos ya están marcados como invalidated en la base de datos
+        // Los nodos removidos quedan marcados como invalidated en la base de datos
         // No necesitamos actualizar el TreeState manualmente
+        let affected_paths = self.rewrite_nodes(invalidated, dry_run).await?;
 
         let removed_count = nodes_to_remove.len();
         let preserved_count = nodes_to_keep.len();
+        let method_explanation = if locked_leafs.is_empty() {
+            format!("Leaf pruning: kept top {} highest probability nodes", preserved_count)
+        } else {
+            format!("Leaf pruning: kept {} locked nodes and the top {} highest probability others",
+                locked_leafs.len(), keep_unlocked)
+        };
 
         Ok(PruningResult {
             nodes_removed: removed_ids,
             nodes_preserved: nodes_to_keep.iter().map(|n| n.id.as_ref().unwrap().to_string()).collect(),
             manual_overrides: vec![],
+            skipped_locked,
+            skipped_residual,
+            dry_run,
+            affected_paths,
             statistics: PruningStatistics {
                 original_count,
                 removed_count,
@@ -757,7 +1605,7 @@ impl TreeEngineService {
                 aggressiveness_level: 0.0,
                 cost_complexity_alpha: 0.0,
                 effective_threshold: 0.0,
-                method_explanation: format!("Leaf pruning: kept top {} highest probability nodes", preserved_count),
+                method_explanation,
             },
         })
     }
@@ -767,7 +1615,8 @@ impl TreeEngineService {
     /// This method adjusts probability distributions across leaf nodes without removing
     /// any nodes, applying different balancing algorithms based on the uncertainty context.
     /// It's designed to handle cognitive biases and uncertainty scenarios that can distort
-    /// probability assessments in decision trees.
+    /// probability assessments in decision trees. Leaves are smoothed among their unlocked
+    /// siblings only, so every parent's children keep their combined probability.
     ///
     /// # Balancing Strategies
     ///
@@ -792,6 +1641,7 @@ impl TreeEngineService {
     /// # Parameters
     ///
     /// * `uncertainty_type` - The specific uncertainty scenario requiring probability adjustment
+    /// * `dry_run` - Compute the result without writing anything
     ///
     /// # Returns
     ///
@@ -800,6 +1650,7 @@ impl TreeEngineService {
     ///   - `uncertainty_type`: The strategy used for balancing
     ///   - `original_probabilities`: Pre-balancing probability values by node ID
     ///   - `new_probabilities`: Post-balancing probability values by node ID
+    ///   - `affected_paths`: Root-to-leaf paths whose probability or rank changed
     ///
     /// # Errors
     ///
@@ -826,7 +1677,7 @@ impl TreeEngineService {
     /// service.add_leaf("Low confidence".to_string(), "Weak evidence".to_string(), 0.2, 3).await?;
     ///
     /// // Balance for insufficient data scenario
-    /// let result = service.balance_leafs(UncertaintyType::InsufficientData).await?;
+    /// let result = service.balance_leafs(UncertaintyType::InsufficientData, false).await?;
     ///
     /// // High confidence (0.95) gets moderated down
     /// // Other probabilities remain unchanged (below threshold)
@@ -855,8 +1706,20 @@ impl TreeEngineService {
     /// - [`prune_leafs()`] - Remove nodes rather than adjust probabilities
     /// - [`prune_tree()`] - Threshold-based node removal with aggressiveness control
     /// - [`validate_coherence()`] - Verify probability consistency after balancing
-    pub async fn balance_leafs(&mut self, uncertainty_type: UncertaintyType) -> TreeResult<BalancingResult> {
-        let leaf_nodes = self.get_leaf_nodes().await?;
+    pub async fn balance_leafs(&mut self, uncertainty_type: UncertaintyType, dry_run: bool) -> TreeResult<BalancingResult> {
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let locked = Self::locked_ids(&all_nodes);
+
+        // Locked leaves keep their probabilities and residual leaves absorb whatever their
+        // siblings leave; only the others are smoothed
+        let (residual_leafs, leaf_nodes): (Vec<TreeNode>, Vec<TreeNode>) = self.get_leaf_nodes().await?
+            .into_iter()
+            .partition(|node| node.residual);
+        let skipped_residual: Vec<String> = residual_leafs.iter().map(|node| node.id.as_ref().unwrap().to_string()).collect();
+        let (locked_leafs, leaf_nodes): (Vec<TreeNode>, Vec<TreeNode>) = leaf_nodes
+            .into_iter()
+            .partition(|node| locked.contains(node.id.as_ref().unwrap()));
+        let skipped_locked: Vec<String> = locked_leafs.iter().map(|node| node.id.as_ref().unwrap().to_string()).collect();
 
         if leaf_nodes.is_empty() {
             return Ok(BalancingResult {
@@ -866,7 +1729,15 @@ impl TreeEngineService {
                 new_probabilities: HashMap::new(),
                 laplace_alpha: 0.0,
                 total_count: 0,
-                smoothing_explanation: "No leaf nodes to balance".to_string(),
+                smoothing_explanation: if skipped_locked.is_empty() {
+                    "No leaf nodes to balance".to_string()
+                } else {
+                    "Every leaf node is locked - nothing to balance".to_string()
+                },
+                skipped_locked,
+                skipped_residual,
+                dry_run,
+                affected_paths: vec![],
             });
         }
 
@@ -881,33 +1752,44 @@ impl TreeEngineService {
         let mut balanced_nodes = Vec::new();
         let mut original_probabilities = HashMap::new();
         let mut new_probabilities = HashMap::new();
-
-        // Convert probabilities to pseudo-counts for Laplace smoothing
-        let total_mass: f64 = leaf_nodes.iter().map(|n| n.probability).sum();
+        let mut smoothed = Vec::new();
+
+        // Only siblings compete for their parent's probability, so each parent's unlocked
+        // leaves are smoothed among themselves
+        let mut sibling_groups: BTreeMap<String, Vec<TreeNode>> = BTreeMap::new();
+        for node in leaf_nodes {
+            let parent = node.parent_id.as_ref().map(|id| id.to_string()).unwrap_or_default();
+            sibling_groups.entry(parent).or_default().push(node);
+        }
+        let group_count = sibling_groups.len();
         let scale_factor = 100.0; // Scale to reasonable count range
 
-        for mut node in leaf_nodes {
-            let old_prob = node.probability;
+        for siblings in sibling_groups.into_values() {
+            // Convert probabilities to pseudo-counts for Laplace smoothing
+            let group_mass: f64 = siblings.iter().map(|n| n.probability).sum();
+            let categories = siblings.len();
 
-            // Convert probability to pseudo-count: count = prob * scale_factor
-            let pseudo_count = old_prob * scale_factor;
+            for mut node in siblings {
+                let old_prob = node.probability;
 
-            // Apply Laplace smoothing: P_smooth = (count + α) / (total + α*categories)
-            let smoothed_prob = (pseudo_count + laplace_alpha) / (scale_factor + laplace_alpha * total_count as f64);
+                // Convert probability to pseudo-count relative to the group's unlocked mass, so the
+                // smoothed siblings share exactly that mass and locked siblings keep theirs
+                let pseudo_count = if group_mass > 0.0 { old_prob / group_mass * scale_factor } else { 0.0 };
 
-            // Normalize to maintain probability constraints while smoothing
-            let normalized_prob = smoothed_prob * (total_mass / 1.0); // Maintain relative scale
+                // Apply Laplace smoothing: P_smooth = (count + α) / (total + α*categories)
+                let smoothed_prob = (pseudo_count + laplace_alpha) / (scale_factor + laplace_alpha * categories as f64);
 
-            node.probability = normalized_prob.min(1.0); // Cap at 1.0
+                // Scale back to the group's mass
+                node.probability = (smoothed_prob * group_mass).min(1.0); // Cap at 1.0
 
-            let node_id = node.id.as_ref().unwrap().clone();
-            let _: Option<TreeNode> = self.db.update(&node_id).content(node.clone()).await?;
-
-            let node_id_str = node_id.to_string();
-            balanced_nodes.push(node_id_str.clone());
-            original_probabilities.insert(node_id_str.clone(), old_prob);
-            new_probabilities.insert(node_id_str, node.probability);
+                let node_id_str = node.id.as_ref().unwrap().to_string();
+                balanced_nodes.push(node_id_str.clone());
+                original_probabilities.insert(node_id_str.clone(), old_prob);
+                new_probabilities.insert(node_id_str, node.probability);
+                smoothed.push(node);
+            }
         }
+        let affected_paths = self.rewrite_nodes(smoothed, dry_run).await?;
 
         let explanation_prefix = match uncertainty_type {
             UncertaintyType::InsufficientData => "Conservative Laplace α=0.5 (Jeffreys prior)",
@@ -915,10 +1797,13 @@ impl TreeEngineService {
             UncertaintyType::CognitiveOverload => "Strong Laplace α=2.0 (high regularization)",
         };
 
-        let smoothing_explanation = format!(
-            "{}: Applied to {} nodes. Formula: P_smooth = (count + {}) / (total + {} × {}). Reduces zero-probability risk.",
-            explanation_prefix, total_count, laplace_alpha, laplace_alpha, total_count
+        let mut smoothing_explanation = format!(
+            "{}: Applied to {} nodes in {} sibling groups. Formula: P_smooth = (count + {}) / (total + {} × siblings), scaled to each group's mass. Reduces zero-probability risk.",
+            explanation_prefix, total_count, group_count, laplace_alpha, laplace_alpha
         );
+        if !skipped_locked.is_empty() {
+            smoothing_explanation.push_str(&format!(" {} locked nodes kept their probabilities.", skipped_locked.len()));
+        }
 
         Ok(BalancingResult {
             balanced_nodes,
@@ -928,6 +1813,10 @@ impl TreeEngineService {
             laplace_alpha,
             total_count,
             smoothing_explanation,
+            skipped_locked,
+            skipped_residual,
+            dry_run,
+            affected_paths,
         })
     }
 
@@ -948,7 +1837,11 @@ impl TreeEngineService {
     ///
     /// # Validation Checks
     /// - Parent-child probability relationships (child ≤ parent)
-    /// - Sum of sibling probabilities ≤ 1.0
+    /// - Sum of sibling probabilities ≤ 1.0, counting cross-linked children
+    /// - Probability reaching a shared (cross-linked) node ≤ 1.0 across all its parents
+    /// - Propositions along each scenario path are jointly satisfiable; nodes only
+    ///   reachable through unsatisfiable paths are reported as eliminated
+    /// - Sibling propositions are mutually exclusive given their ancestors
     /// - Logical consistency in reasoning chains
     /// - Confidence level appropriateness
     /// - Structural integrity validation
@@ -974,7 +1867,7 @@ impl TreeEngineService {
     /// ```
     pub async fn validate_coherence(&self) -> TreeResult<CoherenceAnalysis> {
         let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
-        let tree_state = self.get_current_tree_state().await?;
+        self.get_current_tree_state().await?;
 
         let mut violations = Vec::new();
         let mut contradictions = Vec::new();
@@ -1001,27 +1894,72 @@ impl TreeEngineService {
             }
         }
 
-        // Check for contradictions in sibling nodes
-        let mut parent_children: HashMap<RecordId, Vec<&TreeNode>> = HashMap::new();
-        for node in &all_nodes {
-            if let Some(parent_id) = &node.parent_id {
-                parent_children.entry(parent_id.clone()).or_default().push(node);
-            }
-        }
-
-        for (parent_id, children) in parent_children {
+        // Check for contradictions in sibling nodes, counting cross-linked children under each parent
+        let links = self.list_cross_links().await?;
+        let edges = Self::scenario_edges(&all_nodes, &links);
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = all_nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap().clone(), node))
+            .collect();
+        let premise_of = |id: &RecordId| nodes_by_id.get(id)
+            .map(|node| node.premise.clone())
+            .unwrap_or_else(|| id.to_string());
+
+        let enforces_sibling_sums = self.get_current_tree_state().await
+            .ok().is_none_or(|state| state.config.enforces_sibling_sums());
+        // The inputs of a fault-tree gate are independent events, not shares of their parent
+        let gated = |id: &RecordId| nodes_by_id.get(id).is_some_and(|node| node.gate.is_some());
+        for (parent_id, children) in edges.iter().filter(|(parent_id, _)| enforces_sibling_sums && !gated(parent_id)) {
             if children.len() > 1 {
-                let total_prob: f64 = children.iter().map(|n| n.probability).sum();
+                let total_prob: f64 = children.iter().map(|(_, probability)| probability).sum();
                 if total_prob > 1.1 { // Allow small tolerance
                     contradictions.push(ContradictionResult {
                         node_id: parent_id.to_string(),
-                        conflicting_premises: children.iter().map(|n| n.premise.clone()).collect(),
+                        conflicting_premises: children.iter().map(|(id, _)| premise_of(id)).collect(),
                         explanation: "Child probabilities sum exceeds 1.0".to_string(),
                     });
                 }
             }
         }
 
+        // Children of an exhaustive node must account for all of its probability
+        for parent in all_nodes.iter().filter(|node| node.exhaustive && !node.is_invalidated && node.gate.is_none() && enforces_sibling_sums) {
+            let active: Vec<&(RecordId, f64)> = edges.get(parent.id.as_ref().unwrap()).map(Vec::as_slice).unwrap_or_default().iter()
+                .filter(|(id, _)| nodes_by_id.get(id).is_some_and(|child| !child.is_invalidated))
+                .collect();
+            let total: f64 = active.iter().map(|(_, probability)| probability).sum();
+            if (total - parent.probability).abs() > EXHAUSTIVE_TOLERANCE {
+                contradictions.push(ContradictionResult {
+                    node_id: parent.id.as_ref().unwrap().to_string(),
+                    conflicting_premises: active.iter().map(|(id, _)| premise_of(id)).collect(),
+                    explanation: format!("Exhaustive children sum to {:.3} instead of the parent's probability {:.3} (tolerance {})",
+                        total, parent.probability, EXHAUSTIVE_TOLERANCE),
+                });
+            }
+        }
+
+        // A shared outcome must not receive more than certainty across all routes into it
+        if let Some(root) = all_nodes.iter().find(|node| node.parent_id.is_none()) {
+            let marginals = Self::scenario_marginals(root.id.as_ref().unwrap(), root.probability, &edges);
+            let shared_nodes: HashSet<&RecordId> = links.iter().map(|link| &link.child).collect();
+            for node_id in shared_nodes {
+                let mass = marginals.get(node_id).copied().unwrap_or(0.0);
+                if mass > 1.001 {
+                    let mut conflicting_premises = vec![premise_of(node_id)];
+                    conflicting_premises.extend(links.iter()
+                        .filter(|link| &link.child == node_id)
+                        .map(|link| premise_of(&link.parent)));
+                    contradictions.push(ContradictionResult {
+                        node_id: node_id.to_string(),
+                        conflicting_premises,
+                        explanation: format!("Shared outcome receives probability {:.3} across its parents, which exceeds 1.0", mass),
+                    });
+                }
+            }
+        }
+
+        let (logical_contradictions, truth_table, eliminated_nodes, warnings) = Self::propositional_analysis(&all_nodes, &edges);
+        contradictions.extend(logical_contradictions);
+
         // Generate suggestions
         if violations.is_empty() && contradictions.is_empty() {
             suggestions.push("Tree structure appears coherent".to_string());
@@ -1034,26 +1972,49 @@ impl TreeEngineService {
             }
         }
 
-        // Build truth table for logical consistency
-        let mut truth_table = Vec::new();
-        for node in all_nodes.iter().take(5) { // Limit for performance
-            let mut premises = HashMap::new();
-            premises.insert(node.premise.clone(), node.probability > tree_state.config.min_probability);
-            truth_table.push(TruthTableRow {
-                premises,
-                is_consistent: node.probability > tree_state.config.min_probability,
-                affected_nodes: vec![node.id.as_ref().unwrap().to_string()],
-            });
-        }
-
         Ok(CoherenceAnalysis {
             is_coherent: violations.is_empty() && contradictions.is_empty(),
             contradictions,
             truth_table,
-            eliminated_nodes: vec![],
+            eliminated_nodes,
+            warnings,
         })
     }
 
+    /// Attaches a formal proposition to a node's premise, or clears it with `None`.
+    ///
+    /// Propositions combine atoms with `AND`/`&`, `OR`/`|`, `NOT`/`!` and parentheses,
+    /// e.g. `recession AND NOT rates_up`. Nodes sharing an atom name refer to the same
+    /// fact, which lets `validate_coherence` check paths and siblings logically.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (record id or friendly id)
+    /// * `proposition` - Formula to attach; `None` or blank text removes it
+    ///
+    /// # Returns
+    /// * `Ok(Option<String>)` - The normalized formula now stored on the node
+    /// * `Err(TreeEngineError::InvalidInput)` - If the formula does not parse
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_proposition(&mut self, node_id: String, proposition: Option<String>) -> TreeResult<Option<String>> {
+        let normalized = match proposition.as_deref().map(str::trim) {
+            Some(text) if !text.is_empty() => Some(Proposition::parse(text)
+                .map_err(|e| TreeEngineError::InvalidInput("proposition".to_string(), e))?
+                .to_string()),
+            _ => None,
+        };
+
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        node.proposition = normalized.clone();
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        self.publish_change().await;
+        Ok(normalized)
+    }
+
     /// Exports surviving probability paths with comprehensive analysis and integrated insights.
     ///
     /// This method generates a detailed analysis report of all viable probability paths
@@ -1122,40 +2083,68 @@ impl TreeEngineService {
             }
         }
 
-        let leaf_nodes = self.get_leaf_nodes().await?;
         let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = all_nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap().clone(), node))
+            .collect();
+
+        // Follow cross-links as well, so a shared outcome yields one path per distinct route into it
+        let edges = Self::scenario_edges(&all_nodes, &links);
+        let scenario_paths = match all_nodes.iter().find(|node| node.parent_id.is_none()) {
+            Some(root) => Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, &edges),
+            None => Vec::new(),
+        };
 
         let mut surviving_paths = Vec::new();
+        let mut bibliography = Vec::new();
         let mut total_tokens = 0;
 
-        // Build paths from leaf to root for each leaf node
-        for leaf in &leaf_nodes {
-            if leaf.is_invalidated {
+        for steps in &scenario_paths {
+            let path_nodes: Vec<&TreeNode> = steps.iter()
+                .filter_map(|(id, _)| nodes_by_id.get(id).copied())
+                .collect();
+
+            // A lone root is not a path
+            if path_nodes.len() < 2 {
                 continue;
             }
 
-            let mut path = vec![leaf.id.as_ref().unwrap().to_string()];
-            let mut premises = vec![leaf.premise.clone()];
-            let mut reasoning_chain = leaf.reasoning.clone();
-            let mut current_node = leaf;
-            let mut path_probability = leaf.probability;
-
-            // Walk up to root
-            while let Some(parent_id) = &current_node.parent_id {
-                if let Some(parent_node) = all_nodes.iter().find(|n| n.id.as_ref() == Some(parent_id)) {
-                    path.push(parent_id.to_string());
-                    premises.push(parent_node.premise.clone());
-                    reasoning_chain = format!("{} -> {}", parent_node.reasoning, reasoning_chain);
-                    path_probability *= parent_node.probability;
-                    current_node = parent_node;
-                } else {
-                    break;
-                }
+            let leaf = *path_nodes.last().unwrap();
+            if leaf.is_invalidated {
+                continue;
             }
 
-            // Reverse to get root-to-leaf order
-            path.reverse();
-            premises.reverse();
+            let path: Vec<String> = steps.iter().map(|(id, _)| id.to_string()).collect();
+            let premises: Vec<String> = path_nodes.iter().map(|node| node.premise.clone()).collect();
+            let path_probability: f64 = steps.iter().map(|(_, probability)| probability).product();
+
+            // Steps that cross a cross-link are explained by the link's reasoning
+            let reasoning_chain = path_nodes.iter().enumerate()
+                .map(|(i, node)| {
+                    let previous = i.checked_sub(1).and_then(|j| path_nodes[j].id.as_ref());
+                    if previous.is_some() && node.parent_id.as_ref() != previous {
+                        links.iter()
+                            .find(|link| Some(&link.parent) == previous && Some(&link.child) == node.id.as_ref())
+                            .map(|link| link.reasoning.clone())
+                            .unwrap_or_else(|| node.reasoning.clone())
+                    } else {
+                        node.reasoning.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            bibliography.push(PathBibliography {
+                path_number: surviving_paths.len() + 1,
+                leaf_id: leaf.display_id(),
+                citations: path_nodes.iter()
+                    .flat_map(|node| node.evidence.iter().map(|evidence| Citation {
+                        node_id: node.display_id(),
+                        evidence: evidence.clone(),
+                    }))
+                    .collect(),
+            });
 
             surviving_paths.push(PathResult {
                 path,
@@ -1181,7 +2170,7 @@ impl TreeEngineService {
             .map(|viz| viz.active_paths.clone())
             .unwrap_or_default();
 
-        Ok(AnalysisResult {
+        let result = AnalysisResult {
             surviving_paths,
             insights,
             confidence_assessment,
@@ -1192,226 +2181,139 @@ impl TreeEngineService {
             tree_statistics,
             tree_distributions,
             active_paths_detail,
-        })
+            bibliography,
+        };
+
+        // Keep the latest report per tree for the tree://{tree_id}/report resource
+        if let Ok(state) = self.get_current_tree_state().await {
+            let _: Option<AnalysisResult> = self.db.upsert(("report", state.tree_id.as_str())).content(result.clone()).await?;
+            let _ = self.changes.send(state.tree_id);
+        }
+
+        Ok(result)
     }
 
-    /// Generates a comprehensive visualization and analysis of the current probability tree.
+    /// Attaches a cited source to a node as structured evidence for its estimate.
     ///
-    /// This method performs deep analysis of the tree structure, calculating statistics,
-    /// generating ASCII visualization, and providing detailed node information. It's
-    /// primarily used for debugging, monitoring, and presenting tree state to users.
+    /// # Arguments
+    /// * `node_id` - Friendly alias, record id or unambiguous prefix of the node
+    /// * `source_title` - Title of the source (non-empty)
+    /// * `source_ref` - URI or document reference locating the source (non-empty)
+    /// * `quote` - Relevant excerpt from the source (may be empty)
+    /// * `date` - Publication or observation date of the source, if known
+    /// * `strength` - How strongly the source supports the estimate
     ///
     /// # Returns
-    /// * `Ok(TreeVisualization)` - Complete tree analysis including:
-    ///   - Statistical summary (node count, depth, probabilities, etc.)
-    ///   - ASCII tree representation
-    ///   - Detailed node information and relationships
-    ///   - Active path analysis
-    /// * `Err(TreeEngineError::NotFound)` - If no tree state is initialized
-    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
-    ///
-    /// # Generated Statistics
-    /// - Total number of nodes in the tree
-    /// - Maximum depth reached
-    /// - Number of active probability paths
-    /// - Average probability across all nodes
-    /// - Complexity score based on tree structure
-    /// - Distribution of confidence levels
+    /// * `Ok(Evidence)` - The stored evidence entry, including its generated id
+    /// * `Err(TreeEngineError::InvalidInput)` - If title or reference is empty
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
     ///
     /// # Example
     /// ```rust,no_run
     /// # use std::sync::Arc;
     /// # use surrealdb::Surreal;
     /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # use deep_analytics::domain::models::EvidenceStrength;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
     /// # let mut service = TreeEngineService::new(Arc::new(db));
-    /// # service.create_tree("Test premise".to_string(), 5).await?;
-    /// let visualization = service.inspect_tree().await?;
-    /// println!("Tree has {} nodes with max depth {}",
-    ///          visualization.statistics.total_nodes,
-    ///          visualization.statistics.max_depth);
-    /// println!("ASCII Tree:\n{}", visualization.ascii_tree);
+    /// # let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await?;
+    /// let evidence = service.attach_evidence(
+    ///     root_id,
+    ///     "Supplier audit".to_string(),
+    ///     "https://example.com/audit.pdf".to_string(),
+    ///     "Lead times doubled in Q1".to_string(),
+    ///     chrono::NaiveDate::from_ymd_opt(2025, 3, 14),
+    ///     EvidenceStrength::Strong,
+    /// ).await?;
+    /// println!("Stored evidence {}", evidence.id);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn inspect_tree(&self) -> TreeResult<TreeVisualization> {
-        let tree_state = self.get_current_tree_state().await?;
-
-        // Get all nodes with comprehensive information
-        let mut nodes_query = self.db.query("SELECT * FROM node ORDER BY depth, probability DESC").await?;
-        let nodes: Vec<TreeNode> = nodes_query.take(0)?;
-
-        // Get leaf nodes and invalidated nodes
-        let leaf_nodes = self.get_leaf_nodes().await?;
-        let invalidated_nodes = self.get_invalidated_nodes().await?;
+    pub async fn attach_evidence(
+        &mut self,
+        node_id: String,
+        source_title: String,
+        source_ref: String,
+        quote: String,
+        date: Option<chrono::NaiveDate>,
+        strength: EvidenceStrength,
+    ) -> TreeResult<Evidence> {
+        if source_title.trim().is_empty() {
+            return Err(TreeEngineError::InvalidInput("source_title".to_string(), "Source title cannot be empty".to_string()));
+        }
+        if source_ref.trim().is_empty() {
+            return Err(TreeEngineError::InvalidInput("source_ref".to_string(), "Source URI or document reference cannot be empty".to_string()));
+        }
 
-        // Build comprehensive node details (no ASCII generation)
-        let mut node_details = HashMap::new();
-        let mut max_depth = 0;
-        let mut total_probability = 0.0;
-        let mut confidence_distribution = std::collections::HashMap::new();
-        let mut depth_distribution = std::collections::HashMap::new();
-        let mut probability_distribution = Vec::new();
-        let mut premise_analysis = Vec::new();
-        let mut reasoning_analysis = Vec::new();
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
 
-        for node in &nodes {
-            let node_id_str = node.id.as_ref().unwrap().to_string();
-            let friendly_id = format!("N{}", node_id_str.chars().take(8).collect::<String>());
+        let evidence = Evidence::new(source_title, source_ref, quote, date, strength);
+        node.evidence.push(evidence.clone());
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
 
-            // Enhanced node details with complete information
-            node_details.insert(friendly_id.clone(), NodeVisualization {
-                friendly_id: friendly_id.clone(),
-                premise_summary: node.premise.clone(),
-                full_premise: node.premise.clone(),
-                full_reasoning: node.reasoning.clone(),
-                probability: node.probability,
-                depth: node.depth as u32,
-                children_count: node.children.len(),
-                children_ids: node.children.iter().map(|c| c.to_string()).collect(),
-                parent_id: None, // TreeNode doesn't have parent field, would need separate query
-                is_leaf: node.is_leaf(),
-                can_expand: node.can_expand(),
-                confidence: node.confidence,
-                status: if node.is_invalidated {
-                    NodeStatus::Invalidated
-                } else {
-                    NodeStatus::Active
-                },
-            });
+        self.publish_change().await;
+        Ok(evidence)
+    }
 
-            // Collect analytics data
-            *confidence_distribution.entry(node.confidence).or_insert(0) += 1;
-            *depth_distribution.entry(node.depth as u32).or_insert(0) += 1;
-            probability_distribution.push(node.probability);
-            premise_analysis.push(node.premise.len());
-            if !node.reasoning.trim().is_empty() {
-                reasoning_analysis.push(node.reasoning.len());
+    /// Lists the evidence attached to one node, or to every node that has any.
+    ///
+    /// # Arguments
+    /// * `node_id` - Optional node reference; when `None` all nodes with evidence are listed
+    ///
+    /// # Returns
+    /// * `Ok(Vec<NodeEvidence>)` - Evidence grouped by node, ordered by depth
+    /// * `Err(TreeEngineError::NotFound)` - If the referenced node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn list_evidence(&self, node_id: Option<String>) -> TreeResult<Vec<NodeEvidence>> {
+        let mut nodes: Vec<TreeNode> = match node_id {
+            Some(reference) => {
+                let node_record_id = self.resolve_node_ref(&reference).await?;
+                let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+                vec![node.ok_or(TreeEngineError::NotFound(reference))?]
             }
-
-            if node.depth > max_depth {
-                max_depth = node.depth;
+            None => {
+                let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+                all_nodes.into_iter().filter(|node| !node.evidence.is_empty()).collect()
             }
-            total_probability += node.probability;
-        }
-
-        // Calculate comprehensive statistics
-        let avg_probability = if nodes.is_empty() { 0.0 } else { total_probability / nodes.len() as f64 };
-        let total_nodes = nodes.len();
-        let active_nodes = nodes.iter().filter(|n| !n.is_invalidated).count();
-        let complexity_score = (max_depth as f64 * total_nodes as f64).sqrt();
-
-        // Probability statistics
-        probability_distribution.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        let prob_median = if probability_distribution.is_empty() {
-            0.0
-        } else {
-            probability_distribution[probability_distribution.len() / 2]
-        };
-        let prob_min = probability_distribution.first().copied().unwrap_or(0.0);
-        let prob_max = probability_distribution.last().copied().unwrap_or(0.0);
-
-        // Content analysis
-        let avg_premise_length = if premise_analysis.is_empty() { 0.0 } else {
-            premise_analysis.iter().sum::<usize>() as f64 / premise_analysis.len() as f64
-        };
-        let avg_reasoning_length = if reasoning_analysis.is_empty() { 0.0 } else {
-            reasoning_analysis.iter().sum::<usize>() as f64 / reasoning_analysis.len() as f64
         };
-
-        Ok(TreeVisualization {
-            // No ASCII generation here - only data
-            ascii_tree: String::new(),
-            node_details,
-            tree_metadata: TreeMetadata {
-                tree_id: tree_state.tree_id.clone(),
-                complexity: tree_state.config.complexity,
-                config: tree_state.config.clone(),
-                created_at: tree_state.created_at,
-                status: "Active".to_string(),
-            },
-            statistics: TreeStatsSummary {
-                total_nodes,
-                active_nodes,
-                invalidated_nodes: invalidated_nodes.len(),
-                active_paths: leaf_nodes.len(),
-                max_depth: max_depth as u32,
-                avg_depth: if total_nodes > 0 {
-                    depth_distribution.iter().map(|(k, v)| *k as f64 * *v as f64).sum::<f64>() / total_nodes as f64
-                } else { 0.0 },
-                avg_probability,
-                probability_median: prob_median,
-                probability_range: (prob_min, prob_max),
-                complexity_score,
-                avg_premise_length,
-                avg_reasoning_length,
-            },
-            distributions: TreeDistributions {
-                confidence_distribution,
-                depth_distribution,
-                probability_distribution,
-            },
-            active_paths: leaf_nodes.iter().enumerate().map(|(i, leaf)| {
-                let leaf_id = leaf.id.as_ref().unwrap().to_string();
-                let friendly_leaf_id = format!("N{}", leaf_id.chars().take(8).collect::<String>());
-
-                ActivePath {
-                    path_number: i + 1,
-                    leaf_id: friendly_leaf_id,
-                    premise: leaf.premise.clone(),
-                    probability: leaf.probability,
-                    confidence: leaf.confidence,
-                }
-            }).collect(),
-            recommendations: self.generate_recommendations(&leaf_nodes, max_depth as u32, avg_probability, &invalidated_nodes, total_nodes),
-        })
-    }
-
-    /// Generate analysis recommendations based on tree state
-    fn generate_recommendations(&self, leaf_nodes: &[TreeNode], max_depth: u32, avg_probability: f64, invalidated_nodes: &[TreeNode], total_nodes: usize) -> Vec<String> {
-        let mut recommendations = Vec::new();
-
-        if leaf_nodes.len() < 2 {
-            recommendations.push("⚠️  Consider adding more leaf nodes for richer analysis".to_string());
-        }
-        if max_depth < 2 {
-            recommendations.push("⚠️  Tree depth is shallow - consider expanding promising branches".to_string());
-        }
-        if avg_probability < 0.3 {
-            recommendations.push("⚠️  Low average probability - review premise strength".to_string());
-        }
-        if invalidated_nodes.len() > total_nodes / 3 {
-            recommendations.push("⚠️  High invalidation rate - consider tree restructuring".to_string());
-        }
-        if leaf_nodes.iter().any(|n| n.probability > 0.8) {
-            recommendations.push("✅ High-confidence paths identified - good for decision making".to_string());
-        }
-
-        recommendations
+        nodes.sort_by_key(|node| node.depth);
+
+        Ok(nodes.into_iter()
+            .map(|node| NodeEvidence {
+                node_id: node.display_id(),
+                premise: node.premise.clone(),
+                evidence: node.evidence,
+            })
+            .collect())
     }
 
-    /// Analyzes and validates probability values throughout the entire tree structure.
+    /// Adds a cross-link making an existing node an additional child of another node.
     ///
-    /// This method performs comprehensive probability validation across all nodes,
-    /// checking for range violations, consistency issues, and providing suggestions
-    /// for maintaining probability coherence within the tree structure.
+    /// Cross-links turn the tree into a scenario DAG: a shared downstream outcome
+    /// (e.g. "market collapses") can be reached from several causes without
+    /// duplicating the branch. The link is stored as a `cross_link` relation and
+    /// carries its own probability, which replaces the child's probability on paths
+    /// that cross the link.
     ///
-    /// # Returns
-    /// * `Ok(ValidationResult)` - Complete validation report including:
-    ///   - Overall validation status (valid/invalid)
-    ///   - List of probability violations found
-    ///   - Suggested corrections and improvements
-    ///   - Statistical summary of probability health
-    /// * `Err(TreeEngineError::NotFound)` - If tree state is not initialized
-    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    /// # Arguments
+    /// * `parent_id` - Friendly alias, record id or unambiguous prefix of the extra parent
+    /// * `child_id` - Friendly alias, record id or unambiguous prefix of the shared child
+    /// * `probability` - Probability of reaching the child through this parent (0.0 to 1.0)
+    /// * `reasoning` - Why this cause also leads to the child (non-empty)
     ///
-    /// # Validation Checks
-    /// - Probability values must be within [0.0, 1.0] range
-    /// - Confidence levels must be within [1, 10] range
-    /// - Child node probabilities should not exceed parent probabilities
-    /// - Sum of sibling probabilities should be ≤ 1.0
-    /// - Minimum probability threshold compliance
+    /// # Returns
+    /// * `Ok(CrossLink)` - The stored link
+    /// * `Err(TreeEngineError::InvalidInput)` - If reasoning is empty or both ends are the same node
+    /// * `Err(TreeEngineError::ProbabilityOutOfRange)` - If probability not in [0.0, 1.0]
+    /// * `Err(TreeEngineError::NotFound)` - If either node doesn't exist
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the link duplicates an edge, targets the root,
+    ///   would create a cycle or would push the parent's outgoing probability above its own (unless
+    ///   the parent is a fault-tree gate or the tree is in argument mode)
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
     ///
     /// # Example
     /// ```rust,no_run
@@ -1421,113 +2323,2657 @@ impl TreeEngineService {
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
     /// # let mut service = TreeEngineService::new(Arc::new(db));
-    /// # service.create_tree("Test premise".to_string(), 5).await?;
-    /// let validation = service.probability_status().await?;
-    ///
-    /// if validation.is_valid {
-    ///     println!("All probabilities are within valid ranges");
-    /// } else {
-    ///     println!("Found {} violations", validation.violations.len());
-    ///     for suggestion in &validation.suggestions {
-    ///         println!("Suggestion: {}", suggestion);
-    ///     }
-    /// }
+    /// # service.create_tree("Will the regional market hold up?".to_string(), 5).await?;
+    /// let link = service.add_cross_link(
+    ///     "clever_turing".to_string(),
+    ///     "brave_curie".to_string(),
+    ///     0.3,
+    ///     "A rate shock also triggers the collapse".to_string(),
+    /// ).await?;
+    /// println!("Linked with probability {}", link.probability);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn probability_status(&self) -> TreeResult<ValidationResult> {
+    pub async fn add_cross_link(
+        &mut self,
+        parent_id: String,
+        child_id: String,
+        probability: f64,
+        reasoning: String,
+    ) -> TreeResult<CrossLink> {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(TreeEngineError::ProbabilityOutOfRange(probability));
+        }
+
+        if reasoning.trim().is_empty() {
+            return Err(TreeEngineError::InvalidInput("reasoning".to_string(), "Reasoning cannot be empty".to_string()));
+        }
+
+        let parent_record_id = self.resolve_node_ref(&parent_id).await?;
+        let child_record_id = self.resolve_node_ref(&child_id).await?;
+        if parent_record_id == child_record_id {
+            return Err(TreeEngineError::InvalidInput("child_id".to_string(), "A node cannot be linked to itself".to_string()));
+        }
+
         let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = all_nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap().clone(), node))
+            .collect();
+
+        let parent_node = nodes_by_id.get(&parent_record_id).ok_or_else(|| TreeEngineError::NotFound(parent_id.clone()))?;
+        let child_node = nodes_by_id.get(&child_record_id).ok_or_else(|| TreeEngineError::NotFound(child_id.clone()))?;
+
+        if child_node.parent_id.is_none() {
+            return Err(TreeEngineError::OperationNotAllowed("The root node cannot be linked as a child".to_string()));
+        }
+
+        if parent_node.is_invalidated || child_node.is_invalidated {
+            return Err(TreeEngineError::OperationNotAllowed("Invalidated nodes cannot be cross-linked".to_string()));
+        }
+
+        let already_linked = child_node.parent_id.as_ref() == Some(&parent_record_id)
+            || links.iter().any(|link| link.parent == parent_record_id && link.child == child_record_id);
+        if already_linked {
+            return Err(TreeEngineError::OperationNotAllowed(
+                format!("{} is already a child of {}", child_node.display_id(), parent_node.display_id())
+            ));
+        }
+
+        let edges = Self::scenario_edges(&all_nodes, &links);
+        if Self::reaches(&edges, &child_record_id, &parent_record_id) {
+            return Err(TreeEngineError::OperationNotAllowed(
+                format!("Linking {} under {} would create a cycle", child_node.display_id(), parent_node.display_id())
+            ));
+        }
+
+        // Same sibling constraint as add_leaf; a residual child gives way to the link
         let tree_state = self.get_current_tree_state().await?;
+        let outgoing_probability = self.allocated_probability(parent_node).await?;
+        if Self::limits_children(&tree_state.config, parent_node) && outgoing_probability + probability > parent_node.probability + 0.001 {
+            return Err(TreeEngineError::OperationNotAllowed(
+                format!(
+                    "Probability constraint violation: Linking with probability {:.3} would result in total child probability {:.3}, which exceeds parent probability {:.3}.",
+                    probability,
+                    outgoing_probability + probability,
+                    parent_node.probability
+                )
+            ));
+        }
 
-        let mut violations = Vec::new();
-        let mut _warnings: Vec<ValidationViolation> = Vec::new();
+        let mut response = self.db
+            .query("RELATE $parent->cross_link->$child SET probability = $probability, reasoning = $reasoning, created_at = $created_at")
+            .bind(("parent", parent_record_id))
+            .bind(("child", child_record_id))
+            .bind(("probability", probability))
+            .bind(("reasoning", reasoning))
+            .bind(("created_at", chrono::Utc::now()))
+            .await?;
+        let created: Vec<CrossLink> = response.take(0)?;
+
+        let link = created.into_iter().next()
+            .ok_or_else(|| TreeEngineError::DatabaseError("Failed to create cross-link".to_string()))?;
+        self.sync_residuals().await?;
+        self.publish_change().await;
+        Ok(link)
+    }
 
-        // Check each node for probability violations
-        for node in &all_nodes {
-            if node.probability < 0.0 || node.probability > 1.0 {
-                violations.push(ValidationViolation {
-                    node_id: node.id.as_ref().unwrap().to_string(),
-                    violation_type: ViolationType::ProbabilityRange,
-                    message: format!("Node probability {} is outside valid range [0,1]", node.probability),
-                    severity: Severity::Error,
-                });
+    /// Removes the cross-link between two nodes.
+    ///
+    /// Only cross-links can be removed this way; the primary `parent_id` edge is untouched.
+    ///
+    /// # Arguments
+    /// * `parent_id` - Friendly alias, record id or unambiguous prefix of the linked parent
+    /// * `child_id` - Friendly alias, record id or unambiguous prefix of the linked child
+    ///
+    /// # Returns
+    /// * `Ok(CrossLink)` - The removed link
+    /// * `Err(TreeEngineError::NotFound)` - If either node or the link doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn remove_cross_link(&mut self, parent_id: String, child_id: String) -> TreeResult<CrossLink> {
+        let parent_record_id = self.resolve_node_ref(&parent_id).await?;
+        let child_record_id = self.resolve_node_ref(&child_id).await?;
+
+        let mut response = self.db
+            .query("DELETE cross_link WHERE in = $parent AND out = $child RETURN BEFORE")
+            .bind(("parent", parent_record_id))
+            .bind(("child", child_record_id))
+            .await?;
+        let removed: Vec<CrossLink> = response.take(0)?;
+
+        let link = removed.into_iter().next()
+            .ok_or_else(|| TreeEngineError::NotFound(format!("cross-link {} -> {}", parent_id, child_id)))?;
+        self.sync_residuals().await?;
+        self.publish_change().await;
+        Ok(link)
+    }
+
+    /// Lists every cross-link in the current tree.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CrossLink>)` - All stored links, possibly empty
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn list_cross_links(&self) -> TreeResult<Vec<CrossLink>> {
+        let links: Vec<CrossLink> = self.db.select("cross_link").await?;
+        Ok(links)
+    }
+
+    /// Captures the full current tree (state, nodes and cross-links) under a name.
+    ///
+    /// Checkpoints survive `create_tree`, so they can be compared with `diff_trees`
+    /// across iterations of one tree or across different trees.
+    ///
+    /// # Arguments
+    /// * `name` - Unique checkpoint name (e.g. "before pruning"); "current" is reserved
+    ///
+    /// # Returns
+    /// * `Ok(CheckpointSummary)` - Name, tree id, node count and capture time
+    /// * `Err(TreeEngineError::InvalidInput)` - If the name is empty or reserved
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If a checkpoint with that name exists
+    /// * `Err(TreeEngineError::NotFound)` - If no tree state is initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Will the product launch on time?".to_string(), 5).await?;
+    /// service.checkpoint("before pruning".to_string()).await?;
+    /// service.prune_tree(0.5, false).await?;
+    /// let diff = service.diff_trees("before pruning".to_string(), "current".to_string(), 5).await?;
+    /// println!("{}", diff);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn checkpoint(&self, name: String) -> TreeResult<CheckpointSummary> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(TreeEngineError::InvalidInput("name".to_string(), "Checkpoint name cannot be empty".to_string()));
+        }
+        if name.eq_ignore_ascii_case(CURRENT_TREE_REF) {
+            return Err(TreeEngineError::InvalidInput("name".to_string(), format!("'{}' is reserved for the live tree", CURRENT_TREE_REF)));
+        }
+
+        let existing: Option<TreeCheckpoint> = self.db.select(("checkpoint", name.as_str())).await?;
+        if existing.is_some() {
+            return Err(TreeEngineError::OperationNotAllowed(format!("Checkpoint '{}' already exists", name)));
+        }
+
+        let mut snapshot = self.current_snapshot().await?;
+        snapshot.name = name.clone();
+        let created: Option<TreeCheckpoint> = self.db.create(("checkpoint", name.as_str())).content(snapshot).await?;
+
+        created.map(|checkpoint| checkpoint.summary())
+            .ok_or_else(|| TreeEngineError::DatabaseError("Failed to create checkpoint".to_string()))
+    }
+
+    /// Lists every stored checkpoint, oldest first.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CheckpointSummary>)` - All checkpoints, possibly empty
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn list_checkpoints(&self) -> TreeResult<Vec<CheckpointSummary>> {
+        let mut response = self.db.query("SELECT * FROM checkpoint ORDER BY created_at ASC").await?;
+        let checkpoints: Vec<TreeCheckpoint> = response.take(0)?;
+        Ok(checkpoints.iter().map(TreeCheckpoint::summary).collect())
+    }
+
+    /// Compares two versions of a tree.
+    ///
+    /// Nodes are matched by record id, falling back to identical premises so that
+    /// separately built trees can be compared too. Path rankings follow cross-links
+    /// and order root-to-leaf paths by their probability.
+    ///
+    /// # Arguments
+    /// * `from` - Checkpoint name, tree id (its latest checkpoint) or "current"
+    /// * `to` - Checkpoint name, tree id (its latest checkpoint) or "current"
+    /// * `top_k` - Number of top-ranked paths whose ranking is compared (at least 1)
+    ///
+    /// # Returns
+    /// * `Ok(TreeDiff)` - Added and removed nodes, probability, confidence and premise
+    ///   changes, and paths whose top-k rank changed
+    /// * `Err(TreeEngineError::InvalidInput)` - If top_k is 0
+    /// * `Err(TreeEngineError::NotFound)` - If either reference cannot be resolved
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn diff_trees(&self, from: String, to: String, top_k: usize) -> TreeResult<TreeDiff> {
+        if top_k == 0 {
+            return Err(TreeEngineError::InvalidInput("top_k".to_string(), "top_k must be at least 1".to_string()));
+        }
+
+        let before = self.load_snapshot(&from).await?;
+        let after = self.load_snapshot(&to).await?;
+        Ok(Self::diff_snapshots(&before, &after, top_k))
+    }
+
+    /// Merges another version of the tree, built independently from the same root premise,
+    /// into the live tree.
+    ///
+    /// Starting from the roots, the children of each matched pair are aligned by premise
+    /// similarity, best match first. Matched nodes receive a confidence-weighted pool of
+    /// both probabilities, and their reasoning and evidence are combined. Source children
+    /// without a match are grafted, with their whole subtree, under the matched live parent.
+    /// Every grafted node must respect the depth and branch limits and attach to an active
+    /// parent. Probabilities are not renormalized: any parent whose children, pooled or
+    /// grafted, now exceed its own probability is listed as a conflict for manual
+    /// resolution. Cross-links of the source are not merged. All changes are written in one
+    /// transaction, so a rejected graft leaves the tree untouched.
+    ///
+    /// # Arguments
+    /// * `source` - Checkpoint name or tree id holding the other analyst's tree
+    /// * `similarity_threshold` - Minimum premise similarity (0.0 exclusive to 1.0) for two
+    ///   nodes to be treated as the same; defaults to 0.75
+    /// * `dry_run` - Compute the report without writing anything; a later real merge grafts
+    ///   under the same aliases
+    ///
+    /// # Returns
+    /// * `Ok(MergeReport)` - Matched, pooled and grafted nodes, sibling-sum conflicts and the
+    ///   root-to-leaf paths whose probability or rank the merge changes
+    /// * `Err(TreeEngineError::InvalidInput)` - If the threshold is out of range or the source is the live tree
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the root premises do not match or a graft exceeds a depth or branch limit
+    /// * `Err(TreeEngineError::NotFound)` - If the source or the live tree cannot be found
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Will the product launch on time?".to_string(), 5).await?;
+    /// let report = service.merge_tree("analyst_b".to_string(), None, false).await?;
+    /// for conflict in &report.conflicts {
+    ///     println!("{}", conflict);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn merge_tree(&mut self, source: String, similarity_threshold: Option<f64>, dry_run: bool) -> TreeResult<MergeReport> {
+        let threshold = similarity_threshold.unwrap_or(0.75);
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(TreeEngineError::InvalidInput("similarity_threshold".to_string(), "Similarity threshold must be in (0, 1]".to_string()));
+        }
+        if source.trim().eq_ignore_ascii_case(CURRENT_TREE_REF) {
+            return Err(TreeEngineError::InvalidInput("source".to_string(), "Cannot merge the live tree into itself".to_string()));
+        }
+
+        let snapshot = self.load_snapshot(&source).await?;
+        let before = self.current_snapshot().await?;
+        let config = before.state.config.clone();
+        let links = before.cross_links.clone();
+        let mut nodes: HashMap<RecordId, TreeNode> = before.nodes.iter().cloned()
+            .map(|node| (node.id.clone().unwrap(), node))
+            .collect();
+        let source_by_id: HashMap<&RecordId, &TreeNode> = snapshot.nodes.iter()
+            .filter_map(|node| node.id.as_ref().map(|id| (id, node)))
+            .collect();
+
+        let target_root = nodes.values().find(|node| node.parent_id.is_none())
+            .ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let source_root = snapshot.nodes.iter().find(|node| node.parent_id.is_none())
+            .ok_or_else(|| TreeEngineError::NotFound(format!("root node of '{}'", snapshot.name)))?;
+
+        let root_similarity = text_similarity::premise_similarity(&target_root.premise, &source_root.premise);
+        if root_similarity < threshold {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Root premises differ ({:.0}% similar): \"{}\" vs \"{}\"",
+                root_similarity * 100.0, target_root.premise, source_root.premise
+            )));
+        }
+
+        // Grafts take aliases from a copy of the allocator that is kept only once the merge
+        // is written, so a preview or a rejected graft leaves the live allocator untouched
+        let namespace = before.tree_id.clone();
+        let mut aliases = self.friendly_ids.clone();
+        for node in before.nodes.iter().filter(|node| !node.friendly_id.is_empty()) {
+            aliases.register(&namespace, &node.friendly_id);
+        }
+
+        let mut matched = Vec::new();
+        let mut grafted = Vec::new();
+        let mut dirty: HashSet<RecordId> = HashSet::new();
+        let mut pairs = vec![(target_root.id.clone().unwrap(), source_root, root_similarity)];
+
+        while let Some((target_id, source_node, similarity)) = pairs.pop() {
+            let target = nodes.get_mut(&target_id).unwrap();
+            let probability_before = target.probability;
+            Self::pool_into(target, source_node);
+            dirty.insert(target_id.clone());
+            matched.push(MergedNode {
+                node_id: target.display_id(),
+                premise: target.premise.clone(),
+                source_premise: source_node.premise.clone(),
+                similarity,
+                probability_before,
+                source_probability: source_node.probability,
+                pooled_probability: target.probability,
+            });
+
+            // Align children greedily, most similar pair first
+            let target_children = target.children.clone();
+            let source_children: Vec<&TreeNode> = source_node.children.iter()
+                .filter_map(|id| source_by_id.get(id).copied())
+                .collect();
+            let mut candidates = Vec::new();
+            for target_child in &target_children {
+                let Some(target_child_node) = nodes.get(target_child) else { continue };
+                for (i, source_child) in source_children.iter().enumerate() {
+                    let score = text_similarity::premise_similarity(&target_child_node.premise, &source_child.premise);
+                    if score >= threshold {
+                        candidates.push((score, target_child.clone(), i));
+                    }
+                }
             }
+            candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
 
-            if node.probability < tree_state.config.min_probability {
-                violations.push(ValidationViolation {
-                    node_id: node.id.as_ref().unwrap().to_string(),
-                    violation_type: ViolationType::ProbabilityRange,
-                    message: format!("Node probability {} is below minimum threshold {}",
-                                       node.probability, tree_state.config.min_probability),
-                    severity: Severity::Warning,
+            let mut used_targets = HashSet::new();
+            let mut used_sources = HashSet::new();
+            for (score, target_child, i) in candidates {
+                if used_targets.contains(&target_child) || used_sources.contains(&i) {
+                    continue;
+                }
+                used_targets.insert(target_child.clone());
+                used_sources.insert(i);
+                pairs.push((target_child, source_children[i], score));
+            }
+
+            // Graft unmatched source subtrees, keeping their source depth and estimates
+            let mut to_graft: Vec<(&TreeNode, RecordId)> = source_children.iter().enumerate()
+                .filter(|(i, _)| !used_sources.contains(i))
+                .map(|(_, child)| (*child, target_id.clone()))
+                .collect();
+            while let Some((source_child, parent_id)) = to_graft.pop() {
+                // Sibling budgets are not enforced here: parents they overflow are reported as conflicts
+                let parent = &nodes[&parent_id];
+                Self::check_attachment(&config, parent, 0.0, 0.0, 0).map_err(|e| match e {
+                    TreeEngineError::OperationNotAllowed(message) => TreeEngineError::OperationNotAllowed(
+                        format!("Cannot graft \"{}\": {}", source_child.premise, message)),
+                    e => e,
+                })?;
+
+                let created_id = RecordId::from(("node", uuid::Uuid::new_v4().simple().to_string()));
+                let mut created = source_child.clone();
+                created.id = Some(created_id.clone());
+                created.parent_id = Some(parent_id.clone());
+                created.children = Vec::new();
+                created.depth = parent.depth + 1;
+                created.friendly_id = aliases.allocate(&namespace);
+
+                nodes.get_mut(&parent_id).unwrap().add_child(created_id.clone());
+                dirty.insert(parent_id);
+                dirty.insert(created_id.clone());
+                grafted.push(DiffNode {
+                    node_id: created.display_id(),
+                    premise: created.premise.clone(),
+                    probability: created.probability,
                 });
+                to_graft.extend(source_child.children.iter()
+                    .filter_map(|id| source_by_id.get(id))
+                    .map(|child| (*child, created_id.clone())));
+                nodes.insert(created_id, created);
             }
         }
 
-        let is_valid = violations.is_empty();
+        let mut all_nodes: Vec<TreeNode> = nodes.into_values().collect();
+        let residuals = Self::absorb_residuals(&mut all_nodes, &links);
+        dirty.extend(residuals.into_iter().filter_map(|node| node.id));
+        let mut after = before.clone();
+        after.nodes = all_nodes.clone();
+        let affected_paths = Self::path_changes(&before, &after);
+        if !dry_run {
+            self.store_nodes(all_nodes.iter()
+                .filter(|node| node.id.as_ref().is_some_and(|id| dirty.contains(id)))
+                .cloned()
+                .collect()).await?;
+            self.friendly_ids = aliases;
+        }
 
-        Ok(ValidationResult {
-            is_valid,
-            violations,
-            suggestions: if is_valid {
-                vec!["Tree appears valid".to_string()]
-            } else {
-                vec!["Fix probability range violations".to_string()]
-            },
+        // Report every parent whose children now exceed it, including cross-linked children.
+        // Residuals only hold what is left, and gate inputs and arguments have no budget.
+        let edges = Self::scenario_edges(&all_nodes, &links);
+        let nodes_by_id: HashMap<&RecordId, &TreeNode> = all_nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap(), node))
+            .collect();
+        let mut conflicts: Vec<SiblingConflict> = edges.iter()
+            .filter_map(|(parent_id, children)| {
+                let parent = nodes_by_id.get(parent_id).filter(|parent| Self::limits_children(&config, parent))?;
+                let children: Vec<&(RecordId, f64)> = children.iter()
+                    .filter(|(id, _)| !nodes_by_id.get(id).is_some_and(|child| child.residual))
+                    .collect();
+                let children_total: f64 = children.iter().map(|(_, probability)| probability).sum();
+                (children_total > parent.probability + 0.001).then(|| SiblingConflict {
+                    parent_id: parent.display_id(),
+                    parent_premise: parent.premise.clone(),
+                    parent_probability: parent.probability,
+                    children: children.iter()
+                        .filter_map(|(id, probability)| nodes_by_id.get(id).map(|child| DiffNode {
+                            node_id: child.display_id(),
+                            premise: child.premise.clone(),
+                            probability: *probability,
+                        }))
+                        .collect(),
+                    children_total,
+                })
+            })
+            .collect();
+        conflicts.sort_by(|a, b| a.parent_id.cmp(&b.parent_id));
+
+        if !dry_run {
+            self.publish_change().await;
+        }
+        Ok(MergeReport {
+            source: snapshot.name.clone(),
+            similarity_threshold: threshold,
+            matched,
+            grafted,
+            conflicts,
+            dry_run,
+            affected_paths,
         })
     }
 
-    /// Retrieves the current tree state configuration and metadata.
+    /// Records which leaf scenario actually occurred for the live tree.
+    ///
+    /// Every leaf of the scenario DAG is frozen as a forecast carrying the probability
+    /// mass that reaches it, so the tree can still be scored by `calibration_report`
+    /// after it has been replaced by a new one. Resolving the same tree again replaces
+    /// its earlier resolution.
+    ///
+    /// # Arguments
+    /// * `node_id` - Leaf that occurred (friendly alias, record id or unambiguous prefix)
+    /// * `notes` - Optional notes on how the outcome was established
     ///
     /// # Returns
-    /// * `Ok(TreeState)` - The complete tree state including configuration and metadata
-    /// * `Err(TreeEngineError::NotFound)` - If no tree state has been initialized
-    /// * `Err(TreeEngineError::DatabaseError)` - If database query fails
-    pub async fn get_state(&self) -> TreeResult<TreeState> {
-        self.get_current_tree_state().await
+    /// * `Ok(Resolution)` - The stored resolution with its leaf forecasts
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the node is the root or has children
+    /// * `Err(TreeEngineError::NotFound)` - If the node or the tree doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Will the product launch on time?".to_string(), 5).await?;
+    /// # let leaf_id = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.6, 7).await?;
+    /// let resolution = service.resolve_outcome(leaf_id, Some("Shipped in week 14".to_string())).await?;
+    /// println!("Brier score: {:.3}", resolution.brier_score());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_outcome(&mut self, node_id: String, notes: Option<String>) -> TreeResult<Resolution> {
+        let resolved_id = self.resolve_node_ref(&node_id).await?;
+        let state = self.get_current_tree_state().await?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let edges = Self::scenario_edges(&nodes, &links);
+        let root = nodes.iter().find(|node| node.parent_id.is_none())
+            .ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let root_id = root.id.clone().unwrap();
+
+        let is_leaf = |id: &RecordId| id != &root_id && edges.get(id).is_none_or(|children| children.is_empty());
+        if !is_leaf(&resolved_id) {
+            return Err(TreeEngineError::OperationNotAllowed(format!(
+                "Node {} is not a leaf scenario; resolve the most specific outcome that occurred", node_id
+            )));
+        }
+
+        let marginals = Self::scenario_marginals(&root_id, root.probability, &edges);
+        let mut forecasts: Vec<LeafForecast> = nodes.iter()
+            .filter(|node| node.id.as_ref().is_some_and(is_leaf))
+            .map(|node| {
+                let id = node.id.as_ref().unwrap();
+                LeafForecast {
+                    node_id: node.display_id(),
+                    premise: node.premise.clone(),
+                    probability: marginals.get(id).copied().unwrap_or(0.0),
+                    occurred: id == &resolved_id,
+                }
+            })
+            .collect();
+        forecasts.sort_by(|a, b| b.probability.partial_cmp(&a.probability).unwrap_or(std::cmp::Ordering::Equal));
+
+        let resolved = forecasts.iter().find(|forecast| forecast.occurred).unwrap();
+        let resolution = Resolution {
+            id: None,
+            tree_id: state.tree_id.clone(),
+            root_premise: root.premise.clone(),
+            resolved_node_id: resolved.node_id.clone(),
+            resolved_premise: resolved.premise.clone(),
+            forecasts,
+            notes: notes.map(|notes| notes.trim().to_string()).unwrap_or_default(),
+            resolved_at: chrono::Utc::now(),
+        };
+
+        let stored: Option<Resolution> = self.db.upsert(("resolution", state.tree_id.as_str())).content(resolution).await?;
+        stored.ok_or_else(|| TreeEngineError::DatabaseError("Failed to store resolution".to_string()))
     }
 
-    /// Gets the ID of the current node in the navigation context.
+    /// Scores resolved forecasts for one tree, or across every resolved tree.
     ///
-    /// Returns the currently focused node ID, falling back to root node if no
-    /// current node is explicitly set in the tree state metadata.
+    /// Resolutions outlive `create_tree`, so the report covers every tree resolved
+    /// in this database. Each tree gets a Brier and a log score, and all leaf forecasts
+    /// are pooled into ten calibration buckets comparing stated probability with how
+    /// often those scenarios actually occurred.
+    ///
+    /// # Arguments
+    /// * `tree_id` - A tree id, or "current" for the live tree; `None` covers all resolved trees
     ///
     /// # Returns
-    /// * `Ok(String)` - The ID of the current node
-    /// * `Err(TreeEngineError::NotFound)` - If neither current node nor root node exist
-    /// * `Err(TreeEngineError::DatabaseError)` - If tree state query fails
-    pub async fn get_current_node(&self) -> TreeResult<String> {
-        let tree_state = self.get_current_tree_state().await?;
-        tree_state.metadata.get("current_node")
-            .cloned()
-            .or_else(|| tree_state.config.root_id.as_ref().map(|id| id.to_string()))
-            .ok_or_else(|| TreeEngineError::NotFound("No current node set".to_string()))
+    /// * `Ok(CalibrationReport)` - Per-tree scores, their means and the calibration buckets
+    /// * `Err(TreeEngineError::NotFound)` - If the requested tree has not been resolved
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn calibration_report(&self, tree_id: Option<String>) -> TreeResult<CalibrationReport> {
+        let (scope, mut resolutions) = match tree_id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+            Some(reference) => {
+                let tree_id = if reference.eq_ignore_ascii_case(CURRENT_TREE_REF) {
+                    self.get_current_tree_state().await?.tree_id
+                } else {
+                    reference.to_string()
+                };
+                let resolution: Option<Resolution> = self.db.select(("resolution", tree_id.as_str())).await?;
+                let resolution = resolution.ok_or_else(|| TreeEngineError::NotFound(format!("resolution for {}", tree_id)))?;
+                (tree_id, vec![resolution])
+            }
+            None => {
+                let resolutions: Vec<Resolution> = self.db.select("resolution").await?;
+                ("all resolved trees".to_string(), resolutions)
+            }
+        };
+        resolutions.sort_by_key(|resolution| resolution.resolved_at);
+
+        Ok(CalibrationReport::from_resolutions(scope, &resolutions))
     }
 
-    /// Retrieves the root node ID of the current tree, if available.
+    /// Sets when a node's scenario is expected to occur and when to revisit its estimate.
     ///
-    /// This method safely attempts to get the root node ID without returning errors,
-    /// making it suitable for conditional operations and existence checks.
+    /// Both values are replaced, so passing `None` clears them. Nodes whose review date
+    /// or window has passed are flagged as stale in `inspect_tree` recommendations.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `time_window` - Dates between which the scenario is expected to occur
+    /// * `review_by` - Date by which the estimate should be revisited
     ///
     /// # Returns
-    /// * `Some(String)` - The root node ID if tree is initialized
-    /// * `None` - If no tree has been created or tree state is not accessible
-    pub async fn get_root_id(&self) -> Option<String> {
-        if let Ok(state) = self.get_current_tree_state().await {
-            state.config.root_id.as_ref().map(|id| id.to_string())
+    /// * `Ok(())` - If the node was updated
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_time_horizon(
+        &mut self,
+        node_id: String,
+        time_window: Option<TimeWindow>,
+        review_by: Option<chrono::NaiveDate>,
+    ) -> TreeResult<()> {
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        node.time_window = time_window;
+        node.review_by = review_by;
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        self.publish_change().await;
+        Ok(())
+    }
+
+    /// Locks or unlocks a node against `prune_tree`, `prune_leafs` and `balance_leafs`.
+    ///
+    /// A lock pins just the node; with `subtree` the node is frozen instead, which pins
+    /// it and every descendant, including ones added later. Unlocking only clears the
+    /// flag named by `subtree`.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `locked` - Whether to set or clear the flag
+    /// * `subtree` - Freeze or unfreeze the whole subtree instead of locking the node
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the node is still pinned afterwards, e.g. unlocked inside a frozen subtree
+    /// * `Ok(false)` - If automated operations may now change the node
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_lock(&mut self, node_id: String, locked: bool, subtree: bool) -> TreeResult<bool> {
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        if subtree {
+            node.frozen = locked;
         } else {
-            None
+            node.locked = locked;
+        }
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let still_locked = Self::locked_ids(&nodes).contains(&node_record_id);
+
+        self.publish_change().await;
+        Ok(still_locked)
+    }
+
+    /// Sets the fault-tree gate that combines a node's child events, or clears it with `None`.
+    ///
+    /// The children of a gated node are independent events, so their probabilities are not
+    /// required to fit within the node's own.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `gate` - AND, OR or k-of-n gate; nodes with children but no gate act as OR
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the node was updated
+    /// * `Err(TreeEngineError::InvalidInput)` - If a k-of-n gate has k = 0
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_gate(&mut self, node_id: String, gate: Option<Gate>) -> TreeResult<()> {
+        if gate == Some(Gate::KOfN(0)) {
+            return Err(TreeEngineError::InvalidInput("k".to_string(), "A k-of-n gate needs k of at least 1".to_string()));
         }
+
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        node.gate = gate;
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        self.publish_change().await;
+        Ok(())
+    }
+
+    /// Reads the tree as a fault tree whose root is the top event.
+    ///
+    /// Active nodes without active children are basic events, with their probability read
+    /// as the independent chance of the event occurring. Every other node combines its
+    /// children through its gate. Cross-links are not followed.
+    ///
+    /// # Returns
+    /// * `Ok(FaultTreeAnalysis)` - Top-event probability, gate outputs, minimal cut sets and
+    ///   Fussell-Vesely and Birnbaum importance of each basic event
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the root has no children, a k-of-n
+    ///   gate has fewer inputs than k, or there are too many cut sets to enumerate
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn fault_tree_analysis(&self) -> TreeResult<FaultTreeAnalysis> {
+        let state = self.get_current_tree_state().await?;
+        let root_id = state.config.root_id.ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let root = nodes_by_id.get(&root_id).ok_or_else(|| TreeEngineError::NotFound(root_id.to_string()))?;
+
+        let top = Self::fault_event(root, &nodes_by_id);
+        if matches!(top.kind, FaultEventKind::Basic(_)) {
+            return Err(TreeEngineError::OperationNotAllowed("The top event has no contributing events yet".to_string()));
+        }
+        FaultTreeAnalysis::analyze(&top).map_err(TreeEngineError::OperationNotAllowed)
+    }
+
+    /// The fault event of `node`: basic if it has no active children, otherwise its gate over them.
+    fn fault_event(node: &TreeNode, nodes_by_id: &HashMap<RecordId, &TreeNode>) -> FaultEvent {
+        let inputs: Vec<FaultEvent> = node.children.iter()
+            .filter_map(|id| nodes_by_id.get(id))
+            .filter(|child| !child.is_invalidated)
+            .map(|child| Self::fault_event(child, nodes_by_id))
+            .collect();
+
+        FaultEvent {
+            node_id: node.display_id(),
+            premise: node.premise.clone(),
+            kind: if inputs.is_empty() {
+                FaultEventKind::Basic(node.probability)
+            } else {
+                FaultEventKind::Gate(node.gate.unwrap_or(Gate::Or), inputs)
+            },
+        }
+    }
+
+    /// Sets a node's impact score and risk category, or clears them with `None`.
+    ///
+    /// Leaves without their own values inherit them from the nearest ancestor that has
+    /// them, so a branch can score every scenario below it at once.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `impact` - Severity if the scenario occurs, from 0 to 10
+    /// * `category` - Risk category such as "supply" or "regulatory"
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the node was updated
+    /// * `Err(TreeEngineError::InvalidInput)` - If the impact is outside [0,10] or the category is blank
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_risk_profile(&mut self, node_id: String, impact: Option<f64>, category: Option<String>) -> TreeResult<()> {
+        if let Some(impact) = impact.filter(|impact| !(0.0..=MAX_IMPACT).contains(impact)) {
+            return Err(TreeEngineError::InvalidInput("impact".to_string(), format!("Impact {} is out of range [0,{}]", impact, MAX_IMPACT)));
+        }
+        let category = match category.map(|category| category.trim().to_string()) {
+            Some(category) if category.is_empty() => {
+                return Err(TreeEngineError::InvalidInput("category".to_string(), "Category cannot be empty".to_string()));
+            }
+            category => category,
+        };
+
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        node.impact = impact;
+        node.category = category;
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        self.publish_change().await;
+        Ok(())
+    }
+
+    /// Bins the surviving leaf scenarios into a probability × impact matrix.
+    ///
+    /// Each leaf takes its impact and category from itself or its nearest ancestor with
+    /// one. With `ProbabilityBasis::Path` every root-to-leaf path is placed separately,
+    /// inheriting along that path; with `ProbabilityBasis::Marginal` each leaf is placed
+    /// once with the mass reaching it, inheriting along its tree ancestors.
+    ///
+    /// # Arguments
+    /// * `basis` - Which probability places a leaf on the probability axis
+    /// * `probability_levels` - Number of probability bands (2-10)
+    /// * `impact_levels` - Number of impact bands (2-10)
+    ///
+    /// # Returns
+    /// * `Ok(RiskMatrix)` - The grid with each cell's occupants, per-category risk and unscored leaves
+    /// * `Err(TreeEngineError::InvalidInput)` - If a level count is out of range
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn risk_matrix(&self, basis: ProbabilityBasis, probability_levels: usize, impact_levels: usize) -> TreeResult<RiskMatrix> {
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let edges = Self::scenario_edges(&nodes, &links);
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let root = nodes.iter().find(|node| node.parent_id.is_none());
+
+        // (leaf, probability, nodes from the root down to the leaf)
+        let placements: Vec<(&TreeNode, f64, Vec<&TreeNode>)> = match (root, basis) {
+            (None, _) => Vec::new(),
+            (Some(root), ProbabilityBasis::Path) => {
+                Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, &edges).into_iter()
+                    .filter(|steps| steps.len() > 1)
+                    .filter_map(|steps| {
+                        let path: Vec<&TreeNode> = steps.iter().filter_map(|(id, _)| nodes_by_id.get(id).copied()).collect();
+                        let leaf = *path.last()?;
+                        (!leaf.is_invalidated).then(|| (leaf, steps.iter().map(|(_, p)| p).product::<f64>(), path))
+                    })
+                    .collect()
+            }
+            (Some(root), ProbabilityBasis::Marginal) => {
+                let root_id = root.id.as_ref().unwrap();
+                let marginals = Self::scenario_marginals(root_id, root.probability, &edges);
+                nodes.iter()
+                    .filter(|node| !node.is_invalidated)
+                    .filter(|node| node.id.as_ref().is_some_and(|id| id != root_id && edges.get(id).is_none_or(|children| children.is_empty())))
+                    .map(|leaf| (leaf, marginals.get(leaf.id.as_ref().unwrap()).copied().unwrap_or(0.0), Self::root_path(leaf, &nodes_by_id)))
+                    .collect()
+            }
+        };
+
+        let mut items = Vec::new();
+        let mut unscored = Vec::new();
+        for (leaf, probability, path) in placements {
+            match path.iter().rev().find_map(|node| node.impact) {
+                Some(impact) => items.push(RiskItem {
+                    node_id: leaf.display_id(),
+                    premise: leaf.premise.clone(),
+                    probability,
+                    impact,
+                    category: path.iter().rev().find_map(|node| node.category.clone()),
+                    risk: probability * impact,
+                }),
+                None => {
+                    let id = leaf.display_id();
+                    if !unscored.contains(&id) {
+                        unscored.push(id);
+                    }
+                }
+            }
+        }
+
+        RiskMatrix::build(basis, probability_levels, impact_levels, items, unscored)
+            .map_err(|e| TreeEngineError::InvalidInput("levels".to_string(), e))
+    }
+
+    /// Switches the current tree between scenario and argument mode.
+    ///
+    /// In argument mode children are arguments for or against their parent claim rather
+    /// than exclusive scenarios, so sibling probabilities may sum past their parent.
+    /// Switching back does not rewrite anything; `validate_coherence` flags any parent
+    /// whose children then exceed it.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the mode was stored
+    /// * `Err(TreeEngineError::NotFound)` - If no tree state has been initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_tree_mode(&mut self, mode: TreeMode) -> TreeResult<()> {
+        let mut tree_state = self.get_current_tree_state().await?;
+        tree_state.config.mode = mode;
+        self.update_tree_state(tree_state).await?;
+
+        self.publish_change().await;
+        Ok(())
+    }
+
+    /// Tags a node as an argument for or against its parent claim, or clears the tag with `None`.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `tag` - Stance towards the parent and strength (0-1) of the argument
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the node was updated
+    /// * `Err(TreeEngineError::InvalidInput)` - If the strength is outside [0,1]
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the node is the root claim
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn tag_argument(&mut self, node_id: String, tag: Option<ArgumentTag>) -> TreeResult<()> {
+        if let Some(tag) = tag {
+            ArgumentTag::new(tag.stance, tag.strength)
+                .map_err(|e| TreeEngineError::InvalidInput("strength".to_string(), e))?;
+        }
+
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+        if node.parent_id.is_none() {
+            return Err(TreeEngineError::OperationNotAllowed("The root is the claim under debate and takes no stance".to_string()));
+        }
+
+        node.argument = tag;
+        let _: Option<TreeNode> = self.db.update(&node_record_id).content(node).await?;
+
+        self.publish_change().await;
+        Ok(())
+    }
+
+    /// Scores an argument-mode tree bottom-up and judges its root claim.
+    ///
+    /// Each argument's plausibility starts at its node probability and is raised or
+    /// lowered by its own tagged sub-arguments; the root claim starts undecided. Pruned
+    /// nodes and cross-links are ignored, and untagged children are listed but not scored.
+    ///
+    /// # Returns
+    /// * `Ok(ArgumentMap)` - Verdict, score and the strongest arguments on each side
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the tree is not in argument mode
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn argument_map(&self) -> TreeResult<ArgumentMap> {
+        let state = self.get_current_tree_state().await?;
+        if state.config.mode != TreeMode::Argument {
+            return Err(TreeEngineError::OperationNotAllowed("The tree is in scenario mode; switch it to argument mode first".to_string()));
+        }
+        let root_id = state.config.root_id.ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let root = nodes_by_id.get(&root_id).ok_or_else(|| TreeEngineError::NotFound(root_id.to_string()))?;
+
+        Ok(Self::argument_node(root, &nodes_by_id).evaluate())
+    }
+
+    /// The argument rooted at `node`, with its active children nested inside.
+    fn argument_node(node: &TreeNode, nodes_by_id: &HashMap<RecordId, &TreeNode>) -> ArgumentNode {
+        ArgumentNode {
+            node_id: node.display_id(),
+            premise: node.premise.clone(),
+            base: node.probability,
+            tag: node.argument,
+            children: node.children.iter()
+                .filter_map(|id| nodes_by_id.get(id))
+                .filter(|child| !child.is_invalidated)
+                .map(|child| Self::argument_node(child, nodes_by_id))
+                .collect(),
+        }
+    }
+
+    /// Exports the tree as a Bayesian network in XMLBIF or plain-text BIF.
+    ///
+    /// Every node with active children becomes one variable whose states are those
+    /// children, named by friendly id, plus `other` for probability the children leave
+    /// unassigned. Its parent is the variable holding the node itself as a state; under
+    /// any other parent state it takes the `na` state. Node probabilities are read as the
+    /// chance of reaching the node, as the sibling-sum constraint implies, so each CPT
+    /// entry is the child's probability divided by its parent's. Premises are kept as
+    /// variable properties. Pruned nodes and cross-links are left out.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The network document
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If the root has no children yet
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn export_bayes_net(&self, format: BayesNetFormat) -> TreeResult<String> {
+        let state = self.get_current_tree_state().await?;
+        let root_id = state.config.root_id.ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let active_children = |node: &TreeNode| -> Vec<&TreeNode> {
+            node.children.iter()
+                .filter_map(|id| nodes_by_id.get(id).copied())
+                .filter(|child| !child.is_invalidated)
+                .collect()
+        };
+
+        // Breadth-first from the root, so each parent variable precedes its children
+        let mut variables: Vec<BayesVariable> = Vec::new();
+        let mut queue: VecDeque<(&TreeNode, Option<usize>)> = nodes_by_id.get(&root_id)
+            .map(|root| (*root, None))
+            .into_iter()
+            .collect();
+        while let Some((node, parent_variable)) = queue.pop_front() {
+            let children = active_children(node);
+            if children.is_empty() {
+                continue;
+            }
+
+            let name = bayes_net::identifier(&node.display_id());
+            let mut states: Vec<String> = children.iter().map(|child| bayes_net::identifier(&child.display_id())).collect();
+            let mut reached: Vec<f64> = children.iter()
+                .map(|child| if node.probability > 0.0 { (child.probability / node.probability).min(1.0) } else { 0.0 })
+                .collect();
+            // Argument-mode trees may overshoot their parent; scale them back to a distribution
+            let total: f64 = reached.iter().sum();
+            if total > 1.0 {
+                reached.iter_mut().for_each(|probability| *probability /= total);
+            }
+            if 1.0 - total > 1e-6 {
+                states.push(OTHER_STATE.to_string());
+                reached.push(1.0 - total);
+            }
+
+            let mut properties = BTreeMap::from([(PREMISE_PROPERTY.to_string(), node.premise.clone())]);
+            for (state, child) in states.iter().zip(&children) {
+                properties.insert(format!("{}{}", OUTCOME_PROPERTY_PREFIX, state), child.premise.clone());
+            }
+
+            let (parent, table) = match parent_variable {
+                None => (None, vec![reached]),
+                Some(index) => {
+                    let parent: &BayesVariable = &variables[index];
+                    states.push(NOT_APPLICABLE_STATE.to_string());
+                    reached.push(0.0);
+                    let not_applicable: Vec<f64> = (0..states.len()).map(|i| if i + 1 == states.len() { 1.0 } else { 0.0 }).collect();
+                    let table = parent.states.iter()
+                        .map(|state| if *state == name { reached.clone() } else { not_applicable.clone() })
+                        .collect();
+                    (Some(parent.name.clone()), table)
+                }
+            };
+
+            let index = variables.len();
+            variables.push(BayesVariable { name, states, parent, table, properties });
+            queue.extend(children.into_iter().map(|child| (child, Some(index))));
+        }
+
+        if variables.is_empty() {
+            return Err(TreeEngineError::OperationNotAllowed("The tree has no branches to export yet".to_string()));
+        }
+        Ok(BayesNet { name: state.tree_id, variables }.render(format))
+    }
+
+    /// Creates a new tree from a tree-shaped Bayesian network in XMLBIF or BIF.
+    ///
+    /// The root variable's states become the top-level branches and each further variable
+    /// expands the state of its parent under which it applies, as written by
+    /// `export_bayes_net`. The whole tree is validated like `add_subtree` before anything
+    /// is written, so an invalid network leaves the current tree in place.
+    ///
+    /// # Arguments
+    /// * `network` - XMLBIF (starting with `<`) or plain-text BIF document
+    /// * `complexity` - Complexity of the new tree (1-10), setting its depth and branch limits
+    ///
+    /// # Returns
+    /// * `Ok(SubtreeInsertion)` - The nodes created under the new root
+    /// * `Err(TreeEngineError::InvalidInput)` - If the network cannot be parsed, is not
+    ///   tree-shaped, or does not fit the tree limits
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn import_bayes_net(&mut self, network: String, complexity: i64) -> TreeResult<SubtreeInsertion> {
+        let invalid = |e: String| TreeEngineError::InvalidInput("network".to_string(), e);
+        let net = BayesNet::parse(&network).map_err(invalid)?;
+        let (premise, nodes) = net.to_subtree().map_err(invalid)?;
+        if nodes.is_empty() {
+            return Err(invalid("The network assigns no probability to any state".to_string()));
+        }
+
+        let anchor = SubtreeAnchor { probability: 1.0, depth: 0, existing_children: 0, allocated_probability: 0.0, gated: false };
+        let errors = SubtreeNode::validate_all(&nodes, &anchor, &TreeConfig::new(complexity));
+        if !errors.is_empty() {
+            return Err(invalid(errors.join("; ")));
+        }
+
+        let root_id = self.create_tree(premise, complexity).await?;
+        self.add_subtree(root_id, nodes).await
+    }
+
+    /// Orders the tree's scenarios chronologically.
+    ///
+    /// Each root-to-leaf path of the scenario DAG is dated by the window of its most
+    /// specific dated node, and lists every dated node along it as a milestone.
+    /// Paths ending in invalidated leaves are skipped; undated paths come last.
+    ///
+    /// # Returns
+    /// * `Ok(Timeline)` - Scenarios with their windows, probabilities and milestones
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn timeline(&self) -> TreeResult<Timeline> {
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let links = self.list_cross_links().await?;
+        let edges = Self::scenario_edges(&nodes, &links);
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let paths = match nodes.iter().find(|node| node.parent_id.is_none()) {
+            Some(root) => Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, &edges),
+            None => Vec::new(),
+        };
+
+        let scenarios = paths.into_iter()
+            .filter(|steps| steps.len() > 1)
+            .filter_map(|steps| {
+                let path_nodes: Vec<&TreeNode> = steps.iter()
+                    .filter_map(|(id, _)| nodes_by_id.get(id).copied())
+                    .collect();
+                let leaf = *path_nodes.last()?;
+                if leaf.is_invalidated {
+                    return None;
+                }
+                let milestones: Vec<TimelineMilestone> = path_nodes.iter()
+                    .filter_map(|node| node.time_window.map(|window| TimelineMilestone {
+                        node_id: node.display_id(),
+                        premise: node.premise.clone(),
+                        window,
+                    }))
+                    .collect();
+                Some(TimelineScenario {
+                    leaf_id: leaf.display_id(),
+                    premises: path_nodes.iter().map(|node| node.premise.clone()).collect(),
+                    probability: steps.iter().map(|(_, p)| p).product::<f64>(),
+                    window: milestones.last().map(|milestone| milestone.window),
+                    milestones,
+                })
+            })
+            .collect();
+
+        Ok(Timeline::new(chrono::Utc::now().date_naive(), scenarios))
+    }
+
+    /// Full copy of the live tree: its state, every node and every cross-link.
+    pub async fn snapshot(&self) -> TreeResult<TreeCheckpoint> {
+        self.current_snapshot().await
+    }
+
+    /// The last report `export_paths` produced for the current tree, if any.
+    pub async fn latest_report(&self) -> TreeResult<Option<AnalysisResult>> {
+        let state = self.get_current_tree_state().await?;
+        let report: Option<AnalysisResult> = self.db.select(("report", state.tree_id.as_str())).await?;
+        Ok(report)
+    }
+
+    /// Exports paths or nodes as a flat table for spreadsheets and notebooks.
+    ///
+    /// Paths come from the latest `export_paths` report, in report order; nodes are every
+    /// node of the live tree as shown by `inspect_tree`, ordered by depth then friendly id.
+    /// Every row carries `tabular::TABULAR_SCHEMA_VERSION` and the tree id, and columns
+    /// keep a fixed order so downstream scripts can rely on them.
+    ///
+    /// # Arguments
+    /// * `table` - Which rows to export
+    /// * `format` - CSV with a header row, or JSON Lines
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The table
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - If paths are requested before any report exists
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn export_table(&self, table: TabularTable, format: TabularFormat) -> TreeResult<String> {
+        let state = self.get_current_tree_state().await?;
+        let rendered = match table {
+            TabularTable::Paths => {
+                let report = self.latest_report().await?.ok_or_else(|| TreeEngineError::OperationNotAllowed(
+                    "No report exported yet; run export_paths first".to_string()))?;
+                let records: Vec<PathRecord> = report.surviving_paths.iter().enumerate()
+                    .map(|(i, path)| PathRecord::new(&state.tree_id, i + 1, path))
+                    .collect();
+                tabular::render(&records, format)
+            }
+            TabularTable::Nodes => {
+                let visualization = self.inspect_tree().await?;
+                let mut nodes: Vec<&NodeVisualization> = visualization.node_details.values().collect();
+                nodes.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.friendly_id.cmp(&b.friendly_id)));
+                let records: Vec<NodeRecord> = nodes.into_iter().map(|node| NodeRecord::new(&state.tree_id, node)).collect();
+                tabular::render(&records, format)
+            }
+        };
+        rendered.map_err(TreeEngineError::DatabaseError)
+    }
+
+    /// Starts a new tree from a Markdown outline.
+    ///
+    /// The `# heading` becomes the root premise and each `- Premise (p=0.4, c=7): reasoning`
+    /// bullet a node under the bullet it is indented beneath. Nodes are checked with the
+    /// same rules as `add_leaf` before the current tree is replaced, and every problem is
+    /// reported with its line number.
+    ///
+    /// # Arguments
+    /// * `outline` - The outline text
+    /// * `complexity` - Complexity of the new tree (1-10)
+    ///
+    /// # Returns
+    /// * `Ok(SubtreeInsertion)` - The created nodes under the new root
+    /// * `Err(TreeEngineError::InvalidInput)` - If any line is malformed or breaks a tree limit
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn import_outline(&mut self, outline: String, complexity: i64) -> TreeResult<SubtreeInsertion> {
+        let invalid = |errors: Vec<String>| TreeEngineError::InvalidInput("outline".to_string(), errors.join("; "));
+        let outline = Outline::parse(&outline).map_err(invalid)?;
+        let errors = outline.validate(&TreeConfig::new(complexity));
+        if !errors.is_empty() {
+            return Err(invalid(errors));
+        }
+
+        let root_id = self.create_tree(outline.premise, complexity).await.map_err(|e| match e {
+            TreeEngineError::InvalidInput(field, message) if field == "premise" =>
+                invalid(vec![format!("line {}: {}", outline.premise_line, message)]),
+            e => e,
+        })?;
+        self.add_subtree(root_id, outline.nodes).await
+    }
+
+    /// Writes the tree as a Markdown outline that `import_outline` reads back.
+    ///
+    /// Follows primary children only; pruned nodes and cross-links are left out.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The outline, two spaces of indentation per level
+    /// * `Err(TreeEngineError::NotFound)` - If there is no tree yet
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    pub async fn export_outline(&self) -> TreeResult<String> {
+        let state = self.get_current_tree_state().await?;
+        let root_id = state.config.root_id.ok_or_else(|| TreeEngineError::NotFound("root node".to_string()))?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let root = nodes_by_id.get(&root_id).ok_or_else(|| TreeEngineError::NotFound(root_id.to_string()))?;
+
+        Ok(Outline::render(&root.premise, &Self::outline_nodes(root, &nodes_by_id)))
+    }
+
+    /// The active children of `node` with their own children nested inside.
+    fn outline_nodes(node: &TreeNode, nodes_by_id: &HashMap<RecordId, &TreeNode>) -> Vec<SubtreeNode> {
+        node.children.iter()
+            .filter_map(|id| nodes_by_id.get(id))
+            .filter(|child| !child.is_invalidated)
+            .map(|child| SubtreeNode {
+                premise: child.premise.clone(),
+                reasoning: child.reasoning.clone(),
+                probability: child.probability,
+                confidence: child.confidence,
+                children: Self::outline_nodes(child, nodes_by_id),
+            })
+            .collect()
+    }
+
+    /// Marks a node's children as a mutually exclusive, collectively exhaustive set, or clears the mark.
+    ///
+    /// `validate_coherence` then reports the node unless its active children, cross-linked
+    /// ones included, sum to its probability within `EXHAUSTIVE_TOLERANCE`. With `residual`,
+    /// an "other / unanticipated" child is added (or kept) and resized after every change to
+    /// its siblings to hold whatever probability they leave; it gives way to new siblings
+    /// and is skipped by prune and balance operations. Clearing either flag keeps an
+    /// existing residual as an ordinary child.
+    ///
+    /// # Arguments
+    /// * `node_id` - Node reference (friendly alias, record id or unambiguous prefix)
+    /// * `exhaustive` - Whether the children form a complete set of outcomes
+    /// * `residual` - Whether to maintain a residual child; ignored unless `exhaustive`
+    ///
+    /// # Returns
+    /// * `Ok(Option<String>)` - Friendly id of the residual child, if one is maintained
+    /// * `Err(TreeEngineError::OperationNotAllowed)` - In argument-mode trees, or if the residual
+    ///   cannot be attached (pruned node, maximum depth or branch limit)
+    /// * `Err(TreeEngineError::NotFound)` - If the node doesn't exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If database operations fail
+    pub async fn set_exhaustive(&mut self, node_id: String, exhaustive: bool, residual: bool) -> TreeResult<Option<String>> {
+        let tree_state = self.get_current_tree_state().await?;
+        if exhaustive && !tree_state.config.enforces_sibling_sums() {
+            return Err(TreeEngineError::OperationNotAllowed(
+                "Arguments in argument-mode trees are not exclusive outcomes and cannot be exhaustive".to_string()));
+        }
+
+        let node_record_id = self.resolve_node_ref(&node_id).await?;
+        let node: Option<TreeNode> = self.db.select(&node_record_id).await?;
+        let mut node = node.ok_or_else(|| TreeEngineError::NotFound(node_id.clone()))?;
+
+        let keep_residual = exhaustive && residual;
+        let mut residual_id = None;
+        let mut changed = Vec::new();
+        for child_id in &node.children {
+            let Some(mut child) = self.db.select::<Option<TreeNode>>(child_id).await? else {
+                continue;
+            };
+            if !child.residual {
+                continue;
+            }
+            if keep_residual && residual_id.is_none() {
+                residual_id = Some(child.display_id());
+            } else {
+                child.residual = false;
+                changed.push(child);
+            }
+        }
+
+        if keep_residual && residual_id.is_none() {
+            Self::check_attachment(&tree_state.config, &node, self.allocated_probability(&node).await?, 0.0, 0)?;
+            let mut leaf = TreeNode::new_leaf(
+                RESIDUAL_PREMISE.to_string(),
+                "Auto-managed: holds the probability the other outcomes leave unassigned".to_string(),
+                0.0, 5, node_record_id.clone(), node.depth + 1);
+            let leaf_id = RecordId::from(("node", uuid::Uuid::new_v4().simple().to_string()));
+            leaf.id = Some(leaf_id.clone());
+            leaf.friendly_id = self.allocate_friendly_id().await?;
+            leaf.residual = true;
+            residual_id = Some(leaf.display_id());
+            node.add_child(leaf_id);
+            node.expand_to_branch();
+            changed.push(leaf);
+        }
+
+        node.exhaustive = exhaustive;
+        changed.push(node);
+        self.store_with_residuals(changed).await?;
+
+        self.publish_change().await;
+        Ok(residual_id)
+    }
+
+    /// Builds an unsaved snapshot of the live tree.
+    async fn current_snapshot(&self) -> TreeResult<TreeCheckpoint> {
+        let state = self.get_current_tree_state().await?;
+        let nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let cross_links = self.list_cross_links().await?;
+
+        Ok(TreeCheckpoint {
+            id: None,
+            name: CURRENT_TREE_REF.to_string(),
+            tree_id: state.tree_id.clone(),
+            state,
+            nodes,
+            cross_links,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Resolves "current", a checkpoint name or a tree id to a snapshot.
+    async fn load_snapshot(&self, reference: &str) -> TreeResult<TreeCheckpoint> {
+        let reference = reference.trim();
+        if reference.eq_ignore_ascii_case(CURRENT_TREE_REF) {
+            return self.current_snapshot().await;
+        }
+
+        let named: Option<TreeCheckpoint> = self.db.select(("checkpoint", reference)).await?;
+        if let Some(checkpoint) = named {
+            return Ok(checkpoint);
+        }
+
+        if let Ok(state) = self.get_current_tree_state().await {
+            if state.tree_id == reference {
+                return self.current_snapshot().await;
+            }
+        }
+
+        let mut response = self.db
+            .query("SELECT * FROM checkpoint WHERE tree_id = $tree_id ORDER BY created_at DESC LIMIT 1")
+            .bind(("tree_id", reference.to_string()))
+            .await?;
+        let latest: Vec<TreeCheckpoint> = response.take(0)?;
+        latest.into_iter().next()
+            .ok_or_else(|| TreeEngineError::NotFound(format!("checkpoint or tree '{}'", reference)))
+    }
+
+    /// Generates a comprehensive visualization and analysis of the current probability tree.
+    ///
+    /// This method performs deep analysis of the tree structure, calculating statistics,
+    /// generating ASCII visualization, and providing detailed node information. It's
+    /// primarily used for debugging, monitoring, and presenting tree state to users.
+    ///
+    /// # Returns
+    /// * `Ok(TreeVisualization)` - Complete tree analysis including:
+    ///   - Statistical summary (node count, depth, probabilities, etc.)
+    ///   - ASCII tree representation
+    ///   - Detailed node information and relationships
+    ///   - Active path analysis
+    /// * `Err(TreeEngineError::NotFound)` - If no tree state is initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    ///
+    /// # Generated Statistics
+    /// - Total number of nodes in the tree
+    /// - Maximum depth reached
+    /// - Number of active probability paths
+    /// - Average probability across all nodes
+    /// - Complexity score based on tree structure
+    /// - Distribution of confidence levels
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Test premise".to_string(), 5).await?;
+    /// let visualization = service.inspect_tree().await?;
+    /// println!("Tree has {} nodes with max depth {}",
+    ///          visualization.statistics.total_nodes,
+    ///          visualization.statistics.max_depth);
+    /// println!("ASCII Tree:\n{}", visualization.ascii_tree);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn inspect_tree(&self) -> TreeResult<TreeVisualization> {
+        let tree_state = self.get_current_tree_state().await?;
+
+        // Get all nodes with comprehensive information
+        let mut nodes_query = self.db.query("SELECT * FROM node ORDER BY depth, probability DESC").await?;
+        let nodes: Vec<TreeNode> = nodes_query.take(0)?;
+
+        // Get leaf nodes and invalidated nodes
+        let leaf_nodes = self.get_leaf_nodes().await?;
+        let invalidated_nodes = self.get_invalidated_nodes().await?;
+
+        // Estimates whose review date or expected window has passed
+        let today = chrono::Utc::now().date_naive();
+        let stale_nodes: Vec<(&TreeNode, String)> = nodes.iter()
+            .filter_map(|node| node.staleness(today).map(|reason| (node, reason)))
+            .collect();
+
+        // Build comprehensive node details (no ASCII generation)
+        let mut node_details = HashMap::new();
+        let mut max_depth = 0;
+        let mut total_probability = 0.0;
+        let mut confidence_distribution = std::collections::HashMap::new();
+        let mut depth_distribution = std::collections::HashMap::new();
+        let mut probability_distribution = Vec::new();
+        let mut premise_analysis = Vec::new();
+        let mut reasoning_analysis = Vec::new();
+
+        // Map record ids to friendly aliases so every reference in the output is resolvable
+        let aliases: HashMap<RecordId, String> = nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap().clone(), node.display_id()))
+            .collect();
+        let alias_of = |id: &RecordId| aliases.get(id).cloned().unwrap_or_else(|| id.to_string());
+
+        // Cross-links add extra parents on top of each node's primary parent
+        let links = self.list_cross_links().await?;
+        let mut linked_parents: HashMap<&RecordId, Vec<String>> = HashMap::new();
+        let mut linked_children: HashMap<&RecordId, Vec<String>> = HashMap::new();
+        for link in &links {
+            linked_parents.entry(&link.child).or_default().push(alias_of(&link.parent));
+            linked_children.entry(&link.parent).or_default().push(alias_of(&link.child));
+        }
+        let locked = Self::locked_ids(&nodes);
+
+        for node in &nodes {
+            let friendly_id = node.display_id();
+            let node_id = node.id.as_ref().unwrap();
+            let linked_children_ids = linked_children.get(node_id).cloned().unwrap_or_default();
+
+            // Enhanced node details with complete information
+            node_details.insert(friendly_id.clone(), NodeVisualization {
+                friendly_id: friendly_id.clone(),
+                premise_summary: node.premise.clone(),
+                full_premise: node.premise.clone(),
+                full_reasoning: node.reasoning.clone(),
+                probability: node.probability,
+                depth: node.depth as u32,
+                children_count: node.children.len(),
+                children_ids: node.children.iter().map(alias_of).collect(),
+                parent_id: node.parent_id.as_ref().map(alias_of),
+                linked_parent_ids: linked_parents.get(node_id).cloned().unwrap_or_default(),
+                is_leaf: node.is_leaf() && linked_children_ids.is_empty(),
+                linked_children_ids,
+                can_expand: node.can_expand(),
+                confidence: node.confidence,
+                status: if node.is_invalidated {
+                    NodeStatus::Invalidated
+                } else {
+                    NodeStatus::Active
+                },
+                evidence_count: node.evidence.len(),
+                time_window: node.time_window,
+                review_by: node.review_by,
+                locked: locked.contains(node_id),
+                gate: node.gate,
+                argument: node.argument,
+                exhaustive: node.exhaustive,
+                residual: node.residual,
+            });
+
+            // Collect analytics data
+            *confidence_distribution.entry(node.confidence).or_insert(0) += 1;
+            *depth_distribution.entry(node.depth as u32).or_insert(0) += 1;
+            probability_distribution.push(node.probability);
+            premise_analysis.push(node.premise.len());
+            if !node.reasoning.trim().is_empty() {
+                reasoning_analysis.push(node.reasoning.len());
+            }
+
+            if node.depth > max_depth {
+                max_depth = node.depth;
+            }
+            total_probability += node.probability;
+        }
+
+        // Calculate comprehensive statistics
+        let avg_probability = if nodes.is_empty() { 0.0 } else { total_probability / nodes.len() as f64 };
+        let total_nodes = nodes.len();
+        let active_nodes = nodes.iter().filter(|n| !n.is_invalidated).count();
+        let complexity_score = (max_depth as f64 * total_nodes as f64).sqrt();
+        let scenario_paths = match nodes.iter().find(|node| node.parent_id.is_none()) {
+            Some(root) => Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, &Self::scenario_edges(&nodes, &links))
+                .iter()
+                .filter(|path| path.len() > 1)
+                .count(),
+            None => 0,
+        };
+
+        // Probability statistics
+        probability_distribution.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let prob_median = if probability_distribution.is_empty() {
+            0.0
+        } else {
+            probability_distribution[probability_distribution.len() / 2]
+        };
+        let prob_min = probability_distribution.first().copied().unwrap_or(0.0);
+        let prob_max = probability_distribution.last().copied().unwrap_or(0.0);
+
+        // Content analysis
+        let avg_premise_length = if premise_analysis.is_empty() { 0.0 } else {
+            premise_analysis.iter().sum::<usize>() as f64 / premise_analysis.len() as f64
+        };
+        let avg_reasoning_length = if reasoning_analysis.is_empty() { 0.0 } else {
+            reasoning_analysis.iter().sum::<usize>() as f64 / reasoning_analysis.len() as f64
+        };
+
+        Ok(TreeVisualization {
+            // No ASCII generation here - only data
+            ascii_tree: String::new(),
+            node_details,
+            tree_metadata: TreeMetadata {
+                tree_id: tree_state.tree_id.clone(),
+                complexity: tree_state.config.complexity,
+                config: tree_state.config.clone(),
+                created_at: tree_state.created_at,
+                status: "Active".to_string(),
+            },
+            statistics: TreeStatsSummary {
+                total_nodes,
+                active_nodes,
+                invalidated_nodes: invalidated_nodes.len(),
+                active_paths: leaf_nodes.len(),
+                cross_links: links.len(),
+                scenario_paths,
+                max_depth: max_depth as u32,
+                avg_depth: if total_nodes > 0 {
+                    depth_distribution.iter().map(|(k, v)| *k as f64 * *v as f64).sum::<f64>() / total_nodes as f64
+                } else { 0.0 },
+                avg_probability,
+                probability_median: prob_median,
+                probability_range: (prob_min, prob_max),
+                complexity_score,
+                avg_premise_length,
+                avg_reasoning_length,
+            },
+            distributions: TreeDistributions {
+                confidence_distribution,
+                depth_distribution,
+                probability_distribution,
+            },
+            active_paths: leaf_nodes.iter().enumerate().map(|(i, leaf)| {
+                ActivePath {
+                    path_number: i + 1,
+                    leaf_id: leaf.display_id(),
+                    premise: leaf.premise.clone(),
+                    probability: leaf.probability,
+                    confidence: leaf.confidence,
+                }
+            }).collect(),
+            recommendations: self.generate_recommendations(&leaf_nodes, max_depth as u32, avg_probability, &invalidated_nodes, total_nodes, &stale_nodes),
+        })
+    }
+
+    /// Generate analysis recommendations based on tree state
+    fn generate_recommendations(&self, leaf_nodes: &[TreeNode], max_depth: u32, avg_probability: f64, invalidated_nodes: &[TreeNode], total_nodes: usize, stale_nodes: &[(&TreeNode, String)]) -> Vec<String> {
+        let mut recommendations = Vec::new();
+
+        for (node, reason) in stale_nodes {
+            recommendations.push(format!("⏰ Stale estimate [{}] {}: {} - re-estimate it or record the outcome", node.display_id(), node.premise, reason));
+        }
+
+        if leaf_nodes.len() < 2 {
+            recommendations.push("⚠️  Consider adding more leaf nodes for richer analysis".to_string());
+        }
+        if max_depth < 2 {
+            recommendations.push("⚠️  Tree depth is shallow - consider expanding promising branches".to_string());
+        }
+        if avg_probability < 0.3 {
+            recommendations.push("⚠️  Low average probability - review premise strength".to_string());
+        }
+        if invalidated_nodes.len() > total_nodes / 3 {
+            recommendations.push("⚠️  High invalidation rate - consider tree restructuring".to_string());
+        }
+        if leaf_nodes.iter().any(|n| n.probability > 0.8) {
+            recommendations.push("✅ High-confidence paths identified - good for decision making".to_string());
+        }
+
+        recommendations
+    }
+
+    /// Analyzes and validates probability values throughout the entire tree structure.
+    ///
+    /// This method performs comprehensive probability validation across all nodes,
+    /// checking for range violations, consistency issues, and providing suggestions
+    /// for maintaining probability coherence within the tree structure.
+    ///
+    /// # Returns
+    /// * `Ok(ValidationResult)` - Complete validation report including:
+    ///   - Overall validation status (valid/invalid)
+    ///   - List of probability violations found
+    ///   - Suggested corrections and improvements
+    ///   - Statistical summary of probability health
+    /// * `Err(TreeEngineError::NotFound)` - If tree state is not initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    ///
+    /// # Validation Checks
+    /// - Probability values must be within [0.0, 1.0] range
+    /// - Confidence levels must be within [1, 10] range
+    /// - Child node probabilities should not exceed parent probabilities
+    /// - Sum of sibling probabilities should be ≤ 1.0
+    /// - Minimum probability threshold compliance
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Test premise".to_string(), 5).await?;
+    /// let validation = service.probability_status().await?;
+    ///
+    /// if validation.is_valid {
+    ///     println!("All probabilities are within valid ranges");
+    /// } else {
+    ///     println!("Found {} violations", validation.violations.len());
+    ///     for suggestion in &validation.suggestions {
+    ///         println!("Suggestion: {}", suggestion);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn probability_status(&self) -> TreeResult<ValidationResult> {
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let tree_state = self.get_current_tree_state().await?;
+
+        let mut violations = Vec::new();
+        let mut _warnings: Vec<ValidationViolation> = Vec::new();
+
+        // Check each node for probability violations
+        for node in &all_nodes {
+            if node.probability < 0.0 || node.probability > 1.0 {
+                violations.push(ValidationViolation {
+                    node_id: node.id.as_ref().unwrap().to_string(),
+                    violation_type: ViolationType::ProbabilityRange,
+                    message: format!("Node probability {} is outside valid range [0,1]", node.probability),
+                    severity: Severity::Error,
+                });
+            }
+
+            if node.probability < tree_state.config.min_probability {
+                violations.push(ValidationViolation {
+                    node_id: node.id.as_ref().unwrap().to_string(),
+                    violation_type: ViolationType::ProbabilityRange,
+                    message: format!("Node probability {} is below minimum threshold {}",
+                                       node.probability, tree_state.config.min_probability),
+                    severity: Severity::Warning,
+                });
+            }
+        }
+
+        let is_valid = violations.is_empty();
+
+        Ok(ValidationResult {
+            is_valid,
+            violations,
+            suggestions: if is_valid {
+                vec!["Tree appears valid".to_string()]
+            } else {
+                vec!["Fix probability range violations".to_string()]
+            },
+        })
+    }
+
+    /// Defines the full-text analyzer and indexes over node premises and reasoning.
+    ///
+    /// The statements are idempotent, so this is safe to call before every search.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the analyzer and indexes exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If the definitions fail
+    async fn ensure_search_index(&self) -> TreeResult<()> {
+        self.db.query(
+            "DEFINE ANALYZER IF NOT EXISTS node_text TOKENIZERS blank, class, punct FILTERS lowercase, ascii, snowball(english);
+             DEFINE INDEX IF NOT EXISTS node_premise_search ON TABLE node FIELDS premise SEARCH ANALYZER node_text BM25;
+             DEFINE INDEX IF NOT EXISTS node_reasoning_search ON TABLE node FIELDS reasoning SEARCH ANALYZER node_text BM25;"
+        ).await?.check()?;
+        Ok(())
+    }
+
+    /// Status a search hit reports for `node`: pruned, expanded into children, or still open.
+    fn search_status(node: &TreeNode) -> NodeStatus {
+        if node.is_invalidated {
+            NodeStatus::Invalidated
+        } else if node.children.is_empty() {
+            NodeStatus::Active
+        } else {
+            NodeStatus::Expanded
+        }
+    }
+
+    /// Returns the chain of nodes from the root down to `node`, inclusive.
+    fn root_path<'a>(node: &'a TreeNode, nodes_by_id: &HashMap<RecordId, &'a TreeNode>) -> Vec<&'a TreeNode> {
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(parent) = current.parent_id.as_ref().and_then(|id| nodes_by_id.get(id)) {
+            // Guard against malformed cycles in stored data
+            if path.iter().any(|visited| visited.id == parent.id) {
+                break;
+            }
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Builds the scenario DAG adjacency: each node's tree children weighted by their
+    /// own probability, followed by its cross-linked children weighted by the link.
+    fn scenario_edges(nodes: &[TreeNode], links: &[CrossLink]) -> ScenarioEdges {
+        let mut edges: ScenarioEdges = HashMap::new();
+        for node in nodes {
+            if let (Some(id), Some(parent_id)) = (&node.id, &node.parent_id) {
+                edges.entry(parent_id.clone()).or_default().push((id.clone(), node.probability));
+            }
+        }
+        for link in links {
+            edges.entry(link.parent.clone()).or_default().push((link.child.clone(), link.probability));
+        }
+        edges
+    }
+
+    /// Checks the propositions attached to nodes for logical contradictions.
+    ///
+    /// Returns the contradictions found, a truth table over every atom in the tree
+    /// (when small enough), the nodes that no satisfiable path reaches, and a warning for
+    /// each node whose check was skipped. Each row of the truth table lists the leaves
+    /// whose whole path holds under that assignment. Formulas mentioning more than
+    /// `proposition::MAX_ATOMS` atoms together are left unchecked.
+    fn propositional_analysis(all_nodes: &[TreeNode], edges: &ScenarioEdges) -> (Vec<ContradictionResult>, Vec<TruthTableRow>, Vec<String>, Vec<ValidationViolation>) {
+        let mut contradictions = Vec::new();
+        let mut truth_table = Vec::new();
+        let mut eliminated_nodes = Vec::new();
+        let mut warnings = Vec::new();
+
+        let propositions: HashMap<RecordId, Proposition> = all_nodes.iter()
+            .filter_map(|node| {
+                let parsed = Proposition::parse(node.proposition.as_deref()?).ok()?;
+                Some((node.id.clone()?, parsed))
+            })
+            .collect();
+        let Some(root) = all_nodes.iter().find(|node| node.parent_id.is_none()) else {
+            return (contradictions, truth_table, eliminated_nodes, warnings);
+        };
+        if propositions.is_empty() {
+            return (contradictions, truth_table, eliminated_nodes, warnings);
+        }
+
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = all_nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let describe = |id: &RecordId| format!("{}: {}",
+            nodes_by_id.get(id).map(|node| node.premise.as_str()).unwrap_or_default(),
+            propositions[id]);
+        let is_unsatisfiable = |ids: &[&RecordId]| {
+            let formulas: Vec<&Proposition> = ids.iter().map(|id| &propositions[*id]).collect();
+            proposition::satisfying_assignment(&formulas).map(|witness| witness.is_none())
+        };
+        let mut skipped = HashSet::new();
+        let mut skip = |node_id: &RecordId, reason: String| {
+            if skipped.insert(node_id.clone()) {
+                warnings.push(ValidationViolation {
+                    node_id: node_id.to_string(),
+                    violation_type: ViolationType::LogicalIncoherence,
+                    message: format!("Satisfiability check skipped: {}", reason),
+                    severity: Severity::Warning,
+                });
+            }
+        };
+
+        // Walk each scenario path, stopping at the first node whose proposition
+        // cannot hold together with those above it
+        let paths = Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, edges);
+        let mut reachable: HashMap<&RecordId, bool> = HashMap::new();
+        let mut reported = HashSet::new();
+        for path in &paths {
+            let mut asserted: Vec<&RecordId> = Vec::new();
+            let mut consistent = true;
+            for (node_id, _) in path {
+                if consistent && propositions.contains_key(node_id) {
+                    asserted.push(node_id);
+                    let unsatisfiable = is_unsatisfiable(&asserted).unwrap_or_else(|e| {
+                        skip(node_id, e);
+                        false
+                    });
+                    if unsatisfiable {
+                        consistent = false;
+                        if reported.insert(node_id.clone()) {
+                            // Shrink to a minimal conflicting set, always keeping the offending node
+                            let mut core = asserted.clone();
+                            let mut i = 0;
+                            while i + 1 < core.len() {
+                                let without: Vec<&RecordId> = core.iter().enumerate()
+                                    .filter(|(j, _)| *j != i)
+                                    .map(|(_, id)| *id)
+                                    .collect();
+                                if is_unsatisfiable(&without) == Ok(true) {
+                                    core = without;
+                                } else {
+                                    i += 1;
+                                }
+                            }
+                            contradictions.push(ContradictionResult {
+                                node_id: node_id.to_string(),
+                                conflicting_premises: core.iter().map(|id| describe(*id)).collect(),
+                                explanation: "Propositions along this path are jointly unsatisfiable".to_string(),
+                            });
+                        }
+                    }
+                }
+                *reachable.entry(node_id).or_insert(false) |= consistent;
+            }
+        }
+        eliminated_nodes.extend(all_nodes.iter()
+            .filter_map(|node| node.id.as_ref())
+            .filter(|id| reachable.get(id) == Some(&false))
+            .map(|id| id.to_string()));
+
+        // Alternatives under the same parent must not be able to hold at once
+        for (parent_id, children) in edges {
+            if reachable.get(parent_id) == Some(&false) {
+                continue;
+            }
+            let context: Vec<&Proposition> = nodes_by_id.get(parent_id)
+                .map(|parent| Self::root_path(parent, &nodes_by_id))
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|node| node.id.as_ref().and_then(|id| propositions.get(id)))
+                .collect();
+            let children: Vec<&RecordId> = children.iter()
+                .map(|(child, _)| child)
+                .filter(|child| propositions.contains_key(*child))
+                .collect();
+
+            for (i, first) in children.iter().enumerate() {
+                for second in &children[i + 1..] {
+                    let mut formulas = context.clone();
+                    formulas.push(&propositions[*first]);
+                    formulas.push(&propositions[*second]);
+                    let witness = match proposition::satisfying_assignment(&formulas) {
+                        Ok(witness) => witness,
+                        Err(e) => {
+                            skip(parent_id, e);
+                            continue;
+                        }
+                    };
+                    if let Some(witness) = witness {
+                        let mut atoms: Vec<(&String, &bool)> = witness.iter().collect();
+                        atoms.sort();
+                        let witness = atoms.iter()
+                            .map(|(atom, value)| format!("{}={}", atom, value))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        contradictions.push(ContradictionResult {
+                            node_id: parent_id.to_string(),
+                            conflicting_premises: vec![describe(*first), describe(*second)],
+                            explanation: format!("Sibling propositions are not mutually exclusive: both hold when {}", witness),
+                        });
+                    }
+                }
+            }
+        }
+
+        let atoms: Vec<String> = propositions.values()
+            .flat_map(|formula| formula.atoms())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if atoms.len() <= MAX_TRUTH_TABLE_ATOMS {
+            for assignment in proposition::assignments(&atoms) {
+                let affected_nodes: Vec<String> = paths.iter()
+                    .filter(|path| path.len() > 1)
+                    .filter(|path| path.iter()
+                        .all(|(id, _)| propositions.get(id).is_none_or(|formula| formula.eval(&assignment))))
+                    .map(|path| path.last().unwrap().0.to_string())
+                    .collect();
+                truth_table.push(TruthTableRow {
+                    premises: assignment,
+                    is_consistent: !affected_nodes.is_empty(),
+                    affected_nodes,
+                });
+            }
+        }
+
+        (contradictions, truth_table, eliminated_nodes, warnings)
+    }
+
+    /// Returns true if `to` can be reached from `from` by following DAG edges.
+    fn reaches(edges: &ScenarioEdges, from: &RecordId, to: &RecordId) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(current) = stack.pop() {
+            if current == to {
+                return true;
+            }
+            if visited.insert(current) {
+                stack.extend(edges.get(current).into_iter().flatten().map(|(child, _)| child));
+            }
+        }
+        false
+    }
+
+    /// Enumerates every distinct root-to-leaf path through the scenario DAG.
+    ///
+    /// Each path is a list of `(node, step probability)` pairs starting at the root.
+    /// The step probability is the node's own, or the link's when the step crosses a
+    /// cross-link. A shared node appears once per distinct route that reaches it.
+    fn scenario_paths(root: &RecordId, root_probability: f64, edges: &ScenarioEdges) -> Vec<ScenarioPath> {
+        let mut paths = Vec::new();
+        let mut current = vec![(root.clone(), root_probability)];
+        Self::collect_paths(edges, &mut current, &mut paths);
+        paths
+    }
+
+    fn collect_paths(
+        edges: &ScenarioEdges,
+        current: &mut ScenarioPath,
+        paths: &mut Vec<ScenarioPath>,
+    ) {
+        let (node_id, _) = current.last().unwrap().clone();
+        let children: Vec<&(RecordId, f64)> = edges.get(&node_id).into_iter().flatten()
+            // Guard against malformed cycles in stored data
+            .filter(|(child, _)| !current.iter().any(|(visited, _)| visited == child))
+            .collect();
+
+        if children.is_empty() {
+            paths.push(current.clone());
+            return;
+        }
+
+        for (child, probability) in children {
+            current.push((child.clone(), *probability));
+            Self::collect_paths(edges, current, paths);
+            current.pop();
+        }
+    }
+
+    /// Probability mass reaching each node, summed over the distinct routes into it.
+    ///
+    /// Mass is propagated once per edge in topological order, so a shared sub-path
+    /// is credited once per route into it rather than once per leaf below it.
+    fn scenario_marginals(root: &RecordId, root_probability: f64, edges: &ScenarioEdges) -> HashMap<RecordId, f64> {
+        let mut in_degree: HashMap<&RecordId, usize> = HashMap::new();
+        for children in edges.values() {
+            for (child, _) in children {
+                *in_degree.entry(child).or_insert(0) += 1;
+            }
+        }
+
+        let mut marginals: HashMap<RecordId, f64> = HashMap::new();
+        marginals.insert(root.clone(), root_probability);
+        let mut ready = vec![root];
+        while let Some(node_id) = ready.pop() {
+            let mass = marginals.get(node_id).copied().unwrap_or(0.0);
+            for (child, probability) in edges.get(node_id).into_iter().flatten() {
+                *marginals.entry(child.clone()).or_insert(0.0) += mass * probability;
+                let remaining = in_degree.get_mut(child).unwrap();
+                *remaining -= 1;
+                if *remaining == 0 {
+                    ready.push(child);
+                }
+            }
+        }
+        marginals
+    }
+
+    /// Root-to-leaf paths of a snapshot ranked by probability, highest first.
+    ///
+    /// Each entry holds the node ids along the path, the path probability and the premises.
+    fn ranked_paths(snapshot: &TreeCheckpoint) -> Vec<RankedPath> {
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = snapshot.nodes.iter()
+            .filter_map(|node| node.id.clone().map(|id| (id, node)))
+            .collect();
+        let edges = Self::scenario_edges(&snapshot.nodes, &snapshot.cross_links);
+        let paths = match snapshot.nodes.iter().find(|node| node.parent_id.is_none()) {
+            Some(root) => Self::scenario_paths(root.id.as_ref().unwrap(), root.probability, &edges),
+            None => Vec::new(),
+        };
+
+        let mut ranked: Vec<RankedPath> = paths.into_iter()
+            .filter(|steps| steps.len() > 1)
+            .filter(|steps| !steps.last()
+                .and_then(|(id, _)| nodes_by_id.get(id))
+                .is_some_and(|leaf| leaf.is_invalidated))
+            .map(|steps| {
+                let probability = steps.iter().map(|(_, p)| p).product::<f64>();
+                let premises: Vec<String> = steps.iter()
+                    .map(|(id, _)| nodes_by_id.get(id).map(|node| node.premise.clone()).unwrap_or_default())
+                    .collect();
+                (steps.into_iter().map(|(id, _)| id).collect::<Vec<_>>(), probability, premises)
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.2.cmp(&b.2)));
+        ranked
+    }
+
+    /// Root-to-leaf paths whose rank or probability differ between two versions of the
+    /// same tree, which share record ids. Paths ending in pruned leaves have no rank.
+    fn path_changes(before: &TreeCheckpoint, after: &TreeCheckpoint) -> Vec<PathRankChange> {
+        let ranked_before = Self::ranked_paths(before);
+        let ranked_after = Self::ranked_paths(after);
+        let rank_in = |ranking: &[RankedPath], ids: &[RecordId]| {
+            ranking.iter().position(|(path, _, _)| path.as_slice() == ids)
+        };
+
+        let mut changes = Vec::new();
+        let mut compared: HashSet<&[RecordId]> = HashSet::new();
+        for (ids, _, premises) in ranked_before.iter().chain(ranked_after.iter()) {
+            if !compared.insert(ids.as_slice()) {
+                continue;
+            }
+            let rank_before = rank_in(&ranked_before, ids);
+            let rank_after = rank_in(&ranked_after, ids);
+            let probability_before = rank_before.map(|i| ranked_before[i].1);
+            let probability_after = rank_after.map(|i| ranked_after[i].1);
+            let probability_changed = match (probability_before, probability_after) {
+                (Some(before), Some(after)) => (before - after).abs() > 1e-9,
+                _ => true,
+            };
+            if rank_before != rank_after || probability_changed {
+                changes.push(PathRankChange {
+                    premises: premises.clone(),
+                    rank_before: rank_before.map(|i| i + 1),
+                    rank_after: rank_after.map(|i| i + 1),
+                    probability_before,
+                    probability_after,
+                });
+            }
+        }
+        changes
+    }
+
+    /// Pools a matched source node into a live node: confidence-weighted probability,
+    /// averaged confidence, combined reasoning and the union of both evidence lists.
+    fn pool_into(target: &mut TreeNode, source: &TreeNode) {
+        let target_weight = target.confidence.max(1) as f64;
+        let source_weight = source.confidence.max(1) as f64;
+        target.probability = (target.probability * target_weight + source.probability * source_weight)
+            / (target_weight + source_weight);
+        target.confidence = ((target_weight + source_weight) / 2.0).round() as i64;
+
+        let source_reasoning = source.reasoning.trim();
+        if !source_reasoning.is_empty() && !target.reasoning.contains(source_reasoning) {
+            target.reasoning = format!("{}\n[merged] {}", target.reasoning, source_reasoning);
+        }
+
+        for evidence in &source.evidence {
+            if !target.evidence.iter().any(|existing| existing.id == evidence.id) {
+                target.evidence.push(evidence.clone());
+            }
+        }
+    }
+
+    /// Computes the structural diff between two snapshots.
+    fn diff_snapshots(before: &TreeCheckpoint, after: &TreeCheckpoint, top_k: usize) -> TreeDiff {
+        let normalize = |premise: &str| premise.trim().to_lowercase();
+        let before_by_id: HashMap<&RecordId, &TreeNode> = before.nodes.iter()
+            .filter_map(|node| node.id.as_ref().map(|id| (id, node)))
+            .collect();
+
+        // Match nodes of the newer version to the older one: same record id first, then same premise
+        let mut matches: Vec<(&TreeNode, &TreeNode)> = Vec::new();
+        let mut matched_before: HashSet<&RecordId> = HashSet::new();
+        let mut unmatched_after = Vec::new();
+        for node in &after.nodes {
+            match node.id.as_ref().and_then(|id| before_by_id.get(id)) {
+                Some(&previous) => {
+                    matched_before.insert(previous.id.as_ref().unwrap());
+                    matches.push((previous, node));
+                }
+                None => unmatched_after.push(node),
+            }
+        }
+
+        let mut added_nodes = Vec::new();
+        for node in unmatched_after {
+            let previous = before.nodes.iter().find(|candidate| {
+                !matched_before.contains(candidate.id.as_ref().unwrap()) && normalize(&candidate.premise) == normalize(&node.premise)
+            });
+            match previous {
+                Some(previous) => {
+                    matched_before.insert(previous.id.as_ref().unwrap());
+                    matches.push((previous, node));
+                }
+                None => added_nodes.push(DiffNode {
+                    node_id: node.display_id(),
+                    premise: node.premise.clone(),
+                    probability: node.probability,
+                }),
+            }
+        }
+
+        let removed_nodes: Vec<DiffNode> = before.nodes.iter()
+            .filter(|node| !matched_before.contains(node.id.as_ref().unwrap()))
+            .map(|node| DiffNode {
+                node_id: node.display_id(),
+                premise: node.premise.clone(),
+                probability: node.probability,
+            })
+            .collect();
+
+        let mut probability_changes = Vec::new();
+        let mut confidence_changes = Vec::new();
+        let mut premise_changes = Vec::new();
+        for (previous, node) in &matches {
+            if (previous.probability - node.probability).abs() > 1e-9 {
+                probability_changes.push(ValueDelta {
+                    node_id: node.display_id(),
+                    premise: node.premise.clone(),
+                    before: previous.probability,
+                    after: node.probability,
+                });
+            }
+            if previous.confidence != node.confidence {
+                confidence_changes.push(ValueDelta {
+                    node_id: node.display_id(),
+                    premise: node.premise.clone(),
+                    before: previous.confidence as f64,
+                    after: node.confidence as f64,
+                });
+            }
+            if previous.premise != node.premise {
+                premise_changes.push(PremiseChange {
+                    node_id: node.display_id(),
+                    before: previous.premise.clone(),
+                    after: node.premise.clone(),
+                });
+            }
+        }
+
+        // Express newer paths in the older version's ids so the same route compares equal
+        let to_before_id: HashMap<&RecordId, &RecordId> = matches.iter()
+            .map(|(previous, node)| (node.id.as_ref().unwrap(), previous.id.as_ref().unwrap()))
+            .collect();
+        let ranked_before = Self::ranked_paths(before);
+        let ranked_after: Vec<RankedPath> = Self::ranked_paths(after).into_iter()
+            .map(|(ids, probability, premises)| {
+                let ids: Vec<RecordId> = ids.iter().map(|id| (*to_before_id.get(id).unwrap_or(&id)).clone()).collect();
+                (ids, probability, premises)
+            })
+            .collect();
+
+        let rank_in = |ranking: &[RankedPath], ids: &[RecordId]| {
+            ranking.iter().position(|(path, _, _)| path.as_slice() == ids)
+        };
+        let mut ranking_changes = Vec::new();
+        let mut compared: HashSet<&[RecordId]> = HashSet::new();
+        for (ids, _, premises) in ranked_before.iter().take(top_k).chain(ranked_after.iter().take(top_k)) {
+            if !compared.insert(ids.as_slice()) {
+                continue;
+            }
+            let rank_before = rank_in(&ranked_before, ids);
+            let rank_after = rank_in(&ranked_after, ids);
+            if rank_before != rank_after {
+                ranking_changes.push(PathRankChange {
+                    premises: rank_after.map(|i| ranked_after[i].2.clone()).unwrap_or_else(|| premises.clone()),
+                    rank_before: rank_before.map(|i| i + 1),
+                    rank_after: rank_after.map(|i| i + 1),
+                    probability_before: rank_before.map(|i| ranked_before[i].1),
+                    probability_after: rank_after.map(|i| ranked_after[i].1),
+                });
+            }
+        }
+
+        TreeDiff {
+            from: before.name.clone(),
+            to: after.name.clone(),
+            added_nodes,
+            removed_nodes,
+            probability_changes,
+            confidence_changes,
+            premise_changes,
+            top_k,
+            ranking_changes,
+        }
+    }
+
+    /// Searches tree nodes by text and attributes.
+    ///
+    /// Text matching runs over both premise and reasoning. In `Keyword` mode it is
+    /// backed by SurrealDB full-text indexes (BM25 ranking with English stemming);
+    /// in `Fuzzy` mode each query word is matched against its closest word in the
+    /// node so typos and partial words still match. Attribute filters are applied
+    /// on top of the text match, or alone when no text is given.
+    ///
+    /// # Arguments
+    /// * `query` - Text, mode and attribute filters:
+    ///   - `min_probability` / `max_probability`: node probability range
+    ///   - `min_confidence` / `max_confidence`: confidence range (1-10)
+    ///   - `min_depth` / `max_depth`: depth range (root is 0)
+    ///   - `status`: `Active` (childless), `Expanded` (has children) or `Invalidated` (pruned),
+    ///     matched against the status each hit reports
+    ///   - `metadata_keys`: keys that must all be present in the node metadata
+    ///   - `limit`: maximum number of hits returned (defaults to 20)
+    ///
+    /// # Returns
+    /// * `Ok(NodeSearchResult)` - Hits ordered by score, each with its root path
+    /// * `Err(TreeEngineError::InvalidInput)` - If a range is inverted or out of bounds
+    /// * `Err(TreeEngineError::DatabaseError)` - If database queries fail
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use std::sync::Arc;
+    /// # use surrealdb::Surreal;
+    /// # use deep_analytics::domain::services::tree_engine_service::TreeEngineService;
+    /// # use deep_analytics::domain::models::{NodeSearchQuery, SearchMode};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await?;
+    /// # let mut service = TreeEngineService::new(Arc::new(db));
+    /// # service.create_tree("Will the product launch on time?".to_string(), 5).await?;
+    /// let result = service.search_nodes(NodeSearchQuery {
+    ///     text: Some("supplier delays".to_string()),
+    ///     mode: SearchMode::Fuzzy,
+    ///     min_probability: Some(0.2),
+    ///     ..Default::default()
+    /// }).await?;
+    /// for hit in &result.hits {
+    ///     println!("{}: {}", hit.friendly_id, hit.root_path.join(" → "));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_nodes(&self, query: NodeSearchQuery) -> TreeResult<NodeSearchResult> {
+        if let (Some(min), Some(max)) = (query.min_probability, query.max_probability) {
+            if min > max {
+                return Err(TreeEngineError::InvalidInput("probability".to_string(), "min_probability cannot exceed max_probability".to_string()));
+            }
+        }
+        for probability in [query.min_probability, query.max_probability].into_iter().flatten() {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(TreeEngineError::ProbabilityOutOfRange(probability));
+            }
+        }
+        if let (Some(min), Some(max)) = (query.min_confidence, query.max_confidence) {
+            if min > max {
+                return Err(TreeEngineError::InvalidInput("confidence".to_string(), "min_confidence cannot exceed max_confidence".to_string()));
+            }
+        }
+        if let (Some(min), Some(max)) = (query.min_depth, query.max_depth) {
+            if min > max {
+                return Err(TreeEngineError::InvalidInput("depth".to_string(), "min_depth cannot exceed max_depth".to_string()));
+            }
+        }
+        if query.limit == Some(0) {
+            return Err(TreeEngineError::InvalidInput("limit".to_string(), "limit must be greater than 0".to_string()));
+        }
+
+        let all_nodes: Vec<TreeNode> = self.db.select("node").await?;
+        let nodes_by_id: HashMap<RecordId, &TreeNode> = all_nodes.iter()
+            .map(|node| (node.id.as_ref().unwrap().clone(), node))
+            .collect();
+
+        // Text relevance per node; None means no text filter was requested
+        let text = query.text.as_deref().map(str::trim).filter(|text| !text.is_empty());
+        let scores: Option<HashMap<RecordId, f64>> = match (text, &query.mode) {
+            (None, _) => None,
+            (Some(text), SearchMode::Keyword) => {
+                #[derive(Deserialize)]
+                struct ScoredId {
+                    id: RecordId,
+                    score: Option<f64>,
+                }
+
+                self.ensure_search_index().await?;
+                let mut response = self.db
+                    .query("SELECT id, (search::score(0) OR 0) + (search::score(1) OR 0) AS score FROM node WHERE premise @0@ $text OR reasoning @1@ $text")
+                    .bind(("text", text.to_string()))
+                    .await?;
+                let scored: Vec<ScoredId> = response.take(0)?;
+                Some(scored.into_iter().map(|hit| (hit.id, hit.score.unwrap_or(0.0))).collect())
+            }
+            (Some(text), SearchMode::Fuzzy) => {
+                const FUZZY_THRESHOLD: f64 = 0.75;
+                Some(all_nodes.iter()
+                    .map(|node| {
+                        let score = text_similarity::fuzzy_score(text, &node.premise)
+                            .max(text_similarity::fuzzy_score(text, &node.reasoning));
+                        (node.id.as_ref().unwrap().clone(), score)
+                    })
+                    .filter(|(_, score)| *score >= FUZZY_THRESHOLD)
+                    .collect())
+            }
+        };
+
+        let mut hits: Vec<NodeSearchHit> = all_nodes.iter()
+            .filter(|node| query.min_probability.is_none_or(|min| node.probability >= min))
+            .filter(|node| query.max_probability.is_none_or(|max| node.probability <= max))
+            .filter(|node| query.min_confidence.is_none_or(|min| node.confidence >= min))
+            .filter(|node| query.max_confidence.is_none_or(|max| node.confidence <= max))
+            .filter(|node| query.min_depth.is_none_or(|min| node.depth >= min))
+            .filter(|node| query.max_depth.is_none_or(|max| node.depth <= max))
+            .filter(|node| query.status.as_ref().is_none_or(|status| *status == Self::search_status(node)))
+            .filter(|node| query.metadata_keys.iter().all(|key| node.metadata.contains_key(key)))
+            .filter_map(|node| {
+                let score = match &scores {
+                    None => node.probability,
+                    Some(scores) => *scores.get(node.id.as_ref().unwrap())?,
+                };
+                let path = Self::root_path(node, &nodes_by_id);
+                Some(NodeSearchHit {
+                    friendly_id: node.display_id(),
+                    node_id: node.id.as_ref().unwrap().to_string(),
+                    premise: node.premise.clone(),
+                    reasoning: node.reasoning.clone(),
+                    probability: node.probability,
+                    confidence: node.confidence,
+                    depth: node.depth as u32,
+                    status: Self::search_status(node),
+                    score,
+                    root_path: path.iter().map(|n| n.display_id()).collect(),
+                    root_path_premises: path.iter().map(|n| n.premise.clone()).collect(),
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.depth.cmp(&b.depth))
+        });
+
+        let total_matches = hits.len();
+        hits.truncate(query.limit.unwrap_or(20));
+
+        Ok(NodeSearchResult {
+            query,
+            total_matches,
+            hits,
+        })
+    }
+
+    /// Retrieves the current tree state configuration and metadata.
+    ///
+    /// # Returns
+    /// * `Ok(TreeState)` - The complete tree state including configuration and metadata
+    /// * `Err(TreeEngineError::NotFound)` - If no tree state has been initialized
+    /// * `Err(TreeEngineError::DatabaseError)` - If database query fails
+    pub async fn get_state(&self) -> TreeResult<TreeState> {
+        self.get_current_tree_state().await
+    }
+
+    /// Gets the ID of the current node in the navigation context.
+    ///
+    /// Returns the currently focused node ID, falling back to root node if no
+    /// current node is explicitly set in the tree state metadata.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The ID of the current node
+    /// * `Err(TreeEngineError::NotFound)` - If neither current node nor root node exist
+    /// * `Err(TreeEngineError::DatabaseError)` - If tree state query fails
+    pub async fn get_current_node(&self) -> TreeResult<String> {
+        let tree_state = self.get_current_tree_state().await?;
+        tree_state.metadata.get("current_node")
+            .cloned()
+            .or_else(|| tree_state.config.root_id.as_ref().map(|id| id.to_string()))
+            .ok_or_else(|| TreeEngineError::NotFound("No current node set".to_string()))
+    }
+
+    /// Record id of the node that `add_leaf` attaches new children to, if a tree exists.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor_node_id.as_deref()
+    }
+
+    /// Retrieves the root node ID of the current tree, if available.
+    ///
+    /// This method safely attempts to get the root node ID without returning errors,
+    /// making it suitable for conditional operations and existence checks.
+    ///
+    /// # Returns
+    /// * `Some(String)` - The root node ID if tree is initialized
+    /// * `None` - If no tree has been created or tree state is not accessible
+    pub async fn get_root_id(&self) -> Option<String> {
+        if let Ok(state) = self.get_current_tree_state().await {
+            state.config.root_id.as_ref().map(|id| id.to_string())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{Stance, Verdict, TabularRecord, TABULAR_SCHEMA_VERSION};
+    use tokio;
+
+    #[tokio::test]
+    async fn test_initialize_with_tree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let tree_id = "test_tree_123".to_string();
+        let complexity = 5;
+
+        let result = service.initialize_with_tree(tree_id.clone(), complexity).await;
+        assert!(result.is_ok());
+
+        let tree_state = service.get_current_tree_state().await.unwrap();
+        assert_eq!(tree_state.tree_id, tree_id);
+        assert_eq!(tree_state.config.complexity, complexity);
+    }
+
+    #[tokio::test]
+    async fn test_create_tree_success() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let premise = "This is a valid test premise for creating a tree".to_string();
+        let complexity = 5;
+
+        let result = service.create_tree(premise.clone(), complexity).await;
+        assert!(result.is_ok());
+
+        let root_id = result.unwrap();
+        assert!(!root_id.is_empty());
+
+        let tree_state = service.get_current_tree_state().await.unwrap();
+        assert_eq!(tree_state.config.complexity, complexity);
+        // total_nodes is now calculated dynamically from the database
+    }
+
+    #[tokio::test]
+    async fn test_create_tree_invalid_complexity() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        
+        let premise = "Valid premise".to_string();
+        let complexity = 15; // Invalid - out of range
+
+        let result = service.create_tree(premise, complexity).await;
+        assert!(result.is_err());
+
+        if let Err(TreeEngineError::InvalidInput(field, _)) = result {
+            assert_eq!(field, "complexity");
+        } else {
+            panic!("Expected InvalidInput error for complexity");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_tree_short_premise() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Short".to_string(); // Too short
+        let complexity = 5;
+
+        let result = service.create_tree(premise, complexity).await;
+        assert!(result.is_err());
+
+        if let Err(TreeEngineError::InvalidInput(field, _)) = result {
+            assert_eq!(field, "premise");
+        } else {
+            panic!("Expected InvalidInput error for premise");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_leaf_success() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // First create a tree
+        let premise = "Root premise for leaf test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        // Then add a leaf
+        let leaf_premise = "Child leaf premise".to_string();
+        let reasoning = "This is the reasoning for the leaf".to_string();
+        let probability = 0.7;
+        let confidence = 8;
+
+        let result = service.add_leaf(leaf_premise.clone(), reasoning.clone(), probability, confidence).await;
+        assert!(result.is_ok());
+
+        let leaf_id = result.unwrap();
+        assert!(!leaf_id.is_empty());
+
+        let _tree_state = service.get_current_tree_state().await.unwrap();
+        // Leaf nodes and counts are now calculated dynamically from the database
+    }
+
+    #[tokio::test]
+    async fn test_add_leaf_invalid_inputs() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let premise = "Root premise for invalid inputs test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        // Test empty premise
+        let result = service.add_leaf("".to_string(), "Valid reasoning".to_string(), 0.5, 5).await;
+        assert!(result.is_err());
+
+        // Test empty reasoning
+        let result = service.add_leaf("Valid premise".to_string(), "".to_string(), 0.5, 5).await;
+        assert!(result.is_err());
+
+        // Test invalid probability
+        let result = service.add_leaf("Valid premise".to_string(), "Valid reasoning".to_string(), 1.5, 5).await;
+        assert!(result.is_err());
+
+        // Test invalid confidence
+        let result = service.add_leaf("Valid premise".to_string(), "Valid reasoning".to_string(), 0.5, 15).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_expand_leaf_success() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // Create tree and add leaf
+        let premise = "Root premise for expand test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let leaf_id = service.add_leaf("Leaf premise".to_string(), "Initial reasoning".to_string(), 0.6, 7).await.unwrap();
+
+        // Expand the leaf
+        let new_reasoning = "Updated reasoning after expansion".to_string();
+        let result = service.expand_leaf(leaf_id, new_reasoning).await;
+        assert!(result.is_ok());
+
+        let _tree_state = service.get_current_tree_state().await.unwrap();
+        // Leaf nodes are now tracked in the database, not in TreeState
+    }
+
+    #[tokio::test]
+    async fn test_expand_leaf_invalid_node_id() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let result = service.expand_leaf("invalid_id".to_string(), "Rationale".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_current_tree_state_error() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let service = TreeEngineService::new(Arc::new(db));
+        let result = service.get_current_tree_state().await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_get_root_id() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // Test when no tree state is initialized
+        assert!(service.get_root_id().await.is_none());
+
+        // Test when tree state is initialized but no root is set
+        service.initialize_with_tree("test_get_root_id".to_string(), 5).await.unwrap();
+        assert!(service.get_root_id().await.is_none());
+
+        // Test when root is set
+        let premise = "This is a valid test premise for creating a tree for root id test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        let retrieved_root_id = service.get_root_id();
+        assert!(retrieved_root_id.await.is_some());
+        let retrieved_value = service.get_root_id().await;
+        assert_eq!(retrieved_value.unwrap(), root_id);
+    }
+
+    #[tokio::test]
+    async fn test_validate_hierarchical_constraint() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        
+        let premise = "Test hierarchical constraint".to_string();
+        let root_id = service.create_tree(premise, 3).await.unwrap();
+
+        // Add some nodes to create a hierarchy
+        let _leaf1 = service.add_leaf("Child 1".to_string(), "Reasoning 1".to_string(), 0.6, 7).await.unwrap();
+        let _leaf2 = service.add_leaf("Child 2".to_string(), "Reasoning 2".to_string(), 0.4, 6).await.unwrap();
+
+        // Test validation
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_leaf_without_tree_state() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // Try to add leaf without initializing tree state
+        let result = service.add_leaf("premise".to_string(), "reasoning".to_string(), 0.5, 5).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_methods_without_tree_state() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // Test methods that require tree state but none exists
+        assert!(service.get_state().await.is_err()); // Should fail - no tree state
+        assert!(service.get_current_node().await.is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tokio;
+    // New comprehensive tests for 100% coverage
 
     #[tokio::test]
-    async fn test_initialize_with_tree() {
+    async fn test_get_leaf_nodes() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1536,19 +4982,41 @@ mod tests {
 
         let mut service = TreeEngineService::new(Arc::new(db));
 
-        let tree_id = "test_tree_123".to_string();
-        let complexity = 5;
+        // Initially no leaf nodes
+        let result = service.get_leaf_nodes().await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 0);
 
-        let result = service.initialize_with_tree(tree_id.clone(), complexity).await;
+        // Create tree with leaf nodes
+        let premise = "Root for leaf test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let _leaf1 = service.add_leaf("Leaf 1".to_string(), "Reasoning 1".to_string(), 0.6, 7).await.unwrap();
+        let _leaf2 = service.add_leaf("Leaf 2".to_string(), "Reasoning 2".to_string(), 0.4, 6).await.unwrap();
+
+        // Now should have leaf nodes
+        let result = service.get_leaf_nodes().await;
         assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 2);
+    }
 
-        let tree_state = service.get_current_tree_state().await.unwrap();
-        assert_eq!(tree_state.tree_id, tree_id);
-        assert_eq!(tree_state.config.complexity, complexity);
+    #[tokio::test]
+    async fn test_get_invalidated_nodes() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        // Initially no invalidated nodes
+        let result = service.get_invalidated_nodes().await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 0);
     }
 
     #[tokio::test]
-    async fn test_create_tree_success() {
+    async fn test_prune_tree() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1556,23 +5024,24 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        
+        let premise = "Root for prune test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        let premise = "This is a valid test premise for creating a tree".to_string();
-        let complexity = 5;
+        // Add nodes with different probabilities
+        let _leaf1 = service.add_leaf("High prob leaf".to_string(), "Reasoning".to_string(), 0.9, 8).await.unwrap();
+        let _leaf2 = service.add_leaf("Low prob leaf".to_string(), "Reasoning".to_string(), 0.1, 3).await.unwrap();
 
-        let result = service.create_tree(premise.clone(), complexity).await;
+        // Prune with medium aggressiveness
+        let result = service.prune_tree(0.5, false).await;
         assert!(result.is_ok());
 
-        let root_id = result.unwrap();
-        assert!(!root_id.is_empty());
-
-        let tree_state = service.get_current_tree_state().await.unwrap();
-        assert_eq!(tree_state.config.complexity, complexity);
-        // total_nodes is now calculated dynamically from the database
+        let prune_result = result.unwrap();
+        assert!(prune_result.statistics.removed_count > 0 || prune_result.statistics.preserved_count > 0);
     }
 
     #[tokio::test]
-    async fn test_create_tree_invalid_complexity() {
+    async fn test_prune_tree_invalid_aggressiveness() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1580,22 +5049,17 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        
-        let premise = "Valid premise".to_string();
-        let complexity = 15; // Invalid - out of range
 
-        let result = service.create_tree(premise, complexity).await;
+        // Test invalid aggressiveness values
+        let result = service.prune_tree(1.5, false).await;
         assert!(result.is_err());
 
-        if let Err(TreeEngineError::InvalidInput(field, _)) = result {
-            assert_eq!(field, "complexity");
-        } else {
-            panic!("Expected InvalidInput error for complexity");
-        }
+        let result = service.prune_tree(-0.1, false).await;
+        assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn test_create_tree_short_premise() {
+    async fn test_prune_leafs() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1603,21 +5067,34 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Short".to_string(); // Too short
-        let complexity = 5;
+        let premise = "Root for leaf prune test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        let result = service.create_tree(premise, complexity).await;
-        assert!(result.is_err());
+        // Add multiple leafs that sum within 1.0
+        let _leaf1 = service.add_leaf("Leaf 1".to_string(), "Reasoning".to_string(), 0.5, 8).await.unwrap();
+        let _leaf2 = service.add_leaf("Leaf 2".to_string(), "Reasoning".to_string(), 0.3, 7).await.unwrap();
+        let leaf3 = service.add_leaf("Leaf 3".to_string(), "Reasoning".to_string(), 0.15, 5).await.unwrap();
 
-        if let Err(TreeEngineError::InvalidInput(field, _)) = result {
-            assert_eq!(field, "premise");
-        } else {
-            panic!("Expected InvalidInput error for premise");
-        }
+        // A dry run reports the removal without invalidating anything
+        let preview = service.prune_leafs(2, true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.nodes_removed, vec![leaf3.clone()]);
+        assert_eq!(preview.affected_paths.len(), 1);
+        assert_eq!(preview.affected_paths[0].rank_after, None);
+        let leaf3_node: TreeNode = service.db.select(&leaf3.parse::<RecordId>().unwrap()).await.unwrap().unwrap();
+        assert!(!leaf3_node.is_invalidated);
+
+        // Prune to keep only 2 leafs
+        let result = service.prune_leafs(2, false).await;
+        assert!(result.is_ok());
+
+        let prune_result = result.unwrap();
+        assert_eq!(prune_result.statistics.preserved_count, 2);
+        assert_eq!(prune_result.statistics.removed_count, 1);
     }
 
     #[tokio::test]
-    async fn test_add_leaf_success() {
+    async fn test_prune_leafs_invalid_max() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1626,29 +5103,61 @@ mod tests {
 
         let mut service = TreeEngineService::new(Arc::new(db));
 
-        // First create a tree
-        let premise = "Root premise for leaf test".to_string();
+        // Test invalid max_leafs
+        let result = service.prune_leafs(0, false).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_balance_leafs_conservative() {
+                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for balance test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Then add a leaf
-        let leaf_premise = "Child leaf premise".to_string();
-        let reasoning = "This is the reasoning for the leaf".to_string();
-        let probability = 0.7;
-        let confidence = 8;
+        // Add leafs with high probabilities that sum within 1.0
+        let _leaf1 = service.add_leaf("High prob 1".to_string(), "Reasoning".to_string(), 0.6, 9).await.unwrap();
+        let _leaf2 = service.add_leaf("High prob 2".to_string(), "Reasoning".to_string(), 0.35, 8).await.unwrap();
 
-        let result = service.add_leaf(leaf_premise.clone(), reasoning.clone(), probability, confidence).await;
+        let result = service.balance_leafs(UncertaintyType::InsufficientData, false).await;
         assert!(result.is_ok());
 
-        let leaf_id = result.unwrap();
-        assert!(!leaf_id.is_empty());
+        let balance_result = result.unwrap();
+        assert_eq!(balance_result.uncertainty_type, UncertaintyType::InsufficientData);
+    }
 
-        let _tree_state = service.get_current_tree_state().await.unwrap();
-        // Leaf nodes and counts are now calculated dynamically from the database
+    #[tokio::test]
+    async fn test_balance_leafs_optimistic() {
+                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let premise = "Root for optimistic balance test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        // Add leafs with low probabilities
+        let _leaf1 = service.add_leaf("Low prob 1".to_string(), "Reasoning".to_string(), 0.3, 4).await.unwrap();
+        let _leaf2 = service.add_leaf("Low prob 2".to_string(), "Reasoning".to_string(), 0.4, 5).await.unwrap();
+
+        let result = service.balance_leafs(UncertaintyType::EqualLikelihood, false).await;
+        assert!(result.is_ok());
+
+        let balance_result = result.unwrap();
+        assert_eq!(balance_result.uncertainty_type, UncertaintyType::EqualLikelihood);
     }
 
     #[tokio::test]
-    async fn test_add_leaf_invalid_inputs() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_balance_leafs_neutral() {
+                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
@@ -1656,28 +5165,43 @@ mod tests {
 
         let mut service = TreeEngineService::new(Arc::new(db));
 
-        let premise = "Root premise for invalid inputs test".to_string();
+        let premise = "Root for neutral balance test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Test empty premise
-        let result = service.add_leaf("".to_string(), "Valid reasoning".to_string(), 0.5, 5).await;
-        assert!(result.is_err());
+        let _leaf1 = service.add_leaf("Mixed prob 1".to_string(), "Reasoning".to_string(), 0.8, 7).await.unwrap();
+        let _leaf2 = service.add_leaf("Mixed prob 2".to_string(), "Reasoning".to_string(), 0.2, 3).await.unwrap();
 
-        // Test empty reasoning
-        let result = service.add_leaf("Valid premise".to_string(), "".to_string(), 0.5, 5).await;
-        assert!(result.is_err());
+        let result = service.balance_leafs(UncertaintyType::CognitiveOverload, false).await;
+        assert!(result.is_ok());
 
-        // Test invalid probability
-        let result = service.add_leaf("Valid premise".to_string(), "Valid reasoning".to_string(), 1.5, 5).await;
-        assert!(result.is_err());
+        let balance_result = result.unwrap();
+        assert_eq!(balance_result.uncertainty_type, UncertaintyType::CognitiveOverload);
+    }
 
-        // Test invalid confidence
-        let result = service.add_leaf("Valid premise".to_string(), "Valid reasoning".to_string(), 0.5, 15).await;
-        assert!(result.is_err());
+    #[tokio::test]
+    async fn test_validate_coherence() {
+                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let premise = "Root for coherence test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        let _leaf1 = service.add_leaf("Valid leaf".to_string(), "Reasoning".to_string(), 0.7, 7).await.unwrap();
+
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
+
+        let coherence = result.unwrap();
+        assert!(coherence.is_coherent);
     }
 
     #[tokio::test]
-    async fn test_expand_leaf_success() {
+    async fn test_export_paths() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1685,23 +5209,43 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-
-        // Create tree and add leaf
-        let premise = "Root premise for expand test".to_string();
+        let premise = "Root for export test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
-        let leaf_id = service.add_leaf("Leaf premise".to_string(), "Initial reasoning".to_string(), 0.6, 7).await.unwrap();
 
-        // Expand the leaf
-        let new_reasoning = "Updated reasoning after expansion".to_string();
-        let result = service.expand_leaf(leaf_id, new_reasoning).await;
+        let _leaf1 = service.add_leaf("Export leaf".to_string(), "Reasoning for export".to_string(), 0.8, 8).await.unwrap();
+
+        let insights = vec![
+            "First insight".to_string(),
+            "Second insight".to_string(),
+            "Third insight".to_string(),
+        ];
+
+        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.85).await;
         assert!(result.is_ok());
 
-        let _tree_state = service.get_current_tree_state().await.unwrap();
-        // Leaf nodes are now tracked in the database, not in TreeState
+        let analysis = result.unwrap();
+        assert_eq!(analysis.confidence_assessment, 0.85);
+        assert_eq!(analysis.narrative_style, NarrativeStyle::Analytical);
     }
 
     #[tokio::test]
-    async fn test_expand_leaf_invalid_node_id() {
+    async fn test_export_paths_insufficient_insights() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+    
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let insights = vec!["Only one insight".to_string()];
+
+        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_paths_invalid_confidence() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1709,26 +5253,41 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let insights = vec![
+            "First".to_string(),
+            "Second".to_string(),
+            "Third".to_string(),
+        ];
 
-        let result = service.expand_leaf("invalid_id".to_string(), "Rationale".to_string()).await;
+        let result = service.export_paths(NarrativeStyle::Analytical, insights, 1.5).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn test_get_current_tree_state_error() {
+    async fn test_inspect_tree() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let service = TreeEngineService::new(Arc::new(db));
-        let result = service.get_current_tree_state().await;
-        assert!(result.is_err());
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for inspect test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+
+        let _leaf1 = service.add_leaf("Inspect leaf".to_string(), "Reasoning for inspect".to_string(), 0.7, 7).await.unwrap();
+
+        let result = service.inspect_tree().await;
+        assert!(result.is_ok());
+
+        let visualization = result.unwrap();
+        // ASCII tree generation is currently disabled, so we check the actual data
+        assert!(visualization.statistics.total_nodes > 0);
+        assert!(!visualization.node_details.is_empty());
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-    async fn test_get_root_id() {
+    #[tokio::test]
+    async fn test_probability_status() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1736,26 +5295,20 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for probability status test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Test when no tree state is initialized
-        assert!(service.get_root_id().await.is_none());
-
-        // Test when tree state is initialized but no root is set
-        service.initialize_with_tree("test_get_root_id".to_string(), 5).await.unwrap();
-        assert!(service.get_root_id().await.is_none());
+        let _leaf1 = service.add_leaf("Status leaf".to_string(), "Reasoning".to_string(), 0.6, 6).await.unwrap();
 
-        // Test when root is set
-        let premise = "This is a valid test premise for creating a tree for root id test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let result = service.probability_status().await;
+        assert!(result.is_ok());
 
-        let retrieved_root_id = service.get_root_id();
-        assert!(retrieved_root_id.await.is_some());
-        let retrieved_value = service.get_root_id().await;
-        assert_eq!(retrieved_value.unwrap(), root_id);
+        let validation = result.unwrap();
+        assert!(validation.is_valid);
     }
 
     #[tokio::test]
-    async fn test_validate_hierarchical_constraint() {
+    async fn test_navigate_to() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1763,21 +5316,19 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        
-        let premise = "Test hierarchical constraint".to_string();
-        let root_id = service.create_tree(premise, 3).await.unwrap();
-
-        // Add some nodes to create a hierarchy
-        let _leaf1 = service.add_leaf("Child 1".to_string(), "Reasoning 1".to_string(), 0.6, 7).await.unwrap();
-        let _leaf2 = service.add_leaf("Child 2".to_string(), "Reasoning 2".to_string(), 0.4, 6).await.unwrap();
+        let premise = "Root for navigation test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Test validation
-        let result = service.validate_coherence().await;
+        let result = service.navigate_to(root_id).await;
         assert!(result.is_ok());
+
+        // Check that current node was set
+        let current = service.get_current_node().await;
+        assert!(current.is_ok());
     }
 
     #[tokio::test]
-    async fn test_add_leaf_without_tree_state() {
+    async fn test_navigate_to_invalid_node() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1785,14 +5336,16 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
 
-        // Try to add leaf without initializing tree state
-        let result = service.add_leaf("premise".to_string(), "reasoning".to_string(), 0.5, 5).await;
+        let result = service.navigate_to("invalid_node_id".to_string()).await;
         assert!(result.is_err());
     }
 
+    // Additional tests for better coverage
+
     #[tokio::test]
-    async fn test_methods_without_tree_state() {
+    async fn test_add_leaf_invalid_parent_id_format() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1800,16 +5353,26 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
 
-        // Test methods that require tree state but none exists
-        assert!(service.get_state().await.is_err()); // Should fail - no tree state
-        assert!(service.get_current_node().await.is_err());
-    }
+        // Test with completely invalid ID format
+        let result = service.add_leaf(
+            "Valid premise".to_string(),
+            "Valid reasoning for this test".to_string(),
+            0.7,
+            8
+        ).await;
 
-    // New comprehensive tests for 100% coverage
+        assert!(result.is_err());
+        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result {
+            assert!(msg.contains("No cursor set"));
+        } else {
+            panic!("Expected OperationNotAllowed error for no cursor");
+        }
+    }
 
     #[tokio::test]
-    async fn test_get_leaf_nodes() {
+    async fn test_add_leaf_max_depth_reached() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1817,26 +5380,50 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        // Create tree with very low max depth
+        let premise = "Root premise for max depth test".to_string();
+        let root_id = service.create_tree(premise, 1).await.unwrap(); // complexity 1 = max_depth 3
 
-        // Initially no leaf nodes
-        let result = service.get_leaf_nodes().await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 0);
+        // Add first level (depth 1) - cursor is at root
+        let level1_id = service.add_leaf(
+            "Level 1 premise".to_string(),
+            "Level 1 reasoning for depth test".to_string(),
+            0.7,
+            8
+        ).await.unwrap();
 
-        // Create tree with leaf nodes
-        let premise = "Root for leaf test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-        let _leaf1 = service.add_leaf("Leaf 1".to_string(), "Reasoning 1".to_string(), 0.6, 7).await.unwrap();
-        let _leaf2 = service.add_leaf("Leaf 2".to_string(), "Reasoning 2".to_string(), 0.4, 6).await.unwrap();
+        // Expand the level1 node to move cursor to it
+        service.expand_leaf(level1_id.clone(), "Expanding to add level 2".to_string()).await.unwrap();
 
-        // Now should have leaf nodes
-        let result = service.get_leaf_nodes().await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 2);
+        // Add second level (depth 2) - cursor is now at level1
+        let level2_id = service.add_leaf(
+            "Level 2 premise".to_string(),
+            "Level 2 reasoning for depth test".to_string(),
+            0.6,
+            7
+        ).await.unwrap();
+
+        // Expand the level2 node to move cursor to it
+        service.expand_leaf(level2_id, "Expanding to try level 3".to_string()).await.unwrap();
+
+        // Try to add third level (should fail - max depth reached)
+        let result = service.add_leaf(
+            "Level 3 premise".to_string(),
+            "Level 3 reasoning for depth test".to_string(),
+            0.5,
+            6
+        ).await;
+
+        assert!(result.is_err());
+        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result {
+            assert!(msg.contains("Maximum depth"));
+        } else {
+            panic!("Expected OperationNotAllowed error for max depth");
+        }
     }
 
     #[tokio::test]
-    async fn test_get_invalidated_nodes() {
+    async fn test_prune_tree_no_nodes() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1844,15 +5431,21 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("empty_tree".to_string(), 5).await.unwrap();
 
-        // Initially no invalidated nodes
-        let result = service.get_invalidated_nodes().await;
+        // Try to prune when there are no nodes
+        let result = service.prune_tree(0.5, false).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 0);
+
+        let prune_result = result.unwrap();
+        assert_eq!(prune_result.statistics.original_count, 0);
+        assert_eq!(prune_result.statistics.removed_count, 0);
+        assert_eq!(prune_result.statistics.preserved_count, 0);
+        assert_eq!(prune_result.statistics.aggressiveness_level, 0.5);
     }
 
     #[tokio::test]
-    async fn test_prune_tree() {
+    async fn test_update_tree_state_failure_path() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1860,24 +5453,20 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        
-        let premise = "Root for prune test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        // Add nodes with different probabilities
-        let _leaf1 = service.add_leaf("High prob leaf".to_string(), "Reasoning".to_string(), 0.9, 8).await.unwrap();
-        let _leaf2 = service.add_leaf("Low prob leaf".to_string(), "Reasoning".to_string(), 0.1, 3).await.unwrap();
 
-        // Prune with medium aggressiveness
-        let result = service.prune_tree(0.5).await;
-        assert!(result.is_ok());
+        // This should fail because no tree state exists to update
+        let result = service.get_current_tree_state().await;
+        assert!(result.is_err());
 
-        let prune_result = result.unwrap();
-        assert!(prune_result.statistics.removed_count > 0 || prune_result.statistics.preserved_count > 0);
+        if let Err(TreeEngineError::NotFound(msg)) = result {
+            assert!(msg.contains("No tree state initialized"));
+        } else {
+            panic!("Expected NotFound error");
+        }
     }
 
     #[tokio::test]
-    async fn test_prune_tree_invalid_aggressiveness() {
+    async fn test_add_leaf_nonexistent_parent() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -1885,150 +5474,261 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
 
-        // Test invalid aggressiveness values
-        let result = service.prune_tree(1.5).await;
-        assert!(result.is_err());
+        // Create tree to set cursor
+        let _root_id = service.create_tree("Root for nonexistent test".to_string(), 5).await.unwrap();
 
-        let result = service.prune_tree(-0.1).await;
-        assert!(result.is_err());
+        // Try to add leaf - this will use cursor (should work)
+        let result = service.add_leaf(
+            "Valid premise".to_string(),
+            "Valid reasoning for nonexistent parent test".to_string(),
+            0.7,
+            8
+        ).await;
+
+        // This should actually succeed since we have a cursor now
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn test_prune_leafs() {
+    async fn test_add_leaf_probability_sum_constraint() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for leaf prune test".to_string();
+
+        // Create tree with root (default probability 1.0)
+        let premise = "Root premise for probability sum test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Add multiple leafs that sum within 1.0
-        let _leaf1 = service.add_leaf("Leaf 1".to_string(), "Reasoning".to_string(), 0.5, 8).await.unwrap();
-        let _leaf2 = service.add_leaf("Leaf 2".to_string(), "Reasoning".to_string(), 0.3, 7).await.unwrap();
-        let _leaf3 = service.add_leaf("Leaf 3".to_string(), "Reasoning".to_string(), 0.15, 5).await.unwrap();
+        // Add first child with probability 0.6
+        let result1 = service.add_leaf(
+            "First child".to_string(),
+            "First child reasoning".to_string(),
+            0.6,
+            7
+        ).await;
+        assert!(result1.is_ok());
 
-        // Prune to keep only 2 leafs
-        let result = service.prune_leafs(2).await;
-        assert!(result.is_ok());
+        // Add second child with probability 0.3 (total = 0.9, within parent's 1.0)
+        let result2 = service.add_leaf(
+            "Second child".to_string(),
+            "Second child reasoning".to_string(),
+            0.3,
+            6
+        ).await;
+        assert!(result2.is_ok());
 
-        let prune_result = result.unwrap();
-        assert_eq!(prune_result.statistics.preserved_count, 2);
-        assert_eq!(prune_result.statistics.removed_count, 1);
+        // Try to add third child with probability 0.2 (total would be 1.1, exceeds parent's 1.0)
+        let result3 = service.add_leaf(
+            "Third child".to_string(),
+            "Third child reasoning".to_string(),
+            0.2,
+            5
+        ).await;
+
+        // This should fail due to probability constraint violation
+        assert!(result3.is_err());
+        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result3 {
+            assert!(msg.contains("Probability constraint violation"));
+            assert!(msg.contains("would result in total child probability"));
+            assert!(msg.contains("exceeds parent probability"));
+        } else {
+            panic!("Expected OperationNotAllowed error with probability constraint violation message");
+        }
+
+        // Verify that we can still add a child with a smaller probability that fits
+        let result4 = service.add_leaf(
+            "Fourth child".to_string(),
+            "Fourth child reasoning".to_string(),
+            0.1, // Total would be 1.0, exactly equal to parent
+            4
+        ).await;
+        assert!(result4.is_ok());
+
+        // Try to add any more children (even with small probability) should fail
+        let result5 = service.add_leaf(
+            "Fifth child".to_string(),
+            "Fifth child reasoning".to_string(),
+            0.01, // Total would be 1.01, exceeds parent's 1.0 beyond tolerance
+            3
+        ).await;
+        assert!(result5.is_err());
     }
 
     #[tokio::test]
-    async fn test_prune_leafs_invalid_max() {
+    async fn test_add_leaf_probability_sum_constraint_with_expand() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
 
-        // Test invalid max_leafs
-        let result = service.prune_leafs(0).await;
-        assert!(result.is_err());
+        // Create tree with default root probability (1.0)
+        let root_id = service.create_tree("Root for expand constraint test".to_string(), 5).await.unwrap();
+
+        // Add child with probability 0.6
+        let child_id = service.add_leaf(
+            "Child to expand".to_string(),
+            "Child reasoning".to_string(),
+            0.6,
+            7
+        ).await.unwrap();
+
+        // Expand this child to work on its children
+        service.expand_leaf(child_id, "Expanding child for deeper analysis".to_string()).await.unwrap();
+
+        // Now the cursor is on the child (probability 0.6)
+        // Add grandchild with probability 0.4 (within 0.6)
+        let result1 = service.add_leaf(
+            "Grandchild 1".to_string(),
+            "First grandchild".to_string(),
+            0.4,
+            6
+        ).await;
+        assert!(result1.is_ok());
+
+        // Try to add another grandchild with probability 0.3 (total would be 0.7, exceeds parent's 0.6)
+        let result2 = service.add_leaf(
+            "Grandchild 2".to_string(),
+            "Second grandchild".to_string(),
+            0.3,
+            5
+        ).await;
+        assert!(result2.is_err());
+        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result2 {
+            assert!(msg.contains("Probability constraint violation"));
+        } else {
+            panic!("Expected OperationNotAllowed error");
+        }
     }
 
+    // Comprehensive edge case tests
+
     #[tokio::test]
-    async fn test_balance_leafs_conservative() {
-                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_prune_leafs_with_insufficient_nodes() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for balance test".to_string();
+        let premise = "Root for insufficient nodes test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Add leafs with high probabilities that sum within 1.0
-        let _leaf1 = service.add_leaf("High prob 1".to_string(), "Reasoning".to_string(), 0.6, 9).await.unwrap();
-        let _leaf2 = service.add_leaf("High prob 2".to_string(), "Reasoning".to_string(), 0.35, 8).await.unwrap();
+        // Add only 1 leaf
+        let _leaf1 = service.add_leaf(
+            "Single leaf".to_string(),
+            "Only one leaf for insufficient test".to_string(),
+            0.8,
+            8
+        ).await.unwrap();
 
-        let result = service.balance_leafs(UncertaintyType::InsufficientData).await;
+        // Try to prune to keep 3 leafs when we only have 1
+        let result = service.prune_leafs(3, false).await;
         assert!(result.is_ok());
 
-        let balance_result = result.unwrap();
-        assert_eq!(balance_result.uncertainty_type, UncertaintyType::InsufficientData);
+        let prune_result = result.unwrap();
+        // Should preserve the single leaf and not remove anything
+        assert_eq!(prune_result.statistics.preserved_count, 1);
+        assert_eq!(prune_result.statistics.removed_count, 0);
+        assert_eq!(prune_result.statistics.original_count, 1);
     }
 
     #[tokio::test]
-    async fn test_balance_leafs_optimistic() {
-                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_balance_leafs_empty_tree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("empty_balance_test".to_string(), 5).await.unwrap();
 
-        let premise = "Root for optimistic balance test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        // Add leafs with low probabilities
-        let _leaf1 = service.add_leaf("Low prob 1".to_string(), "Reasoning".to_string(), 0.3, 4).await.unwrap();
-        let _leaf2 = service.add_leaf("Low prob 2".to_string(), "Reasoning".to_string(), 0.4, 5).await.unwrap();
-
-        let result = service.balance_leafs(UncertaintyType::EqualLikelihood).await;
+        // Try to balance when there are no leaf nodes
+        let result = service.balance_leafs(UncertaintyType::InsufficientData, false).await;
         assert!(result.is_ok());
 
         let balance_result = result.unwrap();
-        assert_eq!(balance_result.uncertainty_type, UncertaintyType::EqualLikelihood);
+        assert!(balance_result.balanced_nodes.is_empty());
+        assert!(balance_result.original_probabilities.is_empty());
+        assert!(balance_result.new_probabilities.is_empty());
     }
 
     #[tokio::test]
-    async fn test_balance_leafs_neutral() {
-                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_balance_leafs_mixed_probabilities() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-
-        let premise = "Root for neutral balance test".to_string();
+        let premise = "Root for mixed probabilities test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        let _leaf1 = service.add_leaf("Mixed prob 1".to_string(), "Reasoning".to_string(), 0.8, 7).await.unwrap();
-        let _leaf2 = service.add_leaf("Mixed prob 2".to_string(), "Reasoning".to_string(), 0.2, 3).await.unwrap();
+        // Add two leaves with very different probabilities that sum within 1.0
+        let high_leaf = service.add_leaf(
+            "High confidence leaf".to_string(),
+            "Very confident reasoning for balance test".to_string(),
+            0.7,
+            9
+        ).await.unwrap();
+
+        let low_leaf = service.add_leaf(
+            "Low confidence leaf".to_string(),
+            "Less confident reasoning for balance test".to_string(),
+            0.2,
+            3
+        ).await.unwrap();
 
-        let result = service.balance_leafs(UncertaintyType::CognitiveOverload).await;
+        let result = service.balance_leafs(UncertaintyType::InsufficientData, false).await;
         assert!(result.is_ok());
 
         let balance_result = result.unwrap();
-        assert_eq!(balance_result.uncertainty_type, UncertaintyType::CognitiveOverload);
+        // Should balance the high probability node (avg = 0.55, high = 0.9 > 0.55 + 0.1)
+        assert!(balance_result.balanced_nodes.len() >= 1);
+        // Should contain the high probability node
+        assert!(balance_result.balanced_nodes.contains(&high_leaf) ||
+                balance_result.original_probabilities.contains_key(&high_leaf));
     }
 
     #[tokio::test]
-    async fn test_validate_coherence() {
-                let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_export_paths_empty_tree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
     
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        service.initialize_with_tree("empty_export_test".to_string(), 5).await.unwrap();
 
-        let premise = "Root for coherence test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        let _leaf1 = service.add_leaf("Valid leaf".to_string(), "Reasoning".to_string(), 0.7, 7).await.unwrap();
+        let insights = vec![
+            "First insight".to_string(),
+            "Second insight".to_string(),
+            "Third insight".to_string(),
+        ];
 
-        let result = service.validate_coherence().await;
+        // Try to export paths when there are no surviving paths
+        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
         assert!(result.is_ok());
 
-        let coherence = result.unwrap();
-        assert!(coherence.is_coherent);
+        let analysis = result.unwrap();
+        assert!(analysis.surviving_paths.is_empty());
+        assert_eq!(analysis.total_thought_tokens, 0);
     }
 
     #[tokio::test]
-    async fn test_export_paths() {
+    async fn test_export_paths_with_invalidated_nodes() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2036,27 +5736,44 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for export test".to_string();
+        let premise = "Root for invalidated nodes test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        let _leaf1 = service.add_leaf("Export leaf".to_string(), "Reasoning for export".to_string(), 0.8, 8).await.unwrap();
+        // Add leaf and then prune to invalidate some nodes
+        let _leaf1 = service.add_leaf(
+            "Leaf to be invalidated".to_string(),
+            "This leaf will be invalidated in pruning test".to_string(),
+            0.1, // Low probability, likely to be pruned
+            2
+        ).await.unwrap();
+
+        let _leaf2 = service.add_leaf(
+            "Leaf to survive".to_string(),
+            "This leaf should survive pruning test".to_string(),
+            0.9, // High probability
+            9
+        ).await.unwrap();
+
+        // Prune aggressively to invalidate low-probability nodes
+        let _prune_result = service.prune_tree(0.8, false).await.unwrap();
 
         let insights = vec![
-            "First insight".to_string(),
-            "Second insight".to_string(),
-            "Third insight".to_string(),
+            "Analysis insight one".to_string(),
+            "Analysis insight two".to_string(),
+            "Analysis insight three".to_string(),
         ];
 
-        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.85).await;
+        // Export should skip invalidated nodes
+        let result = service.export_paths(NarrativeStyle::Strategic, insights, 0.7).await;
         assert!(result.is_ok());
 
         let analysis = result.unwrap();
-        assert_eq!(analysis.confidence_assessment, 0.85);
-        assert_eq!(analysis.narrative_style, NarrativeStyle::Analytical);
+        // Should only include paths from non-invalidated nodes
+        assert_eq!(analysis.narrative_style, NarrativeStyle::Strategic);
     }
 
     #[tokio::test]
-    async fn test_export_paths_insufficient_insights() {
+    async fn test_probability_status_with_violations() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2064,15 +5781,32 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for violations test".to_string();
+        let root_id = service.create_tree(premise, 1).await.unwrap(); // Very low complexity for tight constraints
 
-        let insights = vec!["Only one insight".to_string()];
+        // Add a leaf that should be fine initially
+        let leaf_id = service.add_leaf(
+            "Normal leaf".to_string(),
+            "Normal reasoning for probability test".to_string(),
+            0.05, // Very low probability - below min_probability threshold
+            3
+        ).await.unwrap();
 
-        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
-        assert!(result.is_err());
+        // Check probability status - should find violations
+        let result = service.probability_status().await;
+        assert!(result.is_ok());
+
+        let validation = result.unwrap();
+        // Should detect the low probability violation
+        assert!(!validation.violations.is_empty());
+
+        // Should have suggestions
+        assert!(!validation.suggestions.is_empty());
+        assert_eq!(validation.suggestions[0], "Fix probability range violations");
     }
 
     #[tokio::test]
-    async fn test_export_paths_invalid_confidence() {
+    async fn test_validate_coherence_complex_tree() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2080,18 +5814,54 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let insights = vec![
-            "First".to_string(),
-            "Second".to_string(),
-            "Third".to_string(),
-        ];
+        let premise = "Root for complex coherence test".to_string();
+        let root_id = service.create_tree(premise, 7).await.unwrap(); // Higher complexity
 
-        let result = service.export_paths(NarrativeStyle::Analytical, insights, 1.5).await;
-        assert!(result.is_err());
+        // Build a more complex tree structure
+        let branch1 = service.add_leaf(
+            "Branch 1 premise".to_string(),
+            "First branch reasoning for coherence test".to_string(),
+            0.7,
+            7
+        ).await.unwrap();
+
+        let branch2 = service.add_leaf(
+            "Branch 2 premise".to_string(),
+            "Second branch reasoning for coherence test".to_string(),
+            0.3,
+            4
+        ).await.unwrap();
+
+        // Navigate to branch1 and add sub-branch
+        service.navigate_to(branch1).await.unwrap();
+        let _subbranch1 = service.add_leaf(
+            "Sub-branch 1.1".to_string(),
+            "Sub-branch reasoning under branch 1 for coherence".to_string(),
+            0.6,
+            6
+        ).await.unwrap();
+
+        // Navigate to branch2 and add sub-branch
+        service.navigate_to(branch2).await.unwrap();
+        let _subbranch2 = service.add_leaf(
+            "Sub-branch 2.1".to_string(),
+            "Sub-branch reasoning under branch 2 for coherence".to_string(),
+            0.25, // Must be <= branch2's probability of 0.3
+            5
+        ).await.unwrap();
+
+        // Validate coherence of complex structure
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
+
+        let coherence = result.unwrap();
+        // Complex tree should still be coherent
+        assert!(coherence.is_coherent);
+        assert!(coherence.contradictions.is_empty());
     }
 
     #[tokio::test]
-    async fn test_inspect_tree() {
+    async fn test_inspect_tree_complex_structure() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2099,63 +5869,96 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for inspect test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let premise = "Root for complex inspect test".to_string();
+        let root_id = service.create_tree(premise, 8).await.unwrap();
 
-        let _leaf1 = service.add_leaf("Inspect leaf".to_string(), "Reasoning for inspect".to_string(), 0.7, 7).await.unwrap();
+        // Create a tree with multiple levels and nodes - cursor is at root
+        let level1_1 = service.add_leaf(
+            "Level 1 Node 1".to_string(),
+            "First level one node reasoning for inspection".to_string(),
+            0.6,
+            8
+        ).await.unwrap();
+
+        let level1_2 = service.add_leaf(
+            "Level 1 Node 2".to_string(),
+            "Second level one node reasoning for inspection".to_string(),
+            0.3,
+            6
+        ).await.unwrap();
+
+        // Navigate to first level 1 node to add children
+        service.navigate_to(level1_1).await.unwrap();
+
+        // Add second level under level1_1 (probability 0.6)
+        let _level2_1 = service.add_leaf(
+            "Level 2 Node 1".to_string(),
+            "Level two node one reasoning for inspection".to_string(),
+            0.4, // Must be <= level1_1's probability of 0.6
+            7
+        ).await.unwrap();
 
+        let _level2_2 = service.add_leaf(
+            "Level 2 Node 2".to_string(),
+            "Level two node two reasoning for inspection".to_string(),
+            0.15, // 0.4 + 0.15 = 0.55 <= 0.6
+            5
+        ).await.unwrap();
+
+        // Inspect the complex tree
         let result = service.inspect_tree().await;
         assert!(result.is_ok());
 
         let visualization = result.unwrap();
+
         // ASCII tree generation is currently disabled, so we check the actual data
-        assert!(visualization.statistics.total_nodes > 0);
-        assert!(!visualization.node_details.is_empty());
+        // Verify that node details contain the expected premises
+        let has_level1_node1 = visualization.node_details.values().any(|n| n.premise_summary.contains("Level 1 Node 1"));
+        let has_level2_node1 = visualization.node_details.values().any(|n| n.premise_summary.contains("Level 2 Node 1"));
+        assert!(has_level1_node1);
+        assert!(has_level2_node1);
+
+        // Should have node details for all nodes
+        assert!(visualization.node_details.len() >= 4); // At least root + 4 added nodes
+
+        // Should have meaningful statistics
+        assert!(visualization.statistics.total_nodes >= 5); // Root + 4 added
+        assert_eq!(visualization.statistics.active_paths, 3); // 3 leaf nodes
+        assert_eq!(visualization.statistics.max_depth, 2);
+        assert!(visualization.statistics.avg_probability > 0.0);
+        assert!(visualization.statistics.complexity_score > 0.0);
     }
 
     #[tokio::test]
-    async fn test_probability_status() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_database_namespace_setup() {
+        // This test will exercise the database setup line (line 22)
+        let db1 = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
-        db.use_ns("analytics").use_db("trees").await.unwrap();
-
-        let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for probability status test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        let _leaf1 = service.add_leaf("Status leaf".to_string(), "Reasoning".to_string(), 0.6, 6).await.unwrap();
 
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
+        db1.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let validation = result.unwrap();
-        assert!(validation.is_valid);
-    }
+        let mut service1 = TreeEngineService::new(Arc::new(db1));
 
-    #[tokio::test]
-    async fn test_navigate_to() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+        let db2 = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
-        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for navigation test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
+        db2.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let result = service.navigate_to(root_id).await;
-        assert!(result.is_ok());
+        let mut service2 = TreeEngineService::new(Arc::new(db2));
 
-        // Check that current node was set
-        let current = service.get_current_node().await;
-        assert!(current.is_ok());
+        let result1 = service1.initialize_with_tree("tree1".to_string(), 3).await;
+        let result2 = service2.initialize_with_tree("tree2".to_string(), 4).await;
+
+        assert!(result1.is_ok());
+        assert!(result2.is_ok());
     }
 
+    // CRITICAL EDGE CASES - These are the failure points in production
+
     #[tokio::test]
-    async fn test_navigate_to_invalid_node() {
+    async fn test_export_paths_empty_insight_strings() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2163,16 +5966,30 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
+        let premise = "Root for empty insights test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let _leaf = service.add_leaf("Test leaf".to_string(), "Test reasoning for empty insights".to_string(), 0.7, 7).await.unwrap();
 
-        let result = service.navigate_to("invalid_node_id".to_string()).await;
+        // This is the critical edge case: insights with empty strings
+        let insights = vec![
+            "Valid insight".to_string(),
+            "".to_string(), // Empty string - this should fail
+            "Another valid insight".to_string(),
+        ];
+
+        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
         assert!(result.is_err());
-    }
 
-    // Additional tests for better coverage
+        if let Err(TreeEngineError::InvalidInput(field, msg)) = result {
+            assert_eq!(field, "insights");
+            assert!(msg.contains("Insight 2 cannot be empty"));
+        } else {
+            panic!("Expected InvalidInput error for empty insight string");
+        }
+    }
 
     #[tokio::test]
-    async fn test_add_leaf_invalid_parent_id_format() {
+    async fn test_export_paths_whitespace_only_insights() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2180,26 +5997,30 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
+        let premise = "Root for whitespace insights test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let _leaf = service.add_leaf("Test leaf".to_string(), "Test reasoning for whitespace insights".to_string(), 0.8, 8).await.unwrap();
 
-        // Test with completely invalid ID format
-        let result = service.add_leaf(
-            "Valid premise".to_string(),
-            "Valid reasoning for this test".to_string(),
-            0.7,
-            8
-        ).await;
+        // Another critical case: insights with only whitespace
+        let insights = vec![
+            "Valid insight".to_string(),
+            "   \t\n  ".to_string(), // Only whitespace - should fail
+            "Third valid insight".to_string(),
+        ];
 
+        let result = service.export_paths(NarrativeStyle::Strategic, insights, 0.6).await;
         assert!(result.is_err());
-        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result {
-            assert!(msg.contains("No cursor set"));
+
+        if let Err(TreeEngineError::InvalidInput(field, msg)) = result {
+            assert_eq!(field, "insights");
+            assert!(msg.contains("Insight 2 cannot be empty"));
         } else {
-            panic!("Expected OperationNotAllowed error for no cursor");
+            panic!("Expected InvalidInput error for whitespace-only insight");
         }
     }
 
     #[tokio::test]
-    async fn test_add_leaf_max_depth_reached() {
+    async fn test_probability_status_invalid_probability_range() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2207,50 +6028,59 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        // Create tree with very low max depth
-        let premise = "Root premise for max depth test".to_string();
-        let root_id = service.create_tree(premise, 1).await.unwrap(); // complexity 1 = max_depth 3
-
-        // Add first level (depth 1) - cursor is at root
-        let level1_id = service.add_leaf(
-            "Level 1 premise".to_string(),
-            "Level 1 reasoning for depth test".to_string(),
-            0.7,
-            8
-        ).await.unwrap();
+        let premise = "Root for invalid probability range test".to_string();
+        let _root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Expand the level1 node to move cursor to it
-        service.expand_leaf(level1_id.clone(), "Expanding to add level 2".to_string()).await.unwrap();
+        // Manually create a node with invalid probability by direct database manipulation
+        // This simulates corruption or external modification
+        let invalid_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Invalid probability node".to_string(),
+            reasoning: "This node has invalid probability for critical test".to_string(),
+            probability: 1.5, // INVALID - greater than 1.0
+            confidence: 5,
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
 
-        // Add second level (depth 2) - cursor is now at level1
-        let level2_id = service.add_leaf(
-            "Level 2 premise".to_string(),
-            "Level 2 reasoning for depth test".to_string(),
-            0.6,
-            7
-        ).await.unwrap();
+        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
 
-        // Expand the level2 node to move cursor to it
-        service.expand_leaf(level2_id, "Expanding to try level 3".to_string()).await.unwrap();
+        // Now check probability status - should detect the invalid range
+        let result = service.probability_status().await;
+        assert!(result.is_ok());
 
-        // Try to add third level (should fail - max depth reached)
-        let result = service.add_leaf(
-            "Level 3 premise".to_string(),
-            "Level 3 reasoning for depth test".to_string(),
-            0.5,
-            6
-        ).await;
+        let validation = result.unwrap();
+        assert!(!validation.is_valid);
+        assert!(!validation.violations.is_empty());
 
-        assert!(result.is_err());
-        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result {
-            assert!(msg.contains("Maximum depth"));
-        } else {
-            panic!("Expected OperationNotAllowed error for max depth");
-        }
+        // Check that we caught the specific error
+        let violation = &validation.violations[0];
+        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
+        assert!(violation.message.contains("1.5 is outside valid range [0,1]"));
+        assert_eq!(violation.severity, crate::domain::models::types::Severity::Error);
+        assert_eq!(validation.suggestions[0], "Fix probability range violations");
     }
 
     #[tokio::test]
-    async fn test_prune_tree_no_nodes() {
+    async fn test_probability_status_negative_probability() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2258,21 +6088,55 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("empty_tree".to_string(), 5).await.unwrap();
+        let premise = "Root for negative probability test".to_string();
+        let _root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Try to prune when there are no nodes
-        let result = service.prune_tree(0.5).await;
+        // Create node with negative probability
+        let negative_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Negative probability node".to_string(),
+            reasoning: "This node has negative probability for critical test".to_string(),
+            probability: -0.3, // INVALID - negative
+            confidence: 4,
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
+
+        let _created: Option<TreeNode> = service.db.create("node").content(negative_node).await.unwrap();
+
+        let result = service.probability_status().await;
         assert!(result.is_ok());
 
-        let prune_result = result.unwrap();
-        assert_eq!(prune_result.statistics.original_count, 0);
-        assert_eq!(prune_result.statistics.removed_count, 0);
-        assert_eq!(prune_result.statistics.preserved_count, 0);
-        assert_eq!(prune_result.statistics.aggressiveness_level, 0.5);
+        let validation = result.unwrap();
+        assert!(!validation.is_valid);
+        assert!(!validation.violations.is_empty());
+
+        let violation = &validation.violations[0];
+        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
+        assert!(violation.message.contains("-0.3 is outside valid range [0,1]"));
+        assert_eq!(violation.severity, crate::domain::models::types::Severity::Error);
     }
 
     #[tokio::test]
-    async fn test_update_tree_state_failure_path() {
+    async fn test_probability_status_below_minimum_threshold() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2280,20 +6144,55 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for below threshold test".to_string();
+        let _root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // This should fail because no tree state exists to update
-        let result = service.get_current_tree_state().await;
-        assert!(result.is_err());
+        // Create node with probability below min_probability (0.15)
+        let below_threshold_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Below threshold node".to_string(),
+            reasoning: "This node has probability below minimum threshold for critical test".to_string(),
+            probability: 0.05, // Below 0.15 min_probability
+            confidence: 3,
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
 
-        if let Err(TreeEngineError::NotFound(msg)) = result {
-            assert!(msg.contains("No tree state initialized"));
-        } else {
-            panic!("Expected NotFound error");
-        }
+        let _created: Option<TreeNode> = service.db.create("node").content(below_threshold_node).await.unwrap();
+
+        let result = service.probability_status().await;
+        assert!(result.is_ok());
+
+        let validation = result.unwrap();
+        assert!(!validation.is_valid);
+        assert!(!validation.violations.is_empty());
+
+        let violation = &validation.violations[0];
+        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
+        assert!(violation.message.contains("0.05 is below minimum threshold 0.15"));
+        assert_eq!(violation.severity, crate::domain::models::types::Severity::Warning);
     }
 
     #[tokio::test]
-    async fn test_add_leaf_nonexistent_parent() {
+    async fn test_probability_status_all_valid() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2301,197 +6200,184 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("test".to_string(), 5).await.unwrap();
-
-        // Create tree to set cursor
-        let _root_id = service.create_tree("Root for nonexistent test".to_string(), 5).await.unwrap();
+        let premise = "Root for all valid test".to_string();
+        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Try to add leaf - this will use cursor (should work)
-        let result = service.add_leaf(
-            "Valid premise".to_string(),
-            "Valid reasoning for nonexistent parent test".to_string(),
-            0.7,
-            8
-        ).await;
+        // Add nodes with all valid probabilities
+        let _leaf1 = service.add_leaf("Valid leaf 1".to_string(), "Valid reasoning one for all valid test".to_string(), 0.7, 7).await.unwrap();
+        let _leaf2 = service.add_leaf("Valid leaf 2".to_string(), "Valid reasoning two for all valid test".to_string(), 0.3, 5).await.unwrap();
 
-        // This should actually succeed since we have a cursor now
+        let result = service.probability_status().await;
         assert!(result.is_ok());
+
+        let validation = result.unwrap();
+        assert!(validation.is_valid);
+        assert!(validation.violations.is_empty());
+        assert_eq!(validation.suggestions[0], "Tree appears valid");
     }
 
+    // ULTRA-SPECIFIC EDGE CASES - The final frontier
+
     #[tokio::test]
-    async fn test_add_leaf_probability_sum_constraint() {
+    async fn test_validate_coherence_invalid_probability_causes_incoherence() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-
+    
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for invalid probability coherence test".to_string();
+        let _root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Create tree with root (default probability 1.0)
-        let premise = "Root premise for probability sum test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        // Add first child with probability 0.6
-        let result1 = service.add_leaf(
-            "First child".to_string(),
-            "First child reasoning".to_string(),
-            0.6,
-            7
-        ).await;
-        assert!(result1.is_ok());
-
-        // Add second child with probability 0.3 (total = 0.9, within parent's 1.0)
-        let result2 = service.add_leaf(
-            "Second child".to_string(),
-            "Second child reasoning".to_string(),
-            0.3,
-            6
-        ).await;
-        assert!(result2.is_ok());
-
-        // Try to add third child with probability 0.2 (total would be 1.1, exceeds parent's 1.0)
-        let result3 = service.add_leaf(
-            "Third child".to_string(),
-            "Third child reasoning".to_string(),
-            0.2,
-            5
-        ).await;
+        // Create node with invalid probability directly in DB (lines 408-414)
+        let invalid_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Node with invalid probability".to_string(),
+            reasoning: "This node tests the probability validation in validate_coherence".to_string(),
+            probability: 1.5, // INVALID - exceeds 1.0, should trigger lines 408-414
+            confidence: 5,
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
 
-        // This should fail due to probability constraint violation
-        assert!(result3.is_err());
-        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result3 {
-            assert!(msg.contains("Probability constraint violation"));
-            assert!(msg.contains("would result in total child probability"));
-            assert!(msg.contains("exceeds parent probability"));
-        } else {
-            panic!("Expected OperationNotAllowed error with probability constraint violation message");
-        }
+        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
 
-        // Verify that we can still add a child with a smaller probability that fits
-        let result4 = service.add_leaf(
-            "Fourth child".to_string(),
-            "Fourth child reasoning".to_string(),
-            0.1, // Total would be 1.0, exactly equal to parent
-            4
-        ).await;
-        assert!(result4.is_ok());
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
 
-        // Try to add any more children (even with small probability) should fail
-        let result5 = service.add_leaf(
-            "Fifth child".to_string(),
-            "Fifth child reasoning".to_string(),
-            0.01, // Total would be 1.01, exceeds parent's 1.0 beyond tolerance
-            3
-        ).await;
-        assert!(result5.is_err());
+        let coherence = result.unwrap();
+        // The invalid probability should make the tree incoherent (line 473)
+        assert!(!coherence.is_coherent);
     }
 
     #[tokio::test]
-    async fn test_add_leaf_probability_sum_constraint_with_expand() {
+    async fn test_validate_coherence_invalid_confidence_causes_incoherence() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-
+    
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
+        let premise = "Root for invalid confidence coherence test".to_string();
+        let _root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Create tree with default root probability (1.0)
-        let root_id = service.create_tree("Root for expand constraint test".to_string(), 5).await.unwrap();
-
-        // Add child with probability 0.6
-        let child_id = service.add_leaf(
-            "Child to expand".to_string(),
-            "Child reasoning".to_string(),
-            0.6,
-            7
-        ).await.unwrap();
+        // Create node with invalid confidence to trigger lines 417-422
+        let invalid_conf_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Node with invalid confidence".to_string(),
+            reasoning: "This node tests confidence validation in validate_coherence".to_string(),
+            probability: 0.6,
+            confidence: 11, // INVALID - above range [1,10], should trigger lines 417-422
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
 
-        // Expand this child to work on its children
-        service.expand_leaf(child_id, "Expanding child for deeper analysis".to_string()).await.unwrap();
+        let _created: Option<TreeNode> = service.db.create("node").content(invalid_conf_node).await.unwrap();
 
-        // Now the cursor is on the child (probability 0.6)
-        // Add grandchild with probability 0.4 (within 0.6)
-        let result1 = service.add_leaf(
-            "Grandchild 1".to_string(),
-            "First grandchild".to_string(),
-            0.4,
-            6
-        ).await;
-        assert!(result1.is_ok());
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
 
-        // Try to add another grandchild with probability 0.3 (total would be 0.7, exceeds parent's 0.6)
-        let result2 = service.add_leaf(
-            "Grandchild 2".to_string(),
-            "Second grandchild".to_string(),
-            0.3,
-            5
-        ).await;
-        assert!(result2.is_err());
-        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result2 {
-            assert!(msg.contains("Probability constraint violation"));
-        } else {
-            panic!("Expected OperationNotAllowed error");
-        }
+        let coherence = result.unwrap();
+        // Invalid confidence should make the tree incoherent (line 473)
+        assert!(!coherence.is_coherent);
     }
 
-    // Comprehensive edge case tests
-
     #[tokio::test]
-    async fn test_prune_leafs_with_insufficient_nodes() {
+    async fn test_add_leaf_prevents_child_probabilities_exceed_sum() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for insufficient nodes test".to_string();
+        let premise = "Root for child probability sum test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Add only 1 leaf
-        let _leaf1 = service.add_leaf(
-            "Single leaf".to_string(),
-            "Only one leaf for insufficient test".to_string(),
+        // Create parent node with probability 0.8
+        let parent_id = service.add_leaf(
+            "Parent for probability sum test".to_string(),
+            "Parent node that will test probability constraint".to_string(),
             0.8,
             8
         ).await.unwrap();
 
-        // Try to prune to keep 3 leafs when we only have 1
-        let result = service.prune_leafs(3).await;
-        assert!(result.is_ok());
+        // Navigate to parent to add children
+        service.navigate_to(parent_id).await.unwrap();
 
-        let prune_result = result.unwrap();
-        // Should preserve the single leaf and not remove anything
-        assert_eq!(prune_result.statistics.preserved_count, 1);
-        assert_eq!(prune_result.statistics.removed_count, 0);
-        assert_eq!(prune_result.statistics.original_count, 1);
-    }
+        // Add first child with probability 0.5 (within 0.8)
+        let _child1 = service.add_leaf(
+            "First child".to_string(),
+            "First child with moderate probability".to_string(),
+            0.5,
+            7
+        ).await.unwrap();
 
-    #[tokio::test]
-    async fn test_balance_leafs_empty_tree() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
-                .await
-                .unwrap();
-    
-        db.use_ns("analytics").use_db("trees").await.unwrap();
+        // Try to add second child with probability 0.4 (total would be 0.9 > 0.8)
+        let result2 = service.add_leaf(
+            "Second child".to_string(),
+            "Second child that would exceed parent probability".to_string(),
+            0.4, // 0.5 + 0.4 = 0.9 > 0.8
+            6
+        ).await;
 
-        let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("empty_balance_test".to_string(), 5).await.unwrap();
+        // This should fail due to probability constraint validation
+        assert!(result2.is_err());
+        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result2 {
+            assert!(msg.contains("Probability constraint violation"));
+        } else {
+            panic!("Expected OperationNotAllowed error with probability constraint violation");
+        }
 
-        // Try to balance when there are no leaf nodes
-        let result = service.balance_leafs(UncertaintyType::InsufficientData).await;
+        // validate_coherence should pass since no invalid data was added
+        let result = service.validate_coherence().await;
         assert!(result.is_ok());
 
-        let balance_result = result.unwrap();
-        assert!(balance_result.balanced_nodes.is_empty());
-        assert!(balance_result.original_probabilities.is_empty());
-        assert!(balance_result.new_probabilities.is_empty());
+        let coherence = result.unwrap();
+        assert!(coherence.is_coherent); // Should be coherent since invalid data was prevented
+        assert!(coherence.contradictions.is_empty()); // No contradictions since no invalid data was added
     }
 
     #[tokio::test]
-    async fn test_balance_leafs_mixed_probabilities() {
+    async fn test_validate_coherence_mixed_violations_and_contradictions() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2499,63 +6385,81 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for mixed probabilities test".to_string();
+        let premise = "Root for mixed violations test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Add two leaves with very different probabilities that sum within 1.0
-        let high_leaf = service.add_leaf(
-            "High confidence leaf".to_string(),
-            "Very confident reasoning for balance test".to_string(),
-            0.7,
+        // Create scenario with valid probabilities for coherence testing
+        let parent_id = service.add_leaf(
+            "Parent for mixed violations".to_string(),
+            "Parent node for testing coherence validation".to_string(),
+            0.9,
             9
         ).await.unwrap();
 
-        let low_leaf = service.add_leaf(
-            "Low confidence leaf".to_string(),
-            "Less confident reasoning for balance test".to_string(),
-            0.2,
-            3
+        // Navigate to parent to add children
+        service.navigate_to(parent_id).await.unwrap();
+
+        // Add children that sum within parent's probability
+        let _child1 = service.add_leaf(
+            "Child 1".to_string(),
+            "Child one for coherence test".to_string(),
+            0.5,
+            8
         ).await.unwrap();
 
-        let result = service.balance_leafs(UncertaintyType::InsufficientData).await;
-        assert!(result.is_ok());
+        let _child2 = service.add_leaf(
+            "Child 2".to_string(),
+            "Child two for coherence test".to_string(),
+            0.3, // 0.5 + 0.3 = 0.8 < 0.9 (valid)
+            7
+        ).await.unwrap();
 
-        let balance_result = result.unwrap();
-        // Should balance the high probability node (avg = 0.55, high = 0.9 > 0.55 + 0.1)
-        assert!(balance_result.balanced_nodes.len() >= 1);
-        // Should contain the high probability node
-        assert!(balance_result.balanced_nodes.contains(&high_leaf) ||
-                balance_result.original_probabilities.contains_key(&high_leaf));
-    }
+        // Also add a node with invalid confidence (violation)
+        let invalid_node = TreeNode {
+            id: None,
+            friendly_id: String::new(),
+            premise: "Invalid confidence mixed test node".to_string(),
+            reasoning: "Node with invalid confidence for mixed violation test".to_string(),
+            probability: 0.5,
+            confidence: 0, // INVALID - below range [1,10]
+            parent_id: None,
+            children: Vec::new(),
+            node_type: crate::domain::models::tree_node::NodeType::Leaf,
+            is_invalidated: false,
+            depth: 1,
+            created_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+            evidence: Vec::new(),
+            proposition: None,
+            time_window: None,
+            review_by: None,
+            locked: false,
+            frozen: false,
+            gate: None,
+            impact: None,
+            category: None,
+            argument: None,
+            exhaustive: false,
+            residual: false,
+        };
 
-    #[tokio::test]
-    async fn test_export_paths_empty_tree() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
-                .await
-                .unwrap();
-    
-        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
 
-        let mut service = TreeEngineService::new(Arc::new(db));
-        service.initialize_with_tree("empty_export_test".to_string(), 5).await.unwrap();
+        let result = service.validate_coherence().await;
+        assert!(result.is_ok());
 
-        let insights = vec![
-            "First insight".to_string(),
-            "Second insight".to_string(),
-            "Third insight".to_string(),
-        ];
+        let coherence = result.unwrap();
+        assert!(!coherence.is_coherent);
 
-        // Try to export paths when there are no surviving paths
-        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
-        assert!(result.is_ok());
+        // Should have violations from the manually inserted invalid node
+        // (No probability sum contradictions since valid data was added through add_leaf)
 
-        let analysis = result.unwrap();
-        assert!(analysis.surviving_paths.is_empty());
-        assert_eq!(analysis.total_thought_tokens, 0);
+        // The only contradiction should be from the manually inserted node with invalid confidence
+        // No probability sum violations since we used add_leaf which enforces constraints
     }
 
     #[tokio::test]
-    async fn test_export_paths_with_invalidated_nodes() {
+    async fn test_validate_coherence_perfectly_coherent_suggestions() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
@@ -2563,694 +6467,1305 @@ mod tests {
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for invalidated nodes test".to_string();
+        let premise = "Root for perfectly coherent test".to_string();
         let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Add leaf and then prune to invalidate some nodes
-        let _leaf1 = service.add_leaf(
-            "Leaf to be invalidated".to_string(),
-            "This leaf will be invalidated in pruning test".to_string(),
-            0.1, // Low probability, likely to be pruned
-            2
+        // Create a perfectly valid tree structure to trigger line 450
+        let parent_id = service.add_leaf(
+            "Perfect parent node".to_string(),
+            "Perfectly valid parent node for coherence test".to_string(),
+            0.8,
+            8
         ).await.unwrap();
 
-        let _leaf2 = service.add_leaf(
-            "Leaf to survive".to_string(),
-            "This leaf should survive pruning test".to_string(),
-            0.9, // High probability
-            9
+        // Navigate to parent and add children with valid probabilities that sum < 1.0
+        service.navigate_to(parent_id).await.unwrap();
+        let _child1 = service.add_leaf(
+            "Valid child 1".to_string(),
+            "First perfectly valid child for coherence test".to_string(),
+            0.3, // Low probability
+            5
         ).await.unwrap();
 
-        // Prune aggressively to invalidate low-probability nodes
-        let _prune_result = service.prune_tree(0.8).await.unwrap();
-
-        let insights = vec![
-            "Analysis insight one".to_string(),
-            "Analysis insight two".to_string(),
-            "Analysis insight three".to_string(),
-        ];
+        let _child2 = service.add_leaf(
+            "Valid child 2".to_string(),
+            "Second perfectly valid child for coherence test".to_string(),
+            0.2, // 0.3 + 0.2 = 0.5 < 1.0 ✓
+            4
+        ).await.unwrap();
 
-        // Export should skip invalidated nodes
-        let result = service.export_paths(NarrativeStyle::Strategic, insights, 0.7).await;
+        let result = service.validate_coherence().await;
         assert!(result.is_ok());
 
-        let analysis = result.unwrap();
-        // Should only include paths from non-invalidated nodes
-        assert_eq!(analysis.narrative_style, NarrativeStyle::Strategic);
+        let coherence = result.unwrap();
+        assert!(coherence.is_coherent);
+        assert!(coherence.contradictions.is_empty());
+
+        // Should trigger the "perfectly coherent" suggestion path (line 450)
+        // This is difficult to assert directly, but the coherence should be true
     }
 
     #[tokio::test]
-    async fn test_probability_status_with_violations() {
+    async fn test_nodes_receive_unique_friendly_ids() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for violations test".to_string();
-        let root_id = service.create_tree(premise, 1).await.unwrap(); // Very low complexity for tight constraints
-
-        // Add a leaf that should be fine initially
-        let leaf_id = service.add_leaf(
-            "Normal leaf".to_string(),
-            "Normal reasoning for probability test".to_string(),
-            0.05, // Very low probability - below min_probability threshold
-            3
-        ).await.unwrap();
-
-        // Check probability status - should find violations
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
-
-        let validation = result.unwrap();
-        // Should detect the low probability violation
-        assert!(!validation.violations.is_empty());
+        let root_id = service.create_tree("Root for friendly id test".to_string(), 5).await.unwrap();
+        let leaf1 = service.add_leaf("Leaf 1".to_string(), "Reasoning 1".to_string(), 0.5, 7).await.unwrap();
+        let leaf2 = service.add_leaf("Leaf 2".to_string(), "Reasoning 2".to_string(), 0.4, 6).await.unwrap();
+
+        let aliases = vec![
+            service.get_friendly_id(&root_id).await.unwrap(),
+            service.get_friendly_id(&leaf1).await.unwrap(),
+            service.get_friendly_id(&leaf2).await.unwrap(),
+        ];
 
-        // Should have suggestions
-        assert!(!validation.suggestions.is_empty());
-        assert_eq!(validation.suggestions[0], "Fix probability range violations");
+        assert!(aliases.iter().all(|alias| !alias.is_empty()));
+        let unique: HashSet<&String> = aliases.iter().collect();
+        assert_eq!(unique.len(), aliases.len());
     }
 
     #[tokio::test]
-    async fn test_validate_coherence_complex_tree() {
+    async fn test_resolve_node_ref_accepts_alias_record_id_and_key() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for complex coherence test".to_string();
-        let root_id = service.create_tree(premise, 7).await.unwrap(); // Higher complexity
-
-        // Build a more complex tree structure
-        let branch1 = service.add_leaf(
-            "Branch 1 premise".to_string(),
-            "First branch reasoning for coherence test".to_string(),
-            0.7,
-            7
-        ).await.unwrap();
-
-        let branch2 = service.add_leaf(
-            "Branch 2 premise".to_string(),
-            "Second branch reasoning for coherence test".to_string(),
-            0.3,
-            4
-        ).await.unwrap();
-
-        // Navigate to branch1 and add sub-branch
-        service.navigate_to(branch1).await.unwrap();
-        let _subbranch1 = service.add_leaf(
-            "Sub-branch 1.1".to_string(),
-            "Sub-branch reasoning under branch 1 for coherence".to_string(),
-            0.6,
-            6
-        ).await.unwrap();
-
-        // Navigate to branch2 and add sub-branch
-        service.navigate_to(branch2).await.unwrap();
-        let _subbranch2 = service.add_leaf(
-            "Sub-branch 2.1".to_string(),
-            "Sub-branch reasoning under branch 2 for coherence".to_string(),
-            0.25, // Must be <= branch2's probability of 0.3
-            5
-        ).await.unwrap();
-
-        // Validate coherence of complex structure
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
-
-        let coherence = result.unwrap();
-        // Complex tree should still be coherent
-        assert!(coherence.is_coherent);
-        assert!(coherence.contradictions.is_empty());
+        service.create_tree("Root for resolve test".to_string(), 5).await.unwrap();
+        let leaf_id = service.add_leaf("Leaf".to_string(), "Reasoning".to_string(), 0.5, 7).await.unwrap();
+        let alias = service.get_friendly_id(&leaf_id).await.unwrap();
+        let key = leaf_id.split_once(':').unwrap().1.to_string();
+
+        assert_eq!(service.resolve_node_ref(&alias).await.unwrap().to_string(), leaf_id);
+        assert_eq!(service.resolve_node_ref(&leaf_id).await.unwrap().to_string(), leaf_id);
+        assert_eq!(service.resolve_node_ref(&key).await.unwrap().to_string(), leaf_id);
+        assert_eq!(service.resolve_node_ref(&leaf_id[..leaf_id.len() - 2]).await.unwrap().to_string(), leaf_id);
     }
 
     #[tokio::test]
-    async fn test_inspect_tree_complex_structure() {
+    async fn test_resolve_node_ref_rejects_unknown_and_ambiguous() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for complex inspect test".to_string();
-        let root_id = service.create_tree(premise, 8).await.unwrap();
+        service.create_tree("Root for ambiguous test".to_string(), 5).await.unwrap();
+        service.add_leaf("Leaf".to_string(), "Reasoning".to_string(), 0.5, 7).await.unwrap();
 
-        // Create a tree with multiple levels and nodes - cursor is at root
-        let level1_1 = service.add_leaf(
-            "Level 1 Node 1".to_string(),
-            "First level one node reasoning for inspection".to_string(),
-            0.6,
-            8
-        ).await.unwrap();
+        let result = service.resolve_node_ref("zzz_not_a_node").await;
+        assert!(matches!(result, Err(TreeEngineError::NotFound(_))));
 
-        let level1_2 = service.add_leaf(
-            "Level 1 Node 2".to_string(),
-            "Second level one node reasoning for inspection".to_string(),
-            0.3,
-            6
-        ).await.unwrap();
+        // Every record id shares the table prefix, so it cannot identify a single node
+        let result = service.resolve_node_ref("node:").await;
+        assert!(matches!(result, Err(TreeEngineError::InvalidInput(_, _))));
+
+        let result = service.resolve_node_ref("   ").await;
+        assert!(matches!(result, Err(TreeEngineError::InvalidInput(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_expand_leaf_and_navigate_by_alias() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
 
-        // Navigate to first level 1 node to add children
-        service.navigate_to(level1_1).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        // Add second level under level1_1 (probability 0.6)
-        let _level2_1 = service.add_leaf(
-            "Level 2 Node 1".to_string(),
-            "Level two node one reasoning for inspection".to_string(),
-            0.4, // Must be <= level1_1's probability of 0.6
-            7
-        ).await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let root_id = service.create_tree("Root for alias navigation test".to_string(), 5).await.unwrap();
+        let leaf_id = service.add_leaf("Leaf".to_string(), "Reasoning".to_string(), 0.5, 7).await.unwrap();
+        let leaf_alias = service.get_friendly_id(&leaf_id).await.unwrap();
+        let root_alias = service.get_friendly_id(&root_id).await.unwrap();
 
-        let _level2_2 = service.add_leaf(
-            "Level 2 Node 2".to_string(),
-            "Level two node two reasoning for inspection".to_string(),
-            0.15, // 0.4 + 0.15 = 0.55 <= 0.6
-            5
-        ).await.unwrap();
+        let expanded = service.expand_leaf(leaf_alias, "Expanding by alias".to_string()).await.unwrap();
+        assert_eq!(expanded, leaf_id);
 
-        // Inspect the complex tree
-        let result = service.inspect_tree().await;
-        assert!(result.is_ok());
+        service.navigate_to(root_alias).await.unwrap();
+        assert_eq!(service.get_current_node().await.unwrap(), root_id);
 
-        let visualization = result.unwrap();
+        let visualization = service.inspect_tree().await.unwrap();
+        let root_view = visualization.node_details.values().find(|n| n.depth == 0).unwrap();
+        assert!(root_view.children_ids.contains(&service.get_friendly_id(&leaf_id).await.unwrap()));
+    }
 
-        // ASCII tree generation is currently disabled, so we check the actual data
-        // Verify that node details contain the expected premises
-        let has_level1_node1 = visualization.node_details.values().any(|n| n.premise_summary.contains("Level 1 Node 1"));
-        let has_level2_node1 = visualization.node_details.values().any(|n| n.premise_summary.contains("Level 2 Node 1"));
-        assert!(has_level1_node1);
-        assert!(has_level2_node1);
+    #[tokio::test]
+    async fn test_search_nodes_keyword() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
 
-        // Should have node details for all nodes
-        assert!(visualization.node_details.len() >= 4); // At least root + 4 added nodes
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        // Should have meaningful statistics
-        assert!(visualization.statistics.total_nodes >= 5); // Root + 4 added
-        assert_eq!(visualization.statistics.active_paths, 3); // 3 leaf nodes
-        assert_eq!(visualization.statistics.max_depth, 2);
-        assert!(visualization.statistics.avg_probability > 0.0);
-        assert!(visualization.statistics.complexity_score > 0.0);
+        let mut service = TreeEngineService::new(Arc::new(db));
+        service.create_tree("Will the product launch on schedule?".to_string(), 5).await.unwrap();
+        let delayed = service.add_leaf("Supplier delays push launch".to_string(), "Key supplier reported shortages".to_string(), 0.4, 7).await.unwrap();
+        service.add_leaf("Launch on time".to_string(), "Engineering is ahead of plan".to_string(), 0.5, 6).await.unwrap();
+
+        let result = service.search_nodes(NodeSearchQuery {
+            text: Some("supplier".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.hits[0].node_id, delayed);
+        assert_eq!(result.hits[0].root_path.len(), 2);
+        assert_eq!(result.hits[0].root_path_premises[1], "Supplier delays push launch");
     }
 
     #[tokio::test]
-    async fn test_database_namespace_setup() {
-        // This test will exercise the database setup line (line 22)
-        let db1 = Surreal::new::<surrealdb::engine::local::Mem>(())
+    async fn test_search_nodes_fuzzy_and_filters() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
 
-        db1.use_ns("analytics").use_db("trees").await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let mut service1 = TreeEngineService::new(Arc::new(db1));
+        let mut service = TreeEngineService::new(Arc::new(db));
+        service.create_tree("Will the product launch on schedule?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays push launch".to_string(), "Key supplier reported shortages".to_string(), 0.4, 7).await.unwrap();
+        service.add_leaf("Supplier switch succeeds".to_string(), "A backup supplier is qualified".to_string(), 0.2, 4).await.unwrap();
+
+        // Typo still matches both supplier nodes
+        let result = service.search_nodes(NodeSearchQuery {
+            text: Some("suplier".to_string()),
+            mode: SearchMode::Fuzzy,
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(result.total_matches, 2);
+
+        // Attribute filters narrow the fuzzy matches
+        let result = service.search_nodes(NodeSearchQuery {
+            text: Some("suplier".to_string()),
+            mode: SearchMode::Fuzzy,
+            min_confidence: Some(5),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.hits[0].premise, "Supplier delays push launch");
+
+        // Without text, filters alone select nodes
+        let result = service.search_nodes(NodeSearchQuery {
+            min_depth: Some(1),
+            max_probability: Some(0.3),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.hits[0].premise, "Supplier switch succeeds");
+
+        let result = service.search_nodes(NodeSearchQuery {
+            metadata_keys: vec!["complexity".to_string()],
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(result.total_matches, 1);
+        assert_eq!(result.hits[0].depth, 0);
+
+        // Each status filter selects exactly the hits reported with that status
+        for (status, expected) in [(NodeStatus::Active, 2), (NodeStatus::Expanded, 1), (NodeStatus::Invalidated, 0)] {
+            let result = service.search_nodes(NodeSearchQuery {
+                status: Some(status.clone()),
+                ..Default::default()
+            }).await.unwrap();
+            assert_eq!(result.total_matches, expected);
+            assert!(result.hits.iter().all(|hit| hit.status == status));
+        }
+    }
 
-        let db2 = Surreal::new::<surrealdb::engine::local::Mem>(())
+    #[tokio::test]
+    async fn test_search_nodes_invalid_ranges() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
 
-        db2.use_ns("analytics").use_db("trees").await.unwrap();
-
-        let mut service2 = TreeEngineService::new(Arc::new(db2));
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let result1 = service1.initialize_with_tree("tree1".to_string(), 3).await;
-        let result2 = service2.initialize_with_tree("tree2".to_string(), 4).await;
+        let service = TreeEngineService::new(Arc::new(db));
 
-        assert!(result1.is_ok());
-        assert!(result2.is_ok());
+        let result = service.search_nodes(NodeSearchQuery {
+            min_probability: Some(0.8),
+            max_probability: Some(0.2),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(TreeEngineError::InvalidInput(_, _))));
+
+        let result = service.search_nodes(NodeSearchQuery {
+            max_probability: Some(1.5),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(TreeEngineError::ProbabilityOutOfRange(_))));
     }
 
-    // CRITICAL EDGE CASES - These are the failure points in production
-
     #[tokio::test]
-    async fn test_export_paths_empty_insight_strings() {
+    async fn test_attach_and_list_evidence() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for empty insights test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-        let _leaf = service.add_leaf("Test leaf".to_string(), "Test reasoning for empty insights".to_string(), 0.7, 7).await.unwrap();
-
-        // This is the critical edge case: insights with empty strings
-        let insights = vec![
-            "Valid insight".to_string(),
-            "".to_string(), // Empty string - this should fail
-            "Another valid insight".to_string(),
-        ];
-
-        let result = service.export_paths(NarrativeStyle::Analytical, insights, 0.5).await;
-        assert!(result.is_err());
+        service.create_tree("Root for evidence test".to_string(), 5).await.unwrap();
+        let leaf_id = service.add_leaf("Supplier delays".to_string(), "Audit findings".to_string(), 0.4, 7).await.unwrap();
+
+        let evidence = service.attach_evidence(
+            leaf_id.clone(),
+            "Supplier audit".to_string(),
+            "https://example.com/audit.pdf".to_string(),
+            "Lead times doubled".to_string(),
+            chrono::NaiveDate::from_ymd_opt(2025, 3, 14),
+            EvidenceStrength::Strong,
+        ).await.unwrap();
 
-        if let Err(TreeEngineError::InvalidInput(field, msg)) = result {
-            assert_eq!(field, "insights");
-            assert!(msg.contains("Insight 2 cannot be empty"));
-        } else {
-            panic!("Expected InvalidInput error for empty insight string");
-        }
+        let listed = service.list_evidence(Some(leaf_id.clone())).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].evidence, vec![evidence]);
+
+        let all = service.list_evidence(None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].node_id, service.get_friendly_id(&leaf_id).await.unwrap());
+
+        let result = service.attach_evidence(
+            leaf_id,
+            "  ".to_string(),
+            "ref".to_string(),
+            String::new(),
+            None,
+            EvidenceStrength::Weak,
+        ).await;
+        assert!(matches!(result, Err(TreeEngineError::InvalidInput(_, _))));
     }
 
     #[tokio::test]
-    async fn test_export_paths_whitespace_only_insights() {
+    async fn test_export_paths_includes_bibliography() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for whitespace insights test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-        let _leaf = service.add_leaf("Test leaf".to_string(), "Test reasoning for whitespace insights".to_string(), 0.8, 8).await.unwrap();
+        let root_id = service.create_tree("Root for bibliography test".to_string(), 5).await.unwrap();
+        let cited = service.add_leaf("Cited branch".to_string(), "Backed by a source".to_string(), 0.6, 7).await.unwrap();
+        service.add_leaf("Uncited branch".to_string(), "No source yet".to_string(), 0.3, 5).await.unwrap();
 
-        // Another critical case: insights with only whitespace
-        let insights = vec![
-            "Valid insight".to_string(),
-            "   \t\n  ".to_string(), // Only whitespace - should fail
-            "Third valid insight".to_string(),
-        ];
+        service.attach_evidence(root_id, "Context memo".to_string(), "memo-001".to_string(), String::new(), None, EvidenceStrength::Moderate).await.unwrap();
+        service.attach_evidence(cited.clone(), "Field survey".to_string(), "https://example.com/survey".to_string(), String::new(), None, EvidenceStrength::Strong).await.unwrap();
 
-        let result = service.export_paths(NarrativeStyle::Strategic, insights, 0.6).await;
-        assert!(result.is_err());
+        let insights = vec!["First".to_string(), "Second".to_string(), "Third".to_string()];
+        let analysis = service.export_paths(NarrativeStyle::Analytical, insights, 0.8).await.unwrap();
 
-        if let Err(TreeEngineError::InvalidInput(field, msg)) = result {
-            assert_eq!(field, "insights");
-            assert!(msg.contains("Insight 2 cannot be empty"));
-        } else {
-            panic!("Expected InvalidInput error for whitespace-only insight");
-        }
+        assert_eq!(analysis.bibliography.len(), analysis.surviving_paths.len());
+        let cited_alias = service.get_friendly_id(&cited).await.unwrap();
+        let cited_entry = analysis.bibliography.iter().find(|entry| entry.leaf_id == cited_alias).unwrap();
+        let titles: Vec<&str> = cited_entry.citations.iter().map(|c| c.evidence.source_title.as_str()).collect();
+        assert_eq!(titles, vec!["Context memo", "Field survey"]);
+
+        let uncited_entry = analysis.bibliography.iter().find(|entry| entry.leaf_id != cited_alias).unwrap();
+        assert_eq!(uncited_entry.citations.len(), 1);
+        assert!(analysis.to_string().contains("BIBLIOGRAPHY"));
     }
 
     #[tokio::test]
-    async fn test_probability_status_invalid_probability_range() {
+    async fn test_cross_link_shared_outcome() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for invalid probability range test".to_string();
-        let _root_id = service.create_tree(premise, 5).await.unwrap();
+        service.create_tree("Will the regional market hold up?".to_string(), 5).await.unwrap();
+        let demand_shock = service.add_leaf("Demand shock".to_string(), "Consumer spending drops".to_string(), 0.5, 7).await.unwrap();
+        let rate_shock = service.add_leaf("Rate shock".to_string(), "Central bank hikes sharply".to_string(), 0.4, 6).await.unwrap();
+        service.expand_leaf(demand_shock.clone(), "Demand shock cascades".to_string()).await.unwrap();
+        let collapse = service.add_leaf("Market collapse".to_string(), "Demand never recovers".to_string(), 0.3, 6).await.unwrap();
+
+        let link = service.add_cross_link(rate_shock.clone(), collapse.clone(), 0.2, "Financing dries up".to_string()).await.unwrap();
+        assert_eq!(link.child.to_string(), collapse);
+        assert_eq!(service.list_cross_links().await.unwrap().len(), 1);
+
+        // One path per distinct route into the shared outcome; the rate shock is no longer a leaf
+        let insights = vec!["First".to_string(), "Second".to_string(), "Third".to_string()];
+        let analysis = service.export_paths(NarrativeStyle::Analytical, insights.clone(), 0.8).await.unwrap();
+        assert_eq!(analysis.surviving_paths.len(), 2);
+        assert!(analysis.surviving_paths.iter().all(|path| path.path.last() == Some(&collapse)));
+        let mut probabilities: Vec<f64> = analysis.surviving_paths.iter().map(|path| path.final_probability).collect();
+        probabilities.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((probabilities[0] - 0.08).abs() < 1e-9);
+        assert!((probabilities[1] - 0.15).abs() < 1e-9);
+        assert!(analysis.surviving_paths.iter().any(|path| path.reasoning_chain.ends_with("Financing dries up")));
+
+        let visualization = service.inspect_tree().await.unwrap();
+        let collapse_alias = service.get_friendly_id(&collapse).await.unwrap();
+        let rate_alias = service.get_friendly_id(&rate_shock).await.unwrap();
+        assert_eq!(visualization.node_details[&collapse_alias].linked_parent_ids, vec![rate_alias.clone()]);
+        assert!(!visualization.node_details[&rate_alias].is_leaf);
+        assert_eq!(visualization.statistics.cross_links, 1);
+        assert_eq!(visualization.statistics.scenario_paths, 2);
+
+        assert!(service.validate_coherence().await.unwrap().is_coherent);
+
+        service.remove_cross_link(rate_alias, collapse_alias).await.unwrap();
+        let analysis = service.export_paths(NarrativeStyle::Analytical, insights, 0.8).await.unwrap();
+        assert_eq!(analysis.surviving_paths.len(), 2);
+        assert!(analysis.surviving_paths.iter().any(|path| path.path.last() == Some(&rate_shock)));
+    }
 
-        // Manually create a node with invalid probability by direct database manipulation
-        // This simulates corruption or external modification
-        let invalid_node = TreeNode {
-            id: None,
-            premise: "Invalid probability node".to_string(),
-            reasoning: "This node has invalid probability for critical test".to_string(),
-            probability: 1.5, // INVALID - greater than 1.0
-            confidence: 5,
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
+    #[tokio::test]
+    async fn test_cross_link_rejections() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
 
-        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        // Now check probability status - should detect the invalid range
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let root_id = service.create_tree("Will the regional market hold up?".to_string(), 5).await.unwrap();
+        let demand_shock = service.add_leaf("Demand shock".to_string(), "Consumer spending drops".to_string(), 0.5, 7).await.unwrap();
+        let rate_shock = service.add_leaf("Rate shock".to_string(), "Central bank hikes sharply".to_string(), 0.4, 6).await.unwrap();
+        service.expand_leaf(demand_shock.clone(), "Demand shock cascades".to_string()).await.unwrap();
+        let collapse = service.add_leaf("Market collapse".to_string(), "Demand never recovers".to_string(), 0.3, 6).await.unwrap();
 
-        let validation = result.unwrap();
-        assert!(!validation.is_valid);
-        assert!(!validation.violations.is_empty());
+        let reasoning = || "Shared outcome".to_string();
+        let cycle = service.add_cross_link(collapse.clone(), demand_shock.clone(), 0.1, reasoning()).await;
+        assert!(matches!(cycle, Err(TreeEngineError::OperationNotAllowed(_))));
 
-        // Check that we caught the specific error
-        let violation = &validation.violations[0];
-        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
-        assert!(violation.message.contains("1.5 is outside valid range [0,1]"));
-        assert_eq!(violation.severity, crate::domain::models::types::Severity::Error);
-        assert_eq!(validation.suggestions[0], "Fix probability range violations");
+        let duplicate = service.add_cross_link(demand_shock.clone(), collapse.clone(), 0.1, reasoning()).await;
+        assert!(matches!(duplicate, Err(TreeEngineError::OperationNotAllowed(_))));
+
+        let to_root = service.add_cross_link(rate_shock.clone(), root_id, 0.1, reasoning()).await;
+        assert!(matches!(to_root, Err(TreeEngineError::OperationNotAllowed(_))));
+
+        let over_budget = service.add_cross_link(rate_shock.clone(), collapse.clone(), 0.5, reasoning()).await;
+        assert!(matches!(over_budget, Err(TreeEngineError::OperationNotAllowed(_))));
+
+        let self_link = service.add_cross_link(collapse.clone(), collapse.clone(), 0.1, reasoning()).await;
+        assert!(matches!(self_link, Err(TreeEngineError::InvalidInput(_, _))));
+
+        let missing = service.remove_cross_link(rate_shock, collapse).await;
+        assert!(matches!(missing, Err(TreeEngineError::NotFound(_))));
     }
 
     #[tokio::test]
-    async fn test_probability_status_negative_probability() {
+    async fn test_checkpoint_and_list() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for negative probability test".to_string();
-        let _root_id = service.create_tree(premise, 5).await.unwrap();
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.5, 7).await.unwrap();
 
-        // Create node with negative probability
-        let negative_node = TreeNode {
-            id: None,
-            premise: "Negative probability node".to_string(),
-            reasoning: "This node has negative probability for critical test".to_string(),
-            probability: -0.3, // INVALID - negative
-            confidence: 4,
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
+        let summary = service.checkpoint("  before pruning ".to_string()).await.unwrap();
+        assert_eq!(summary.name, "before pruning");
+        assert_eq!(summary.node_count, 2);
 
-        let _created: Option<TreeNode> = service.db.create("node").content(negative_node).await.unwrap();
+        let duplicate = service.checkpoint("before pruning".to_string()).await;
+        assert!(matches!(duplicate, Err(TreeEngineError::OperationNotAllowed(_))));
+        let reserved = service.checkpoint("Current".to_string()).await;
+        assert!(matches!(reserved, Err(TreeEngineError::InvalidInput(_, _))));
 
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
+        let checkpoints = service.list_checkpoints().await.unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].name, "before pruning");
 
-        let validation = result.unwrap();
-        assert!(!validation.is_valid);
-        assert!(!validation.violations.is_empty());
+        let unchanged = service.diff_trees("before pruning".to_string(), "current".to_string(), 5).await.unwrap();
+        assert!(unchanged.is_empty());
 
-        let violation = &validation.violations[0];
-        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
-        assert!(violation.message.contains("-0.3 is outside valid range [0,1]"));
-        assert_eq!(violation.severity, crate::domain::models::types::Severity::Error);
+        let missing = service.diff_trees("no such checkpoint".to_string(), "current".to_string(), 5).await;
+        assert!(matches!(missing, Err(TreeEngineError::NotFound(_))));
+        let zero_k = service.diff_trees("before pruning".to_string(), "current".to_string(), 0).await;
+        assert!(matches!(zero_k, Err(TreeEngineError::InvalidInput(_, _))));
     }
 
     #[tokio::test]
-    async fn test_probability_status_below_minimum_threshold() {
+    async fn test_diff_trees_across_trees() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for below threshold test".to_string();
-        let _root_id = service.create_tree(premise, 5).await.unwrap();
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.5, 7).await.unwrap();
+        service.add_leaf("Team attrition".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+        service.checkpoint("v1".to_string()).await.unwrap();
+
+        // Rebuild with new evidence: nodes are matched by premise across trees
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays".to_string(), "Second supplier secured".to_string(), 0.2, 8).await.unwrap();
+        service.add_leaf("Team attrition".to_string(), "Hiring freeze announced".to_string(), 0.6, 6).await.unwrap();
+        service.add_leaf("Regulatory hold".to_string(), "New certification rules".to_string(), 0.1, 4).await.unwrap();
+
+        let diff = service.diff_trees("v1".to_string(), "current".to_string(), 5).await.unwrap();
+        assert_eq!(diff.added_nodes.len(), 1);
+        assert_eq!(diff.added_nodes[0].premise, "Regulatory hold");
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.probability_changes.len(), 2);
+        assert_eq!(diff.confidence_changes.len(), 1);
+        assert_eq!(diff.confidence_changes[0].delta(), 1.0);
+        assert!(diff.premise_changes.is_empty());
+
+        let rank_of = |premise: &str| diff.ranking_changes.iter()
+            .find(|change| change.premises.last().map(String::as_str) == Some(premise))
+            .map(|change| (change.rank_before, change.rank_after));
+        assert_eq!(rank_of("Supplier delays"), Some((Some(1), Some(2))));
+        assert_eq!(rank_of("Team attrition"), Some((Some(2), Some(1))));
+        assert_eq!(rank_of("Regulatory hold"), Some((None, Some(3))));
+        assert!(diff.to_string().contains("TOP-5 PATH RANKING CHANGES (3)"));
+    }
 
-        // Create node with probability below min_probability (0.15)
-        let below_threshold_node = TreeNode {
-            id: None,
-            premise: "Below threshold node".to_string(),
-            reasoning: "This node has probability below minimum threshold for critical test".to_string(),
-            probability: 0.05, // Below 0.15 min_probability
-            confidence: 3,
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
+    #[tokio::test]
+    async fn test_merge_tree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
 
-        let _created: Option<TreeNode> = service.db.create("node").content(below_threshold_node).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        let validation = result.unwrap();
-        assert!(!validation.is_valid);
-        assert!(!validation.violations.is_empty());
+        // Analyst B's tree, kept as a checkpoint
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays cause a slip".to_string(), "Lead times doubled".to_string(), 0.6, 6).await.unwrap();
+        let hold = service.add_leaf("Regulatory hold blocks release".to_string(), "New certification rules".to_string(), 0.3, 5).await.unwrap();
+        service.expand_leaf(hold, "Certification is the bottleneck".to_string()).await.unwrap();
+        service.add_leaf("Certification lab backlog".to_string(), "Labs booked for months".to_string(), 0.2, 5).await.unwrap();
+        service.checkpoint("analyst_b".to_string()).await.unwrap();
+
+        // Analyst A's live tree
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays cause slip".to_string(), "Single-source components".to_string(), 0.4, 6).await.unwrap();
+        service.add_leaf("Team attrition slows work".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+
+        let invalid = service.merge_tree("current".to_string(), None, false).await;
+        assert!(matches!(invalid, Err(TreeEngineError::InvalidInput(_, _))));
+        let invalid = service.merge_tree("analyst_b".to_string(), Some(0.0), false).await;
+        assert!(matches!(invalid, Err(TreeEngineError::InvalidInput(_, _))));
+
+        // A dry run reports the same merge without writing it
+        let preview = service.merge_tree("analyst_b".to_string(), None, true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.grafted.len(), 2);
+        assert_eq!(preview.conflicts.len(), 1);
+        assert!(preview.affected_paths.iter().any(|path| path.rank_before.is_none()
+            && path.premises.last().map(String::as_str) == Some("Certification lab backlog")));
+        assert_eq!(service.inspect_tree().await.unwrap().statistics.total_nodes, 3);
+
+        let report = service.merge_tree("analyst_b".to_string(), None, false).await.unwrap();
+        assert!(!report.dry_run);
+        // The preview did not use up its aliases, so the real merge grafts under the same ones
+        let aliases = |report: &MergeReport| report.grafted.iter().map(|node| node.node_id.clone()).collect::<Vec<_>>();
+        assert_eq!(aliases(&report), aliases(&preview));
+        assert_eq!(report.matched.len(), 2);
+        let supplier = report.matched.iter().find(|node| node.premise.starts_with("Supplier")).unwrap();
+        assert!((supplier.pooled_probability - 0.5).abs() < 1e-9);
+
+        let grafted: Vec<&str> = report.grafted.iter().map(|node| node.premise.as_str()).collect();
+        assert_eq!(grafted, vec!["Regulatory hold blocks release", "Certification lab backlog"]);
+
+        // 0.5 + 0.3 + 0.3 now exceeds the root
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].parent_premise, "Will the product launch on time?");
+        assert!((report.conflicts[0].children_total - 1.1).abs() < 1e-9);
+
+        let visualization = service.inspect_tree().await.unwrap();
+        assert_eq!(visualization.statistics.total_nodes, 5);
+        let backlog = visualization.node_details.values().find(|node| node.full_premise == "Certification lab backlog").unwrap();
+        let hold = visualization.node_details.values().find(|node| node.full_premise == "Regulatory hold blocks release").unwrap();
+        assert_eq!(backlog.parent_id.as_deref(), Some(hold.friendly_id.as_str()));
+        assert_eq!(backlog.depth, 2);
+    }
 
-        let violation = &validation.violations[0];
-        assert_eq!(violation.violation_type, crate::domain::models::types::ViolationType::ProbabilityRange);
-        assert!(violation.message.contains("0.05 is below minimum threshold 0.15"));
-        assert_eq!(violation.severity, crate::domain::models::types::Severity::Warning);
+    #[tokio::test]
+    async fn test_create_tree_from_template() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
+
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+
+        let mut service = TreeEngineService::new(Arc::new(db));
+        let root_id = service.create_tree_from_template("go_no_go".to_string(), "the EU launch".to_string(), None).await.unwrap();
+
+        let visualization = service.inspect_tree().await.unwrap();
+        assert_eq!(visualization.statistics.total_nodes, 8);
+        let root_alias = service.get_friendly_id(&root_id).await.unwrap();
+        assert_eq!(visualization.node_details[&root_alias].full_premise, "Should we proceed with the EU launch?");
+        let go = visualization.node_details.values().find(|node| node.full_premise == "[Go] Proceed as planned").unwrap();
+        assert_eq!(go.probability, 0.45);
+        assert_eq!(go.confidence, 3);
+        assert_eq!(go.children_count, 2);
+
+        // The cursor is back at the root, with the template's probability budget left over
+        assert!(service.add_leaf("Partner-led launch".to_string(), "Distributor takes the risk".to_string(), 0.05, 5).await.is_ok());
+
+        let missing = service.create_tree_from_template("no_such_template".to_string(), "the EU launch".to_string(), None).await;
+        assert!(matches!(missing, Err(TreeEngineError::NotFound(_))));
+        let too_shallow = service.create_tree_from_template("five_whys".to_string(), "the outage".to_string(), Some(1)).await;
+        assert!(matches!(too_shallow, Err(TreeEngineError::InvalidInput(_, _))));
     }
 
     #[tokio::test]
-    async fn test_probability_status_all_valid() {
+    async fn test_register_templates() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for all valid test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
+        let builtin_count = service.list_templates().len();
+
+        let dir = std::env::temp_dir().join(format!("deep_analytics_templates_{}", uuid::Uuid::new_v4().simple()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("vendor.json"), r#"{
+            "name": "vendor_selection",
+            "title": "Vendor selection",
+            "root_premise": "Which vendor should supply {subject}?",
+            "complexity": 3,
+            "branches": [
+                { "premise": "<vendor A> delivers", "reasoning": "Incumbent", "probability": 0.5 },
+                { "premise": "<vendor B> delivers", "reasoning": "Challenger", "probability": 0.4 }
+            ]
+        }"#).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a template").unwrap();
+
+        let names = service.register_template_file(&dir).unwrap();
+        assert_eq!(names, vec!["vendor_selection"]);
+        assert_eq!(service.list_templates().len(), builtin_count + 1);
+
+        service.create_tree_from_template("vendor_selection".to_string(), "battery cells".to_string(), None).await.unwrap();
+        assert_eq!(service.inspect_tree().await.unwrap().statistics.total_nodes, 3);
+
+        let invalid = service.register_templates_json(r#"{ "name": "broken" }"#);
+        assert!(matches!(invalid, Err(TreeEngineError::InvalidInput(_, _))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
-        // Add nodes with all valid probabilities
-        let _leaf1 = service.add_leaf("Valid leaf 1".to_string(), "Valid reasoning one for all valid test".to_string(), 0.7, 7).await.unwrap();
-        let _leaf2 = service.add_leaf("Valid leaf 2".to_string(), "Valid reasoning two for all valid test".to_string(), 0.3, 5).await.unwrap();
+    #[tokio::test]
+    async fn test_validate_coherence_with_propositions() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
+                .await
+                .unwrap();
 
-        let result = service.probability_status().await;
-        assert!(result.is_ok());
+        db.use_ns("analytics").use_db("trees").await.unwrap();
 
-        let validation = result.unwrap();
-        assert!(validation.is_valid);
-        assert!(validation.violations.is_empty());
-        assert_eq!(validation.suggestions[0], "Tree appears valid");
+        let mut service = TreeEngineService::new(Arc::new(db));
+        service.create_tree("Where will rates go next year?".to_string(), 5).await.unwrap();
+        let hike = service.add_leaf("Rates rise".to_string(), "Inflation stays sticky".to_string(), 0.5, 7).await.unwrap();
+        let cut = service.add_leaf("Rates fall".to_string(), "Labour market cools".to_string(), 0.4, 6).await.unwrap();
+        service.set_proposition(hike.clone(), Some("rates_up".to_string())).await.unwrap();
+        service.set_proposition(cut.clone(), Some("!rates_up".to_string())).await.unwrap();
+
+        // Mutually exclusive siblings on consistent paths
+        let coherence = service.validate_coherence().await.unwrap();
+        assert!(coherence.is_coherent);
+        assert!(coherence.eliminated_nodes.is_empty());
+        assert_eq!(coherence.truth_table.len(), 2);
+        assert!(coherence.truth_table.iter().all(|row| row.is_consistent && row.affected_nodes.len() == 1));
+
+        // A child that contradicts its parent, and overlapping siblings under the other branch
+        service.expand_leaf(hike.clone(), "Hikes have consequences".to_string()).await.unwrap();
+        let easing = service.add_leaf("Easing cycle".to_string(), "Central bank pivots".to_string(), 0.3, 5).await.unwrap();
+        service.set_proposition(easing.clone(), Some("NOT rates_up AND easing".to_string())).await.unwrap();
+        service.expand_leaf(cut.clone(), "Cuts have causes".to_string()).await.unwrap();
+        let recession = service.add_leaf("Recession".to_string(), "Output contracts".to_string(), 0.2, 5).await.unwrap();
+        let slowdown = service.add_leaf("Slowdown".to_string(), "Growth stalls".to_string(), 0.15, 5).await.unwrap();
+        service.set_proposition(recession.clone(), Some("recession".to_string())).await.unwrap();
+        service.set_proposition(slowdown.clone(), Some("slowdown".to_string())).await.unwrap();
+
+        let coherence = service.validate_coherence().await.unwrap();
+        assert!(!coherence.is_coherent);
+        assert_eq!(coherence.eliminated_nodes, vec![easing.clone()]);
+
+        let unsatisfiable = coherence.contradictions.iter().find(|c| c.node_id == easing).unwrap();
+        assert_eq!(unsatisfiable.conflicting_premises, vec![
+            "Rates rise: rates_up".to_string(),
+            "Easing cycle: NOT rates_up AND easing".to_string(),
+        ]);
+        let overlapping = coherence.contradictions.iter().find(|c| c.node_id == cut).unwrap();
+        assert!(overlapping.explanation.contains("not mutually exclusive"));
+        assert!(overlapping.explanation.contains("rates_up=false"));
+
+        // 4 atoms: easing, rates_up, recession, slowdown
+        assert_eq!(coherence.truth_table.len(), 16);
+        let both = coherence.truth_table.iter()
+            .find(|row| !row.premises["rates_up"] && row.premises["recession"] && row.premises["slowdown"])
+            .unwrap();
+        assert!(both.is_consistent);
+        assert!(both.affected_nodes.contains(&recession) && both.affected_nodes.contains(&slowdown));
+        assert!(!both.affected_nodes.contains(&easing));
+
+        // Fixing the slowdown branch leaves only the path contradiction
+        service.set_proposition(slowdown.clone(), Some("slowdown & ~recession".to_string())).await.unwrap();
+        let coherence = service.validate_coherence().await.unwrap();
+        assert_eq!(coherence.contradictions.len(), 1);
+
+        assert!(matches!(
+            service.set_proposition(recession, Some("recession AND".to_string())).await,
+            Err(TreeEngineError::InvalidInput(_, _))
+        ));
+        assert_eq!(service.set_proposition(easing, None).await.unwrap(), None);
+        assert!(service.validate_coherence().await.unwrap().is_coherent);
+
+        // Too many atoms to check exhaustively: reported as skipped, not as satisfiable in silence
+        let oversized = (1..=proposition::MAX_ATOMS + 1).map(|i| format!("factor_{}", i)).collect::<Vec<_>>().join(" & ");
+        service.set_proposition(slowdown.clone(), Some(oversized)).await.unwrap();
+        let coherence = service.validate_coherence().await.unwrap();
+        assert!(coherence.is_coherent);
+        assert!(coherence.truth_table.is_empty());
+        let skipped = coherence.warnings.iter().find(|warning| warning.node_id == slowdown).unwrap();
+        assert_eq!(skipped.severity, Severity::Warning);
+        assert!(skipped.message.contains("Satisfiability check skipped"));
+        assert!(coherence.warnings.iter().any(|warning| warning.node_id == cut));
     }
 
-    // ULTRA-SPECIFIC EDGE CASES - The final frontier
-
     #[tokio::test]
-    async fn test_validate_coherence_invalid_probability_causes_incoherence() {
+    async fn test_resolve_outcome_and_calibration_report() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for invalid probability coherence test".to_string();
-        let _root_id = service.create_tree(premise, 5).await.unwrap();
-
-        // Create node with invalid probability directly in DB (lines 408-414)
-        let invalid_node = TreeNode {
-            id: None,
-            premise: "Node with invalid probability".to_string(),
-            reasoning: "This node tests the probability validation in validate_coherence".to_string(),
-            probability: 1.5, // INVALID - exceeds 1.0, should trigger lines 408-414
-            confidence: 5,
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
-
-        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
-
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
-
-        let coherence = result.unwrap();
-        // The invalid probability should make the tree incoherent (line 473)
-        assert!(!coherence.is_coherent);
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delays = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.6, 7).await.unwrap();
+        service.add_leaf("Team attrition".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+
+        assert!(matches!(
+            service.resolve_outcome(root_id, None).await,
+            Err(TreeEngineError::OperationNotAllowed(_))
+        ));
+
+        let resolution = service.resolve_outcome(delays, Some(" Slipped three weeks ".to_string())).await.unwrap();
+        assert_eq!(resolution.resolved_premise, "Supplier delays");
+        assert_eq!(resolution.notes, "Slipped three weeks");
+        assert_eq!(resolution.forecasts.len(), 2);
+        assert!((resolution.resolved_probability() - 0.6).abs() < 1e-9);
+        assert!((resolution.brier_score() - 0.26).abs() < 1e-9);
+
+        let report = service.calibration_report(Some("current".to_string())).await.unwrap();
+        assert_eq!(report.trees.len(), 1);
+
+        // Resolutions survive the next tree
+        service.create_tree("Will the migration finish this quarter?".to_string(), 5).await.unwrap();
+        service.add_leaf("On schedule".to_string(), "Plan is on track".to_string(), 0.8, 7).await.unwrap();
+        let late = service.add_leaf("Late".to_string(), "Vendor API changes".to_string(), 0.2, 5).await.unwrap();
+        service.resolve_outcome(late, None).await.unwrap();
+
+        let report = service.calibration_report(None).await.unwrap();
+        assert_eq!(report.trees.len(), 2);
+        assert!((report.brier_score - (0.26 + 1.28) / 2.0).abs() < 1e-9);
+        let eighties = &report.buckets[8];
+        assert_eq!(eighties.forecasts, 1);
+        assert_eq!(eighties.observed_frequency, 0.0);
+        assert!(report.to_string().contains("2 resolved trees"));
+
+        assert!(matches!(
+            service.calibration_report(Some("tree_unknown".to_string())).await,
+            Err(TreeEngineError::NotFound(_))
+        ));
     }
 
     #[tokio::test]
-    async fn test_validate_coherence_invalid_confidence_causes_incoherence() {
+    async fn test_time_horizons_and_timeline() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for invalid confidence coherence test".to_string();
-        let _root_id = service.create_tree(premise, 5).await.unwrap();
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delays = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.5, 7).await.unwrap();
+        let attrition = service.add_leaf("Team attrition".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+        service.add_leaf("Regulatory hold".to_string(), "New certification rules".to_string(), 0.1, 4).await.unwrap();
+
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let far_q3 = TimeWindow::new(date(2099, 7, 1), date(2099, 9, 30)).unwrap();
+        let far_q1 = TimeWindow::new(date(2099, 1, 1), date(2099, 3, 31)).unwrap();
+        service.set_time_horizon(delays.clone(), Some(far_q3), None).await.unwrap();
+        service.set_time_horizon(attrition.clone(), Some(far_q1), Some(date(2099, 1, 15))).await.unwrap();
+
+        let timeline = service.timeline().await.unwrap();
+        let order: Vec<&str> = timeline.scenarios.iter().map(|scenario| scenario.premises[1].as_str()).collect();
+        assert_eq!(order, vec!["Team attrition", "Supplier delays", "Regulatory hold"]);
+        assert_eq!(timeline.scenarios[0].window, Some(far_q1));
+        assert_eq!(timeline.scenarios[0].milestones.len(), 1);
+        assert!(timeline.scenarios[2].window.is_none());
+
+        // Nothing is overdue yet
+        let visualization = service.inspect_tree().await.unwrap();
+        assert!(!visualization.recommendations.iter().any(|r| r.contains("Stale estimate")));
+        let attrition_alias = service.get_friendly_id(&attrition).await.unwrap();
+        assert_eq!(visualization.node_details[&attrition_alias].time_window, Some(far_q1));
+
+        // A past review date or a closed window makes the estimate stale
+        service.set_time_horizon(attrition, None, Some(date(2020, 1, 15))).await.unwrap();
+        service.set_time_horizon(delays, Some(TimeWindow::new(date(2020, 1, 1), date(2020, 3, 31)).unwrap()), None).await.unwrap();
+        let visualization = service.inspect_tree().await.unwrap();
+        let stale: Vec<&String> = visualization.recommendations.iter().filter(|r| r.contains("Stale estimate")).collect();
+        assert_eq!(stale.len(), 2);
+        assert!(stale.iter().any(|r| r.contains("Team attrition: review was due 2020-01-15")));
+        assert!(stale.iter().any(|r| r.contains("Supplier delays: expected window ended 2020-03-31")));
+    }
 
-        // Create node with invalid confidence to trigger lines 417-422
-        let invalid_conf_node = TreeNode {
-            id: None,
-            premise: "Node with invalid confidence".to_string(),
-            reasoning: "This node tests confidence validation in validate_coherence".to_string(),
-            probability: 0.6,
-            confidence: 11, // INVALID - above range [1,10], should trigger lines 417-422
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
+    #[tokio::test]
+    async fn test_mutations_publish_changes_and_report_is_kept() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let mut changes = service.subscribe_changes();
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let tree_id = service.get_state().await.unwrap().tree_id;
+        assert_eq!(changes.try_recv().unwrap(), tree_id);
+
+        service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.6, 7).await.unwrap();
+        assert_eq!(changes.try_recv().unwrap(), tree_id);
+
+        // Reads publish nothing
+        service.inspect_tree().await.unwrap();
+        service.validate_coherence().await.unwrap();
+        assert!(changes.try_recv().is_err());
+
+        assert!(service.latest_report().await.unwrap().is_none());
+        let insights = vec!["One".to_string(), "Two".to_string(), "Three".to_string()];
+        service.export_paths(NarrativeStyle::Analytical, insights, 0.7).await.unwrap();
+        assert_eq!(changes.try_recv().unwrap(), tree_id);
+        let report = service.latest_report().await.unwrap().unwrap();
+        assert_eq!(report.confidence_assessment, 0.7);
+
+        let snapshot = service.snapshot().await.unwrap();
+        assert_eq!(snapshot.tree_id, tree_id);
+        assert_eq!(snapshot.nodes.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_subtree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("On time".to_string(), "Plan is on track".to_string(), 0.3, 6).await.unwrap();
+
+        let spec = |premise: &str, probability: f64, children: Vec<SubtreeNode>| SubtreeNode {
+            premise: premise.to_string(),
+            reasoning: format!("Because {}", premise),
+            probability,
+            confidence: 6,
+            children,
         };
 
-        let _created: Option<TreeNode> = service.db.create("node").content(invalid_conf_node).await.unwrap();
+        // 0.3 already allocated, so 0.8 more exceeds the root; nothing may be written
+        let over_budget = vec![spec("Delayed", 0.8, vec![spec("Supplier", 0.9, vec![])])];
+        let err = service.add_subtree(root_id.clone(), over_budget).await.unwrap_err().to_string();
+        assert!(err.contains("parent: children sum to 1.100"));
+        assert!(err.contains("1: children sum to 0.900"));
+        assert_eq!(service.db.select::<Vec<TreeNode>>("node").await.unwrap().len(), 2);
+
+        let insertion = service.add_subtree(root_id.clone(), vec![
+            spec("Delayed", 0.6, vec![spec("Supplier delays", 0.4, vec![]), spec("Regulatory hold", 0.2, vec![])]),
+        ]).await.unwrap();
+        let paths: Vec<&str> = insertion.nodes.iter().map(|node| node.path.as_str()).collect();
+        assert_eq!(paths, vec!["1", "1.1", "1.2"]);
+
+        let delayed_id: RecordId = insertion.nodes[0].node_id.parse().unwrap();
+        let delayed: TreeNode = service.db.select(&delayed_id).await.unwrap().unwrap();
+        assert_eq!(delayed.node_type, crate::domain::models::NodeType::Branch);
+        assert_eq!(delayed.children.len(), 2);
+        assert_eq!(delayed.depth, 1);
+        assert_eq!(service.resolve_node_ref(&insertion.nodes[2].friendly_id).await.unwrap().to_string(), insertion.nodes[2].node_id);
+
+        let root: TreeNode = service.db.select(&root_id.parse::<RecordId>().unwrap()).await.unwrap().unwrap();
+        assert_eq!(root.children.len(), 2);
+        // The cursor stays where add_leaf left it
+        assert_eq!(service.cursor(), Some(root_id.as_str()));
+    }
 
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_move_and_copy_subtree() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        let coherence = result.unwrap();
-        // Invalid confidence should make the tree incoherent (line 473)
-        assert!(!coherence.is_coherent);
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delayed = service.add_leaf("Delayed".to_string(), "Supply risk".to_string(), 0.4, 6).await.unwrap();
+        let on_time = service.add_leaf("On time".to_string(), "Buffer stock".to_string(), 0.3, 6).await.unwrap();
+        service.expand_leaf(delayed.clone(), "Break down delay causes".to_string()).await.unwrap();
+        let supplier = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.3, 6).await.unwrap();
+        service.expand_leaf(supplier.clone(), "Which supplier".to_string()).await.unwrap();
+        let port = service.add_leaf("Port strike".to_string(), "Union vote".to_string(), 0.2, 5).await.unwrap();
+
+        let load = |id: &str| id.parse::<RecordId>().unwrap();
+
+        // Cycles, the root and over-budget destinations are refused
+        let err = service.move_subtree(delayed.clone(), port.clone()).await.unwrap_err().to_string();
+        assert!(err.contains("cycle"));
+        assert!(service.move_subtree(root_id.clone(), on_time.clone()).await.is_err());
+        assert!(service.move_subtree(delayed.clone(), on_time.clone()).await.unwrap_err().to_string().contains("exceeds parent probability"));
+
+        // Moving the supplier branch up to the root shifts its whole subtree one level
+        let moved = service.move_subtree(supplier.clone(), root_id.clone()).await.unwrap();
+        assert_eq!(moved.nodes.len(), 2);
+        let delayed_node: TreeNode = service.db.select(&load(&delayed)).await.unwrap().unwrap();
+        assert!(delayed_node.children.is_empty());
+        assert!(delayed_node.is_leaf());
+        let root: TreeNode = service.db.select(&load(&root_id)).await.unwrap().unwrap();
+        assert!(root.children.contains(&load(&supplier)));
+        let port_node: TreeNode = service.db.select(&load(&port)).await.unwrap().unwrap();
+        assert_eq!(port_node.depth, 2);
+
+        // Root children now sum to 1.0, so the copy goes under the delayed branch instead
+        assert!(service.copy_subtree(supplier.clone(), root_id.clone()).await.is_err());
+        let copied = service.copy_subtree(supplier.clone(), delayed.clone()).await.unwrap();
+        assert_eq!(copied.nodes.len(), 2);
+        assert_ne!(copied.nodes[0].node_id, supplier);
+        let copy: TreeNode = service.db.select(&load(&copied.nodes[0].node_id)).await.unwrap().unwrap();
+        assert_eq!(copy.parent_id, Some(load(&delayed)));
+        assert_eq!(copy.depth, 2);
+        assert_eq!(copy.children, vec![load(&copied.nodes[1].node_id)]);
+        assert_eq!(copy.metadata.get("copied_from"), Some(&copied.nodes[0].source_id));
+        assert_eq!(service.db.select::<Vec<TreeNode>>("node").await.unwrap().len(), 7);
     }
 
     #[tokio::test]
-    async fn test_add_leaf_prevents_child_probabilities_exceed_sum() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
-                .await
-                .unwrap();
+    async fn test_locked_nodes_survive_prune_and_balance() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delayed = service.add_leaf("Delayed".to_string(), "Supply risk".to_string(), 0.5, 6).await.unwrap();
+        let on_time = service.add_leaf("On time".to_string(), "Buffer stock".to_string(), 0.2, 6).await.unwrap();
+        let cancelled = service.add_leaf("Cancelled".to_string(), "Budget cuts".to_string(), 0.1, 4).await.unwrap();
+        service.expand_leaf(delayed.clone(), "Break down delay causes".to_string()).await.unwrap();
+        let strike = service.add_leaf("Port strike".to_string(), "Union vote".to_string(), 0.1, 5).await.unwrap();
+
+        // Freezing the delayed branch pins the strike leaf below it
+        assert!(service.set_lock(delayed.clone(), true, true).await.unwrap());
+        let tree = service.inspect_tree().await.unwrap();
+        assert_eq!(tree.node_details.values().filter(|node| node.locked).count(), 2);
+
+        let balanced = service.balance_leafs(UncertaintyType::EqualLikelihood, false).await.unwrap();
+        assert_eq!(balanced.skipped_locked, vec![strike.clone()]);
+        assert_eq!(balanced.balanced_nodes.len(), 2);
+        assert!((balanced.new_probabilities.values().sum::<f64>() - 0.3).abs() < 1e-9);
+        let strike_node: TreeNode = service.db.select(&strike.parse::<RecordId>().unwrap()).await.unwrap().unwrap();
+        assert_eq!(strike_node.probability, 0.1);
+
+        // A locked leaf is kept even when it ranks below max_leafs
+        service.set_lock(cancelled.clone(), true, false).await.unwrap();
+        let pruned = service.prune_leafs(1, false).await.unwrap();
+        assert_eq!(pruned.nodes_removed, vec![on_time.clone()]);
+        assert_eq!(pruned.skipped_locked.len(), 2);
+
+        let pruned = service.prune_tree(0.5, false).await.unwrap();
+        assert_eq!(pruned.skipped_locked.len(), 2);
+        assert!(pruned.skipped_locked.contains(&strike) && pruned.skipped_locked.contains(&cancelled));
+        assert!(!pruned.nodes_removed.contains(&strike));
+
+        // Unlocking a node inside a frozen subtree leaves it pinned until the subtree is unfrozen
+        assert!(service.set_lock(strike.clone(), false, false).await.unwrap());
+        assert!(!service.set_lock(delayed.clone(), false, true).await.unwrap());
+        let pruned = service.prune_tree(0.5, false).await.unwrap();
+        assert_eq!(pruned.skipped_locked, vec![cancelled]);
+        assert!(pruned.nodes_removed.contains(&strike));
+    }
 
+    #[tokio::test]
+    async fn test_dry_run_previews_without_writing() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
         db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let likely = service.add_leaf("On time".to_string(), "Buffer stock".to_string(), 0.6, 7).await.unwrap();
+        let unlikely = service.add_leaf("Cancelled".to_string(), "Budget cuts".to_string(), 0.1, 4).await.unwrap();
+        let load = |id: &str| id.parse::<RecordId>().unwrap();
+
+        let preview = service.prune_tree(0.5, true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.nodes_removed, vec![unlikely.clone()]);
+        assert_eq!(preview.affected_paths.len(), 1);
+        assert_eq!(preview.affected_paths[0].premises.last().unwrap(), "Cancelled");
+        assert_eq!(preview.affected_paths[0].rank_before, Some(2));
+        assert_eq!(preview.affected_paths[0].rank_after, None);
+        let node: TreeNode = service.db.select(&load(&unlikely)).await.unwrap().unwrap();
+        assert!(!node.is_invalidated);
+
+        let preview = service.balance_leafs(UncertaintyType::CognitiveOverload, true).await.unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.affected_paths.len(), 2);
+        assert!(preview.new_probabilities[&likely] < 0.6);
+        let node: TreeNode = service.db.select(&load(&likely)).await.unwrap().unwrap();
+        assert_eq!(node.probability, 0.6);
+
+        // The real run reports the same paths and writes the changes
+        let pruned = service.prune_tree(0.5, false).await.unwrap();
+        assert!(!pruned.dry_run);
+        assert_eq!(pruned.affected_paths.len(), 1);
+        let node: TreeNode = service.db.select(&load(&unlikely)).await.unwrap().unwrap();
+        assert!(node.is_invalidated);
+    }
 
+    #[tokio::test]
+    async fn test_fault_tree_analysis() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for child probability sum test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Create parent node with probability 0.8
-        let parent_id = service.add_leaf(
-            "Parent for probability sum test".to_string(),
-            "Parent node that will test probability constraint".to_string(),
-            0.8,
-            8
-        ).await.unwrap();
+        service.create_tree("Cooling system fails".to_string(), 5).await.unwrap();
+        assert!(service.fault_tree_analysis().await.is_err());
+
+        let pump = service.add_leaf("Pump fails".to_string(), "Vendor failure rate".to_string(), 0.01, 7).await.unwrap();
+        // Power is lost only when both supplies fail: 0.1 × 0.2
+        let power = service.add_leaf("Power lost".to_string(), "Both supplies down".to_string(), 0.02, 6).await.unwrap();
+        service.set_gate(power.clone(), Some(Gate::And)).await.unwrap();
+        service.expand_leaf(power.clone(), "Redundant supplies".to_string()).await.unwrap();
+        // Gate inputs are independent events, so they may sum above the gate's own probability
+        service.add_leaf("Grid outage".to_string(), "Regional outage history".to_string(), 0.1, 6).await.unwrap();
+        service.add_leaf("Generator fails".to_string(), "Maintenance logs".to_string(), 0.2, 6).await.unwrap();
+        assert!(service.validate_coherence().await.unwrap().contradictions.is_empty());
+        // Linking another input under the gate is not held to the gate's probability either
+        service.add_cross_link(power.clone(), pump, 0.05, "A pump trip also drops the supply bus".to_string()).await.unwrap();
+
+        let analysis = service.fault_tree_analysis().await.unwrap();
+        assert!((analysis.top_event_probability - (1.0 - 0.99 * 0.98)).abs() < 1e-9);
+        assert_eq!(analysis.gates.len(), 2);
+        assert_eq!(analysis.minimal_cut_sets.len(), 2);
+        assert_eq!(analysis.minimal_cut_sets[0].events.len(), 2);
+        assert_eq!(analysis.basic_events.len(), 3);
+        let tree = service.inspect_tree().await.unwrap();
+        assert!(tree.node_details.values().any(|node| node.gate == Some(Gate::And)));
+
+        assert!(service.set_gate(power.clone(), Some(Gate::KOfN(0))).await.is_err());
+        service.set_gate(power.clone(), Some(Gate::KOfN(3))).await.unwrap();
+        assert!(service.fault_tree_analysis().await.unwrap_err().to_string().contains("3-of-n gate has only 2 inputs"));
+    }
 
-        // Navigate to parent to add children
-        service.navigate_to(parent_id).await.unwrap();
+    #[tokio::test]
+    async fn test_risk_matrix() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        // Add first child with probability 0.5 (within 0.8)
-        let _child1 = service.add_leaf(
-            "First child".to_string(),
-            "First child with moderate probability".to_string(),
-            0.5,
-            7
-        ).await.unwrap();
+        let root = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.6, 7).await.unwrap();
+        let strike = service.add_leaf("Port strike".to_string(), "Union vote scheduled".to_string(), 0.3, 6).await.unwrap();
+        service.add_leaf("On time".to_string(), "Buffer in the plan".to_string(), 0.1, 5).await.unwrap();
+
+        service.set_risk_profile(root.clone(), Some(4.0), Some("supply".to_string())).await.unwrap();
+        service.set_risk_profile(strike.clone(), Some(9.0), Some("labor".to_string())).await.unwrap();
+        assert!(service.set_risk_profile(strike.clone(), Some(11.0), None).await.is_err());
+        assert!(service.set_risk_profile(strike.clone(), None, Some("  ".to_string())).await.is_err());
+
+        let matrix = service.risk_matrix(ProbabilityBasis::Marginal, 5, 5).await.unwrap();
+        assert_eq!(matrix.scored(), 3);
+        assert!(matrix.unscored.is_empty());
+        // Supplier delays inherits impact 4 and "supply" from the root
+        assert_eq!(matrix.cells[3][2].occupants[0].premise, "Supplier delays");
+        assert_eq!(matrix.cells[3][2].occupants[0].category.as_deref(), Some("supply"));
+        assert_eq!(matrix.cells[1][4].occupants[0].premise, "Port strike");
+        assert_eq!(matrix.categories[0].category, "supply");
+        assert!((matrix.categories[0].total_risk - 2.8).abs() < 1e-9);
+        assert!((matrix.categories[1].total_risk - 2.7).abs() < 1e-9);
+
+        service.set_risk_profile(root, None, None).await.unwrap();
+        let matrix = service.risk_matrix(ProbabilityBasis::Path, 5, 5).await.unwrap();
+        assert_eq!(matrix.scored(), 1);
+        assert_eq!(matrix.unscored.len(), 2);
+        assert!(service.risk_matrix(ProbabilityBasis::Path, 5, 12).await.is_err());
+    }
 
-        // Try to add second child with probability 0.4 (total would be 0.9 > 0.8)
-        let result2 = service.add_leaf(
-            "Second child".to_string(),
-            "Second child that would exceed parent probability".to_string(),
-            0.4, // 0.5 + 0.4 = 0.9 > 0.8
-            6
-        ).await;
+    #[tokio::test]
+    async fn test_argument_mode() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        // This should fail due to probability constraint validation
-        assert!(result2.is_err());
-        if let Err(TreeEngineError::OperationNotAllowed(msg)) = result2 {
-            assert!(msg.contains("Probability constraint violation"));
-        } else {
-            panic!("Expected OperationNotAllowed error with probability constraint violation");
-        }
+        let root = service.create_tree("We should migrate to the new platform".to_string(), 5).await.unwrap();
+        service.add_leaf("Lower hosting cost".to_string(), "Vendor quote".to_string(), 0.9, 7).await.unwrap();
+        assert!(service.add_leaf("Team lacks experience".to_string(), "Skills survey".to_string(), 0.8, 6).await.is_err());
+        assert!(service.argument_map().await.is_err());
+
+        service.set_tree_mode(TreeMode::Argument).await.unwrap();
+        let risk = service.add_leaf("Team lacks experience".to_string(), "Skills survey".to_string(), 0.8, 6).await.unwrap();
+        let nodes: Vec<TreeNode> = service.db.select("node").await.unwrap();
+        let cost = nodes.iter().find(|node| node.premise == "Lower hosting cost").unwrap().display_id();
+        service.tag_argument(cost, Some(ArgumentTag::new(Stance::Pro, 1.0).unwrap())).await.unwrap();
+        service.tag_argument(risk.clone(), Some(ArgumentTag::new(Stance::Con, 0.5).unwrap())).await.unwrap();
+        assert!(service.tag_argument(root, Some(ArgumentTag::new(Stance::Pro, 1.0).unwrap())).await.is_err());
+        assert!(service.tag_argument(risk, Some(ArgumentTag { stance: Stance::Con, strength: 2.0 })).await.is_err());
+
+        let map = service.argument_map().await.unwrap();
+        // 0.5 + 0.5 × (0.9 - 0.4)
+        assert!((map.score - 0.75).abs() < 1e-9);
+        assert_eq!(map.verdict, Verdict::Holds);
+        assert_eq!(map.strongest_pro[0].premise, "Lower hosting cost");
+        assert_eq!(map.strongest_con[0].premise, "Team lacks experience");
+        assert!(service.validate_coherence().await.unwrap().contradictions.is_empty());
+    }
 
-        // validate_coherence should pass since no invalid data was added
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_argument_mode_cross_link() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        let coherence = result.unwrap();
-        assert!(coherence.is_coherent); // Should be coherent since invalid data was prevented
-        assert!(coherence.contradictions.is_empty()); // No contradictions since no invalid data was added
+        let root = service.create_tree("We should migrate to the new platform".to_string(), 5).await.unwrap();
+        service.set_tree_mode(TreeMode::Argument).await.unwrap();
+        service.add_leaf("Lower hosting cost".to_string(), "Vendor quote".to_string(), 0.9, 7).await.unwrap();
+        let risk = service.add_leaf("Team lacks experience".to_string(), "Skills survey".to_string(), 0.8, 6).await.unwrap();
+        service.expand_leaf(risk, "Experience can be bought".to_string()).await.unwrap();
+        let training = service.add_leaf("Training is funded".to_string(), "Budget line approved".to_string(), 0.6, 6).await.unwrap();
+
+        // Arguments are not exclusive, so the claim takes the extra argument although 0.9 + 0.8 + 0.5 exceeds it
+        let link = service.add_cross_link(root, training, 0.5, "Funded training also speeds the migration".to_string()).await.unwrap();
+        assert_eq!(link.probability, 0.5);
+        assert_eq!(service.list_cross_links().await.unwrap().len(), 1);
     }
 
     #[tokio::test]
-    async fn test_validate_coherence_mixed_violations_and_contradictions() {
-        let db = Surreal::new::<surrealdb::engine::local::Mem>(())
-                .await
-                .unwrap();
-    
+    async fn test_bayes_net_round_trip() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
         db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        assert!(service.export_bayes_net(BayesNetFormat::Bif).await.is_err());
+        let delays = service.add_leaf("Supplier delays".to_string(), "Lead times doubled".to_string(), 0.6, 7).await.unwrap();
+        service.add_leaf("On time".to_string(), "Buffer in the plan".to_string(), 0.3, 6).await.unwrap();
+        service.expand_leaf(delays, "Two causes".to_string()).await.unwrap();
+        service.add_leaf("Port strike".to_string(), "Union vote".to_string(), 0.3, 6).await.unwrap();
+        service.add_leaf("Factory fire".to_string(), "Insurance report".to_string(), 0.2, 5).await.unwrap();
+
+        let bif = service.export_bayes_net(BayesNetFormat::Bif).await.unwrap();
+        assert_eq!(bif.matches("variable ").count(), 2);
+        assert!(bif.contains("table 0.6, 0.3, 0.1;"));
+        let xml = service.export_bayes_net(BayesNetFormat::XmlBif).await.unwrap();
+        assert_eq!(BayesNet::parse(&xml).unwrap(), BayesNet::parse(&bif).unwrap());
+
+        let imported = service.import_bayes_net(xml, 5).await.unwrap();
+        assert_eq!(imported.nodes.len(), 4);
+        let nodes: Vec<TreeNode> = service.db.select("node").await.unwrap();
+        assert_eq!(nodes.len(), 5);
+        let root = nodes.iter().find(|node| node.parent_id.is_none()).unwrap();
+        assert_eq!(root.premise, "Will the product launch on time?");
+        let strike = nodes.iter().find(|node| node.premise == "Port strike").unwrap();
+        assert!((strike.probability - 0.3).abs() < 1e-6);
+
+        assert!(service.import_bayes_net("network n {}".to_string(), 5).await.is_err());
+        assert_eq!(service.db.select::<Vec<TreeNode>>("node").await.unwrap().len(), 5);
+    }
 
+    #[tokio::test]
+    async fn test_export_table() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for mixed violations test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
 
-        // Create scenario with valid probabilities for coherence testing
-        let parent_id = service.add_leaf(
-            "Parent for mixed violations".to_string(),
-            "Parent node for testing coherence validation".to_string(),
-            0.9,
-            9
-        ).await.unwrap();
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays".to_string(), "Lead times doubled, again".to_string(), 0.6, 7).await.unwrap();
+        service.add_leaf("On time".to_string(), "Buffer in the plan".to_string(), 0.3, 6).await.unwrap();
+        assert!(service.export_table(TabularTable::Paths, TabularFormat::Csv).await.is_err());
+
+        let csv = service.export_table(TabularTable::Nodes, TabularFormat::Csv).await.unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], NodeRecord::COLUMNS.join(","));
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("1,tree_"));
+        assert!(csv.contains("\"Lead times doubled, again\""));
+
+        let insights = vec!["Supply risk dominates".to_string(), "Buffer is thin".to_string(), "Watch lead times".to_string()];
+        service.export_paths(NarrativeStyle::Analytical, insights, 0.8).await.unwrap();
+        let jsonl = service.export_table(TabularTable::Paths, TabularFormat::JsonLines).await.unwrap();
+        let rows: Vec<serde_json::Value> = jsonl.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["rank"], 1);
+        assert_eq!(rows[0]["schema_version"], TABULAR_SCHEMA_VERSION);
+    }
 
-        // Navigate to parent to add children
-        service.navigate_to(parent_id).await.unwrap();
+    #[tokio::test]
+    async fn test_outline_round_trip() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        // Add children that sum within parent's probability
-        let _child1 = service.add_leaf(
-            "Child 1".to_string(),
-            "Child one for coherence test".to_string(),
-            0.5,
-            8
-        ).await.unwrap();
+        let outline = "# Will the product launch on time?\n\n- Delayed (p=0.6, c=7): Supplier lead times doubled\n  - Port strike (p=0.4, c=5): Union vote in May\n- On time (p=0.3, c=6): Plan is on track\n";
 
-        let _child2 = service.add_leaf(
-            "Child 2".to_string(),
-            "Child two for coherence test".to_string(),
-            0.3, // 0.5 + 0.3 = 0.8 < 0.9 (valid)
-            7
-        ).await.unwrap();
+        // Over-allocated children are reported by line and nothing is written
+        let bad = outline.replace("p=0.4", "p=0.9");
+        let err = service.import_outline(bad, 5).await.unwrap_err().to_string();
+        assert!(err.contains("line 3: children sum to 0.900, exceeding its probability 0.600"));
+        assert!(service.db.select::<Vec<TreeNode>>("node").await.unwrap().is_empty());
 
-        // Also add a node with invalid confidence (violation)
-        let invalid_node = TreeNode {
-            id: None,
-            premise: "Invalid confidence mixed test node".to_string(),
-            reasoning: "Node with invalid confidence for mixed violation test".to_string(),
-            probability: 0.5,
-            confidence: 0, // INVALID - below range [1,10]
-            parent_id: None,
-            children: Vec::new(),
-            node_type: crate::domain::models::tree_node::NodeType::Leaf,
-            is_invalidated: false,
-            depth: 1,
-            created_at: chrono::Utc::now(),
-            metadata: std::collections::HashMap::new(),
-        };
+        let err = service.import_outline("# Too short\n- A (p=0.5, c=5): a\n".to_string(), 5).await.unwrap_err().to_string();
+        assert!(err.contains("line 1: Premise must be at least 10 characters long"));
 
-        let _created: Option<TreeNode> = service.db.create("node").content(invalid_node).await.unwrap();
+        let insertion = service.import_outline(outline.to_string(), 5).await.unwrap();
+        assert_eq!(insertion.nodes.len(), 3);
+        assert_eq!(service.db.select::<Vec<TreeNode>>("node").await.unwrap().len(), 4);
+        assert_eq!(service.export_outline().await.unwrap(), outline);
+    }
 
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_exhaustive_residual() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        let coherence = result.unwrap();
-        assert!(!coherence.is_coherent);
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delayed_id = service.add_leaf("Delayed".to_string(), "Supplier lead times doubled".to_string(), 0.5, 7).await.unwrap();
+        let residual_id = service.set_exhaustive(root_id.clone(), true, true).await.unwrap().unwrap();
 
-        // Should have violations from the manually inserted invalid node
-        // (No probability sum contradictions since valid data was added through add_leaf)
+        async fn probability_of(service: &TreeEngineService, id: &str) -> f64 {
+            let record_id = service.resolve_node_ref(id).await.unwrap();
+            service.db.select::<Option<TreeNode>>(&record_id).await.unwrap().unwrap().probability
+        }
+        assert!((probability_of(&service, &residual_id).await - 0.5).abs() < 1e-9);
+
+        // The residual makes room for a new sibling and shrinks to the leftover
+        let on_time_id = service.add_leaf("On time".to_string(), "Plan is on track".to_string(), 0.3, 6).await.unwrap();
+        assert!((probability_of(&service, &residual_id).await - 0.2).abs() < 1e-9);
+        assert!(service.add_leaf("Early".to_string(), "Buffer unused".to_string(), 0.3, 4).await.is_err());
+        assert_eq!(service.set_exhaustive(root_id.clone(), true, true).await.unwrap(), Some(residual_id.clone()));
+
+        // Balancing leaves the residual to absorb whatever the others end up with
+        let residual_record = service.resolve_node_ref(&residual_id).await.unwrap().to_string();
+        let balanced = service.balance_leafs(UncertaintyType::EqualLikelihood, false).await.unwrap();
+        assert_eq!(balanced.skipped_residual, vec![residual_record.clone()]);
+        assert!(balanced.skipped_locked.is_empty());
+        let others = probability_of(&service, &delayed_id).await + probability_of(&service, &on_time_id).await;
+        assert!((probability_of(&service, &residual_id).await - (1.0 - others).max(0.0)).abs() < 1e-9);
+
+        // Pruning leaves it to its parent too, and does not report it as locked
+        let pruned = service.prune_tree(0.5, true).await.unwrap();
+        assert_eq!(pruned.skipped_residual, vec![residual_record.clone()]);
+        assert!(pruned.skipped_locked.is_empty());
+        let pruned = service.prune_leafs(1, true).await.unwrap();
+        assert_eq!(pruned.skipped_residual, vec![residual_record]);
+        assert_eq!(pruned.statistics.original_count, 2);
+
+        let exhaustive_gaps = |analysis: &CoherenceAnalysis| analysis.contradictions.iter()
+            .filter(|contradiction| contradiction.explanation.starts_with("Exhaustive children"))
+            .count();
+        assert_eq!(exhaustive_gaps(&service.validate_coherence().await.unwrap()), 0);
+
+        // An exhaustive node without a residual must be completed by hand
+        service.set_exhaustive(delayed_id.clone(), true, false).await.unwrap();
+        assert_eq!(exhaustive_gaps(&service.validate_coherence().await.unwrap()), 1);
+
+        // Clearing the mark keeps the residual as an ordinary child
+        assert_eq!(service.set_exhaustive(root_id, false, true).await.unwrap(), None);
+        let record_id = service.resolve_node_ref(&residual_id).await.unwrap();
+        let former: TreeNode = service.db.select(&record_id).await.unwrap().unwrap();
+        assert!(!former.residual);
+        assert_eq!(former.premise, RESIDUAL_PREMISE);
+    }
 
-        // The only contradiction should be from the manually inserted node with invalid confidence
-        // No probability sum violations since we used add_leaf which enforces constraints
+    #[tokio::test]
+    async fn test_cross_link_into_exhaustive_parent() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
+
+        let root_id = service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delayed_id = service.add_leaf("Delayed".to_string(), "Supplier lead times doubled".to_string(), 0.5, 7).await.unwrap();
+        service.expand_leaf(delayed_id, "Causes of delay".to_string()).await.unwrap();
+        let strike_id = service.add_leaf("Port strike".to_string(), "Union vote in May".to_string(), 0.3, 6).await.unwrap();
+        let residual_id = service.set_exhaustive(root_id.clone(), true, true).await.unwrap().unwrap();
+
+        async fn probability_of(service: &TreeEngineService, id: &str) -> f64 {
+            let record_id = service.resolve_node_ref(id).await.unwrap();
+            service.db.select::<Option<TreeNode>>(&record_id).await.unwrap().unwrap().probability
+        }
+        assert!((probability_of(&service, &residual_id).await - 0.5).abs() < 1e-9);
+
+        // The residual shrinks to make room for the link instead of blocking it
+        service.add_cross_link(root_id.clone(), strike_id.clone(), 0.2, "A strike can also halt final assembly".to_string()).await.unwrap();
+        assert!((probability_of(&service, &residual_id).await - 0.3).abs() < 1e-9);
+
+        // Linked and tree children still may not exceed the parent
+        service.remove_cross_link(root_id.clone(), strike_id.clone()).await.unwrap();
+        assert!(service.add_cross_link(root_id, strike_id, 0.6, "A strike can also halt final assembly".to_string()).await.is_err());
     }
 
     #[tokio::test]
-    async fn test_validate_coherence_perfectly_coherent_suggestions() {
+    async fn test_merge_tree_reports_graft_over_budget() {
         let db = Surreal::new::<surrealdb::engine::local::Mem>(())
                 .await
                 .unwrap();
-    
+
         db.use_ns("analytics").use_db("trees").await.unwrap();
 
         let mut service = TreeEngineService::new(Arc::new(db));
-        let premise = "Root for perfectly coherent test".to_string();
-        let root_id = service.create_tree(premise, 5).await.unwrap();
-
-        // Create a perfectly valid tree structure to trigger line 450
-        let parent_id = service.add_leaf(
-            "Perfect parent node".to_string(),
-            "Perfectly valid parent node for coherence test".to_string(),
-            0.8,
-            8
-        ).await.unwrap();
-
-        // Navigate to parent and add children with valid probabilities that sum < 1.0
-        service.navigate_to(parent_id).await.unwrap();
-        let _child1 = service.add_leaf(
-            "Valid child 1".to_string(),
-            "First perfectly valid child for coherence test".to_string(),
-            0.3, // Low probability
-            5
-        ).await.unwrap();
-
-        let _child2 = service.add_leaf(
-            "Valid child 2".to_string(),
-            "Second perfectly valid child for coherence test".to_string(),
-            0.2, // 0.3 + 0.2 = 0.5 < 1.0 ✓
-            4
-        ).await.unwrap();
 
-        let result = service.validate_coherence().await;
-        assert!(result.is_ok());
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays cause a slip".to_string(), "Lead times doubled".to_string(), 0.5, 6).await.unwrap();
+        service.add_leaf("Regulatory hold blocks release".to_string(), "New certification rules".to_string(), 0.5, 5).await.unwrap();
+        service.checkpoint("analyst_b".to_string()).await.unwrap();
+
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        service.add_leaf("Supplier delays cause slip".to_string(), "Single-source components".to_string(), 0.3, 6).await.unwrap();
+        service.add_leaf("Team attrition slows work".to_string(), "Two senior engineers left".to_string(), 0.3, 6).await.unwrap();
+        let before = service.inspect_tree().await.unwrap();
+
+        // The graft is kept and the root, now over budget, is left for manual resolution
+        let report = service.merge_tree("analyst_b".to_string(), None, false).await.unwrap();
+        assert_eq!(report.grafted.len(), 1);
+        assert_eq!(report.grafted[0].premise, "Regulatory hold blocks release");
+        assert_eq!(report.conflicts.len(), 1);
+        let conflict = &report.conflicts[0];
+        assert_eq!(conflict.parent_premise, "Will the product launch on time?");
+        assert_eq!(conflict.children.len(), 3);
+        // Pooled 0.4 for the supplier, 0.3 for attrition and 0.5 grafted
+        assert!((conflict.children_total - 1.2).abs() < 1e-9);
+
+        let after = service.inspect_tree().await.unwrap();
+        assert_eq!(after.statistics.total_nodes, before.statistics.total_nodes + 1);
+    }
 
-        let coherence = result.unwrap();
-        assert!(coherence.is_coherent);
-        assert!(coherence.contradictions.is_empty());
+    #[tokio::test]
+    async fn test_balance_leafs_smooths_each_sibling_group() {
+        let db = Surreal::new::<surrealdb::engine::local::Mem>(()).await.unwrap();
+        db.use_ns("analytics").use_db("trees").await.unwrap();
+        let mut service = TreeEngineService::new(Arc::new(db));
 
-        // Should trigger the "perfectly coherent" suggestion path (line 450)
-        // This is difficult to assert directly, but the coherence should be true
+        service.create_tree("Will the product launch on time?".to_string(), 5).await.unwrap();
+        let delayed = service.add_leaf("Delayed".to_string(), "Supply risk".to_string(), 0.6, 6).await.unwrap();
+        let on_time = service.add_leaf("On time".to_string(), "Buffer stock".to_string(), 0.4, 6).await.unwrap();
+        service.expand_leaf(delayed.clone(), "Break down delay causes".to_string()).await.unwrap();
+        let strike = service.add_leaf("Port strike".to_string(), "Union vote".to_string(), 0.5, 5).await.unwrap();
+        let shortage = service.add_leaf("Chip shortage".to_string(), "Single supplier".to_string(), 0.1, 4).await.unwrap();
+        service.expand_leaf(on_time.clone(), "Break down how it ships on time".to_string()).await.unwrap();
+        let buffer = service.add_leaf("Buffer holds".to_string(), "Three months of stock".to_string(), 0.3, 6).await.unwrap();
+        let overtime = service.add_leaf("Overtime".to_string(), "Crunch budget approved".to_string(), 0.1, 3).await.unwrap();
+
+        let balanced = service.balance_leafs(UncertaintyType::CognitiveOverload, false).await.unwrap();
+        assert_eq!(balanced.balanced_nodes.len(), 4);
+        assert!(balanced.smoothing_explanation.contains("in 2 sibling groups"));
+
+        // Each group keeps its own mass, so no probability moves between the two branches
+        let p = |id: &String| balanced.new_probabilities[id];
+        assert!((p(&strike) + p(&shortage) - 0.6).abs() < 1e-9);
+        assert!((p(&buffer) + p(&overtime) - 0.4).abs() < 1e-9);
+        assert!(p(&strike) < 0.5 && p(&shortage) > 0.1);
+        assert!(p(&buffer) < 0.3 && p(&overtime) > 0.1);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,2 @@
+pub mod proposition;
+pub mod text_similarity;
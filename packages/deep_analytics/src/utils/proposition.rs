@@ -0,0 +1,286 @@
+//! Propositional formulas attached to premises for logical coherence checks.
+//!
+//! A formula is built from atoms (`recession`, `rates_up`) combined with
+//! `AND`/`&`, `OR`/`|`, `NOT`/`!` and parentheses. `NOT` binds tighter than
+//! `AND`, which binds tighter than `OR`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Largest number of distinct atoms checked by exhaustive enumeration (2^16 assignments).
+pub const MAX_ATOMS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Proposition {
+    Atom(String),
+    Not(Box<Proposition>),
+    And(Vec<Proposition>),
+    Or(Vec<Proposition>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Atom(String),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            '!' | '~' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Atom(word),
+                });
+            }
+            other => return Err(format!("Unexpected character '{}' in proposition", other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Proposition, String> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            operands.push(self.parse_and()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Proposition::Or(operands) })
+    }
+
+    fn parse_and(&mut self) -> Result<Proposition, String> {
+        let mut operands = vec![self.parse_not()?];
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            operands.push(self.parse_not()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Proposition::And(operands) })
+    }
+
+    fn parse_not(&mut self) -> Result<Proposition, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Proposition::Not(Box::new(self.parse_not()?))),
+            Some(Token::Atom(name)) => Ok(Proposition::Atom(name)),
+            Some(Token::Open) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("Missing closing parenthesis in proposition".to_string()),
+                }
+            }
+            Some(token) => Err(format!("Unexpected {:?} in proposition", token)),
+            None => Err("Proposition ended unexpectedly".to_string()),
+        }
+    }
+}
+
+impl Proposition {
+    /// Parses a formula such as `recession AND NOT (rates_up | stimulus)`.
+    pub fn parse(input: &str) -> Result<Proposition, String> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("Proposition cannot be empty".to_string());
+        }
+
+        let mut parser = Parser { tokens, position: 0 };
+        let proposition = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected {:?} after end of proposition", token));
+        }
+        Ok(proposition)
+    }
+
+    /// Distinct atom names, in sorted order.
+    pub fn atoms(&self) -> BTreeSet<String> {
+        let mut atoms = BTreeSet::new();
+        self.collect_atoms(&mut atoms);
+        atoms
+    }
+
+    fn collect_atoms(&self, atoms: &mut BTreeSet<String>) {
+        match self {
+            Proposition::Atom(name) => {
+                atoms.insert(name.clone());
+            }
+            Proposition::Not(inner) => inner.collect_atoms(atoms),
+            Proposition::And(operands) | Proposition::Or(operands) => {
+                for operand in operands {
+                    operand.collect_atoms(atoms);
+                }
+            }
+        }
+    }
+
+    /// Truth value under an assignment; atoms missing from it count as false.
+    pub fn eval(&self, assignment: &HashMap<String, bool>) -> bool {
+        match self {
+            Proposition::Atom(name) => assignment.get(name).copied().unwrap_or(false),
+            Proposition::Not(inner) => !inner.eval(assignment),
+            Proposition::And(operands) => operands.iter().all(|operand| operand.eval(assignment)),
+            Proposition::Or(operands) => operands.iter().any(|operand| operand.eval(assignment)),
+        }
+    }
+}
+
+/// Every truth assignment over `atoms`, in binary counting order.
+pub fn assignments(atoms: &[String]) -> impl Iterator<Item = HashMap<String, bool>> + '_ {
+    (0..1u64 << atoms.len()).map(move |bits| {
+        atoms.iter().enumerate()
+            .map(|(i, atom)| (atom.clone(), bits & (1 << (atoms.len() - 1 - i)) != 0))
+            .collect()
+    })
+}
+
+/// Finds an assignment making every proposition true, or `None` if they are jointly unsatisfiable.
+///
+/// Returns an error when the formulas mention more than [`MAX_ATOMS`] atoms.
+pub fn satisfying_assignment(propositions: &[&Proposition]) -> Result<Option<HashMap<String, bool>>, String> {
+    let atoms: Vec<String> = propositions.iter()
+        .flat_map(|proposition| proposition.atoms())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if atoms.len() > MAX_ATOMS {
+        return Err(format!("{} atoms exceed the limit of {} for exhaustive checking", atoms.len(), MAX_ATOMS));
+    }
+
+    Ok(assignments(&atoms).find(|assignment| propositions.iter().all(|proposition| proposition.eval(assignment))))
+}
+
+impl Display for Proposition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        fn operand(f: &mut Formatter<'_>, proposition: &Proposition, wrap: bool) -> FmtResult {
+            if wrap {
+                write!(f, "({})", proposition)
+            } else {
+                write!(f, "{}", proposition)
+            }
+        }
+
+        match self {
+            Proposition::Atom(name) => write!(f, "{}", name),
+            Proposition::Not(inner) => {
+                write!(f, "NOT ")?;
+                operand(f, inner, matches!(**inner, Proposition::And(_) | Proposition::Or(_)))
+            }
+            Proposition::And(operands) | Proposition::Or(operands) => {
+                let is_and = matches!(self, Proposition::And(_));
+                for (i, item) in operands.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", if is_and { " AND " } else { " OR " })?;
+                    }
+                    let wrap = match item {
+                        Proposition::Or(_) => true,
+                        Proposition::And(_) => is_and,
+                        _ => false,
+                    };
+                    operand(f, item, wrap)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_precedence() {
+        let proposition = Proposition::parse("a | b & !c").unwrap();
+        assert_eq!(proposition, Proposition::Or(vec![
+            Proposition::Atom("a".to_string()),
+            Proposition::And(vec![
+                Proposition::Atom("b".to_string()),
+                Proposition::Not(Box::new(Proposition::Atom("c".to_string()))),
+            ]),
+        ]));
+        assert_eq!(proposition.to_string(), "a OR b AND NOT c");
+
+        let grouped = Proposition::parse("recession and not (rates_up OR stimulus)").unwrap();
+        assert_eq!(grouped.to_string(), "recession AND NOT (rates_up OR stimulus)");
+        assert_eq!(Proposition::parse(&grouped.to_string()).unwrap(), grouped);
+        assert_eq!(grouped.atoms().into_iter().collect::<Vec<_>>(), vec!["rates_up", "recession", "stimulus"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Proposition::parse("").is_err());
+        assert!(Proposition::parse("a AND").is_err());
+        assert!(Proposition::parse("(a OR b").is_err());
+        assert!(Proposition::parse("a b").is_err());
+        assert!(Proposition::parse("a + b").is_err());
+    }
+
+    #[test]
+    fn test_satisfiability() {
+        let a = Proposition::parse("rates_up").unwrap();
+        let not_a = Proposition::parse("NOT rates_up AND growth").unwrap();
+        let either = Proposition::parse("rates_up OR growth").unwrap();
+
+        assert_eq!(satisfying_assignment(&[&a, &not_a]).unwrap(), None);
+
+        let witness = satisfying_assignment(&[&not_a, &either]).unwrap().unwrap();
+        assert!(!witness["rates_up"]);
+        assert!(witness["growth"]);
+
+        assert_eq!(assignments(&["x".to_string(), "y".to_string()]).count(), 4);
+    }
+}
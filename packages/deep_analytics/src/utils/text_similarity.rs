@@ -0,0 +1,105 @@
+//! Lightweight text similarity helpers used for fuzzy search and premise alignment.
+
+/// Splits text into lowercase alphanumeric tokens.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+/// Levenshtein edit distance between two strings, counted in characters.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() {
+        return b.len();
+    }
+    if b.is_empty() {
+        return a.len();
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Normalized similarity in [0, 1] derived from the Levenshtein distance.
+pub fn word_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+/// Fuzzy score in [0, 1] of how well `query` matches `text`.
+///
+/// Each query token is matched against its closest token in the text, so typos
+/// ("suplier") and inflections ("delays" vs "delay") still score highly. The
+/// result is the mean of the per-token best matches.
+pub fn fuzzy_score(query: &str, text: &str) -> f64 {
+    let query_tokens = tokenize(query);
+    let text_tokens = tokenize(text);
+    if query_tokens.is_empty() || text_tokens.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = query_tokens.iter()
+        .map(|q| text_tokens.iter().map(|t| word_similarity(q, t)).fold(0.0, f64::max))
+        .sum();
+    total / query_tokens.len() as f64
+}
+
+/// Symmetric similarity in [0, 1] between two premises.
+///
+/// Averages the fuzzy score in both directions so that a short premise fully
+/// contained in a longer one is not considered identical to it.
+pub fn premise_similarity(a: &str, b: &str) -> f64 {
+    (fuzzy_score(a, b) + fuzzy_score(b, a)) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("Supplier-delays, Q3!"), vec!["supplier", "delays", "q3"]);
+        assert!(tokenize("  ,, ").is_empty());
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_score_tolerates_typos() {
+        let text = "Supplier delays push the launch into next quarter";
+        assert!(fuzzy_score("suplier delay", text) > 0.8);
+        assert!(fuzzy_score("currency hedging", text) < 0.5);
+        assert_eq!(fuzzy_score("", text), 0.0);
+    }
+
+    #[test]
+    fn test_premise_similarity_is_symmetric() {
+        let a = "Market demand grows in Europe";
+        let b = "European market demand grows";
+        assert!((premise_similarity(a, b) - premise_similarity(b, a)).abs() < 1e-9);
+        assert!(premise_similarity(a, b) > premise_similarity(a, "Regulators block the merger"));
+    }
+}
@@ -14,6 +14,11 @@ export function createMCPClient() {
 }
 
 export function extractIdFromResponse(response, idType = 'ID') {
+    // create_tree, add_leaf and create_tree_from_template return a NodeRef as structured content
+    if (response.structuredContent && response.structuredContent.node_id) {
+        return response.structuredContent.node_id;
+    }
+
     const patterns = {
         'ID': /ID: ([a-f0-9-]+)/,
         'root ID': /root ID: ([a-f0-9-]+)/
@@ -1,4 +1,7 @@
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 const ADJECTIVES: &[&str] = &[
     "clever", "brilliant", "wise", "analytical", "strategic", "creative", "focused", "sharp",
@@ -16,31 +19,288 @@ const SCIENTISTS: &[&str] = &[
     "locke", "hume", "spinoza", "leibniz", "hobbes", "rousseau", "voltaire", "diderot",
 ];
 
+const EXTRA_ADJECTIVES: &[&str] = &[
+    "astute", "attentive", "candid", "cautious", "curious", "decisive", "diligent", "discerning",
+    "eager", "earnest", "exact", "fair", "frank", "gentle", "grounded", "honest", "humble",
+    "keen", "lucid", "mindful", "modest", "nimble", "patient", "perceptive", "prudent",
+    "quiet", "rigorous", "sagacious", "sensible", "serene", "shrewd", "sober", "steady",
+    "subtle", "tenacious", "vigilant",
+];
+
+const EXTRA_SCIENTISTS: &[&str] = &[
+    "bayes", "bernoulli", "boole", "cantor", "cauchy", "copernicus", "dirac", "fermat",
+    "fermi", "fourier", "hilbert", "hopper", "huygens", "laplace", "lagrange", "lovelace",
+    "markov", "meitner", "noether", "pascal", "planck", "poincare", "riemann", "shannon",
+    "venn", "vonneumann", "wiener", "hypatia", "babbage", "kolmogorov", "chebyshev", "gibbs",
+    "lorentz", "mach", "pauli", "rutherford",
+];
+
+/// Errors raised when building a custom [`WordList`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordListError {
+    /// One of the two lists is empty, so no identifier could ever be produced.
+    Empty(&'static str),
+    /// A word contains characters other than lowercase ASCII letters.
+    InvalidWord(String),
+}
+
+impl Display for WordListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            WordListError::Empty(list) => write!(f, "Word list '{}' cannot be empty", list),
+            WordListError::InvalidWord(word) => {
+                write!(f, "Word '{}' must contain only lowercase ASCII letters", word)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordListError {}
+
+/// A pair of word lists used to compose `adjective_noun` identifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    adjectives: Vec<String>,
+    nouns: Vec<String>,
+}
+
+impl WordList {
+    /// Builds a word list from custom words.
+    ///
+    /// Words must be lowercase ASCII letters so that identifiers stay unambiguous
+    /// when split on `_`. Duplicates are dropped while preserving order.
+    pub fn new<A, N>(adjectives: A, nouns: N) -> Result<Self, WordListError>
+    where
+        A: IntoIterator,
+        A::Item: Into<String>,
+        N: IntoIterator,
+        N::Item: Into<String>,
+    {
+        let adjectives = Self::normalize(adjectives, "adjectives")?;
+        let nouns = Self::normalize(nouns, "nouns")?;
+        Ok(Self { adjectives, nouns })
+    }
+
+    /// The original list of 36 adjectives and 38 scientists.
+    pub fn scientists() -> Self {
+        Self {
+            adjectives: ADJECTIVES.iter().map(|w| w.to_string()).collect(),
+            nouns: SCIENTISTS.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// The original lists extended with additional adjectives and scientists,
+    /// roughly quadrupling the number of combinations.
+    pub fn extended() -> Self {
+        Self {
+            adjectives: ADJECTIVES.iter().chain(EXTRA_ADJECTIVES).map(|w| w.to_string()).collect(),
+            nouns: SCIENTISTS.iter().chain(EXTRA_SCIENTISTS).map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// Number of distinct `adjective_noun` combinations before suffixes are needed.
+    pub fn capacity(&self) -> usize {
+        self.adjectives.len() * self.nouns.len()
+    }
+
+    pub fn adjectives(&self) -> &[String] {
+        &self.adjectives
+    }
+
+    pub fn nouns(&self) -> &[String] {
+        &self.nouns
+    }
+
+    fn combination(&self, index: usize) -> String {
+        let adjective = &self.adjectives[index / self.nouns.len()];
+        let noun = &self.nouns[index % self.nouns.len()];
+        format!("{}_{}", adjective, noun)
+    }
+
+    fn normalize<I>(words: I, list: &'static str) -> Result<Vec<String>, WordListError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut seen = HashSet::new();
+        let mut normalized = Vec::new();
+        for word in words {
+            let word: String = word.into();
+            if word.is_empty() || !word.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(WordListError::InvalidWord(word));
+            }
+            if seen.insert(word.clone()) {
+                normalized.push(word);
+            }
+        }
+        if normalized.is_empty() {
+            return Err(WordListError::Empty(list));
+        }
+        Ok(normalized)
+    }
+}
+
+impl Default for WordList {
+    fn default() -> Self {
+        Self::scientists()
+    }
+}
+
+/// Allocates human-friendly identifiers that are unique within a namespace.
+///
+/// Each namespace keeps a registry of the identifiers handed out (or registered
+/// from existing data), so [`FriendlyIdAllocator::allocate`] never returns a
+/// duplicate. Once every `adjective_noun` combination is taken, identifiers gain
+/// a numeric suffix (`clever_turing_2`, `clever_turing_3`, ...).
+///
+/// # Example
+/// ```rust
+/// use friendly_ids::{FriendlyIdAllocator, WordList};
+///
+/// let mut allocator = FriendlyIdAllocator::with_seed(42).with_word_list(WordList::extended());
+/// let first = allocator.allocate("tree_1");
+/// let second = allocator.allocate("tree_1");
+///
+/// assert_ne!(first, second);
+/// assert_eq!(allocator.resolve_node_id("tree_1", &first), Some(first.clone()));
+/// assert_eq!(allocator.resolve_node_id("tree_2", &first), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FriendlyIdAllocator {
+    words: WordList,
+    rng: StdRng,
+    registry: HashMap<String, HashSet<String>>,
+}
+
+impl FriendlyIdAllocator {
+    /// Random draws attempted before falling back to a linear scan of free combinations.
+    const RANDOM_ATTEMPTS: usize = 32;
+
+    /// Creates an allocator with the default word list and an entropy-seeded generator.
+    pub fn new() -> Self {
+        Self {
+            words: WordList::default(),
+            rng: StdRng::from_rng(&mut rand::rng()),
+            registry: HashMap::new(),
+        }
+    }
+
+    /// Creates an allocator whose sequence of identifiers is fully determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            words: WordList::default(),
+            rng: StdRng::seed_from_u64(seed),
+            registry: HashMap::new(),
+        }
+    }
+
+    /// Replaces the word list used for new identifiers.
+    pub fn with_word_list(mut self, words: WordList) -> Self {
+        self.words = words;
+        self
+    }
+
+    pub fn word_list(&self) -> &WordList {
+        &self.words
+    }
+
+    /// Allocates an identifier that has never been handed out in `namespace`.
+    pub fn allocate(&mut self, namespace: &str) -> String {
+        let capacity = self.words.capacity();
+        let taken = self.registry.entry(namespace.to_string()).or_default();
+
+        let mut suffix = 1;
+        loop {
+            let with_suffix = |base: String| {
+                if suffix == 1 { base } else { format!("{}_{}", base, suffix) }
+            };
+
+            for _ in 0..Self::RANDOM_ATTEMPTS {
+                let candidate = with_suffix(self.words.combination(self.rng.random_range(0..capacity)));
+                if taken.insert(candidate.clone()) {
+                    return candidate;
+                }
+            }
+
+            // Random draws keep colliding as the space fills up, so scan for a free slot
+            let start = self.rng.random_range(0..capacity);
+            for offset in 0..capacity {
+                let candidate = with_suffix(self.words.combination((start + offset) % capacity));
+                if taken.insert(candidate.clone()) {
+                    return candidate;
+                }
+            }
+
+            suffix += 1;
+        }
+    }
+
+    /// Records an identifier that already exists in `namespace` so it is never reallocated.
+    ///
+    /// Returns `false` if the identifier was already registered.
+    pub fn register(&mut self, namespace: &str, id: &str) -> bool {
+        self.registry.entry(namespace.to_string()).or_default().insert(id.to_string())
+    }
+
+    /// Releases an identifier so it may be allocated again.
+    ///
+    /// Returns `false` if the identifier was not registered.
+    pub fn release(&mut self, namespace: &str, id: &str) -> bool {
+        self.registry.get_mut(namespace).is_some_and(|ids| ids.remove(id))
+    }
+
+    /// Forgets every identifier registered in `namespace`.
+    pub fn clear_namespace(&mut self, namespace: &str) {
+        self.registry.remove(namespace);
+    }
+
+    /// Number of identifiers registered in `namespace`.
+    pub fn len(&self, namespace: &str) -> usize {
+        self.registry.get(namespace).map_or(0, HashSet::len)
+    }
+
+    pub fn is_empty(&self, namespace: &str) -> bool {
+        self.len(namespace) == 0
+    }
+
+    /// Validates `id` against the registry of `namespace`.
+    ///
+    /// Surrounding whitespace and letter case are ignored. Returns the canonical
+    /// identifier when it has been allocated or registered in the namespace.
+    pub fn resolve_node_id(&self, namespace: &str, id: &str) -> Option<String> {
+        let candidate = id.trim().to_lowercase();
+        self.registry
+            .get(namespace)
+            .filter(|ids| ids.contains(&candidate))
+            .map(|_| candidate)
+    }
+}
+
+impl Default for FriendlyIdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generates a random `adjective_scientist` identifier with no uniqueness guarantee.
+#[deprecated(note = "use FriendlyIdAllocator::allocate, which never repeats an identifier within a namespace")]
 pub fn generate_friendly_id() -> String {
-    let mut rng = rand::rng();
-    let adjective = ADJECTIVES[rng.random_range(0..ADJECTIVES.len())];
-    let scientist = SCIENTISTS[rng.random_range(0..SCIENTISTS.len())];
-    
-    format!("{}_{}", adjective.to_lowercase(), scientist)
+    FriendlyIdAllocator::new().allocate("")
 }
 
-// Since we're using friendly IDs as the primary IDs now, resolve validates format and returns the ID
+/// Returns `id` if it is an `adjective_scientist` pair from the default word list.
+///
+/// Only the format is checked, not whether the identifier was ever handed out.
+#[deprecated(note = "use FriendlyIdAllocator::resolve_node_id, which checks the namespace's registry")]
 pub fn resolve_node_id(id: &str) -> Option<String> {
-    // Validate that the ID has the expected friendly ID format (adjective_scientist)
-    // Must be exactly two parts separated by one underscore
-    let parts: Vec<&str> = id.split('_').collect();
-    if parts.len() == 2 
-        && parts[0].chars().all(|c| c.is_lowercase() && c.is_alphabetic())
-        && parts[1].chars().all(|c| c.is_lowercase() && c.is_alphabetic())
-        && ADJECTIVES.contains(&parts[0])
-        && SCIENTISTS.contains(&parts[1]) {
-        Some(id.to_string())
-    } else {
-        None // Invalid format
-    }
+    let words = WordList::scientists();
+    let (adjective, scientist) = id.split_once('_')?;
+    let known = |list: &[String], word: &str| list.iter().any(|candidate| candidate == word);
+    (known(words.adjectives(), adjective) && known(words.nouns(), scientist)).then(|| id.to_string())
 }
 
-// Get friendly ID - since IDs are already friendly, just return the same ID
+/// Returns `id` unchanged, since identifiers are already friendly.
+#[deprecated(note = "friendly ids are stored on the nodes themselves; use the id directly")]
 pub fn get_friendly_id(id: &str) -> Option<String> {
     Some(id.to_string())
 }
@@ -50,28 +310,121 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_generate_friendly_id() {
-        let id = generate_friendly_id();
-        
+    fn test_allocate_friendly_id() {
+        let mut allocator = FriendlyIdAllocator::new();
+        let id = allocator.allocate("tree");
+
         // Should contain an underscore
         assert!(id.contains('_'));
-        
+
         // Should be lowercase
         assert_eq!(id.to_lowercase(), id);
-        
-        // Should contain one of the adjectives
-        assert!(ADJECTIVES.iter().any(|&adj| id.contains(&adj.to_lowercase())));
-        
-        // Should contain one of the scientists
-        assert!(SCIENTISTS.iter().any(|&sci| id.contains(&sci.to_lowercase())));
-    }
-    
+
+        let (adjective, scientist) = id.split_once('_').unwrap();
+        assert!(ADJECTIVES.contains(&adjective));
+        assert!(SCIENTISTS.contains(&scientist));
+    }
+
     #[test]
     fn test_friendly_ids_are_unique() {
-        let id1 = generate_friendly_id();
-        let id2 = generate_friendly_id();
-        
-        // Two consecutive IDs should be different
-        assert_ne!(id1, id2);
+        let mut allocator = FriendlyIdAllocator::with_seed(7);
+        let capacity = allocator.word_list().capacity();
+
+        let ids: HashSet<String> = (0..capacity).map(|_| allocator.allocate("tree")).collect();
+
+        assert_eq!(ids.len(), capacity);
+        assert_eq!(allocator.len("tree"), capacity);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_suffix_when_word_space_is_exhausted() {
+        let words = WordList::new(["clever", "wise"], ["turing", "curie"]).unwrap();
+        let mut allocator = FriendlyIdAllocator::with_seed(1).with_word_list(words);
+
+        let ids: Vec<String> = (0..9).map(|_| allocator.allocate("tree")).collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+
+        assert_eq!(unique.len(), 9);
+        assert_eq!(ids.iter().filter(|id| id.ends_with("_2")).count(), 4);
+        assert_eq!(ids.iter().filter(|id| id.ends_with("_3")).count(), 1);
+    }
+
+    #[test]
+    fn test_seed_is_deterministic() {
+        let mut first = FriendlyIdAllocator::with_seed(99);
+        let mut second = FriendlyIdAllocator::with_seed(99);
+
+        let a: Vec<String> = (0..20).map(|_| first.allocate("tree")).collect();
+        let b: Vec<String> = (0..20).map(|_| second.allocate("tree")).collect();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_namespaces_are_independent() {
+        let words = WordList::new(["clever"], ["turing"]).unwrap();
+        let mut allocator = FriendlyIdAllocator::with_seed(3).with_word_list(words);
+
+        assert_eq!(allocator.allocate("tree_a"), "clever_turing");
+        assert_eq!(allocator.allocate("tree_b"), "clever_turing");
+        assert_eq!(allocator.allocate("tree_a"), "clever_turing_2");
+    }
+
+    #[test]
+    fn test_register_and_release() {
+        let words = WordList::new(["clever"], ["turing"]).unwrap();
+        let mut allocator = FriendlyIdAllocator::with_seed(5).with_word_list(words);
+
+        assert!(allocator.register("tree", "clever_turing"));
+        assert!(!allocator.register("tree", "clever_turing"));
+        assert_eq!(allocator.allocate("tree"), "clever_turing_2");
+
+        assert!(allocator.release("tree", "clever_turing"));
+        assert!(!allocator.release("tree", "clever_turing"));
+        assert_eq!(allocator.allocate("tree"), "clever_turing");
+
+        allocator.clear_namespace("tree");
+        assert!(allocator.is_empty("tree"));
+    }
+
+    #[test]
+    fn test_resolve_node_id_uses_registry() {
+        let mut allocator = FriendlyIdAllocator::with_seed(11);
+        let id = allocator.allocate("tree");
+
+        assert_eq!(allocator.resolve_node_id("tree", &id), Some(id.clone()));
+        assert_eq!(allocator.resolve_node_id("tree", &format!(" {} ", id.to_uppercase())), Some(id.clone()));
+        assert_eq!(allocator.resolve_node_id("other_tree", &id), None);
+
+        // Well-formed but never allocated
+        let unallocated = if id == "clever_einstein" { "wise_curie" } else { "clever_einstein" };
+        assert_eq!(allocator.resolve_node_id("tree", unallocated), None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_free_functions() {
+        let id = generate_friendly_id();
+        assert_eq!(resolve_node_id(&id), Some(id.clone()));
+        assert_eq!(resolve_node_id("clever_nobody"), None);
+        assert_eq!(resolve_node_id("clever_turing_2"), None);
+        assert_eq!(get_friendly_id(&id), Some(id));
+    }
+
+    #[test]
+    fn test_word_list_validation() {
+        assert_eq!(
+            WordList::new(Vec::<String>::new(), ["turing"]),
+            Err(WordListError::Empty("adjectives"))
+        );
+        assert_eq!(
+            WordList::new(["clever"], ["Turing"]),
+            Err(WordListError::InvalidWord("Turing".to_string()))
+        );
+        assert_eq!(
+            WordList::new(["clever", "clever"], ["turing"]).unwrap().capacity(),
+            1
+        );
+        assert!(WordList::extended().capacity() > WordList::scientists().capacity() * 3);
+    }
+}